@@ -76,6 +76,11 @@ fn handle_new_finalized_head<P, Block, B>(
 					block_hash = ?hash,
 					"Could not finalize block because it is unknown.",
 				),
+				ClientError::StateUnavailable(_) => tracing::debug!(
+					target: LOG_TARGET,
+					block_hash = ?hash,
+					"Could not finalize block because its state is not available.",
+				),
 				_ => tracing::warn!(
 					target: LOG_TARGET,
 					error = ?e,
@@ -146,6 +151,11 @@ where
 										block_hash = ?imported_block.hash,
 										"Could not finalize block because it is unknown.",
 									),
+									ClientError::StateUnavailable(_) => tracing::debug!(
+										target: LOG_TARGET,
+										block_hash = ?imported_block.hash,
+										"Could not finalize block because its state is not available.",
+									),
 									_ => tracing::warn!(
 										target: LOG_TARGET,
 										error = ?e,
@@ -115,7 +115,7 @@ use xcm::latest::prelude::{
 
 use xcm_runtime_apis::{
 	dry_run::{CallDryRunEffects, Error as XcmDryRunApiError, XcmDryRunEffects},
-	fees::Error as XcmPaymentApiError,
+	fees::{runtime_decl_for_xcm_payment_api::XcmPaymentApiV1, Error as XcmPaymentApiError},
 };
 
 impl_opaque_keys! {
@@ -1065,6 +1065,34 @@ parameter_types! {
 	pub const DepositPerItem: Balance = deposit(1, 0);
 	pub const DepositPerByte: Balance = deposit(0, 1);
 	pub CodeHashLockupDepositPercent: Perbill = Perbill::from_percent(30);
+	/// The address prefix of the ERC-20 precompiles of the trust backed assets.
+	pub const TrustBackedAssetsErc20Prefix: u16 = 0x0120;
+	/// The address of the XCM precompile.
+	pub ReviveXcmPrecompileAddress: H160 = H160::from_low_u64_be(0xA0000);
+}
+
+/// Answers the weight and fee queries of the XCM precompile with the `XcmPaymentApi` of the
+/// runtime.
+pub struct ReviveXcmPaymentInfo;
+
+impl pallet_revive::pure_precompiles::XcmPaymentInfo for ReviveXcmPaymentInfo {
+	fn query_xcm_weight(message: VersionedXcm<()>) -> Result<Weight, XcmPaymentApiError> {
+		<Runtime as XcmPaymentApiV1<Block>>::query_xcm_weight(message)
+	}
+
+	fn query_weight_to_asset_fee(
+		weight: Weight,
+		asset: VersionedAssetId,
+	) -> Result<u128, XcmPaymentApiError> {
+		<Runtime as XcmPaymentApiV1<Block>>::query_weight_to_asset_fee(weight, asset)
+	}
+
+	fn query_delivery_fees(
+		destination: VersionedLocation,
+		message: VersionedXcm<()>,
+	) -> Result<VersionedAssets, XcmPaymentApiError> {
+		<Runtime as XcmPaymentApiV1<Block>>::query_delivery_fees(destination, message)
+	}
 }
 
 impl pallet_revive::Config for Runtime {
@@ -1078,6 +1106,10 @@ impl pallet_revive::Config for Runtime {
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
 	type WeightInfo = pallet_revive::weights::SubstrateWeight<Self>;
 	type ChainExtension = ();
+	type Precompiles = (
+		pallet_revive::pure_precompiles::Erc20<Assets, TrustBackedAssetsErc20Prefix>,
+		pallet_revive::pure_precompiles::XcmPrecompile<ReviveXcmPrecompileAddress>,
+	);
 	type AddressMapper = pallet_revive::AccountId32Mapper<Self>;
 	type RuntimeMemory = ConstU32<{ 128 * 1024 * 1024 }>;
 	type PVFMemory = ConstU32<{ 512 * 1024 * 1024 }>;
@@ -1087,6 +1119,7 @@ impl pallet_revive::Config for Runtime {
 	type RuntimeHoldReason = RuntimeHoldReason;
 	type CodeHashLockupDepositPercent = CodeHashLockupDepositPercent;
 	type Xcm = pallet_xcm::Pallet<Self>;
+	type XcmPayment = ReviveXcmPaymentInfo;
 	type ChainId = ConstU64<420_420_421>;
 	type NativeToEthRatio = ConstU32<1_000_000>; // 10^(18 - 12) Eth is 10^18, Native is 10^12.
 	type EthGasEncoder = ();
@@ -2255,6 +2288,31 @@ impl_runtime_apis! {
 			Revive::bare_eth_transact(tx, blockweights.max_block, tx_fee)
 		}
 
+		fn eth_transact_with_overrides(
+			tx: pallet_revive::evm::GenericTransaction,
+			state_overrides: pallet_revive::evm::StateOverride,
+			block_overrides: pallet_revive::evm::BlockOverrides,
+		) -> Result<pallet_revive::EthTransactInfo<Balance>, pallet_revive::EthTransactError>
+		{
+			if let Some(time) = block_overrides.time {
+				let now: u64 = time.try_into().map_err(|_| {
+					pallet_revive::EthTransactError::Message("Invalid time override".into())
+				})?;
+				pallet_timestamp::Now::<Runtime>::put(now);
+			}
+			Revive::apply_overrides(state_overrides, block_overrides)?;
+			Self::eth_transact(tx)
+		}
+
+		fn create_access_list(
+			tx: pallet_revive::evm::GenericTransaction,
+		) -> Result<pallet_revive::evm::AccessList, pallet_revive::EthTransactError> {
+			use pallet_revive::tracing::trace;
+			let mut tracer = pallet_revive::evm::AccessListTracer::<Runtime>::default();
+			trace(&mut tracer, || Self::eth_transact(tx))?;
+			Ok(tracer.collect_access_list())
+		}
+
 		fn call(
 			origin: AccountId,
 			dest: H160,
@@ -2322,19 +2380,19 @@ impl_runtime_apis! {
 		fn trace_block(
 			block: Block,
 			config: pallet_revive::evm::TracerConfig
-		) -> Vec<(u32, pallet_revive::evm::CallTrace)> {
+		) -> Vec<(u32, pallet_revive::evm::Trace)> {
 			use pallet_revive::tracing::trace;
-			let mut tracer = config.build(Revive::evm_gas_from_weight);
+			let mut tracer = Revive::evm_tracer(config);
 			let mut traces = vec![];
 			let (header, extrinsics) = block.deconstruct();
 
 			Executive::initialize_block(&header);
 			for (index, ext) in extrinsics.into_iter().enumerate() {
-				trace(&mut tracer, || {
+				trace(tracer.as_tracing(), || {
 					let _ = Executive::apply_extrinsic(ext);
 				});
 
-				if let Some(tx_trace) = tracer.collect_traces().pop() {
+				if let Some(tx_trace) = tracer.collect_trace() {
 					traces.push((index as u32, tx_trace));
 				}
 			}
@@ -2346,15 +2404,15 @@ impl_runtime_apis! {
 			block: Block,
 			tx_index: u32,
 			config: pallet_revive::evm::TracerConfig
-		) -> Option<pallet_revive::evm::CallTrace> {
+		) -> Option<pallet_revive::evm::Trace> {
 			use pallet_revive::tracing::trace;
-			let mut tracer = config.build(Revive::evm_gas_from_weight);
+			let mut tracer = Revive::evm_tracer(config);
 			let (header, extrinsics) = block.deconstruct();
 
 			Executive::initialize_block(&header);
 			for (index, ext) in extrinsics.into_iter().enumerate() {
 				if index as u32 == tx_index {
-					trace(&mut tracer, || {
+					trace(tracer.as_tracing(), || {
 						let _ = Executive::apply_extrinsic(ext);
 					});
 					break;
@@ -2363,24 +2421,24 @@ impl_runtime_apis! {
 				}
 			}
 
-			tracer.collect_traces().pop()
+			tracer.collect_trace()
 		}
 
 		fn trace_call(
 			tx: pallet_revive::evm::GenericTransaction,
 			config: pallet_revive::evm::TracerConfig)
-			-> Result<pallet_revive::evm::CallTrace, pallet_revive::EthTransactError>
+			-> Result<pallet_revive::evm::Trace, pallet_revive::EthTransactError>
 		{
 			use pallet_revive::tracing::trace;
-			let mut tracer = config.build(Revive::evm_gas_from_weight);
-			let result = trace(&mut tracer, || Self::eth_transact(tx));
+			let mut tracer = Revive::evm_tracer(config);
+			let result = trace(tracer.as_tracing(), || Self::eth_transact(tx));
 
-			if let Some(trace) = tracer.collect_traces().pop() {
+			if let Some(trace) = tracer.collect_trace() {
 				Ok(trace)
 			} else if let Err(err) = result {
 				Err(err)
 			} else {
-				Ok(Default::default())
+				Ok(tracer.empty_trace())
 			}
 		}
 	}
@@ -22,7 +22,7 @@ use polkadot_primitives::metric_definitions::{
 	PARACHAIN_CREATE_INHERENT_BITFIELDS_SIGNATURE_CHECKS,
 	PARACHAIN_INHERENT_DATA_BITFIELDS_PROCESSED, PARACHAIN_INHERENT_DATA_CANDIDATES_PROCESSED,
 	PARACHAIN_INHERENT_DATA_DISPUTE_SETS_PROCESSED, PARACHAIN_INHERENT_DATA_WEIGHT,
-	PARACHAIN_VERIFY_DISPUTE_SIGNATURE,
+	PARACHAIN_VERIFY_DISPUTE_SIGNATURE, RUNTIME_TRY_STATE_FAILURES,
 };
 
 /// Register the parachain runtime metrics.
@@ -35,4 +35,7 @@ pub fn register_metrics(runtime_metrics_provider: &RuntimeMetricsProvider) {
 	runtime_metrics_provider
 		.register_countervec(PARACHAIN_CREATE_INHERENT_BITFIELDS_SIGNATURE_CHECKS);
 	runtime_metrics_provider.register_histogram(PARACHAIN_VERIFY_DISPUTE_SIGNATURE);
+
+	// Not a parachain metric, but published by the same runtimes.
+	runtime_metrics_provider.register_countervec(RUNTIME_TRY_STATE_FAILURES);
 }
@@ -232,6 +232,10 @@ pub enum Error {
 	#[error("Creating a custom database is required for validators")]
 	DatabasePathRequired,
 
+	#[cfg(feature = "full-node")]
+	#[error("Validators and collators require a local keystore")]
+	RemoteKeystoreNotSupported,
+
 	#[cfg(feature = "full-node")]
 	#[error("Expected at least one of polkadot, kusama, westend or rococo runtime feature")]
 	NoRuntime,
@@ -770,8 +774,13 @@ pub fn new_full<
 	let overseer_connector = OverseerConnector::default();
 	let overseer_handle = Handle::new(overseer_connector.handle());
 
-	let keystore = basics.keystore_container.local_keystore();
 	let auth_or_collator = role.is_authority() || is_parachain_node.is_collator();
+	// The parachain subsystems sign with the keys of the local keystore directly.
+	let keystore = match basics.keystore_container.local_keystore() {
+		Some(keystore) => keystore,
+		None if auth_or_collator => return Err(Error::RemoteKeystoreNotSupported),
+		None => Arc::new(sc_keystore::LocalKeystore::in_memory()),
+	};
 
 	let select_chain = if auth_or_collator {
 		let metrics =
@@ -180,4 +180,12 @@ pub mod metric_definitions {
 			description: "How much time does it take to verify a single validator signature of a dispute statement, in seconds",
 			buckets: &[0.0, 0.00005, 0.00006, 0.0001, 0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.3, 0.5, 1.0],
 	};
+
+	/// Counts the failed sampled `try_state` checks of `frame_executive::SampledTryState` per
+	/// pallet.
+	pub const RUNTIME_TRY_STATE_FAILURES: CounterVecDefinition = CounterVecDefinition {
+		name: "polkadot_runtime_try_state_failures",
+		description: "Counts the number of failed sampled `try_state` checks.",
+		labels: &["pallet"],
+	};
 }
@@ -17,14 +17,16 @@
 //! Auxiliary `struct`/`enum`s for polkadot runtime.
 
 use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_executive::OnTryStateFailure;
 use frame_support::traits::{
 	fungible::{Balanced, Credit},
 	tokens::imbalance::ResolveTo,
 	Contains, ContainsPair, Imbalance, OnUnbalanced,
 };
 use pallet_treasury::TreasuryAccountId;
-use polkadot_primitives::Balance;
-use sp_runtime::{traits::TryConvert, Perquintill, RuntimeDebug};
+use polkadot_primitives::{metric_definitions::RUNTIME_TRY_STATE_FAILURES, Balance};
+use polkadot_runtime_metrics::CounterVec;
+use sp_runtime::{traits::TryConvert, Perquintill, RuntimeDebug, TryRuntimeError};
 use xcm::VersionedLocation;
 
 /// Logic for the author to get a portion of fees.
@@ -239,6 +241,19 @@ where
 	}
 }
 
+/// Counts the failed sampled `try_state` checks of [`frame_executive::SampledTryState`] per pallet
+/// in the [`RUNTIME_TRY_STATE_FAILURES`] runtime metric.
+///
+/// The metric is only published by runtimes built with the `runtime-metrics` feature. See
+/// `polkadot_runtime_metrics` for how to collect it on the node.
+pub struct TryStateFailureMetric;
+impl OnTryStateFailure for TryStateFailureMetric {
+	fn on_try_state_failure(pallet: &'static str, _: &TryRuntimeError) {
+		const METRIC: CounterVec = CounterVec::new(RUNTIME_TRY_STATE_FAILURES);
+		METRIC.with_label_values(&[pallet]).inc();
+	}
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarks {
 	use super::VersionedLocatableAsset;
@@ -612,4 +627,110 @@ mod tests {
 
 		assert_eq!(relay_era_payout(params), payout);
 	}
+
+	#[cfg(all(feature = "runtime-metrics", feature = "try-runtime"))]
+	#[test]
+	fn try_state_failures_are_counted_per_pallet() {
+		use frame_executive::{SampleTryState, SampledTryState};
+		use frame_support::traits::{PalletInfoData, PalletsInfoAccess, TryState, TryStateSelect};
+		use polkadot_primitives::{RuntimeMetricOp, RuntimeMetricUpdate};
+		use std::sync::{Arc, Mutex};
+		use tracing_subscriber::layer::SubscriberExt;
+
+		/// The `try_state` checks of `Failing` fail, the ones of `Passing` don't.
+		struct Pallets;
+		impl PalletsInfoAccess for Pallets {
+			fn infos() -> Vec<PalletInfoData> {
+				["Failing", "Passing"]
+					.into_iter()
+					.enumerate()
+					.map(|(index, name)| PalletInfoData {
+						index,
+						name,
+						module_name: name,
+						crate_version: Default::default(),
+					})
+					.collect()
+			}
+		}
+		impl TryState<u64> for Pallets {
+			fn try_state(_: u64, select: TryStateSelect) -> Result<(), TryRuntimeError> {
+				match select {
+					TryStateSelect::Only(pallets) if pallets == vec![b"Failing".to_vec()] =>
+						Err("invariant violated".into()),
+					_ => Ok(()),
+				}
+			}
+		}
+
+		parameter_types! {
+			pub const Select: TryStateSelect = TryStateSelect::All;
+			pub const WeightPerCheck: Weight = Weight::from_parts(1, 0);
+		}
+		type Sampler = SampledTryState<Select, WeightPerCheck, TryStateFailureMetric>;
+
+		/// Collects the runtime metric updates, like the node does.
+		#[derive(Clone, Default)]
+		struct MetricUpdates(Arc<Mutex<Vec<RuntimeMetricUpdate>>>);
+		impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for MetricUpdates {
+			fn on_event(
+				&self,
+				event: &tracing::Event<'_>,
+				_: tracing_subscriber::layer::Context<'_, S>,
+			) {
+				struct UpdateOp(Option<String>);
+				impl tracing::field::Visit for UpdateOp {
+					fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+						if field.name() == "update_op" {
+							self.0 = Some(value.into());
+						}
+					}
+					fn record_debug(
+						&mut self,
+						_: &tracing::field::Field,
+						_: &dyn core::fmt::Debug,
+					) {
+					}
+				}
+
+				if event.metadata().target() != "metrics" {
+					return
+				}
+				let mut update_op = UpdateOp(None);
+				event.record(&mut update_op);
+				let encoded = bs58::decode(update_op.0.expect("metric updates have an update_op"))
+					.into_vec()
+					.unwrap();
+				self.0
+					.lock()
+					.unwrap()
+					.push(RuntimeMetricUpdate::decode(&mut &encoded[..]).unwrap());
+			}
+		}
+
+		let updates = MetricUpdates::default();
+		let subscriber = tracing_subscriber::registry().with(updates.clone());
+		tracing::subscriber::with_default(subscriber, || {
+			new_test_ext().execute_with(|| {
+				for n in 0..3 {
+					<Sampler as SampleTryState<Test, Pallets>>::sample_try_state(
+						n,
+						Weight::from_parts(2, 0),
+					);
+				}
+			})
+		});
+
+		// One increment of the counter of `Failing` per block.
+		let updates = updates.0.lock().unwrap();
+		assert_eq!(updates.len(), 3);
+		for update in updates.iter() {
+			assert_eq!(update.metric_name(), RUNTIME_TRY_STATE_FAILURES.name);
+			match &update.op {
+				RuntimeMetricOp::IncrementCounterVec(1, labels) =>
+					assert_eq!(labels.as_str_vec(), vec!["Failing"]),
+				op => panic!("unexpected metric update: {op:?}"),
+			}
+		}
+	}
 }
@@ -89,12 +89,25 @@ pub enum Subcommand {
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Export the finalized state of a block into a snapshot.
+	ExportSnapshot(sc_cli::ExportSnapshotCmd),
+
+	/// Call a pallet view function at a given block.
+	CallViewFunction(sc_cli::CallViewFunctionCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
+	/// Seed a fresh database from a snapshot.
+	ImportSnapshot(sc_cli::ImportSnapshotCmd),
+
 	/// Remove the whole chain.
 	PurgeChain(sc_cli::PurgeChainCmd),
 
+	/// Database utilities.
+	#[command(subcommand)]
+	Db(sc_cli::DbSubcommand),
+
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
@@ -122,6 +122,7 @@ pub fn create_extrinsic(
 	let tip = 0;
 	let tx_ext: kitchensink_runtime::TxExtension =
 		(
+			frame_system::AuthorizeCall::<kitchensink_runtime::Runtime>::new(),
 			frame_system::CheckNonZeroSender::<kitchensink_runtime::Runtime>::new(),
 			frame_system::CheckSpecVersion::<kitchensink_runtime::Runtime>::new(),
 			frame_system::CheckTxVersion::<kitchensink_runtime::Runtime>::new(),
@@ -145,6 +146,7 @@ pub fn create_extrinsic(
 		function.clone(),
 		tx_ext.clone(),
 		(
+			(),
 			(),
 			kitchensink_runtime::VERSION.spec_version,
 			kitchensink_runtime::VERSION.transaction_version,
@@ -290,11 +292,15 @@ pub fn new_partial(
 
 	let import_setup = (block_import, grandpa_link, babe_link, beefy_voter_links);
 
+	// The statement store decrypts statements with the keys of the local keystore only.
+	let statement_keystore = keystore_container
+		.local_keystore()
+		.unwrap_or_else(|| Arc::new(sc_keystore::LocalKeystore::in_memory()));
 	let statement_store = sc_statement_store::Store::new_shared(
 		&config.data_path,
 		Default::default(),
 		client.clone(),
-		keystore_container.local_keystore(),
+		statement_keystore,
 		config.prometheus_registry(),
 		&task_manager.spawn_handle(),
 	)
@@ -1052,6 +1058,7 @@ mod tests {
 					value: amount,
 				});
 
+				let authorize_call = frame_system::AuthorizeCall::new();
 				let check_non_zero_sender = frame_system::CheckNonZeroSender::new();
 				let check_spec_version = frame_system::CheckSpecVersion::new();
 				let check_tx_version = frame_system::CheckTxVersion::new();
@@ -1065,6 +1072,7 @@ mod tests {
 				let weight_reclaim = frame_system::WeightReclaim::new();
 				let metadata_hash = frame_metadata_hash_extension::CheckMetadataHash::new(false);
 				let tx_ext: TxExtension = (
+					authorize_call,
 					check_non_zero_sender,
 					check_spec_version,
 					check_tx_version,
@@ -1080,6 +1088,7 @@ mod tests {
 					function,
 					tx_ext,
 					(
+						(),
 						(),
 						spec_version,
 						transaction_version,
@@ -119,7 +119,10 @@ mod multiplier_tests {
 		dispatch::DispatchClass,
 		weights::{Weight, WeightToFee},
 	};
-	use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
+	use pallet_transaction_payment::{
+		MultiDimensionalFeeAdjustment, Multiplier, MultiplierUpdate, TargetedFeeAdjustment,
+		WeightMultiplier,
+	};
 	use polkadot_sdk::*;
 	use sp_runtime::{
 		assert_eq_error_rate,
@@ -159,6 +162,17 @@ mod multiplier_tests {
 		>::convert(fm)
 	}
 
+	// update of the per-dimension multipliers based on runtime impl.
+	fn runtime_weight_multiplier_update(wm: WeightMultiplier) -> WeightMultiplier {
+		MultiDimensionalFeeAdjustment::<
+			Runtime,
+			TargetBlockFullness,
+			AdjustmentVariable,
+			MinimumMultiplier,
+			MaximumMultiplier,
+		>::convert_per_dimension(wm)
+	}
+
 	// update based on reference impl.
 	fn truth_value_update(block_weight: Weight, previous: Multiplier) -> Multiplier {
 		let accuracy = Multiplier::accuracy() as f64;
@@ -430,6 +444,30 @@ mod multiplier_tests {
 		})
 	}
 
+	#[test]
+	fn per_dimension_multipliers_follow_their_own_fullness() {
+		// the block is full in proof size, but empty in ref time.
+		let block_weight = Weight::from_parts(0, max_normal().proof_size());
+		run_with_system_weight(block_weight, || {
+			let fm = Multiplier::one();
+			let next = runtime_weight_multiplier_update(WeightMultiplier::uniform(fm));
+
+			assert!(next.ref_time < fm, "{:?} !< {:?}", next.ref_time, fm);
+			assert!(next.proof_size > fm, "{:?} !> {:?}", next.proof_size, fm);
+			// each dimension is adjusted as if it were the limiting one.
+			assert_eq_error_rate!(
+				next.proof_size,
+				truth_value_update(block_weight, fm),
+				Multiplier::from_inner(100),
+			);
+			assert_eq_error_rate!(
+				next.ref_time,
+				truth_value_update(Weight::zero(), fm),
+				Multiplier::from_inner(100),
+			);
+		});
+	}
+
 	#[test]
 	fn weight_to_fee_should_not_overflow_on_large_weights() {
 		let kb_time = Weight::from_parts(1024, 0);
@@ -94,7 +94,7 @@ use sp_runtime::traits::TransactionExtension;
 use pallet_broker::TaskId;
 #[allow(deprecated)]
 pub use pallet_transaction_payment::{CurrencyAdapter, Multiplier, TargetedFeeAdjustment};
-use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
+use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo, WeightMultiplier};
 use pallet_tx_pause::RuntimeCallNameOf;
 use sp_api::impl_runtime_apis;
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
@@ -1711,6 +1711,7 @@ impl pallet_revive::Config for Runtime {
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
 	type WeightInfo = pallet_revive::weights::SubstrateWeight<Self>;
 	type ChainExtension = ();
+	type Precompiles = ();
 	type AddressMapper = pallet_revive::AccountId32Mapper<Self>;
 	type RuntimeMemory = ConstU32<{ 128 * 1024 * 1024 }>;
 	type PVFMemory = ConstU32<{ 512 * 1024 * 1024 }>;
@@ -1720,6 +1721,7 @@ impl pallet_revive::Config for Runtime {
 	type RuntimeHoldReason = RuntimeHoldReason;
 	type CodeHashLockupDepositPercent = CodeHashLockupDepositPercent;
 	type Xcm = ();
+	type XcmPayment = ();
 	type ChainId = ConstU64<420_420_420>;
 	type NativeToEthRatio = ConstU32<1_000_000>; // 10^(18 - 12) Eth is 10^18, Native is 10^12.
 	type EthGasEncoder = ();
@@ -1775,6 +1777,7 @@ where
 			.saturating_sub(1);
 		let era = Era::mortal(period, current_block);
 		let tx_ext: TxExtension = (
+			frame_system::AuthorizeCall::<Runtime>::new(),
 			frame_system::CheckNonZeroSender::<Runtime>::new(),
 			frame_system::CheckSpecVersion::<Runtime>::new(),
 			frame_system::CheckTxVersion::<Runtime>::new(),
@@ -1805,6 +1808,29 @@ where
 	}
 }
 
+impl<LocalCall> frame_system::offchain::CreateAuthorizedTransaction<LocalCall> for Runtime
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_extension() -> TxExtension {
+		(
+			frame_system::AuthorizeCall::<Runtime>::new(),
+			frame_system::CheckNonZeroSender::<Runtime>::new(),
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(Era::Immortal),
+			frame_system::CheckNonce::<Runtime>::from(0),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_skip_feeless_payment::SkipCheckIfFeeless::from(
+				pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::<Runtime>::from(0, None),
+			),
+			frame_metadata_hash_extension::CheckMetadataHash::new(false),
+			frame_system::WeightReclaim::<Runtime>::new(),
+		)
+	}
+}
+
 impl<LocalCall> frame_system::offchain::CreateInherent<LocalCall> for Runtime
 where
 	RuntimeCall: From<LocalCall>,
@@ -3035,6 +3061,7 @@ pub type BlockId = generic::BlockId<Block>;
 ///
 /// [`sign`]: <../../testing/src/keyring.rs.html>
 pub type TxExtension = (
+	frame_system::AuthorizeCall<Runtime>,
 	frame_system::CheckNonZeroSender<Runtime>,
 	frame_system::CheckSpecVersion<Runtime>,
 	frame_system::CheckTxVersion<Runtime>,
@@ -3059,6 +3086,7 @@ impl EthExtra for EthExtraImpl {
 
 	fn get_eth_extension(nonce: u32, tip: Balance) -> Self::Extension {
 		(
+			frame_system::AuthorizeCall::<Runtime>::new(),
 			frame_system::CheckNonZeroSender::<Runtime>::new(),
 			frame_system::CheckSpecVersion::<Runtime>::new(),
 			frame_system::CheckTxVersion::<Runtime>::new(),
@@ -3631,6 +3659,31 @@ impl_runtime_apis! {
 			Revive::bare_eth_transact(tx, blockweights.max_block, tx_fee)
 		}
 
+		fn eth_transact_with_overrides(
+			tx: pallet_revive::evm::GenericTransaction,
+			state_overrides: pallet_revive::evm::StateOverride,
+			block_overrides: pallet_revive::evm::BlockOverrides,
+		) -> Result<pallet_revive::EthTransactInfo<Balance>, pallet_revive::EthTransactError>
+		{
+			if let Some(time) = block_overrides.time {
+				let now: u64 = time.try_into().map_err(|_| {
+					pallet_revive::EthTransactError::Message("Invalid time override".into())
+				})?;
+				pallet_timestamp::Now::<Runtime>::put(now);
+			}
+			Revive::apply_overrides(state_overrides, block_overrides)?;
+			Self::eth_transact(tx)
+		}
+
+		fn create_access_list(
+			tx: pallet_revive::evm::GenericTransaction,
+		) -> Result<pallet_revive::evm::AccessList, pallet_revive::EthTransactError> {
+			use pallet_revive::tracing::trace;
+			let mut tracer = pallet_revive::evm::AccessListTracer::<Runtime>::default();
+			trace(&mut tracer, || Self::eth_transact(tx))?;
+			Ok(tracer.collect_access_list())
+		}
+
 		fn call(
 			origin: AccountId,
 			dest: H160,
@@ -3696,19 +3749,19 @@ impl_runtime_apis! {
 		fn trace_block(
 			block: Block,
 			config: pallet_revive::evm::TracerConfig
-		) -> Vec<(u32, pallet_revive::evm::CallTrace)> {
+		) -> Vec<(u32, pallet_revive::evm::Trace)> {
 			use pallet_revive::tracing::trace;
-			let mut tracer = config.build(Revive::evm_gas_from_weight);
+			let mut tracer = Revive::evm_tracer(config);
 			let mut traces = vec![];
 			let (header, extrinsics) = block.deconstruct();
 
 			Executive::initialize_block(&header);
 			for (index, ext) in extrinsics.into_iter().enumerate() {
-				trace(&mut tracer, || {
+				trace(tracer.as_tracing(), || {
 					let _ = Executive::apply_extrinsic(ext);
 				});
 
-				if let Some(tx_trace) = tracer.collect_traces().pop() {
+				if let Some(tx_trace) = tracer.collect_trace() {
 					traces.push((index as u32, tx_trace));
 				}
 			}
@@ -3720,15 +3773,15 @@ impl_runtime_apis! {
 			block: Block,
 			tx_index: u32,
 			config: pallet_revive::evm::TracerConfig
-		) -> Option<pallet_revive::evm::CallTrace> {
+		) -> Option<pallet_revive::evm::Trace> {
 			use pallet_revive::tracing::trace;
-			let mut tracer = config.build(Revive::evm_gas_from_weight);
+			let mut tracer = Revive::evm_tracer(config);
 			let (header, extrinsics) = block.deconstruct();
 
 			Executive::initialize_block(&header);
 			for (index, ext) in extrinsics.into_iter().enumerate() {
 				if index as u32 == tx_index {
-					trace(&mut tracer, || {
+					trace(tracer.as_tracing(), || {
 						let _ = Executive::apply_extrinsic(ext);
 					});
 					break;
@@ -3737,24 +3790,24 @@ impl_runtime_apis! {
 				}
 			}
 
-			tracer.collect_traces().pop()
+			tracer.collect_trace()
 		}
 
 		fn trace_call(
 			tx: pallet_revive::evm::GenericTransaction,
 			config: pallet_revive::evm::TracerConfig)
-			-> Result<pallet_revive::evm::CallTrace, pallet_revive::EthTransactError>
+			-> Result<pallet_revive::evm::Trace, pallet_revive::EthTransactError>
 		{
 			use pallet_revive::tracing::trace;
-			let mut tracer = config.build(Revive::evm_gas_from_weight);
-			let result = trace(&mut tracer, || Self::eth_transact(tx));
+			let mut tracer = Revive::evm_tracer(config);
+			let result = trace(tracer.as_tracing(), || Self::eth_transact(tx));
 
-			if let Some(trace) = tracer.collect_traces().pop() {
+			if let Some(trace) = tracer.collect_trace() {
 				Ok(trace)
 			} else if let Err(err) = result {
 				Err(err)
 			} else {
-				Ok(Default::default())
+				Ok(tracer.empty_trace())
 			}
 		}
 	}
@@ -3796,6 +3849,30 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_asset_conversion_tx_payment::AssetConversionTxPaymentApi<
+		Block,
+		Balance,
+		NativeOrWithId<u32>,
+		RuntimeCall,
+	> for Runtime
+	{
+		fn query_asset_fees(
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+			asset_ids: Vec<Option<NativeOrWithId<u32>>>,
+		) -> Vec<Option<Balance>> {
+			AssetConversionTxPayment::query_asset_fees(uxt, len, asset_ids)
+		}
+
+		fn query_call_asset_fees(
+			call: RuntimeCall,
+			len: u32,
+			asset_ids: Vec<Option<NativeOrWithId<u32>>>,
+		) -> Vec<Option<Balance>> {
+			AssetConversionTxPayment::query_call_asset_fees(call, len, asset_ids)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi<Block, Balance, RuntimeCall>
 		for Runtime
 	{
@@ -3813,6 +3890,17 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentMultiplierApi<Block>
+		for Runtime
+	{
+		fn query_fee_multiplier() -> Multiplier {
+			TransactionPayment::next_fee_multiplier()
+		}
+		fn query_weight_multiplier() -> WeightMultiplier {
+			TransactionPayment::next_weight_multiplier()
+		}
+	}
+
 	impl pallet_nfts_runtime_api::NftsApi<Block, AccountId, u32, u32> for Runtime {
 		fn owner(collection: u32, item: u32) -> Option<AccountId> {
 			<Nfts as Inspect<AccountId>>::owner(&collection, &item)
@@ -4125,7 +4213,7 @@ impl_runtime_apis! {
 mod tests {
 	use super::*;
 	use frame_election_provider_support::NposSolution;
-	use frame_system::offchain::CreateSignedTransaction;
+	use frame_system::offchain::{CreateAuthorizedTransaction, CreateSignedTransaction};
 	use sp_runtime::UpperOf;
 
 	#[test]
@@ -4139,6 +4227,17 @@ mod tests {
 		is_submit_signed_transaction::<Runtime>();
 	}
 
+	#[test]
+	fn validate_authorized_transaction_submitter_bounds() {
+		fn is_submit_authorized_transaction<T>()
+		where
+			T: CreateAuthorizedTransaction<pallet_im_online::Call<Runtime>>,
+		{
+		}
+
+		is_submit_authorized_transaction::<Runtime>();
+	}
+
 	#[test]
 	fn perbill_as_onchain_accuracy() {
 		type OnChainAccuracy =
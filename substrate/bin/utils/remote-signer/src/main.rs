@@ -0,0 +1,96 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Reference signer for nodes started with `--keystore-remote`.
+//!
+//! Serves the keys of a local keystore over the protocol of `sc_keystore::remote`, refusing to
+//! sign conflicting GRANDPA and BEEFY votes and BABE blocks.
+
+use clap::Parser;
+use sc_keystore::{
+	remote::{
+		babe_vrf_signing_domain, beefy_signing_domain, grandpa_signing_domain,
+		DoubleSignProtection, RemoteEndpoint, RemoteSigner, SignerListener,
+	},
+	LocalKeystore,
+};
+use sp_core::crypto::{key_types, SecretString};
+use std::{fs, path::PathBuf};
+
+/// The name of the slashing protection history within the keystore directory.
+const DEFAULT_HISTORY_FILE: &str = "slashing-protection.json";
+
+/// Serve the keys of a local keystore to remote Substrate nodes.
+#[derive(Debug, Parser)]
+#[command(name = "remote-signer", version)]
+struct Cli {
+	/// Where to listen for requests, either `unix:<PATH>` or `http://<HOST>:<PORT>[/PATH]`.
+	#[arg(long, value_name = "URL")]
+	listen: RemoteEndpoint,
+
+	/// The path of the keystore.
+	#[arg(long, value_name = "PATH")]
+	keystore_path: PathBuf,
+
+	/// Use interactive shell for entering the password used by the keystore.
+	#[arg(long, conflicts_with = "password_filename")]
+	password_interactive: bool,
+
+	/// File that contains the password used by the keystore.
+	#[arg(long, value_name = "PATH")]
+	password_filename: Option<PathBuf>,
+
+	/// File to remember the signed consensus messages in.
+	///
+	/// Defaults to `slashing-protection.json` in the keystore directory.
+	#[arg(long, value_name = "PATH")]
+	slashing_protection_path: Option<PathBuf>,
+}
+
+fn main() -> Result<(), String> {
+	sp_tracing::try_init_simple();
+	let cli = Cli::parse();
+
+	let password = if cli.password_interactive {
+		Some(rpassword::prompt_password("Keystore password: ").map_err(|e| e.to_string())?)
+	} else if let Some(file) = &cli.password_filename {
+		Some(fs::read_to_string(file).map_err(|e| e.to_string())?)
+	} else {
+		None
+	};
+	let keystore = LocalKeystore::open(&cli.keystore_path, password.map(SecretString::new))
+		.map_err(|e| format!("Failed to open the keystore: {}", e))?;
+
+	let history = cli
+		.slashing_protection_path
+		.unwrap_or_else(|| cli.keystore_path.join(DEFAULT_HISTORY_FILE));
+	let protection = DoubleSignProtection::open(&history)
+		.map_err(|e| format!("Failed to open the slashing protection history: {}", e))?
+		.with_domain(key_types::GRANDPA, grandpa_signing_domain)
+		.with_domain(key_types::BEEFY, beefy_signing_domain)
+		.with_vrf_domain(key_types::BABE, babe_vrf_signing_domain);
+
+	let listener = SignerListener::bind(&cli.listen)
+		.map_err(|e| format!("Failed to listen at {}: {}", cli.listen, e))?;
+	log::info!("Serving the keys in {} at {}", cli.keystore_path.display(), cli.listen);
+
+	RemoteSigner::new(keystore)
+		.with_slashing_protection(protection)
+		.serve(listener)
+		.map_err(|e| format!("Failed to accept requests: {}", e))
+}
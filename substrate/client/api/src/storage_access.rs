@@ -0,0 +1,115 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tracing of the storage accessed while executing a block.
+
+use crate::{BlockBackend, CallExecutor, ExecutorProvider};
+use codec::Encode;
+use sp_blockchain::{Error, HeaderBackend, Result};
+use sp_core::traits::CallContext;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, Saturating};
+use sp_state_machine::OverlayedChanges;
+use std::cell::RefCell;
+
+pub use sp_state_machine::{AccessedKey, StorageAccesses};
+
+/// A phase of the execution of a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionPhase {
+	/// Initialization of the block, `Core_initialize_block`.
+	Initialization,
+	/// Application of the extrinsic with the given index, `BlockBuilder_apply_extrinsic`.
+	ApplyExtrinsic(u32),
+	/// Finalization of the block, `BlockBuilder_finalize_block`.
+	Finalization,
+}
+
+/// The storage accessed while executing a block, per [`ExecutionPhase`].
+#[derive(Debug, Clone)]
+pub struct BlockStorageAccesses<Block: BlockT> {
+	/// Hash of the executed block.
+	pub block_hash: Block::Hash,
+	/// Hash of the parent of the executed block.
+	pub parent_hash: Block::Hash,
+	/// The storage accesses of all phases, in execution order.
+	pub phases: Vec<(ExecutionPhase, StorageAccesses)>,
+}
+
+/// Re-execute the block `hash` on top of the state of its parent and collect the storage accessed
+/// by the initialization, every extrinsic and the finalization of the block.
+///
+/// The block is executed through the `BlockBuilder` runtime API instead of `Core_execute_block`,
+/// so that the accesses can be attributed to the individual extrinsics. This doesn't require a
+/// runtime compiled with tracing support. The changes are discarded after the execution.
+pub fn trace_block_storage_accesses<Block, Client>(
+	client: &Client,
+	hash: Block::Hash,
+) -> Result<BlockStorageAccesses<Block>>
+where
+	Block: BlockT,
+	Client: ExecutorProvider<Block> + HeaderBackend<Block> + BlockBackend<Block>,
+{
+	let mut header = client
+		.header(hash)?
+		.ok_or_else(|| Error::UnknownBlock(format!("Header not found for {hash:?}")))?;
+	let extrinsics = client
+		.block_body(hash)?
+		.ok_or_else(|| Error::UnknownBlock(format!("Body not found for {hash:?}")))?;
+	let parent_hash = *header.parent_hash();
+	// Remove all `Seal`s as they are added by the consensus engines after building the block.
+	header.digest_mut().logs.retain(|d| d.as_seal().is_none());
+
+	let executor = client.executor();
+	let changes = RefCell::new(OverlayedChanges::default());
+	changes.borrow_mut().set_track_storage_accesses(true);
+	let extensions = RefCell::new(
+		client
+			.execution_extensions()
+			.extensions(parent_hash, header.number().saturating_sub(1u32.into())),
+	);
+
+	let mut phases = Vec::with_capacity(extrinsics.len() + 2);
+	let mut execute = |phase: ExecutionPhase, method: &str, call_data: &[u8]| -> Result<()> {
+		executor.contextual_call(
+			parent_hash,
+			method,
+			call_data,
+			&changes,
+			&None,
+			CallContext::Onchain,
+			&extensions,
+		)?;
+		let accesses = changes.borrow_mut().take_storage_accesses().unwrap_or_default();
+		phases.push((phase, accesses));
+		Ok(())
+	};
+
+	execute(ExecutionPhase::Initialization, "Core_initialize_block", &header.encode())?;
+	for (index, extrinsic) in extrinsics.iter().enumerate() {
+		// Failed extrinsics are part of the block and still access the storage, so the result of
+		// the application is ignored.
+		execute(
+			ExecutionPhase::ApplyExtrinsic(index as u32),
+			"BlockBuilder_apply_extrinsic",
+			&extrinsic.encode(),
+		)?;
+	}
+	execute(ExecutionPhase::Finalization, "BlockBuilder_finalize_block", &[])?;
+
+	Ok(BlockStorageAccesses { block_hash: hash, parent_hash, phases })
+}
@@ -0,0 +1,141 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{BlockNumberOrHash, DatabaseParams, PruningParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use sc_client_api::{CallExecutor, ExecutorProvider, HeaderBackend, UsageProvider};
+use sc_rpc::view_functions::{self, ViewFunctionArgs};
+use sp_core::traits::CallContext;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::{fmt::Debug, str::FromStr, sync::Arc};
+
+/// The `call-view-function` command used to call a pallet view function at a given block.
+///
+/// The output is printed as JSON if it can be decoded using the runtime metadata and as a hex
+/// string of the SCALE encoded output otherwise.
+#[derive(Debug, Clone, Parser)]
+pub struct CallViewFunctionCmd {
+	/// Name of the pallet.
+	#[arg(value_name = "PALLET")]
+	pub pallet: String,
+
+	/// Name of the view function.
+	#[arg(value_name = "FUNCTION")]
+	pub function: String,
+
+	/// One JSON value per function parameter.
+	///
+	/// Arguments that are not valid JSON are passed as JSON strings.
+	#[arg(value_name = "ARGS")]
+	pub args: Vec<String>,
+
+	/// Hex encoded SCALE arguments to pass instead of the JSON arguments.
+	#[arg(long, value_name = "HEX", conflicts_with = "args")]
+	pub scale: Option<String>,
+
+	/// Block hash or number to call the view function at. Defaults to the best block.
+	#[arg(long, value_name = "HASH or NUMBER")]
+	pub at: Option<BlockNumberOrHash>,
+
+	/// Always print the hex encoded SCALE output.
+	#[arg(long)]
+	pub raw: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl CallViewFunctionCmd {
+	/// Run the `call-view-function` command
+	pub async fn run<B, C>(&self, client: Arc<C>) -> error::Result<()>
+	where
+		B: BlockT,
+		C: UsageProvider<B> + HeaderBackend<B> + ExecutorProvider<B>,
+		<B::Hash as FromStr>::Err: Debug,
+		<<B::Header as HeaderT>::Number as FromStr>::Err: Debug,
+	{
+		let block_id = self.at.as_ref().map(|b| b.parse()).transpose()?;
+		let hash = match block_id {
+			Some(id) => client.expect_block_hash_from_id(&id)?,
+			None => client.usage_info().chain.best_hash,
+		};
+
+		let args = match &self.scale {
+			Some(scale) => ViewFunctionArgs::Scale(
+				array_bytes::hex2bytes(scale).map_err(error::Error::HexDataConversion)?.into(),
+			),
+			None => ViewFunctionArgs::Json(
+				self.args
+					.iter()
+					.map(|arg| serde_json::from_str(arg).unwrap_or_else(|_| arg.clone().into()))
+					.collect(),
+			),
+		};
+
+		let call = |method: &str, call_data: &[u8]| {
+			client
+				.executor()
+				.call(hash, method, call_data, CallContext::Offchain)
+				.map_err(|e| e.to_string())
+		};
+		let result = view_functions::fetch_metadata(&call)
+			.and_then(|metadata| {
+				view_functions::call_view_function(
+					call,
+					&metadata,
+					&self.pallet,
+					&self.function,
+					args,
+				)
+			})
+			.map_err(|e| error::Error::Application(Box::new(e)))?;
+
+		match result.decoded {
+			Some(decoded) if !self.raw => println!("{decoded:#}"),
+			_ => println!("{}", array_bytes::bytes2hex("0x", &result.output.0)),
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for CallViewFunctionCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -0,0 +1,106 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{DatabaseParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use log::info;
+use sc_client_db::convert;
+use sc_service::DatabaseSource;
+use sp_runtime::traits::Block as BlockT;
+use std::path::{Path, PathBuf};
+
+/// The `db convert` command used to convert a RocksDB database into a ParityDB database.
+///
+/// The conversion streams every column of the database, so it does not need to resync the chain.
+/// An interrupted conversion is resumed by running the command again. Only archive databases can
+/// be converted. Converting a ParityDB database back into RocksDB is not supported.
+#[derive(Debug, Clone, Parser)]
+pub struct DbConvertCmd {
+	/// Where to write the ParityDB database.
+	///
+	/// Defaults to the database the node opens with `--database paritydb`.
+	#[arg(long, value_name = "PATH")]
+	pub target_path: Option<PathBuf>,
+
+	/// The number of finalized blocks whose state is verified against their state root after
+	/// the conversion.
+	///
+	/// Verifying a block reads its whole state.
+	#[arg(long, value_name = "COUNT", default_value_t = 8)]
+	pub verify_blocks: u32,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl DbConvertCmd {
+	/// Run the convert command
+	pub fn run<B: BlockT>(&self, database_config: DatabaseSource) -> error::Result<()> {
+		let (source, cache_size, target) = match database_config {
+			DatabaseSource::RocksDb { path, cache_size } => {
+				let target = paritydb_path(&path);
+				(path, cache_size, target)
+			},
+			DatabaseSource::Auto { rocksdb_path, paritydb_path, cache_size } =>
+				(rocksdb_path, cache_size, Some(paritydb_path)),
+			DatabaseSource::ParityDb { .. } =>
+				return Err(error::Error::Input(
+					"Converting a ParityDB database into RocksDB is not supported".into(),
+				)),
+			DatabaseSource::Custom { .. } =>
+				return Err(error::Error::Input("Only RocksDB databases can be converted".into())),
+		};
+		let target = self.target_path.clone().or(target).ok_or_else(|| {
+			error::Error::Input("Cannot determine the target path, use `--target-path`".into())
+		})?;
+
+		info!("Converting {} into {}", source.display(), target.display());
+		let copied = convert::convert_rocksdb_to_paritydb::<B>(&source, cache_size, &target)?;
+		info!("Copied {} entries", copied);
+
+		let target = DatabaseSource::ParityDb { path: target };
+		let verified = convert::verify_state_roots::<B>(&target, self.verify_blocks)?;
+		info!("Verified the state of {} blocks", verified.len());
+		Ok(())
+	}
+}
+
+/// The ParityDB database next to the RocksDB database at `rocksdb_path`.
+fn paritydb_path(rocksdb_path: &Path) -> Option<PathBuf> {
+	let role_dir = rocksdb_path.file_name()?;
+	Some(rocksdb_path.parent()?.parent()?.join("paritydb").join(role_dir))
+}
+
+impl CliConfiguration for DbConvertCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -18,7 +18,7 @@
 
 use crate::{error, error::Result};
 use clap::Args;
-use sc_service::config::KeystoreConfig;
+use sc_service::config::{KeystoreConfig, RemoteEndpoint};
 use sp_core::crypto::SecretString;
 use std::{
 	fs,
@@ -56,6 +56,17 @@ pub struct KeystoreParams {
 		conflicts_with_all = &["password_interactive", "password"]
 	)]
 	pub password_filename: Option<PathBuf>,
+
+	/// Use a remote signer holding the keys in a separate process instead of a local keystore.
+	///
+	/// Given as `unix:<PATH>` for a Unix socket or `http://<HOST>:<PORT>[/PATH]` for an HTTP
+	/// server. See `sc_keystore::remote` for the protocol.
+	#[arg(
+		long,
+		value_name = "URL",
+		conflicts_with_all = &["keystore_path", "password_interactive", "password", "password_filename"]
+	)]
+	pub keystore_remote: Option<RemoteEndpoint>,
 }
 
 /// Parse a secret string, returning a displayable error.
@@ -66,6 +77,10 @@ pub fn secret_string_from_str(s: &str) -> std::result::Result<SecretString, Stri
 impl KeystoreParams {
 	/// Get the keystore configuration for the parameters
 	pub fn keystore_config(&self, config_dir: &Path) -> Result<KeystoreConfig> {
+		if let Some(endpoint) = &self.keystore_remote {
+			return Ok(KeystoreConfig::Remote { endpoint: endpoint.clone() })
+		}
+
 		let password = if self.password_interactive {
 			Some(SecretString::new(input_keystore_password()?))
 		} else if let Some(ref file) = self.password_filename {
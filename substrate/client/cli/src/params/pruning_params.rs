@@ -45,6 +45,14 @@ pub struct PruningParams {
 	#[arg(alias = "pruning", long, value_name = "PRUNING_MODE")]
 	pub state_pruning: Option<DatabasePruningMode>,
 
+	/// Keep the state of every finalized block with a number that is a multiple of this interval,
+	/// in addition to the last `NUMBER` of finalized blocks kept by `--state-pruning`.
+	///
+	/// Only the checkpoints finalized after the interval is first set are kept. The interval is
+	/// stored in the database, so it is fine to drop this CLI flag for subsequent runs.
+	#[arg(long, value_name = "BLOCKS", requires = "state_pruning")]
+	pub state_checkpoint_interval: Option<u32>,
+
 	/// Specify the blocks pruning mode.
 	///
 	/// This mode specifies when the block's body (including justifications)
@@ -69,7 +77,14 @@ pub struct PruningParams {
 impl PruningParams {
 	/// Get the pruning value from the parameters
 	pub fn state_pruning(&self) -> error::Result<Option<PruningMode>> {
-		Ok(self.state_pruning.map(|v| v.into()))
+		match (self.state_pruning, self.state_checkpoint_interval) {
+			(_, Some(0)) => Err("The state checkpoint interval must be greater than zero".into()),
+			(Some(DatabasePruningMode::Custom(n)), Some(interval)) =>
+				Ok(Some(PruningMode::blocks_pruning_with_checkpoints(n, interval))),
+			(_, Some(_)) =>
+				Err("State checkpoints require `--state-pruning` to be a NUMBER of blocks".into()),
+			(state_pruning, None) => Ok(state_pruning.map(|v| v.into())),
+		}
 	}
 
 	/// Get the block pruning value from the parameters
@@ -162,4 +177,24 @@ mod tests {
 		assert!(matches!(dbg!(pruning.state_pruning), Some(DatabasePruningMode::ArchiveCanonical)));
 		assert!(matches!(pruning.blocks_pruning, DatabasePruningMode::ArchiveCanonical));
 	}
+
+	#[test]
+	fn state_checkpoint_interval_requires_blocks_pruning() {
+		let Cli { pruning } =
+			Cli::parse_from(["", "--state-pruning=1000", "--state-checkpoint-interval=14400"]);
+		assert_eq!(
+			pruning.state_pruning().unwrap(),
+			Some(PruningMode::blocks_pruning_with_checkpoints(1000, 14400))
+		);
+
+		let Cli { pruning } =
+			Cli::parse_from(["", "--state-pruning=archive", "--state-checkpoint-interval=14400"]);
+		assert!(pruning.state_pruning().is_err());
+
+		let Cli { pruning } =
+			Cli::parse_from(["", "--state-pruning=1000", "--state-checkpoint-interval=0"]);
+		assert!(pruning.state_pruning().is_err());
+
+		assert!(Cli::try_parse_from(["", "--state-checkpoint-interval=14400"]).is_err());
+	}
 }
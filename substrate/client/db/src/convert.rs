@@ -0,0 +1,553 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Offline conversion of a RocksDB database into a ParityDB database.
+//!
+//! Every column is streamed from the source into the target in batches. The position of the last
+//! copied entry is committed to the target together with each batch, so that an interrupted
+//! conversion resumes where it stopped. The meta column is copied last and the position is
+//! removed once everything has been copied.
+//!
+//! Converting a ParityDB database into a RocksDB database is not supported and is rejected by the
+//! `db convert` command: ParityDB does not keep the keys of its hash indexed columns, so they can
+//! not be enumerated.
+
+use crate::{
+	columns,
+	utils::{self, DatabaseType, OpenDbError, NUM_COLUMNS},
+	DatabaseSource, DbHash, DB_HASH_LEN,
+};
+use codec::{Decode, Encode};
+use hash_db::{HashDBRef, Hasher, Prefix};
+use kvdb::KeyValueDB;
+use log::info;
+use sc_state_db::{MetaDb, StateDb};
+use sp_blockchain::{Error, Result};
+use sp_core::storage::{well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX, ChildInfo};
+use sp_database::{Database, Transaction};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, HashingFor, Header as HeaderT, NumberFor, UniqueSaturatedInto},
+};
+use sp_trie::{prefixed_key, trie_types::TrieDBBuilder, DBValue, KeySpacedDB, Trie};
+use std::{io, path::Path};
+
+/// The key of the conversion progress in the meta column of the target.
+const PROGRESS_KEY: &[u8] = b"convert_progress";
+
+/// The amount of keys and values written to the target per transaction.
+const BATCH_SIZE: usize = 32 * 1024 * 1024;
+
+/// The partition of a column that holds the empty key.
+///
+/// The other partitions hold the keys starting with the byte preceding their index.
+const EMPTY_KEY_PARTITION: u16 = 0;
+
+/// The last partition of a column.
+const LAST_PARTITION: u16 = 256;
+
+/// The position of the last entry copied into the target.
+#[derive(Encode, Decode)]
+struct Progress {
+	column: u32,
+	partition: u16,
+	key: Vec<u8>,
+}
+
+/// The columns in the order they are copied, with the meta column last.
+fn column_order() -> Vec<u32> {
+	(0..NUM_COLUMNS)
+		.filter(|column| *column != columns::META)
+		.chain([columns::META])
+		.collect()
+}
+
+/// Copy the RocksDB database at `source` into a ParityDB database at `target`.
+///
+/// Only archive databases can be converted, as the pruning journals of the state are specific to
+/// the backend. An interrupted conversion into `target` is resumed. Returns the number of entries
+/// copied by this call.
+pub fn convert_rocksdb_to_paritydb<Block: BlockT>(
+	source: &Path,
+	cache_size: usize,
+	target: &Path,
+) -> Result<u64> {
+	let target_path = target;
+	let source =
+		utils::open_kvdb_rocksdb_raw::<Block>(source, DatabaseType::Full, false, cache_size)?;
+
+	let (_, state_db) =
+		StateDb::<Block::Hash, Vec<u8>, _>::open(SourceMetaDb(&source), None, false, false)
+			.map_err(Error::from_state_db)?;
+	let pruning = state_db.pruning_mode();
+	if !pruning.is_archive() {
+		return Err(Error::Backend(format!(
+			"Only archive databases can be converted, the state pruning mode is {:?}",
+			pruning
+		)))
+	}
+
+	let target = crate::parity_db::open::<DbHash>(target, DatabaseType::Full, true, false)
+		.map_err(OpenDbError::from)?;
+	let resume = match target.get(columns::META, PROGRESS_KEY) {
+		Some(progress) => Some(Progress::decode(&mut &progress[..]).map_err(|e| {
+			Error::Backend(format!("Invalid progress of the previous conversion: {}", e))
+		})?),
+		None if target.get(columns::META, utils::meta_keys::TYPE).is_some() =>
+			return Err(Error::Backend(format!(
+				"{} already contains a converted database",
+				target_path.display()
+			))),
+		None => None,
+	};
+
+	let order = column_order();
+	let first = match &resume {
+		Some(progress) => {
+			info!(
+				target: "db",
+				"Resuming the conversion at column {}, partition {}",
+				progress.column,
+				progress.partition,
+			);
+			order.iter().position(|column| *column == progress.column).ok_or_else(|| {
+				Error::Backend(format!(
+					"Invalid column {} in the conversion progress",
+					progress.column
+				))
+			})?
+		},
+		None => 0,
+	};
+
+	let mut batch = Batch { target: &*target, transaction: Transaction::new(), size: 0, copied: 0 };
+	let mut resume = resume;
+	for &column in &order[first..] {
+		let position = resume.take().map(|progress| (progress.partition, progress.key));
+		copy_column::<Block>(&source, column, position, &mut batch)?;
+		info!(target: "db", "Copied column {}", column);
+	}
+	batch.commit(None)?;
+	Ok(batch.copied)
+}
+
+/// Copy the partitions of `column` from the given position on.
+fn copy_column<Block: BlockT>(
+	source: &kvdb_rocksdb::Database,
+	column: u32,
+	position: Option<(u16, Vec<u8>)>,
+	batch: &mut Batch,
+) -> Result<()> {
+	let (first, mut copied_key) = match position {
+		Some((partition, key)) => (partition, Some(key)),
+		None => (EMPTY_KEY_PARTITION, None),
+	};
+	let read_error =
+		|e: io::Error| Error::Backend(format!("Failed to read column {}: {}", column, e));
+
+	for partition in first..=LAST_PARTITION {
+		let copied_key = copied_key.take();
+		let prefix = [partition.saturating_sub(1) as u8];
+		let entries: Box<dyn Iterator<Item = io::Result<(Vec<u8>, DBValue)>> + '_> =
+			if partition == EMPTY_KEY_PARTITION {
+				let value = source.get(column, &[]).transpose();
+				Box::new(value.into_iter().map(|value| value.map(|value| (Vec::new(), value))))
+			} else {
+				Box::new(
+					source
+						.iter_with_prefix(column, &prefix)
+						.map(|entry| entry.map(|(key, value)| (key.to_vec(), value))),
+				)
+			};
+
+		for entry in entries {
+			let (key, value) = entry.map_err(read_error)?;
+			// Entries are iterated in the order of their keys.
+			if copied_key.as_ref().map_or(false, |copied| key <= *copied) {
+				continue
+			}
+			copy_entry::<Block>(source, column, &key, value, batch)?;
+			if batch.size >= BATCH_SIZE {
+				batch.commit(Some(Progress { column, partition, key }))?;
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Copy a single entry, translating the layouts that differ between the backends.
+fn copy_entry<Block: BlockT>(
+	source: &kvdb_rocksdb::Database,
+	column: u32,
+	key: &[u8],
+	value: DBValue,
+	batch: &mut Batch,
+) -> Result<()> {
+	match column {
+		// RocksDB prefixes the hashes of the trie nodes with their position in the trie.
+		columns::STATE => batch.push(column, trie_node_key(key), value),
+		columns::STATE_META =>
+			match sc_state_db::map_journal_keys::<Block::Hash, Vec<u8>>(key, &value, |node| {
+				trie_node_key(&node).to_vec()
+			}) {
+				Some(journal) => batch.push(
+					column,
+					key,
+					journal.map_err(|e| {
+						Error::Backend(format!("Failed to decode a state journal entry: {}", e))
+					})?,
+				),
+				None => batch.push(column, key, value),
+			},
+		// RocksDB keeps the reference counter next to the value, ParityDB counts the insertions.
+		columns::TRANSACTION => {
+			if key.len() == DB_HASH_LEN + 1 && key.last() == Some(&0) {
+				return Ok(())
+			}
+			let mut counter_key = key.to_vec();
+			counter_key.push(0);
+			let references = match source.get(column, &counter_key) {
+				Ok(Some(counter)) => u32::from_le_bytes(counter.try_into().map_err(|_| {
+					Error::Backend("Unexpected transaction reference counter".into())
+				})?),
+				Ok(None) => 1,
+				Err(e) =>
+					return Err(Error::Backend(format!("Failed to read column {}: {}", column, e))),
+			};
+			for _ in 0..references {
+				batch.push(column, key, value.clone());
+			}
+		},
+		_ => batch.push(column, key, value),
+	}
+	Ok(())
+}
+
+/// The key of a trie node in ParityDB, see `Database::sanitize_key`.
+fn trie_node_key(key: &[u8]) -> &[u8] {
+	&key[key.len().saturating_sub(DB_HASH_LEN)..]
+}
+
+/// A transaction to the target, committed once it holds [`BATCH_SIZE`] bytes.
+struct Batch<'a> {
+	target: &'a dyn Database<DbHash>,
+	transaction: Transaction<DbHash>,
+	size: usize,
+	copied: u64,
+}
+
+impl<'a> Batch<'a> {
+	fn push(&mut self, column: u32, key: &[u8], value: DBValue) {
+		self.size += key.len() + value.len();
+		self.copied += 1;
+		self.transaction.set_from_vec(column, key, value);
+	}
+
+	/// Commit the pending entries together with the progress, removing the progress if `None`.
+	fn commit(&mut self, progress: Option<Progress>) -> Result<()> {
+		let mut transaction = std::mem::take(&mut self.transaction);
+		match progress {
+			Some(progress) =>
+				transaction.set_from_vec(columns::META, PROGRESS_KEY, progress.encode()),
+			None => transaction.remove(columns::META, PROGRESS_KEY),
+		}
+		self.target.commit(transaction).map_err(|e| {
+			Error::Backend(format!("Failed to write to the target database: {}", e))
+		})?;
+		self.size = 0;
+		info!(target: "db", "Copied {} entries", self.copied);
+		Ok(())
+	}
+}
+
+/// Reads the state meta column of the source for [`StateDb`].
+struct SourceMetaDb<'a>(&'a kvdb_rocksdb::Database);
+
+impl<'a> MetaDb for SourceMetaDb<'a> {
+	type Error = io::Error;
+
+	fn get_meta(&self, key: &[u8]) -> io::Result<Option<DBValue>> {
+		self.0.get(columns::STATE_META, key)
+	}
+}
+
+/// Verify the state of `samples` blocks spread evenly over the finalized chain of a database.
+///
+/// Every node of the state trie in the header of a sampled block, including the nodes of its
+/// child tries and the values stored outside of the nodes, is read and checked against its hash.
+/// This reads the whole state of every sampled block. Returns the verified blocks.
+pub fn verify_state_roots<Block: BlockT>(
+	source: &DatabaseSource,
+	samples: u32,
+) -> Result<Vec<(NumberFor<Block>, Block::Hash)>> {
+	let db = utils::open_database::<Block>(source, DatabaseType::Full, false)?;
+	let meta = utils::read_meta::<Block>(&*db, columns::HEADER)?;
+	if meta.genesis_hash == Default::default() {
+		return Err(Error::Backend("The database is empty".into()))
+	}
+
+	// Blocks in a gap have no state.
+	let lowest: u64 = meta.block_gap.map_or(0, |gap| gap.end.unique_saturated_into() + 1);
+	let highest: u64 = meta.finalized_number.unique_saturated_into();
+	let numbers = sample_numbers(lowest, highest, samples);
+
+	let state = CheckedState { db: &*db, prefix_keys: !db.supports_ref_counting() };
+	let mut verified = Vec::with_capacity(numbers.len());
+	for number in numbers {
+		let number: NumberFor<Block> = number.unique_saturated_into();
+		let header = utils::read_header::<Block>(
+			&*db,
+			columns::KEY_LOOKUP,
+			columns::HEADER,
+			BlockId::Number(number),
+		)?
+		.ok_or_else(|| Error::Backend(format!("Missing header of block #{}", number)))?;
+		verify_trie::<Block>(&state, header.state_root())
+			.map_err(|e| Error::Backend(format!("State of block #{} is invalid: {}", number, e)))?;
+		info!(target: "db", "Verified the state of block #{}", number);
+		verified.push((number, header.hash()));
+	}
+	Ok(verified)
+}
+
+/// Up to `samples` block numbers spread evenly from `lowest` to `highest`.
+fn sample_numbers(lowest: u64, highest: u64, samples: u32) -> Vec<u64> {
+	let mut numbers = match samples {
+		0 => Vec::new(),
+		1 => vec![highest],
+		samples => {
+			let range = highest.saturating_sub(lowest) as u128;
+			(0..samples as u128)
+				.map(|i| lowest + (range * i / (samples as u128 - 1)) as u64)
+				.collect()
+		},
+	};
+	numbers.dedup();
+	numbers
+}
+
+/// Walk the trie at `root` and the child tries referenced from it.
+fn verify_trie<Block: BlockT>(
+	state: &CheckedState,
+	root: &Block::Hash,
+) -> std::result::Result<(), String> {
+	let child_roots = walk_trie::<Block>(state, root)?;
+	for (child_info, child_root) in child_roots {
+		let keyspaced = KeySpacedDB::new(state, child_info.keyspace());
+		walk_trie::<Block>(&keyspaced, &child_root)?;
+	}
+	Ok(())
+}
+
+/// Read every value of the trie at `root`, returning the child tries it references.
+fn walk_trie<Block: BlockT>(
+	db: &dyn HashDBRef<HashingFor<Block>, DBValue>,
+	root: &Block::Hash,
+) -> std::result::Result<Vec<(ChildInfo, Block::Hash)>, String> {
+	let trie = TrieDBBuilder::<HashingFor<Block>>::new(db, root).build();
+	let mut child_roots = Vec::new();
+	for item in trie.iter().map_err(|e| e.to_string())? {
+		let (key, value) = item.map_err(|e| e.to_string())?;
+		if let Some(storage_key) = key.strip_prefix(DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+			let mut child_root = Block::Hash::default();
+			if value.len() != child_root.as_ref().len() {
+				return Err(format!("Invalid root of child trie {:?}", storage_key))
+			}
+			child_root.as_mut().copy_from_slice(&value);
+			child_roots.push((ChildInfo::new_default(storage_key), child_root));
+		}
+	}
+	Ok(child_roots)
+}
+
+/// The trie nodes of a database, treating the nodes that do not match their hash as missing.
+struct CheckedState<'a> {
+	db: &'a dyn Database<DbHash>,
+	prefix_keys: bool,
+}
+
+impl<'a, H: Hasher> HashDBRef<H, DBValue> for CheckedState<'a> {
+	fn get(&self, key: &H::Out, prefix: Prefix) -> Option<DBValue> {
+		let value = if self.prefix_keys {
+			self.db.get(columns::STATE, &prefixed_key::<H>(key, prefix))
+		} else {
+			self.db.get(columns::STATE, key.as_ref())
+		}?;
+		(H::hash(&value) == *key).then_some(value)
+	}
+
+	fn contains(&self, key: &H::Out, prefix: Prefix) -> bool {
+		HashDBRef::<H, DBValue>::get(self, key, prefix).is_some()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		tests::{insert_block, Block, UncheckedXt},
+		Backend, BlocksPruning, DatabaseSettings, PruningMode,
+	};
+	use sc_client_api::{
+		backend::Backend as _,
+		blockchain::{Backend as _, HeaderBackend},
+	};
+	use sp_core::H256;
+	use sp_state_machine::{Backend as _, IndexOperation};
+	use tempfile::TempDir;
+
+	fn open_backend(source: DatabaseSource) -> Backend<Block> {
+		let settings = DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: Some(PruningMode::ArchiveAll),
+			source,
+			blocks_pruning: BlocksPruning::KeepAll,
+		};
+		Backend::new(settings, 0).unwrap()
+	}
+
+	/// The hashes of the blocks of an archive RocksDB database created at `path`, and the hash and
+	/// encoding of its indexed transaction.
+	fn create_source(path: &Path) -> (Vec<H256>, H256, Vec<u8>) {
+		let extrinsic = UncheckedXt::new_transaction(0.into(), ()).encode();
+		let indexed = <HashingFor<Block>>::hash(&extrinsic[1..]);
+		let db = utils::open_kvdb_rocksdb_raw::<Block>(path, DatabaseType::Full, true, 16).unwrap();
+		let backend = open_backend(DatabaseSource::Custom {
+			db: sp_database::as_database(db),
+			require_create_flag: false,
+		});
+		let index = vec![IndexOperation::Insert {
+			extrinsic: 0,
+			hash: indexed.as_ref().to_vec(),
+			size: (extrinsic.len() - 1) as u32,
+		}];
+		let body = vec![UncheckedXt::new_transaction(0.into(), ())];
+		let mut hashes = vec![insert_block(
+			&backend,
+			0,
+			H256::default(),
+			None,
+			H256::default(),
+			body,
+			Some(index),
+		)
+		.unwrap()];
+		for number in 1..4 {
+			let parent = hashes[number as usize - 1];
+			hashes.push(
+				insert_block(&backend, number, parent, None, H256::default(), vec![], None)
+					.unwrap(),
+			);
+		}
+		backend.finalize_block(hashes[3], None).unwrap();
+		(hashes, indexed, extrinsic)
+	}
+
+	/// Check that the ParityDB database at `path` holds the database of [`create_source`].
+	fn check_target(path: &Path, (hashes, indexed, extrinsic): &(Vec<H256>, H256, Vec<u8>)) {
+		let target = DatabaseSource::ParityDb { path: path.to_path_buf() };
+		let verified = verify_state_roots::<Block>(&target, 3).unwrap();
+		assert_eq!(verified, vec![(0, hashes[0]), (1, hashes[1]), (3, hashes[3])]);
+
+		let backend = open_backend(target);
+		let info = backend.blockchain().info();
+		assert_eq!((info.best_hash, info.finalized_hash), (hashes[3], hashes[3]));
+		assert_eq!(
+			backend.blockchain().indexed_transaction(*indexed).unwrap(),
+			Some(extrinsic[1..].to_vec())
+		);
+		let state = backend.state_at(hashes[3]).unwrap();
+		assert_eq!(state.storage(hashes[2].as_ref()).unwrap(), Some(hashes[2].as_ref().to_vec()));
+	}
+
+	#[test]
+	fn archive_database_is_converted() {
+		let dir = TempDir::new().unwrap();
+		let rocksdb_path = dir.path().join("db");
+		let paritydb_path = dir.path().join("paritydb");
+		let source = create_source(&rocksdb_path);
+
+		let copied =
+			convert_rocksdb_to_paritydb::<Block>(&rocksdb_path, 16, &paritydb_path).unwrap();
+		assert!(copied > 0);
+		assert!(convert_rocksdb_to_paritydb::<Block>(&rocksdb_path, 16, &paritydb_path).is_err());
+
+		check_target(&paritydb_path, &source);
+	}
+
+	#[test]
+	fn interrupted_conversion_is_resumed() {
+		let dir = TempDir::new().unwrap();
+		let rocksdb_path = dir.path().join("db");
+		let source = create_source(&rocksdb_path);
+
+		let complete_path = dir.path().join("complete");
+		let all = convert_rocksdb_to_paritydb::<Block>(&rocksdb_path, 16, &complete_path).unwrap();
+
+		// Copy the columns preceding the headers and the first two headers, as if the conversion
+		// had been interrupted right after committing them.
+		let paritydb_path = dir.path().join("paritydb");
+		let copied_before = {
+			let rocksdb =
+				utils::open_kvdb_rocksdb_raw::<Block>(&rocksdb_path, DatabaseType::Full, false, 16)
+					.unwrap();
+			let target =
+				crate::parity_db::open::<DbHash>(&paritydb_path, DatabaseType::Full, true, false)
+					.unwrap();
+			let mut batch =
+				Batch { target: &*target, transaction: Transaction::new(), size: 0, copied: 0 };
+			let order = column_order();
+			let header = order.iter().position(|column| *column == columns::HEADER).unwrap();
+			for &column in &order[..header] {
+				copy_column::<Block>(&rocksdb, column, None, &mut batch).unwrap();
+			}
+			let headers = rocksdb
+				.iter(columns::HEADER)
+				.map(|entry| entry.map(|(key, value)| (key.to_vec(), value)))
+				.take(2)
+				.collect::<io::Result<Vec<_>>>()
+				.unwrap();
+			assert_eq!(headers.len(), 2);
+			for (key, value) in headers.iter().cloned() {
+				copy_entry::<Block>(&rocksdb, columns::HEADER, &key, value, &mut batch).unwrap();
+			}
+			let key = headers[1].0.clone();
+			let partition = key.first().map_or(EMPTY_KEY_PARTITION, |byte| *byte as u16 + 1);
+			batch
+				.commit(Some(Progress { column: columns::HEADER, partition, key }))
+				.unwrap();
+			assert!(target.get(columns::META, PROGRESS_KEY).is_some());
+			batch.copied
+		};
+
+		let copied =
+			convert_rocksdb_to_paritydb::<Block>(&rocksdb_path, 16, &paritydb_path).unwrap();
+		assert_eq!(copied_before + copied, all);
+
+		check_target(&paritydb_path, &source);
+	}
+
+	#[test]
+	fn sampled_numbers_are_spread_evenly() {
+		assert_eq!(sample_numbers(0, 100, 0), Vec::<u64>::new());
+		assert_eq!(sample_numbers(0, 100, 1), vec![100]);
+		assert_eq!(sample_numbers(0, 100, 3), vec![0, 50, 100]);
+		assert_eq!(sample_numbers(10, 12, 5), vec![10, 11, 12]);
+		assert_eq!(sample_numbers(5, 5, 4), vec![5]);
+	}
+}
@@ -31,6 +31,8 @@
 pub mod offchain;
 
 pub mod bench;
+#[cfg(any(feature = "rocksdb", test))]
+pub mod convert;
 
 mod children;
 mod parity_db;
@@ -2501,7 +2503,7 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 					let state = RefTrackingState::new(db_state, self.storage.clone(), Some(hash));
 					Ok(RecordStatsState::new(state, Some(hash), self.state_usage.clone()))
 				} else {
-					Err(sp_blockchain::Error::UnknownBlock(format!(
+					Err(sp_blockchain::Error::StateUnavailable(format!(
 						"State already discarded for {hash:?}",
 					)))
 				}
@@ -2615,7 +2617,7 @@ pub(crate) mod tests {
 	const CONS0_ENGINE_ID: ConsensusEngineId = *b"CON0";
 	const CONS1_ENGINE_ID: ConsensusEngineId = *b"CON1";
 
-	type UncheckedXt = TestXt<MockCallU64, ()>;
+	pub(crate) type UncheckedXt = TestXt<MockCallU64, ()>;
 	pub(crate) type Block = RawBlock<UncheckedXt>;
 
 	pub fn insert_header(
@@ -3690,6 +3692,99 @@ pub(crate) mod tests {
 		assert_ne!(block0_hash, block1_hash);
 	}
 
+	#[test]
+	fn checkpoint_states_are_kept() {
+		let state_version = StateVersion::default();
+		let backend = Backend::<Block>::new(
+			DatabaseSettings {
+				trie_cache_maximum_size: Some(16 * 1024 * 1024),
+				state_pruning: Some(PruningMode::blocks_pruning_with_checkpoints(1, 2)),
+				source: DatabaseSource::Custom {
+					db: sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS)),
+					require_create_flag: true,
+				},
+				blocks_pruning: BlocksPruning::KeepFinalized,
+			},
+			0,
+		)
+		.unwrap();
+
+		let genesis_hash = {
+			let mut op = backend.begin_operation().unwrap();
+			backend.begin_state_operation(&mut op, Default::default()).unwrap();
+			let mut header = Header {
+				number: 0,
+				parent_hash: Default::default(),
+				state_root: Default::default(),
+				digest: Default::default(),
+				extrinsics_root: Default::default(),
+			};
+
+			let storage = vec![(b"test".to_vec(), 0u64.encode())];
+			header.state_root = op
+				.old_state
+				.storage_root(storage.iter().map(|(x, y)| (&x[..], Some(&y[..]))), state_version)
+				.0
+				.into();
+			let hash = header.hash();
+
+			op.reset_storage(
+				Storage {
+					top: storage.into_iter().collect(),
+					children_default: Default::default(),
+				},
+				state_version,
+			)
+			.unwrap();
+			op.set_block_data(header, Some(vec![]), None, None, NewBlockState::Final)
+				.unwrap();
+			backend.commit_operation(op).unwrap();
+			hash
+		};
+
+		let mut hashes = vec![genesis_hash];
+		let mut parent_hash = genesis_hash;
+		for number in 1..=5u64 {
+			let mut op = backend.begin_operation().unwrap();
+			backend.begin_state_operation(&mut op, parent_hash).unwrap();
+			let mut header = Header {
+				number,
+				parent_hash,
+				state_root: Default::default(),
+				digest: Default::default(),
+				extrinsics_root: Default::default(),
+			};
+
+			let storage = vec![(b"test".to_vec(), Some(number.encode()))];
+			let (root, overlay) = op.old_state.storage_root(
+				storage.iter().map(|(k, v)| (k.as_slice(), v.as_ref().map(|v| &v[..]))),
+				state_version,
+			);
+			op.update_db_storage(overlay).unwrap();
+			header.state_root = root.into();
+			parent_hash = header.hash();
+
+			op.update_storage(storage, Vec::new()).unwrap();
+			op.set_block_data(header, Some(vec![]), None, None, NewBlockState::Final)
+				.unwrap();
+			backend.commit_operation(op).unwrap();
+			hashes.push(parent_hash);
+		}
+
+		for number in [1, 3] {
+			assert!(!backend.have_state_at(hashes[number], number as u64));
+			assert!(matches!(
+				backend.state_at(hashes[number]),
+				Err(sp_blockchain::Error::StateUnavailable(_))
+			));
+		}
+		for number in [2, 4, 5] {
+			assert!(backend.have_state_at(hashes[number], number as u64));
+			let state = backend.state_at(hashes[number]).unwrap();
+			assert_eq!(state.storage(b"test").unwrap(), Some((number as u64).encode()));
+		}
+	}
+
 	#[test]
 	fn test_finalize_non_sequential() {
 		let backend = Backend::<Block>::new_test(10, 10);
@@ -309,6 +309,17 @@ fn open_kvdb_rocksdb<Block: BlockT>(
 	create: bool,
 	cache_size: usize,
 ) -> OpenDbResult {
+	Ok(sp_database::as_database(open_kvdb_rocksdb_raw::<Block>(path, db_type, create, cache_size)?))
+}
+
+/// Open a RocksDB database without wrapping it into a [`Database`].
+#[cfg(any(feature = "rocksdb", test))]
+pub(crate) fn open_kvdb_rocksdb_raw<Block: BlockT>(
+	path: &Path,
+	db_type: DatabaseType,
+	create: bool,
+	cache_size: usize,
+) -> Result<kvdb_rocksdb::Database, OpenDbError> {
 	// first upgrade database to required version
 	match crate::upgrade::upgrade_db::<Block>(path, db_type) {
 		// in case of missing version file, assume that database simply does not exist at given
@@ -349,7 +360,7 @@ fn open_kvdb_rocksdb<Block: BlockT>(
 	let db = kvdb_rocksdb::Database::open(&db_config, path)?;
 	// write database version only after the database is successfully opened
 	crate::upgrade::update_version(path)?;
-	Ok(sp_database::as_database(db))
+	Ok(db)
 }
 
 #[cfg(not(any(feature = "rocksdb", test)))]
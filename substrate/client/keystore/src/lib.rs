@@ -25,7 +25,9 @@ use std::io;
 
 /// Local keystore implementation
 mod local;
+pub mod remote;
 pub use local::LocalKeystore;
+pub use remote::RemoteKeystore;
 pub use sp_keystore::Keystore;
 
 /// Keystore error.
@@ -0,0 +1,855 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Remote keystore implementation.
+//!
+//! The [`RemoteKeystore`] keeps no secrets itself but forwards every operation to a signer in a
+//! separate process, such that a compromised node cannot exfiltrate the keys. The
+//! [`RemoteSigner`] serves these requests from any other [`Keystore`], usually a
+//! [`LocalKeystore`](crate::LocalKeystore), and consults its [`SlashingProtection`] before
+//! signing anything.
+//!
+//! # Protocol
+//!
+//! Every operation is a single request answered by a single response. The signer is reachable at
+//! a [`RemoteEndpoint`]:
+//!
+//! - `unix:<PATH>`: the node connects to the Unix socket, writes the request as one line of JSON
+//!   terminated by `\n` and reads the response the same way.
+//! - `http://<HOST>:<PORT>[/PATH]`: the node `POST`s the request as a JSON body and receives the
+//!   response as the JSON body of a `200 OK`. There is no authentication or encryption, so the
+//!   server must only be reachable from the node, for example through a TLS terminating proxy.
+//!
+//! Connections are kept open for further requests, which are sent one after another. The node
+//! opens a separate connection for a request while another one is in flight, and reconnects if
+//! the signer closed an idle connection.
+//!
+//! A request names its `method` and carries its `params`:
+//!
+//! ```json
+//! {"method": "sign", "params": {"key_type": "gran", "crypto": "ed25", "public": "0x…", "message": "0x…"}}
+//! ```
+//!
+//! Key type and crypto type ids are strings of four characters, while public keys, messages and
+//! signatures are `0x`-prefixed hex strings. The methods are listed by [`Request`]. A response
+//! either carries the `result` of the method, or an `error` with a `code` and a `message` as
+//! listed by [`RemoteError`]:
+//!
+//! ```json
+//! {"result": "0x…"}
+//! {"error": {"code": "slashing_protection", "message": "…"}}
+//! ```
+//!
+//! Signatures are SCALE encoded. The inputs of sr25519 VRFs are transferred as the SCALE encoded
+//! [`VrfTranscriptData`](sr25519::vrf::VrfTranscriptData) they are built from, see
+//! [`Request::Sr25519VrfSign`]. Bandersnatch VRFs are not supported.
+//!
+//! # Blocking
+//!
+//! The [`Keystore`] trait is synchronous, so every operation blocks the calling thread until the
+//! signer responded, including the async tasks of consensus engines. Every request is limited
+//! to the timeout of the [`RemoteKeystore`], [`DEFAULT_TIMEOUT`] unless configured otherwise, so
+//! the signer should run close to the node.
+
+use parking_lot::Mutex;
+use sp_core::{
+	crypto::{key_types, ByteArray, CryptoTypeId, KeyTypeId},
+	ecdsa, ed25519, sr25519,
+};
+use sp_keystore::{Error as TraitError, Keystore, KeystorePtr};
+use std::{
+	io::{self, Read, Write},
+	net::{TcpStream, ToSocketAddrs},
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+sp_keystore::bandersnatch_experimental_enabled! {
+use sp_core::bandersnatch;
+}
+
+sp_keystore::bls_experimental_enabled! {
+use sp_core::{bls381, ecdsa_bls381};
+}
+
+mod protection;
+mod protocol;
+mod signer;
+
+pub use protection::{
+	babe_vrf_signing_domain, beefy_signing_domain, grandpa_signing_domain, DoubleSignProtection,
+	SigningDomain, SigningDomainFn, VrfSigningDomainFn, HISTORY_DEPTH,
+};
+pub use protocol::{KeyType, RemoteEndpoint, RemoteError, Request, Response, MAX_MESSAGE_SIZE};
+pub use signer::{
+	RemoteSigner, SignerListener, SlashingProtection, MAX_CONNECTIONS, MAX_VRF_LABELS,
+};
+
+const LOG_TARGET: &str = "remote-keystore";
+
+/// The default timeout of a request to the remote signer.
+///
+/// Short enough to not miss a slot of six seconds while waiting for an unresponsive signer.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A connection to the remote signer.
+enum Stream {
+	#[cfg(unix)]
+	Unix(std::os::unix::net::UnixStream),
+	Tcp(TcpStream),
+}
+
+impl Stream {
+	fn set_timeout(&self, timeout: Duration) -> io::Result<()> {
+		match self {
+			#[cfg(unix)]
+			Self::Unix(stream) => {
+				stream.set_read_timeout(Some(timeout))?;
+				stream.set_write_timeout(Some(timeout))
+			},
+			Self::Tcp(stream) => {
+				stream.set_read_timeout(Some(timeout))?;
+				stream.set_write_timeout(Some(timeout))
+			},
+		}
+	}
+}
+
+impl Read for Stream {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		match self {
+			#[cfg(unix)]
+			Self::Unix(stream) => stream.read(buf),
+			Self::Tcp(stream) => stream.read(buf),
+		}
+	}
+}
+
+impl Write for Stream {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		match self {
+			#[cfg(unix)]
+			Self::Unix(stream) => stream.write(buf),
+			Self::Tcp(stream) => stream.write(buf),
+		}
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		match self {
+			#[cfg(unix)]
+			Self::Unix(stream) => stream.flush(),
+			Self::Tcp(stream) => stream.flush(),
+		}
+	}
+}
+
+/// A keystore forwarding all operations to a remote signer.
+///
+/// See the [module documentation](self) for the protocol.
+pub struct RemoteKeystore {
+	endpoint: RemoteEndpoint,
+	timeout: Duration,
+	/// The connection kept open for the next request.
+	connection: Mutex<Option<Stream>>,
+}
+
+impl RemoteKeystore {
+	/// Create a keystore using the signer at `endpoint`.
+	///
+	/// No connection is made before the first operation.
+	pub fn new(endpoint: RemoteEndpoint) -> Self {
+		Self { endpoint, timeout: DEFAULT_TIMEOUT, connection: Mutex::new(None) }
+	}
+
+	/// Set the timeout of every request to the signer, including connecting to it.
+	pub fn with_timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = timeout;
+		self
+	}
+
+	/// The endpoint of the signer.
+	pub fn endpoint(&self) -> &RemoteEndpoint {
+		&self.endpoint
+	}
+
+	/// Send `request` to the signer and return the response.
+	///
+	/// Blocks for at most about the timeout.
+	pub fn request(&self, request: &Request) -> io::Result<Response> {
+		let deadline = Instant::now() + self.timeout;
+		// Another request is in flight on the kept connection.
+		let Some(mut connection) = self.connection.try_lock() else {
+			return self.exchange(&mut self.connect(deadline)?, request, deadline)
+		};
+
+		if let Some(stream) = connection.as_mut() {
+			match self.exchange(stream, request, deadline) {
+				Ok(response) => return Ok(response),
+				// The signer closed the idle connection, reconnect.
+				Err(e) if is_closed(&e) => (),
+				Err(e) => {
+					*connection = None;
+					return Err(e)
+				},
+			}
+		}
+		*connection = None;
+		let mut stream = self.connect(deadline)?;
+		let response = self.exchange(&mut stream, request, deadline)?;
+		*connection = Some(stream);
+		Ok(response)
+	}
+
+	fn connect(&self, deadline: Instant) -> io::Result<Stream> {
+		match &self.endpoint {
+			#[cfg(unix)]
+			RemoteEndpoint::Unix(path) => Ok(Stream::Unix(std::os::unix::net::UnixStream::connect(path)?)),
+			#[cfg(not(unix))]
+			RemoteEndpoint::Unix(_) => Err(io::Error::new(
+				io::ErrorKind::Unsupported,
+				"Unix sockets are not supported on this platform",
+			)),
+			RemoteEndpoint::Http { address, .. } => {
+				let mut error = io::Error::new(io::ErrorKind::NotFound, "unresolvable address");
+				for address in address.to_socket_addrs()? {
+					match TcpStream::connect_timeout(&address, remaining(deadline)?) {
+						Ok(stream) => return Ok(Stream::Tcp(stream)),
+						Err(e) => error = e,
+					}
+				}
+				Err(error)
+			},
+		}
+	}
+
+	fn exchange(
+		&self,
+		stream: &mut Stream,
+		request: &Request,
+		deadline: Instant,
+	) -> io::Result<Response> {
+		stream.set_timeout(remaining(deadline)?)?;
+		match &self.endpoint {
+			RemoteEndpoint::Unix(_) => {
+				protocol::write_line(stream, request)?;
+				protocol::read_line(stream)
+			},
+			RemoteEndpoint::Http { address, path } => {
+				protocol::write_http(
+					stream,
+					&format!("POST {} HTTP/1.1", path),
+					Some(address),
+					request,
+				)?;
+				let (status, response) = protocol::read_http(stream)?;
+				if status.split(' ').nth(1) != Some("200") {
+					return Err(io::Error::new(
+						io::ErrorKind::Other,
+						format!("unexpected response: {}", status),
+					))
+				}
+				Ok(response)
+			},
+		}
+	}
+
+	/// Send `request` and decode the result as `R`.
+	fn call<R: for<'de> serde::Deserialize<'de>>(
+		&self,
+		key_type: KeyTypeId,
+		request: Request,
+	) -> Result<R, TraitError> {
+		let response = self.request(&request).map_err(|e| {
+			log::warn!(target: LOG_TARGET, "Request to signer at {} failed: {}", self.endpoint, e);
+			TraitError::Unavailable
+		})?;
+		match response {
+			Response::Result(result) => serde_json::from_value(result)
+				.map_err(|e| TraitError::Other(format!("Invalid response of the signer: {}", e))),
+			Response::Error(error) => Err(error.into_trait_error(key_type)),
+		}
+	}
+
+	fn public_keys<P: ByteArray>(&self, key_type: KeyTypeId, crypto: CryptoTypeId) -> Vec<P> {
+		self.call::<Vec<sp_core::Bytes>>(
+			key_type,
+			Request::PublicKeys { key_type: key_type.0, crypto: crypto.0 },
+		)
+		.map(|keys| keys.into_iter().filter_map(|key| P::from_slice(&key).ok()).collect())
+		.unwrap_or_else(|e| {
+			log::warn!(target: LOG_TARGET, "Failed to list public keys: {}", e);
+			Vec::new()
+		})
+	}
+
+	fn generate_new<P: ByteArray>(
+		&self,
+		key_type: KeyTypeId,
+		crypto: CryptoTypeId,
+		seed: Option<&str>,
+	) -> Result<P, TraitError> {
+		let public = self.call::<sp_core::Bytes>(
+			key_type,
+			Request::GenerateNew {
+				key_type: key_type.0,
+				crypto: crypto.0,
+				seed: seed.map(Into::into),
+			},
+		)?;
+		P::from_slice(&public)
+			.map_err(|_| TraitError::Other("Invalid public key returned by the signer".into()))
+	}
+
+	fn decode_signature<S: codec::Decode>(
+		signature: Option<sp_core::Bytes>,
+	) -> Result<Option<S>, TraitError> {
+		signature
+			.map(|signature| {
+				S::decode(&mut &signature[..]).map_err(|_| {
+					TraitError::Other("Invalid signature returned by the signer".into())
+				})
+			})
+			.transpose()
+	}
+
+	fn sign<S: codec::Decode>(
+		&self,
+		key_type: KeyTypeId,
+		crypto: CryptoTypeId,
+		public: &[u8],
+		msg: &[u8],
+	) -> Result<Option<S>, TraitError> {
+		let signature = self.call(
+			key_type,
+			Request::Sign {
+				key_type: key_type.0,
+				crypto: crypto.0,
+				public: public.to_vec().into(),
+				message: msg.to_vec().into(),
+			},
+		)?;
+		Self::decode_signature(signature)
+	}
+
+	sp_keystore::bandersnatch_experimental_enabled! {
+		fn vrf_unsupported<T>() -> Result<T, TraitError> {
+			Err(TraitError::Other(
+				"Bandersnatch VRF operations are not supported by the remote keystore".into(),
+			))
+		}
+	}
+}
+
+/// Whether `error` means that the peer closed the connection.
+fn is_closed(error: &io::Error) -> bool {
+	matches!(
+		error.kind(),
+		io::ErrorKind::UnexpectedEof |
+			io::ErrorKind::BrokenPipe |
+			io::ErrorKind::ConnectionReset |
+			io::ErrorKind::ConnectionAborted
+	)
+}
+
+/// The time left until `deadline`.
+fn remaining(deadline: Instant) -> io::Result<Duration> {
+	deadline
+		.checked_duration_since(Instant::now())
+		.filter(|remaining| !remaining.is_zero())
+		.ok_or_else(|| io::ErrorKind::TimedOut.into())
+}
+
+impl Keystore for RemoteKeystore {
+	fn insert(&self, key_type: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
+		self.call::<()>(
+			key_type,
+			Request::Insert {
+				key_type: key_type.0,
+				suri: suri.into(),
+				public: public.to_vec().into(),
+			},
+		)
+		.map_err(|e| log::warn!(target: LOG_TARGET, "Failed to insert key: {}", e))
+	}
+
+	fn keys(&self, key_type: KeyTypeId) -> Result<Vec<Vec<u8>>, TraitError> {
+		let keys =
+			self.call::<Vec<sp_core::Bytes>>(key_type, Request::Keys { key_type: key_type.0 })?;
+		Ok(keys.into_iter().map(|key| key.0).collect())
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		let keys = public_keys
+			.iter()
+			.map(|(public, key_type)| (public.clone().into(), (*key_type).into()))
+			.collect();
+		// The key type is only used to report errors, which are not returned here.
+		self.call::<bool>(key_types::DUMMY, Request::HasKeys { keys })
+			.unwrap_or_else(|e| {
+				log::warn!(target: LOG_TARGET, "Failed to check for keys: {}", e);
+				false
+			})
+	}
+
+	fn sr25519_public_keys(&self, key_type: KeyTypeId) -> Vec<sr25519::Public> {
+		self.public_keys(key_type, sr25519::CRYPTO_ID)
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<sr25519::Public, TraitError> {
+		self.generate_new(key_type, sr25519::CRYPTO_ID, seed)
+	}
+
+	fn sr25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		msg: &[u8],
+	) -> Result<Option<sr25519::Signature>, TraitError> {
+		self.sign(key_type, sr25519::CRYPTO_ID, public.as_slice(), msg)
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		data: &sr25519::vrf::VrfSignData,
+	) -> Result<Option<sr25519::vrf::VrfSignature>, TraitError> {
+		let signature = self.call(
+			key_type,
+			Request::Sr25519VrfSign {
+				key_type: key_type.0,
+				public: public.to_raw_vec().into(),
+				data: codec::Encode::encode(data).into(),
+			},
+		)?;
+		Self::decode_signature(signature)
+	}
+
+	fn sr25519_vrf_pre_output(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		input: &sr25519::vrf::VrfInput,
+	) -> Result<Option<sr25519::vrf::VrfPreOutput>, TraitError> {
+		let pre_output = self.call(
+			key_type,
+			Request::Sr25519VrfPreOutput {
+				key_type: key_type.0,
+				public: public.to_raw_vec().into(),
+				input: codec::Encode::encode(input.data()).into(),
+			},
+		)?;
+		Self::decode_signature(pre_output)
+	}
+
+	fn ed25519_public_keys(&self, key_type: KeyTypeId) -> Vec<ed25519::Public> {
+		self.public_keys(key_type, ed25519::CRYPTO_ID)
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ed25519::Public, TraitError> {
+		self.generate_new(key_type, ed25519::CRYPTO_ID, seed)
+	}
+
+	fn ed25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ed25519::Public,
+		msg: &[u8],
+	) -> Result<Option<ed25519::Signature>, TraitError> {
+		self.sign(key_type, ed25519::CRYPTO_ID, public.as_slice(), msg)
+	}
+
+	fn ecdsa_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.public_keys(key_type, ecdsa::CRYPTO_ID)
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ecdsa::Public, TraitError> {
+		self.generate_new(key_type, ecdsa::CRYPTO_ID, seed)
+	}
+
+	fn ecdsa_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8],
+	) -> Result<Option<ecdsa::Signature>, TraitError> {
+		self.sign(key_type, ecdsa::CRYPTO_ID, public.as_slice(), msg)
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> Result<Option<ecdsa::Signature>, TraitError> {
+		let signature = self.call(
+			key_type,
+			Request::EcdsaSignPrehashed {
+				key_type: key_type.0,
+				public: public.to_raw_vec().into(),
+				message: msg.to_vec().into(),
+			},
+		)?;
+		Self::decode_signature(signature)
+	}
+
+	fn ecdsa_sign_with_keccak256(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8],
+	) -> Result<Option<ecdsa::Signature>, TraitError> {
+		let signature = self.call(
+			key_type,
+			Request::EcdsaSignWithKeccak256 {
+				key_type: key_type.0,
+				public: public.to_raw_vec().into(),
+				message: msg.to_vec().into(),
+			},
+		)?;
+		Self::decode_signature(signature)
+	}
+
+	sp_keystore::bandersnatch_experimental_enabled! {
+		fn bandersnatch_public_keys(&self, key_type: KeyTypeId) -> Vec<bandersnatch::Public> {
+			self.public_keys(key_type, bandersnatch::CRYPTO_ID)
+		}
+
+		fn bandersnatch_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> Result<bandersnatch::Public, TraitError> {
+			self.generate_new(key_type, bandersnatch::CRYPTO_ID, seed)
+		}
+
+		fn bandersnatch_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			msg: &[u8],
+		) -> Result<Option<bandersnatch::Signature>, TraitError> {
+			self.sign(key_type, bandersnatch::CRYPTO_ID, public.as_slice(), msg)
+		}
+
+		fn bandersnatch_vrf_sign(
+			&self,
+			_: KeyTypeId,
+			_: &bandersnatch::Public,
+			_: &bandersnatch::vrf::VrfSignData,
+		) -> Result<Option<bandersnatch::vrf::VrfSignature>, TraitError> {
+			Self::vrf_unsupported()
+		}
+
+		fn bandersnatch_vrf_pre_output(
+			&self,
+			_: KeyTypeId,
+			_: &bandersnatch::Public,
+			_: &bandersnatch::vrf::VrfInput,
+		) -> Result<Option<bandersnatch::vrf::VrfPreOutput>, TraitError> {
+			Self::vrf_unsupported()
+		}
+
+		fn bandersnatch_ring_vrf_sign(
+			&self,
+			_: KeyTypeId,
+			_: &bandersnatch::Public,
+			_: &bandersnatch::vrf::VrfSignData,
+			_: &bandersnatch::ring_vrf::RingProver,
+		) -> Result<Option<bandersnatch::ring_vrf::RingVrfSignature>, TraitError> {
+			Self::vrf_unsupported()
+		}
+	}
+
+	sp_keystore::bls_experimental_enabled! {
+		fn bls381_public_keys(&self, key_type: KeyTypeId) -> Vec<bls381::Public> {
+			self.public_keys(key_type, bls381::CRYPTO_ID)
+		}
+
+		fn bls381_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> Result<bls381::Public, TraitError> {
+			self.generate_new(key_type, bls381::CRYPTO_ID, seed)
+		}
+
+		fn bls381_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bls381::Public,
+			msg: &[u8],
+		) -> Result<Option<bls381::Signature>, TraitError> {
+			self.sign(key_type, bls381::CRYPTO_ID, public.as_slice(), msg)
+		}
+
+		fn ecdsa_bls381_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa_bls381::Public> {
+			self.public_keys(key_type, ecdsa_bls381::CRYPTO_ID)
+		}
+
+		fn ecdsa_bls381_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> Result<ecdsa_bls381::Public, TraitError> {
+			self.generate_new(key_type, ecdsa_bls381::CRYPTO_ID, seed)
+		}
+
+		fn ecdsa_bls381_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &ecdsa_bls381::Public,
+			msg: &[u8],
+		) -> Result<Option<ecdsa_bls381::Signature>, TraitError> {
+			self.sign(key_type, ecdsa_bls381::CRYPTO_ID, public.as_slice(), msg)
+		}
+
+		fn ecdsa_bls381_sign_with_keccak256(
+			&self,
+			key_type: KeyTypeId,
+			public: &ecdsa_bls381::Public,
+			msg: &[u8],
+		) -> Result<Option<ecdsa_bls381::Signature>, TraitError> {
+			let signature = self.call(
+				key_type,
+				Request::EcdsaBls381SignWithKeccak256 {
+					key_type: key_type.0,
+					public: public.to_raw_vec().into(),
+					message: msg.to_vec().into(),
+				},
+			)?;
+			Self::decode_signature(signature)
+		}
+	}
+}
+
+impl Into<KeystorePtr> for RemoteKeystore {
+	fn into(self) -> KeystorePtr {
+		Arc::new(self)
+	}
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+	use super::*;
+	use crate::LocalKeystore;
+	use sp_core::{crypto::VrfPublic, sr25519::vrf::VrfTranscript, Pair};
+	use std::path::Path;
+	use tempfile::TempDir;
+
+	fn signer_at<P: SlashingProtection + 'static>(
+		dir: &Path,
+		endpoint: &RemoteEndpoint,
+		protection: P,
+	) -> Arc<LocalKeystore> {
+		let keystore = Arc::new(LocalKeystore::open(dir.join("keystore"), None).unwrap());
+		let signer = RemoteSigner::new(keystore.clone()).with_slashing_protection(protection);
+		let listener = SignerListener::bind(endpoint).unwrap();
+		std::thread::spawn(move || signer.serve(listener));
+		keystore
+	}
+
+	fn unix_endpoint(dir: &Path) -> RemoteEndpoint {
+		RemoteEndpoint::Unix(dir.join("signer.sock"))
+	}
+
+	#[test]
+	fn keys_are_managed_and_used_remotely() {
+		let dir = TempDir::new().unwrap();
+		let endpoint = unix_endpoint(dir.path());
+		let local = signer_at(dir.path(), &endpoint, ());
+		let remote = RemoteKeystore::new(endpoint);
+
+		let public = remote.sr25519_generate_new(key_types::BABE, None).unwrap();
+		assert_eq!(local.sr25519_public_keys(key_types::BABE), vec![public]);
+		assert_eq!(remote.sr25519_public_keys(key_types::BABE), vec![public]);
+		assert!(remote.ed25519_public_keys(key_types::BABE).is_empty());
+		assert!(remote.has_keys(&[(public.to_raw_vec(), key_types::BABE)]));
+		assert!(!remote.has_keys(&[(public.to_raw_vec(), key_types::GRANDPA)]));
+
+		let signature = remote.sr25519_sign(key_types::BABE, &public, b"message").unwrap().unwrap();
+		assert!(sr25519::Pair::verify(&signature, b"message", &public));
+		let unknown = sr25519::Pair::generate().0.public();
+		assert_eq!(remote.sr25519_sign(key_types::BABE, &unknown, b"message").unwrap(), None);
+
+		let pair = ed25519::Pair::from_string("//Alice", None).unwrap();
+		remote.insert(key_types::GRANDPA, "//Alice", pair.public().as_ref()).unwrap();
+		assert_eq!(remote.keys(key_types::GRANDPA).unwrap(), vec![pair.public().to_raw_vec()]);
+		let signature = remote
+			.ed25519_sign(key_types::GRANDPA, &pair.public(), b"message")
+			.unwrap()
+			.unwrap();
+		assert_eq!(signature, pair.sign(b"message"));
+
+		let public = remote.ecdsa_generate_new(key_types::BEEFY, None).unwrap();
+		let signature = remote
+			.ecdsa_sign_prehashed(key_types::BEEFY, &public, &[7; 32])
+			.unwrap()
+			.unwrap();
+		assert!(ecdsa::Pair::verify_prehashed(&signature, &[7; 32], &public));
+	}
+
+	#[test]
+	fn vrfs_are_evaluated_remotely() {
+		let dir = TempDir::new().unwrap();
+		let endpoint = unix_endpoint(dir.path());
+		let local = signer_at(dir.path(), &endpoint, ());
+		let remote = RemoteKeystore::new(endpoint);
+		let public = remote.sr25519_generate_new(key_types::BABE, None).unwrap();
+
+		let input = VrfTranscript::new(b"label", &[(b"domain", b"data")]);
+		let data = input
+			.clone()
+			.into_sign_data()
+			.with_extra(VrfTranscript::new(b"extra", &[(b"domain", b"more data")]));
+		let signature = remote.sr25519_vrf_sign(key_types::BABE, &public, &data).unwrap().unwrap();
+		assert!(public.vrf_verify(&data, &signature));
+		assert_eq!(
+			remote.sr25519_vrf_pre_output(key_types::BABE, &public, &input).unwrap(),
+			local.sr25519_vrf_pre_output(key_types::BABE, &public, &input).unwrap(),
+		);
+		let unknown = sr25519::Pair::generate().0.public();
+		assert_eq!(remote.sr25519_vrf_sign(key_types::BABE, &unknown, &data).unwrap(), None);
+	}
+
+	#[test]
+	fn connections_are_reused_and_reopened() {
+		let dir = TempDir::new().unwrap();
+		let endpoint = unix_endpoint(dir.path());
+		signer_at(dir.path(), &endpoint, ());
+		let remote = RemoteKeystore::new(endpoint);
+
+		let public = remote.sr25519_generate_new(key_types::BABE, None).unwrap();
+		assert!(remote.connection.lock().is_some());
+		assert_eq!(remote.sr25519_public_keys(key_types::BABE), vec![public]);
+
+		// A connection closed by the signer is replaced.
+		let (closed, _) = std::os::unix::net::UnixStream::pair().unwrap();
+		*remote.connection.lock() = Some(Stream::Unix(closed));
+		assert_eq!(remote.sr25519_public_keys(key_types::BABE), vec![public]);
+
+		// Requests while the kept connection is busy use their own.
+		let busy = remote.connection.lock();
+		assert_eq!(remote.sr25519_public_keys(key_types::BABE), vec![public]);
+		drop(busy);
+	}
+
+	#[test]
+	fn works_over_http() {
+		let dir = TempDir::new().unwrap();
+		let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+		let address = listener.local_addr().unwrap();
+		drop(listener);
+		let endpoint: RemoteEndpoint = format!("http://{}/signer", address).parse().unwrap();
+		signer_at(dir.path(), &endpoint, ());
+		let remote = RemoteKeystore::new(endpoint);
+
+		let public = remote.ed25519_generate_new(key_types::GRANDPA, Some("//Bob")).unwrap();
+		assert_eq!(public, ed25519::Pair::from_string("//Bob", None).unwrap().public());
+		let signature = remote.ed25519_sign(key_types::GRANDPA, &public, b"message").unwrap();
+		assert!(ed25519::Pair::verify(&signature.unwrap(), b"message", &public));
+	}
+
+	#[test]
+	fn unreachable_signer_is_unavailable() {
+		let dir = TempDir::new().unwrap();
+		let remote = RemoteKeystore::new(unix_endpoint(dir.path()));
+
+		assert!(remote.sr25519_public_keys(key_types::BABE).is_empty());
+		assert!(matches!(
+			remote.sr25519_generate_new(key_types::BABE, None),
+			Err(TraitError::Unavailable)
+		));
+	}
+
+	#[test]
+	fn double_signing_is_refused() {
+		let dir = TempDir::new().unwrap();
+		let endpoint = unix_endpoint(dir.path());
+		let protection =
+			DoubleSignProtection::new().with_domain(key_types::GRANDPA, grandpa_signing_domain);
+		signer_at(dir.path(), &endpoint, protection);
+		let remote = RemoteKeystore::new(endpoint);
+		let public = remote.ed25519_generate_new(key_types::GRANDPA, None).unwrap();
+
+		// A prevote for `target` in round 3 of set 1, as signed by GRANDPA.
+		let prevote = |target: u8| {
+			let mut message = vec![0];
+			message.extend([target; 32]);
+			message.extend(10u32.to_le_bytes());
+			message.extend(3u64.to_le_bytes());
+			message.extend(1u64.to_le_bytes());
+			message
+		};
+
+		assert!(remote.ed25519_sign(key_types::GRANDPA, &public, &prevote(1)).unwrap().is_some());
+		// Signing the same vote again is fine.
+		assert!(remote.ed25519_sign(key_types::GRANDPA, &public, &prevote(1)).unwrap().is_some());
+		assert!(matches!(
+			remote.ed25519_sign(key_types::GRANDPA, &public, &prevote(2)),
+			Err(TraitError::Other(message)) if message.contains("slashing protection")
+		));
+	}
+
+	#[test]
+	fn double_voting_in_beefy_is_refused() {
+		let dir = TempDir::new().unwrap();
+		let endpoint = unix_endpoint(dir.path());
+		let protection =
+			DoubleSignProtection::new().with_domain(key_types::BEEFY, beefy_signing_domain);
+		signer_at(dir.path(), &endpoint, protection);
+		let remote = RemoteKeystore::new(endpoint);
+		let public = remote.ecdsa_generate_new(key_types::BEEFY, None).unwrap();
+
+		// A commitment to the MMR root `root` of block 5 in validator set 1, as signed by BEEFY.
+		let commitment = |root: u8| {
+			let mut message = vec![4, b'm', b'h', 128];
+			message.extend([root; 32]);
+			message.extend(5u32.to_le_bytes());
+			message.extend(1u64.to_le_bytes());
+			message
+		};
+
+		let signature = remote
+			.ecdsa_sign_with_keccak256(key_types::BEEFY, &public, &commitment(1))
+			.unwrap()
+			.unwrap();
+		let hash = sp_core::hashing::keccak_256(&commitment(1));
+		assert!(ecdsa::Pair::verify_prehashed(&signature, &hash, &public));
+		assert!(remote
+			.ecdsa_sign_with_keccak256(key_types::BEEFY, &public, &commitment(1))
+			.unwrap()
+			.is_some());
+		assert!(matches!(
+			remote.ecdsa_sign_with_keccak256(key_types::BEEFY, &public, &commitment(2)),
+			Err(TraitError::Other(message)) if message.contains("slashing protection")
+		));
+	}
+}
@@ -0,0 +1,402 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Slashing protection against signing conflicting consensus messages.
+
+use super::{protocol::KeyType, signer::SlashingProtection};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sp_core::{
+	crypto::KeyTypeId, hashing::blake2_256, sr25519::vrf::VrfTranscriptData, Bytes, H256,
+};
+use std::{
+	collections::{BTreeMap, HashMap},
+	fs, io,
+	path::PathBuf,
+};
+
+/// The number of positions remembered per key and kind of message.
+pub const HISTORY_DEPTH: usize = 128;
+
+/// The position of a consensus message.
+///
+/// Signing two different messages of the same kind at the same position is an equivocation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SigningDomain {
+	/// The kind of message, for example a prevote or a precommit.
+	pub kind: u8,
+	/// The set of authorities or the epoch the message belongs to.
+	pub set_id: u64,
+	/// The round, slot or block number of the message within the set.
+	pub round: u64,
+}
+
+/// Extracts the [`SigningDomain`] of a message, if it is a consensus message.
+pub type SigningDomainFn = fn(&[u8]) -> Option<SigningDomain>;
+
+/// The [`SigningDomainFn`] of GRANDPA votes.
+///
+/// GRANDPA signs the vote or primary proposal followed by the round number and the set id, see
+/// `sp_consensus_grandpa::localized_payload`.
+pub fn grandpa_signing_domain(message: &[u8]) -> Option<SigningDomain> {
+	// The message variant, at least a hash and the two trailing 64 bit integers.
+	if message.len() < 1 + 32 + 16 || message[0] > 2 {
+		return None
+	}
+	let integer = |offset: usize| {
+		u64::from_le_bytes(message[offset..offset + 8].try_into().expect("8 bytes; qed"))
+	};
+	Some(SigningDomain {
+		kind: message[0],
+		round: integer(message.len() - 16),
+		set_id: integer(message.len() - 8),
+	})
+}
+
+/// The [`SigningDomainFn`] of BEEFY votes.
+///
+/// BEEFY signs the commitment, which ends with the 32 bit block number and the validator set id,
+/// see `sp_consensus_beefy::Commitment`.
+pub fn beefy_signing_domain(message: &[u8]) -> Option<SigningDomain> {
+	// At least the length of the payload and the two trailing integers.
+	if message.len() < 1 + 4 + 8 {
+		return None
+	}
+	let end = message.len();
+	Some(SigningDomain {
+		kind: 0,
+		round: u32::from_le_bytes(message[end - 12..end - 8].try_into().expect("4 bytes; qed"))
+			.into(),
+		set_id: u64::from_le_bytes(message[end - 8..].try_into().expect("8 bytes; qed")),
+	})
+}
+
+/// Extracts the [`SigningDomain`] of the block claimed with a VRF signature of an input.
+pub type VrfSigningDomainFn = fn(&VrfTranscriptData) -> Option<SigningDomain>;
+
+/// The [`VrfSigningDomainFn`] of BABE slot claims.
+///
+/// BABE evaluates its VRF on the slot and the epoch index, see
+/// `sp_consensus_babe::make_vrf_transcript`.
+pub fn babe_vrf_signing_domain(input: &VrfTranscriptData) -> Option<SigningDomain> {
+	if input.label != b"BABE" {
+		return None
+	}
+	let integer = |domain: &[u8]| {
+		let (_, value) = input.items.iter().find(|(name, _)| name == domain)?;
+		Some(u64::from_le_bytes(value[..].try_into().ok()?))
+	};
+	Some(SigningDomain {
+		kind: 0,
+		set_id: integer(b"current epoch")?,
+		round: integer(b"slot number")?,
+	})
+}
+
+/// Refuses to sign two different messages at the same [`SigningDomain`].
+///
+/// The domain of a message is extracted by the [`SigningDomainFn`] registered for the key type of
+/// the signing key. Messages of key types without a registered function are always signed.
+///
+/// BABE signs the hash of its blocks, from which no domain can be extracted. Its keys are
+/// protected with a [`VrfSigningDomainFn`] instead: a block is attributed to the slot of the
+/// latest VRF signature of its author, which BABE creates for every slot right before claiming
+/// it. Evaluating the VRF for other slots in between, as the unsafe `babe_epochAuthorship` RPC
+/// does, attributes the next block to a wrong slot, so that RPC must not be used together with a
+/// protected signer.
+///
+/// The last [`HISTORY_DEPTH`] positions are remembered per key and kind of message, optionally
+/// persisted to a file. Messages at positions older than these are refused.
+pub struct DoubleSignProtection {
+	domains: HashMap<KeyTypeId, SigningDomainFn>,
+	vrf_domains: HashMap<KeyTypeId, VrfSigningDomainFn>,
+	/// The domain of the latest VRF signature per key.
+	claims: Mutex<HashMap<(KeyTypeId, Vec<u8>), SigningDomain>>,
+	history: Mutex<History>,
+	path: Option<PathBuf>,
+}
+
+type History = BTreeMap<(KeyType, Vec<u8>, u8), BTreeMap<(u64, u64), H256>>;
+
+/// A signed message as persisted.
+#[derive(Serialize, Deserialize)]
+struct Record {
+	key_type: KeyType,
+	public: Bytes,
+	kind: u8,
+	set_id: u64,
+	round: u64,
+	message_hash: H256,
+}
+
+impl DoubleSignProtection {
+	/// Create a protection remembering the signed messages in memory only.
+	pub fn new() -> Self {
+		Self::with_history(History::new(), None)
+	}
+
+	/// Create a protection remembering the signed messages in the file at `path`.
+	///
+	/// The history is loaded from the file if it exists.
+	pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+		let path = path.into();
+		let mut history = History::new();
+		if path.exists() {
+			let records: Vec<Record> = serde_json::from_slice(&fs::read(&path)?)?;
+			for record in records {
+				history
+					.entry((record.key_type, record.public.0, record.kind))
+					.or_default()
+					.insert((record.set_id, record.round), record.message_hash);
+			}
+		}
+		Ok(Self::with_history(history, Some(path)))
+	}
+
+	fn with_history(history: History, path: Option<PathBuf>) -> Self {
+		Self {
+			domains: HashMap::new(),
+			vrf_domains: HashMap::new(),
+			claims: Mutex::new(HashMap::new()),
+			history: Mutex::new(history),
+			path,
+		}
+	}
+
+	/// Protect the keys of `key_type`, extracting the domains of their messages with `domain`.
+	pub fn with_domain(mut self, key_type: KeyTypeId, domain: SigningDomainFn) -> Self {
+		self.domains.insert(key_type, domain);
+		self
+	}
+
+	/// Protect the keys of `key_type`, extracting the domains of their messages from their latest
+	/// VRF signature with `domain`.
+	///
+	/// Messages are refused until the key created a VRF signature with a domain.
+	pub fn with_vrf_domain(mut self, key_type: KeyTypeId, domain: VrfSigningDomainFn) -> Self {
+		self.vrf_domains.insert(key_type, domain);
+		self
+	}
+
+	fn persist(&self, history: &History) -> io::Result<()> {
+		let Some(path) = &self.path else { return Ok(()) };
+		let records = history
+			.iter()
+			.flat_map(|((key_type, public, kind), positions)| {
+				positions.iter().map(|(&(set_id, round), &message_hash)| Record {
+					key_type: *key_type,
+					public: public.clone().into(),
+					kind: *kind,
+					set_id,
+					round,
+					message_hash,
+				})
+			})
+			.collect::<Vec<_>>();
+		let temporary = path.with_extension("tmp");
+		fs::write(&temporary, serde_json::to_vec(&records)?)?;
+		fs::rename(temporary, path)
+	}
+}
+
+impl Default for DoubleSignProtection {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl SlashingProtection for DoubleSignProtection {
+	fn check_and_record(
+		&self,
+		key_type: KeyTypeId,
+		public: &[u8],
+		message: &[u8],
+	) -> Result<(), String> {
+		let domain = match self.domains.get(&key_type) {
+			Some(domain) => domain(message),
+			None if self.vrf_domains.contains_key(&key_type) => Some(
+				*self
+					.claims
+					.lock()
+					.get(&(key_type, public.to_vec()))
+					.ok_or_else(|| "no slot was claimed with a VRF signature".to_string())?,
+			),
+			None => None,
+		};
+		let Some(domain) = domain else { return Ok(()) };
+		let position = (domain.set_id, domain.round);
+		let message_hash = H256(blake2_256(message));
+
+		let mut history = self.history.lock();
+		let positions = history.entry((key_type.into(), public.to_vec(), domain.kind)).or_default();
+		match positions.get(&position) {
+			Some(signed) if *signed == message_hash => return Ok(()),
+			Some(_) =>
+				return Err(format!(
+					"already signed a different message of kind {} in round {} of set {}",
+					domain.kind, domain.round, domain.set_id
+				)),
+			None => (),
+		}
+		if positions.len() >= HISTORY_DEPTH &&
+			positions.keys().next().map_or(false, |oldest| position < *oldest)
+		{
+			return Err(format!(
+				"round {} of set {} is older than the remembered history",
+				domain.round, domain.set_id
+			))
+		}
+
+		positions.insert(position, message_hash);
+		while positions.len() > HISTORY_DEPTH {
+			positions.pop_first();
+		}
+		self.persist(&history)
+			.map_err(|e| format!("failed to persist the slashing protection history: {}", e))
+	}
+
+	fn check_and_record_vrf(
+		&self,
+		key_type: KeyTypeId,
+		public: &[u8],
+		input: &VrfTranscriptData,
+	) -> Result<(), String> {
+		if let Some(domain) = self.vrf_domains.get(&key_type).and_then(|domain| domain(input)) {
+			self.claims.lock().insert((key_type, public.to_vec()), domain);
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::crypto::key_types;
+	use tempfile::TempDir;
+
+	fn vote(kind: u8, target: u8, round: u64, set_id: u64) -> Vec<u8> {
+		let mut message = vec![kind];
+		message.extend([target; 32]);
+		message.extend(10u32.to_le_bytes());
+		message.extend(round.to_le_bytes());
+		message.extend(set_id.to_le_bytes());
+		message
+	}
+
+	#[test]
+	fn grandpa_domains_are_extracted() {
+		assert_eq!(
+			grandpa_signing_domain(&vote(1, 0, 7, 3)),
+			Some(SigningDomain { kind: 1, set_id: 3, round: 7 })
+		);
+		assert_eq!(grandpa_signing_domain(&vote(3, 0, 7, 3)), None);
+		assert_eq!(grandpa_signing_domain(&[0; 32]), None);
+	}
+
+	#[test]
+	fn beefy_domains_are_extracted() {
+		// A commitment with a single payload item, for block 7 of validator set 3.
+		let mut commitment = vec![4, b'm', b'h', 4, 1, 2, 3, 4];
+		commitment.extend(7u32.to_le_bytes());
+		commitment.extend(3u64.to_le_bytes());
+		assert_eq!(
+			beefy_signing_domain(&commitment),
+			Some(SigningDomain { kind: 0, set_id: 3, round: 7 })
+		);
+		assert_eq!(beefy_signing_domain(&[0; 12]), None);
+	}
+
+	#[test]
+	fn babe_blocks_are_attributed_to_the_claimed_slot() {
+		let protection =
+			DoubleSignProtection::new().with_vrf_domain(key_types::BABE, babe_vrf_signing_domain);
+		let claim = |slot: u64| {
+			let input = VrfTranscriptData {
+				label: b"BABE".to_vec(),
+				items: vec![
+					(b"slot number".to_vec(), slot.to_le_bytes().to_vec()),
+					(b"current epoch".to_vec(), 2u64.to_le_bytes().to_vec()),
+					(b"chain randomness".to_vec(), vec![0; 32]),
+				],
+			};
+			protection.check_and_record_vrf(key_types::BABE, &[1; 32], &input).unwrap();
+		};
+		let seal = |block: u8| protection.check_and_record(key_types::BABE, &[1; 32], &[block; 32]);
+
+		// Nothing is signed before a slot was claimed.
+		assert!(seal(1).is_err());
+		claim(10);
+		assert!(seal(1).is_ok());
+		assert!(seal(1).is_ok());
+		assert!(seal(2).is_err());
+		claim(11);
+		assert!(seal(2).is_ok());
+		claim(10);
+		assert!(seal(3).is_err());
+	}
+
+	#[test]
+	fn conflicting_messages_are_refused_across_restarts() {
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().join("history.json");
+		let open = || {
+			DoubleSignProtection::open(&path)
+				.unwrap()
+				.with_domain(key_types::GRANDPA, grandpa_signing_domain)
+		};
+		let check = |protection: &DoubleSignProtection, message: &[u8]| {
+			protection.check_and_record(key_types::GRANDPA, &[1; 32], message)
+		};
+
+		let protection = open();
+		assert!(check(&protection, &vote(0, 1, 1, 0)).is_ok());
+		assert!(check(&protection, &vote(0, 1, 1, 0)).is_ok());
+		// A precommit in the same round and a prevote in the next one are fine.
+		assert!(check(&protection, &vote(1, 2, 1, 0)).is_ok());
+		assert!(check(&protection, &vote(0, 2, 2, 0)).is_ok());
+		assert!(check(&protection, &vote(0, 2, 1, 0)).is_err());
+		// Other keys and key types are independent.
+		assert!(protection
+			.check_and_record(key_types::GRANDPA, &[2; 32], &vote(0, 2, 1, 0))
+			.is_ok());
+		assert!(protection
+			.check_and_record(key_types::BABE, &[1; 32], &vote(0, 2, 1, 0))
+			.is_ok());
+		drop(protection);
+
+		let protection = open();
+		assert!(check(&protection, &vote(0, 1, 1, 0)).is_ok());
+		assert!(check(&protection, &vote(0, 2, 1, 0)).is_err());
+		assert!(check(&protection, &vote(0, 3, 2, 0)).is_err());
+	}
+
+	#[test]
+	fn messages_older_than_the_history_are_refused() {
+		let protection =
+			DoubleSignProtection::new().with_domain(key_types::GRANDPA, grandpa_signing_domain);
+		let check =
+			|message: &[u8]| protection.check_and_record(key_types::GRANDPA, &[1; 32], message);
+
+		for round in 1..=HISTORY_DEPTH as u64 + 1 {
+			assert!(check(&vote(0, 1, round, 0)).is_ok());
+		}
+		assert!(check(&vote(0, 2, 1, 0)).is_err());
+		assert!(check(&vote(0, 1, 2, 0)).is_ok());
+		assert!(check(&vote(0, 1, 1, 1)).is_ok());
+	}
+}
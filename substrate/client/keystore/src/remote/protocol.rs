@@ -0,0 +1,474 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Messages of the remote signer protocol and their framing on the wire.
+
+use serde::{Deserialize, Serialize};
+use sp_core::{
+	crypto::{CryptoTypeId, KeyTypeId},
+	Bytes,
+};
+use sp_keystore::Error as TraitError;
+use std::{
+	fmt,
+	io::{self, BufRead, BufReader, Read, Write},
+	path::PathBuf,
+	str::FromStr,
+};
+
+/// The maximum size of a request or a response in bytes.
+pub const MAX_MESSAGE_SIZE: usize = 1024 * 1024;
+
+/// Where a remote signer listens for requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteEndpoint {
+	/// A Unix socket, given as `unix:<PATH>`.
+	Unix(PathBuf),
+	/// An HTTP server, given as `http://<HOST>:<PORT>[/PATH]`.
+	Http {
+		/// The `<HOST>:<PORT>` of the server.
+		address: String,
+		/// The path requests are posted to.
+		path: String,
+	},
+}
+
+impl FromStr for RemoteEndpoint {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Some(path) = s.strip_prefix("unix:") {
+			let path = path.strip_prefix("//").unwrap_or(path);
+			if path.is_empty() {
+				return Err("missing socket path".into())
+			}
+			Ok(Self::Unix(path.into()))
+		} else if let Some(rest) = s.strip_prefix("http://") {
+			let (address, path) = match rest.find('/') {
+				Some(index) => (&rest[..index], &rest[index..]),
+				None => (rest, "/"),
+			};
+			if !address.contains(':') {
+				return Err("the address must be given as `<HOST>:<PORT>`".into())
+			}
+			Ok(Self::Http { address: address.into(), path: path.into() })
+		} else {
+			Err("expected `unix:<PATH>` or `http://<HOST>:<PORT>[/PATH]`".into())
+		}
+	}
+}
+
+impl fmt::Display for RemoteEndpoint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Unix(path) => write!(f, "unix:{}", path.display()),
+			Self::Http { address, path } => write!(f, "http://{}{}", address, path),
+		}
+	}
+}
+
+/// A request to a remote signer.
+///
+/// Key type and crypto type ids are given as strings of four characters, public keys, messages
+/// and signatures as `0x`-prefixed hex strings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+pub enum Request {
+	/// List all public keys of `key_type`, regardless of their crypto type.
+	///
+	/// Returns a list of public keys.
+	Keys {
+		/// The key type.
+		#[serde(with = "id")]
+		key_type: [u8; 4],
+	},
+	/// List the public keys of `key_type` and `crypto`.
+	///
+	/// Returns a list of public keys.
+	PublicKeys {
+		/// The key type.
+		#[serde(with = "id")]
+		key_type: [u8; 4],
+		/// The crypto type.
+		#[serde(with = "id")]
+		crypto: [u8; 4],
+	},
+	/// Generate a new key of `key_type` and `crypto`, from `seed` if given.
+	///
+	/// Returns the public key.
+	GenerateNew {
+		/// The key type.
+		#[serde(with = "id")]
+		key_type: [u8; 4],
+		/// The crypto type.
+		#[serde(with = "id")]
+		crypto: [u8; 4],
+		/// The secret URI to derive the key from.
+		seed: Option<String>,
+	},
+	/// Insert the key derived from `suri` as `public` of `key_type`.
+	///
+	/// Returns `null`.
+	Insert {
+		/// The key type.
+		#[serde(with = "id")]
+		key_type: [u8; 4],
+		/// The secret URI of the key.
+		suri: String,
+		/// The public key.
+		public: Bytes,
+	},
+	/// Whether the private keys of all the given public keys and key types are available.
+	///
+	/// Returns a boolean.
+	HasKeys {
+		/// The public keys and their key types.
+		keys: Vec<(Bytes, KeyType)>,
+	},
+	/// Sign `message` with `public` of `key_type` and `crypto`.
+	///
+	/// Returns the SCALE encoded signature or `null` if the key is not known.
+	Sign {
+		/// The key type.
+		#[serde(with = "id")]
+		key_type: [u8; 4],
+		/// The crypto type.
+		#[serde(with = "id")]
+		crypto: [u8; 4],
+		/// The public key.
+		public: Bytes,
+		/// The message.
+		message: Bytes,
+	},
+	/// Sign the 32 byte hash `message` with the ECDSA key `public` of `key_type`.
+	///
+	/// Returns the SCALE encoded signature or `null` if the key is not known.
+	EcdsaSignPrehashed {
+		/// The key type.
+		#[serde(with = "id")]
+		key_type: [u8; 4],
+		/// The public key.
+		public: Bytes,
+		/// The hash of the message.
+		message: Bytes,
+	},
+	/// Sign the Keccak-256 hash of `message` with the ECDSA key `public` of `key_type`.
+	///
+	/// Returns the SCALE encoded signature or `null` if the key is not known.
+	EcdsaSignWithKeccak256 {
+		/// The key type.
+		#[serde(with = "id")]
+		key_type: [u8; 4],
+		/// The public key.
+		public: Bytes,
+		/// The message.
+		message: Bytes,
+	},
+	/// Sign the Keccak-256 hash of `message` with the paired ECDSA and BLS12-381 key `public` of
+	/// `key_type`.
+	///
+	/// Returns the SCALE encoded signature or `null` if the key is not known.
+	EcdsaBls381SignWithKeccak256 {
+		/// The key type.
+		#[serde(with = "id")]
+		key_type: [u8; 4],
+		/// The public key.
+		public: Bytes,
+		/// The message.
+		message: Bytes,
+	},
+	/// Create a VRF signature of `data` with the sr25519 key `public` of `key_type`.
+	///
+	/// `data` is the SCALE encoded [`VrfSignData`](sp_core::sr25519::vrf::VrfSignData), that is
+	/// the [`VrfTranscriptData`](sp_core::sr25519::vrf::VrfTranscriptData) of the input followed
+	/// by the optional one of the extra transcript.
+	///
+	/// Returns the SCALE encoded signature or `null` if the key is not known.
+	Sr25519VrfSign {
+		/// The key type.
+		#[serde(with = "id")]
+		key_type: [u8; 4],
+		/// The public key.
+		public: Bytes,
+		/// The data to sign.
+		data: Bytes,
+	},
+	/// Create the VRF pre-output of `input` with the sr25519 key `public` of `key_type`.
+	///
+	/// `input` is the SCALE encoded
+	/// [`VrfTranscriptData`](sp_core::sr25519::vrf::VrfTranscriptData) of the VRF input.
+	///
+	/// Returns the SCALE encoded pre-output or `null` if the key is not known.
+	Sr25519VrfPreOutput {
+		/// The key type.
+		#[serde(with = "id")]
+		key_type: [u8; 4],
+		/// The public key.
+		public: Bytes,
+		/// The VRF input.
+		input: Bytes,
+	},
+}
+
+impl Request {
+	/// The key type and public key that sign a message with this request, if any.
+	///
+	/// VRF signatures are not included, see [`SlashingProtection::check_and_record_vrf`].
+	///
+	/// [`SlashingProtection::check_and_record_vrf`]: super::SlashingProtection::check_and_record_vrf
+	pub fn signer(&self) -> Option<(KeyTypeId, &[u8])> {
+		match self {
+			Self::Sign { key_type, public, .. } |
+			Self::EcdsaSignPrehashed { key_type, public, .. } |
+			Self::EcdsaSignWithKeccak256 { key_type, public, .. } |
+			Self::EcdsaBls381SignWithKeccak256 { key_type, public, .. } =>
+				Some((KeyTypeId(*key_type), &public[..])),
+			_ => None,
+		}
+	}
+}
+
+/// A key type id given as a string of four characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct KeyType(#[serde(with = "id")] pub [u8; 4]);
+
+impl From<KeyTypeId> for KeyType {
+	fn from(id: KeyTypeId) -> Self {
+		Self(id.0)
+	}
+}
+
+impl From<CryptoTypeId> for KeyType {
+	fn from(id: CryptoTypeId) -> Self {
+		Self(id.0)
+	}
+}
+
+/// The response of a remote signer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Response {
+	/// The request succeeded with the given result.
+	Result(serde_json::Value),
+	/// The request failed.
+	Error(RemoteError),
+}
+
+/// Why a request to a remote signer failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "code", content = "message", rename_all = "snake_case")]
+pub enum RemoteError {
+	/// The crypto type of the key is not supported.
+	KeyNotSupported,
+	/// The keystore of the signer is unavailable.
+	Unavailable,
+	/// The request is invalid.
+	Validation(String),
+	/// The slashing protection of the signer refused to sign.
+	SlashingProtection(String),
+	/// Any other failure.
+	Other(String),
+}
+
+impl RemoteError {
+	/// Convert into the error of the [`sp_keystore::Keystore`] trait.
+	pub fn into_trait_error(self, key_type: KeyTypeId) -> TraitError {
+		match self {
+			Self::KeyNotSupported => TraitError::KeyNotSupported(key_type),
+			Self::Unavailable => TraitError::Unavailable,
+			Self::Validation(message) => TraitError::ValidationError(message),
+			Self::SlashingProtection(message) =>
+				TraitError::Other(format!("Refused by slashing protection: {}", message)),
+			Self::Other(message) => TraitError::Other(message),
+		}
+	}
+}
+
+impl From<TraitError> for RemoteError {
+	fn from(error: TraitError) -> Self {
+		match error {
+			TraitError::KeyNotSupported(_) => Self::KeyNotSupported,
+			TraitError::Unavailable => Self::Unavailable,
+			TraitError::ValidationError(message) => Self::Validation(message),
+			TraitError::Other(message) => Self::Other(message),
+		}
+	}
+}
+
+/// (De)serializes a four byte id as a string.
+mod id {
+	use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+	pub fn serialize<S: Serializer>(id: &[u8; 4], serializer: S) -> Result<S::Ok, S::Error> {
+		match std::str::from_utf8(id) {
+			Ok(id) => serializer.serialize_str(id),
+			Err(_) => serializer.serialize_str(&array_bytes::bytes2hex("0x", id)),
+		}
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 4], D::Error> {
+		let id = String::deserialize(deserializer)?;
+		let bytes = if id.starts_with("0x") {
+			array_bytes::hex2bytes(&id).map_err(|_| D::Error::custom("invalid hex id"))?
+		} else {
+			id.into_bytes()
+		};
+		bytes.try_into().map_err(|_| D::Error::custom("ids must be four bytes long"))
+	}
+}
+
+/// Write `message` as a single line of JSON.
+pub(crate) fn write_line<T: Serialize>(stream: &mut impl Write, message: &T) -> io::Result<()> {
+	let mut line = serde_json::to_vec(message)?;
+	line.push(b'\n');
+	stream.write_all(&line)?;
+	stream.flush()
+}
+
+/// Read a single line of JSON.
+///
+/// Reads are buffered, so the peer must not send anything else before the message is answered.
+pub(crate) fn read_line<T: for<'de> Deserialize<'de>>(stream: impl Read) -> io::Result<T> {
+	let mut line = Vec::new();
+	if BufReader::new(stream.take(MAX_MESSAGE_SIZE as u64)).read_until(b'\n', &mut line)? == 0 {
+		return Err(io::ErrorKind::UnexpectedEof.into())
+	}
+	if line.last() != Some(&b'\n') {
+		return Err(io::Error::new(io::ErrorKind::InvalidData, "incomplete or oversized message"))
+	}
+	Ok(serde_json::from_slice(&line)?)
+}
+
+/// Write an HTTP/1.1 message with `first_line` and `body` as JSON.
+pub(crate) fn write_http<T: Serialize>(
+	stream: &mut impl Write,
+	first_line: &str,
+	host: Option<&str>,
+	body: &T,
+) -> io::Result<()> {
+	let body = serde_json::to_vec(body)?;
+	let mut message = format!("{}\r\n", first_line);
+	if let Some(host) = host {
+		message.push_str(&format!("Host: {}\r\n", host));
+	}
+	message.push_str(&format!(
+		"Content-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+		body.len()
+	));
+	let mut message = message.into_bytes();
+	message.extend(body);
+	stream.write_all(&message)?;
+	stream.flush()
+}
+
+/// Read an HTTP/1.1 message and return its first line and its body as JSON.
+///
+/// Reads are buffered, so the peer must not send anything else before the message is answered.
+pub(crate) fn read_http<T: for<'de> Deserialize<'de>>(
+	stream: impl Read,
+) -> io::Result<(String, T)> {
+	let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+	let mut reader = BufReader::new(stream.take(MAX_MESSAGE_SIZE as u64));
+
+	let mut first_line = String::new();
+	if reader.read_line(&mut first_line)? == 0 {
+		return Err(io::ErrorKind::UnexpectedEof.into())
+	}
+	let mut content_length = None;
+	loop {
+		let mut header = String::new();
+		if reader.read_line(&mut header)? == 0 {
+			return Err(invalid("incomplete HTTP headers"))
+		}
+		let header = header.trim_end();
+		if header.is_empty() {
+			break
+		}
+		if let Some((name, value)) = header.split_once(':') {
+			if name.trim().eq_ignore_ascii_case("content-length") {
+				content_length =
+					Some(value.trim().parse::<usize>().map_err(|_| invalid("invalid length"))?);
+			}
+		}
+	}
+
+	let content_length = content_length.ok_or_else(|| invalid("missing content length"))?;
+	let mut body = vec![0; content_length];
+	reader.read_exact(&mut body)?;
+	Ok((first_line.trim_end().to_string(), serde_json::from_slice(&body)?))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn endpoints_are_parsed() {
+		assert_eq!(
+			"unix:/run/signer.sock".parse::<RemoteEndpoint>(),
+			Ok(RemoteEndpoint::Unix("/run/signer.sock".into()))
+		);
+		assert_eq!(
+			"unix:///run/signer.sock".parse::<RemoteEndpoint>(),
+			Ok(RemoteEndpoint::Unix("/run/signer.sock".into()))
+		);
+		assert_eq!(
+			"http://127.0.0.1:9955".parse::<RemoteEndpoint>(),
+			Ok(RemoteEndpoint::Http { address: "127.0.0.1:9955".into(), path: "/".into() })
+		);
+		assert_eq!(
+			"http://localhost:9955/sign".parse::<RemoteEndpoint>(),
+			Ok(RemoteEndpoint::Http { address: "localhost:9955".into(), path: "/sign".into() })
+		);
+		assert!("http://localhost".parse::<RemoteEndpoint>().is_err());
+		assert!("unix:".parse::<RemoteEndpoint>().is_err());
+		assert!("https://localhost:9955".parse::<RemoteEndpoint>().is_err());
+	}
+
+	#[test]
+	fn requests_use_the_documented_encoding() {
+		let request = Request::Sign {
+			key_type: *b"gran",
+			crypto: *b"ed25",
+			public: vec![1, 2].into(),
+			message: vec![3].into(),
+		};
+		let json = serde_json::to_value(&request).unwrap();
+		assert_eq!(
+			json,
+			serde_json::json!({
+				"method": "sign",
+				"params": {
+					"key_type": "gran",
+					"crypto": "ed25",
+					"public": "0x0102",
+					"message": "0x03",
+				},
+			})
+		);
+		assert_eq!(serde_json::from_value::<Request>(json).unwrap(), request);
+
+		let response = Response::Error(RemoteError::SlashingProtection("equivocation".into()));
+		assert_eq!(
+			serde_json::to_value(&response).unwrap(),
+			serde_json::json!({
+				"error": { "code": "slashing_protection", "message": "equivocation" },
+			})
+		);
+	}
+}
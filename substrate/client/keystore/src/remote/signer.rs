@@ -0,0 +1,468 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The serving side of the remote signer protocol.
+
+use super::{
+	protocol::{self, RemoteEndpoint, RemoteError, Request, Response},
+	LOG_TARGET,
+};
+use codec::{DecodeAll, Encode};
+use parking_lot::Mutex;
+use sp_core::{
+	crypto::{ByteArray, CryptoTypeId, KeyTypeId},
+	ecdsa, ed25519,
+	sr25519::{
+		self,
+		vrf::{VrfTranscript, VrfTranscriptData},
+	},
+};
+use sp_keystore::Keystore;
+use std::{
+	io,
+	net::{TcpListener, TcpStream},
+	sync::atomic::{AtomicUsize, Ordering},
+	time::Duration,
+};
+
+sp_keystore::bandersnatch_experimental_enabled! {
+use sp_core::bandersnatch;
+}
+
+sp_keystore::bls_experimental_enabled! {
+use sp_core::{bls381, ecdsa_bls381};
+}
+
+/// How long the signer waits for the client to accept a response.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long an idle connection is kept open for further requests.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The maximum number of connections served at the same time.
+///
+/// Further connections are closed right away.
+pub const MAX_CONNECTIONS: usize = 16;
+
+/// The maximum number of distinct labels of VRF transcripts the signer accepts.
+///
+/// Transcripts require static labels, so every distinct label is kept for the lifetime of the
+/// signer.
+pub const MAX_VRF_LABELS: usize = 64;
+
+/// The maximum length of a label of a VRF transcript.
+const MAX_VRF_LABEL_LENGTH: usize = 64;
+
+/// Decides whether the signer may sign a message, to prevent equivocations.
+///
+/// Consulted by the [`RemoteSigner`] before every signature. An implementation that allows a
+/// message must remember it, such that it can refuse conflicting messages later on, including
+/// after a restart of the signer.
+pub trait SlashingProtection: Send + Sync {
+	/// Check whether `public` of `key_type` may sign `message` and record it if so.
+	///
+	/// `message` is exactly what is signed, which may be a hash of the actual payload.
+	fn check_and_record(
+		&self,
+		key_type: KeyTypeId,
+		public: &[u8],
+		message: &[u8],
+	) -> Result<(), String>;
+
+	/// Check whether `public` of `key_type` may create a VRF signature of `input` and record it if
+	/// so.
+	///
+	/// VRF signatures are no equivocations themselves, but they tell which slot a block author is
+	/// about to claim. Allows everything by default.
+	fn check_and_record_vrf(
+		&self,
+		_key_type: KeyTypeId,
+		_public: &[u8],
+		_input: &VrfTranscriptData,
+	) -> Result<(), String> {
+		Ok(())
+	}
+}
+
+/// Allows signing everything.
+impl SlashingProtection for () {
+	fn check_and_record(&self, _: KeyTypeId, _: &[u8], _: &[u8]) -> Result<(), String> {
+		Ok(())
+	}
+}
+
+/// The labels of the VRF transcripts received so far.
+#[derive(Default)]
+struct VrfLabels(Mutex<Vec<&'static [u8]>>);
+
+impl VrfLabels {
+	/// Map `label` to a static one, see [`VrfTranscript::from_data`].
+	fn intern(&self, label: &[u8]) -> Option<&'static [u8]> {
+		let mut labels = self.0.lock();
+		if let Some(known) = labels.iter().find(|known| **known == label) {
+			return Some(*known)
+		}
+		if labels.len() >= MAX_VRF_LABELS || label.len() > MAX_VRF_LABEL_LENGTH {
+			return None
+		}
+		let label: &'static [u8] = Box::leak(label.to_vec().into_boxed_slice());
+		labels.push(label);
+		Some(label)
+	}
+}
+
+/// Serves the requests of [`RemoteKeystore`](super::RemoteKeystore)s from a local keystore.
+pub struct RemoteSigner<K> {
+	keystore: K,
+	protection: Box<dyn SlashingProtection>,
+	vrf_labels: VrfLabels,
+}
+
+impl<K: Keystore> RemoteSigner<K> {
+	/// Create a signer for the keys in `keystore`, without slashing protection.
+	pub fn new(keystore: K) -> Self {
+		Self { keystore, protection: Box::new(()), vrf_labels: VrfLabels::default() }
+	}
+
+	/// Consult `protection` before signing.
+	pub fn with_slashing_protection(
+		mut self,
+		protection: impl SlashingProtection + 'static,
+	) -> Self {
+		self.protection = Box::new(protection);
+		self
+	}
+
+	/// Handle a single request.
+	pub fn handle(&self, request: Request) -> Response {
+		match self.dispatch(request) {
+			Ok(result) => Response::Result(result),
+			Err(error) => Response::Error(error),
+		}
+	}
+
+	/// Serve the requests arriving at `listener`.
+	///
+	/// Every connection is served by its own thread, up to [`MAX_CONNECTIONS`] at the same time,
+	/// and is kept open for further requests until it is idle for a minute.
+	///
+	/// Only returns if the listener fails.
+	pub fn serve(&self, listener: SignerListener) -> io::Result<()> {
+		let connections = AtomicUsize::new(0);
+		std::thread::scope(|scope| -> io::Result<()> {
+			loop {
+				let connection = listener.accept()?;
+				if connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+					connections.fetch_sub(1, Ordering::SeqCst);
+					log::debug!(target: LOG_TARGET, "Refused connection, too many are open");
+					continue
+				}
+				let connections = &connections;
+				scope.spawn(move || {
+					let result = match connection {
+						#[cfg(unix)]
+						Connection::Unix(stream) => self.serve_unix(stream),
+						Connection::Http(stream, path) => self.serve_http(stream, path),
+					};
+					if let Err(e) = result {
+						log::debug!(target: LOG_TARGET, "Failed to serve request: {}", e);
+					}
+					connections.fetch_sub(1, Ordering::SeqCst);
+				});
+			}
+		})
+	}
+
+	#[cfg(unix)]
+	fn serve_unix(&self, mut stream: std::os::unix::net::UnixStream) -> io::Result<()> {
+		stream.set_read_timeout(Some(IDLE_TIMEOUT))?;
+		stream.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+		loop {
+			let request = match protocol::read_line(&stream) {
+				Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+				request => request?,
+			};
+			protocol::write_line(&mut stream, &self.handle(request))?;
+		}
+	}
+
+	fn serve_http(&self, mut stream: TcpStream, path: &str) -> io::Result<()> {
+		stream.set_read_timeout(Some(IDLE_TIMEOUT))?;
+		stream.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+		loop {
+			let (request_line, request) = match protocol::read_http::<Request>(&stream) {
+				Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+				request => request?,
+			};
+			let response = if request_line.starts_with("POST ") &&
+				request_line.split(' ').nth(1) == Some(path)
+			{
+				self.handle(request)
+			} else {
+				Response::Error(RemoteError::Validation(format!(
+					"Expected a POST request to {}",
+					path
+				)))
+			};
+			protocol::write_http(&mut stream, "HTTP/1.1 200 OK", None, &response)?;
+		}
+	}
+
+	fn dispatch(&self, request: Request) -> Result<serde_json::Value, RemoteError> {
+		if let Some((key_type, public)) = request.signer() {
+			// Unknown keys cannot sign anything, so there is nothing to protect against.
+			if self.keystore.has_keys(&[(public.to_vec(), key_type)]) {
+				let message: &[u8] = match &request {
+					Request::Sign { message, .. } |
+					Request::EcdsaSignPrehashed { message, .. } |
+					Request::EcdsaSignWithKeccak256 { message, .. } |
+					Request::EcdsaBls381SignWithKeccak256 { message, .. } => &message[..],
+					_ => &[],
+				};
+				self.protection
+					.check_and_record(key_type, public, message)
+					.map_err(RemoteError::SlashingProtection)?;
+			}
+		}
+
+		let result = match request {
+			Request::Keys { key_type } =>
+				to_value(&bytes(self.keystore.keys(KeyTypeId(key_type))?)),
+			Request::PublicKeys { key_type, crypto } =>
+				to_value(&bytes(self.public_keys(KeyTypeId(key_type), CryptoTypeId(crypto))?)),
+			Request::GenerateNew { key_type, crypto, seed } => to_value(&sp_core::Bytes(
+				self.generate_new(KeyTypeId(key_type), CryptoTypeId(crypto), seed.as_deref())?,
+			)),
+			Request::Insert { key_type, suri, public } => {
+				self.keystore.insert(KeyTypeId(key_type), &suri, &public).map_err(|_| {
+					RemoteError::Other("Failed to insert the key into the keystore".into())
+				})?;
+				serde_json::Value::Null
+			},
+			Request::HasKeys { keys } => {
+				let keys = keys
+					.into_iter()
+					.map(|(public, key_type)| (public.0, KeyTypeId(key_type.0)))
+					.collect::<Vec<_>>();
+				serde_json::Value::Bool(self.keystore.has_keys(&keys))
+			},
+			Request::Sign { key_type, crypto, public, message } => to_value(
+				&self
+					.keystore
+					.sign_with(KeyTypeId(key_type), CryptoTypeId(crypto), &public, &message)?
+					.map(sp_core::Bytes),
+			),
+			Request::EcdsaSignPrehashed { key_type, public, message } => {
+				let public = ecdsa::Public::from_slice(&public)
+					.map_err(|_| RemoteError::Validation("Invalid public key format".into()))?;
+				let message: [u8; 32] = message[..]
+					.try_into()
+					.map_err(|_| RemoteError::Validation("Expected a 32 byte hash".into()))?;
+				let signature =
+					self.keystore.ecdsa_sign_prehashed(KeyTypeId(key_type), &public, &message)?;
+				to_value(&signature.map(|signature| sp_core::Bytes(signature.encode())))
+			},
+			Request::EcdsaSignWithKeccak256 { key_type, public, message } => {
+				let public = ecdsa::Public::from_slice(&public)
+					.map_err(|_| RemoteError::Validation("Invalid public key format".into()))?;
+				let signature = self.keystore.ecdsa_sign_with_keccak256(
+					KeyTypeId(key_type),
+					&public,
+					&message,
+				)?;
+				to_value(&signature.map(|signature| sp_core::Bytes(signature.encode())))
+			},
+			Request::EcdsaBls381SignWithKeccak256 { key_type, public, message } =>
+				self.ecdsa_bls381_sign_with_keccak256(KeyTypeId(key_type), &public, &message)?,
+			Request::Sr25519VrfSign { key_type, public, data } =>
+				self.sr25519_vrf_sign(KeyTypeId(key_type), &public, &data)?,
+			Request::Sr25519VrfPreOutput { key_type, public, input } => {
+				let public = sr25519::Public::from_slice(&public)
+					.map_err(|_| RemoteError::Validation("Invalid public key format".into()))?;
+				let input = VrfTranscriptData::decode_all(&mut &input[..])
+					.map_err(|_| RemoteError::Validation("Invalid VRF input".into()))?;
+				let input = self.vrf_transcript(&input)?;
+				let pre_output =
+					self.keystore.sr25519_vrf_pre_output(KeyTypeId(key_type), &public, &input)?;
+				to_value(&pre_output.map(|pre_output| sp_core::Bytes(pre_output.encode())))
+			},
+		};
+		Ok(result)
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &[u8],
+		data: &[u8],
+	) -> Result<serde_json::Value, RemoteError> {
+		let (input, extra) =
+			<(VrfTranscriptData, Option<VrfTranscriptData>)>::decode_all(&mut &data[..])
+				.map_err(|_| RemoteError::Validation("Invalid VRF sign data".into()))?;
+		if self.keystore.has_keys(&[(public.to_vec(), key_type)]) {
+			self.protection
+				.check_and_record_vrf(key_type, public, &input)
+				.map_err(RemoteError::SlashingProtection)?;
+		}
+
+		let public = sr25519::Public::from_slice(public)
+			.map_err(|_| RemoteError::Validation("Invalid public key format".into()))?;
+		let mut data = self.vrf_transcript(&input)?.into_sign_data();
+		if let Some(extra) = extra {
+			data = data.with_extra(self.vrf_transcript(&extra)?);
+		}
+		let signature = self.keystore.sr25519_vrf_sign(key_type, &public, &data)?;
+		Ok(to_value(&signature.map(|signature| sp_core::Bytes(signature.encode()))))
+	}
+
+	fn vrf_transcript(&self, data: &VrfTranscriptData) -> Result<VrfTranscript, RemoteError> {
+		VrfTranscript::from_data(data, |label| self.vrf_labels.intern(label)).ok_or_else(|| {
+			RemoteError::Validation(format!(
+				"Only up to {} distinct VRF labels of up to {} bytes are supported",
+				MAX_VRF_LABELS, MAX_VRF_LABEL_LENGTH
+			))
+		})
+	}
+
+	fn public_keys(
+		&self,
+		key_type: KeyTypeId,
+		crypto: CryptoTypeId,
+	) -> Result<Vec<Vec<u8>>, RemoteError> {
+		fn raw<P: ByteArray>(keys: Vec<P>) -> Vec<Vec<u8>> {
+			keys.into_iter().map(|key| key.to_raw_vec()).collect()
+		}
+
+		let keys = match crypto {
+			sr25519::CRYPTO_ID => raw(self.keystore.sr25519_public_keys(key_type)),
+			ed25519::CRYPTO_ID => raw(self.keystore.ed25519_public_keys(key_type)),
+			ecdsa::CRYPTO_ID => raw(self.keystore.ecdsa_public_keys(key_type)),
+			#[cfg(feature = "bandersnatch-experimental")]
+			bandersnatch::CRYPTO_ID => raw(self.keystore.bandersnatch_public_keys(key_type)),
+			#[cfg(feature = "bls-experimental")]
+			bls381::CRYPTO_ID => raw(self.keystore.bls381_public_keys(key_type)),
+			#[cfg(feature = "bls-experimental")]
+			ecdsa_bls381::CRYPTO_ID => raw(self.keystore.ecdsa_bls381_public_keys(key_type)),
+			_ => return Err(RemoteError::KeyNotSupported),
+		};
+		Ok(keys)
+	}
+
+	fn generate_new(
+		&self,
+		key_type: KeyTypeId,
+		crypto: CryptoTypeId,
+		seed: Option<&str>,
+	) -> Result<Vec<u8>, RemoteError> {
+		let public = match crypto {
+			sr25519::CRYPTO_ID => self.keystore.sr25519_generate_new(key_type, seed)?.to_raw_vec(),
+			ed25519::CRYPTO_ID => self.keystore.ed25519_generate_new(key_type, seed)?.to_raw_vec(),
+			ecdsa::CRYPTO_ID => self.keystore.ecdsa_generate_new(key_type, seed)?.to_raw_vec(),
+			#[cfg(feature = "bandersnatch-experimental")]
+			bandersnatch::CRYPTO_ID => self.keystore.bandersnatch_generate_new(key_type, seed)?.to_raw_vec(),
+			#[cfg(feature = "bls-experimental")]
+			bls381::CRYPTO_ID => self.keystore.bls381_generate_new(key_type, seed)?.to_raw_vec(),
+			#[cfg(feature = "bls-experimental")]
+			ecdsa_bls381::CRYPTO_ID => self.keystore.ecdsa_bls381_generate_new(key_type, seed)?.to_raw_vec(),
+			_ => return Err(RemoteError::KeyNotSupported),
+		};
+		Ok(public)
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn ecdsa_bls381_sign_with_keccak256(
+		&self,
+		key_type: KeyTypeId,
+		public: &[u8],
+		message: &[u8],
+	) -> Result<serde_json::Value, RemoteError> {
+		let public = ecdsa_bls381::Public::from_slice(public)
+			.map_err(|_| RemoteError::Validation("Invalid public key format".into()))?;
+		let signature =
+			self.keystore.ecdsa_bls381_sign_with_keccak256(key_type, &public, message)?;
+		Ok(to_value(&signature.map(|signature| sp_core::Bytes(signature.encode()))))
+	}
+
+	#[cfg(not(feature = "bls-experimental"))]
+	fn ecdsa_bls381_sign_with_keccak256(
+		&self,
+		_: KeyTypeId,
+		_: &[u8],
+		_: &[u8],
+	) -> Result<serde_json::Value, RemoteError> {
+		Err(RemoteError::KeyNotSupported)
+	}
+}
+
+/// A bound listener of a [`RemoteSigner`].
+pub enum SignerListener {
+	/// Listening on a Unix socket.
+	#[cfg(unix)]
+	Unix(std::os::unix::net::UnixListener),
+	/// Listening for HTTP requests to the given path.
+	Http(TcpListener, String),
+}
+
+/// An accepted connection of a [`SignerListener`].
+enum Connection<'a> {
+	#[cfg(unix)]
+	Unix(std::os::unix::net::UnixStream),
+	Http(TcpStream, &'a str),
+}
+
+impl SignerListener {
+	/// Start listening at `endpoint`.
+	///
+	/// A stale Unix socket at the path of the endpoint is replaced.
+	pub fn bind(endpoint: &RemoteEndpoint) -> io::Result<Self> {
+		match endpoint {
+			#[cfg(unix)]
+			RemoteEndpoint::Unix(path) => {
+				use std::os::unix::fs::FileTypeExt;
+
+				if std::fs::symlink_metadata(path).map_or(false, |m| m.file_type().is_socket()) {
+					std::fs::remove_file(path)?;
+				}
+				Ok(Self::Unix(std::os::unix::net::UnixListener::bind(path)?))
+			},
+			#[cfg(not(unix))]
+			RemoteEndpoint::Unix(_) => Err(io::Error::new(
+				io::ErrorKind::Unsupported,
+				"Unix sockets are not supported on this platform",
+			)),
+			RemoteEndpoint::Http { address, path } =>
+				Ok(Self::Http(TcpListener::bind(address)?, path.clone())),
+		}
+	}
+
+	fn accept(&self) -> io::Result<Connection<'_>> {
+		match self {
+			#[cfg(unix)]
+			Self::Unix(listener) => Ok(Connection::Unix(listener.accept()?.0)),
+			Self::Http(listener, path) => Ok(Connection::Http(listener.accept()?.0, path)),
+		}
+	}
+}
+
+fn bytes(keys: Vec<Vec<u8>>) -> Vec<sp_core::Bytes> {
+	keys.into_iter().map(sp_core::Bytes).collect()
+}
+
+fn to_value<T: serde::Serialize>(value: &T) -> serde_json::Value {
+	serde_json::to_value(value).expect("Bytes, lists and options always serialize; qed")
+}
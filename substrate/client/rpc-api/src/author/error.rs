@@ -83,6 +83,8 @@ const POOL_NO_TAGS: i32 = POOL_INVALID_TX + 9;
 const POOL_INVALID_BLOCK_ID: i32 = POOL_INVALID_TX + 10;
 /// The pool is not accepting future transactions.
 const POOL_FUTURE_TX: i32 = POOL_INVALID_TX + 11;
+/// The sender has too many transactions in the pool.
+const POOL_SENDER_LIMIT: i32 = POOL_INVALID_TX + 12;
 /// Other error.
 const OTHER_ERR: i32 = BASE_ERROR + 40;
 
@@ -175,6 +177,11 @@ impl From<Error> for ErrorObjectOwned {
 					None::<()>,
 				)
 			},
+			Error::Pool(PoolError::SenderLimitReached) => ErrorObject::owned(
+				POOL_SENDER_LIMIT,
+				"Sender limit reached",
+				Some("The sender has too many transactions in the pool"),
+			),
 			Error::UnsafeRpcCalled(e) => e.into(),
 			other => ErrorObject::owned(
 				OTHER_ERR,
@@ -47,6 +47,12 @@ pub enum Error {
 		/// Maximum allowed value
 		max: u32,
 	},
+	/// Calling a pallet view function failed.
+	#[error("View function call failed: {0}")]
+	ViewFunction(String),
+	/// The state of the requested block was pruned or is not kept by the node.
+	#[error("State unavailable: {0}")]
+	StateUnavailable(String),
 	/// Call to an unsafe RPC was denied.
 	#[error(transparent)]
 	UnsafeRpcCalled(#[from] crate::policy::UnsafeRpcError),
@@ -62,6 +68,9 @@ impl From<Error> for ErrorObjectOwned {
 				ErrorObject::owned(BASE_ERROR + 1, e.to_string(), None::<()>),
 			Error::InvalidCount { .. } =>
 				ErrorObject::owned(BASE_ERROR + 2, e.to_string(), None::<()>),
+			Error::ViewFunction(_) => ErrorObject::owned(BASE_ERROR + 4, e.to_string(), None::<()>),
+			Error::StateUnavailable(_) =>
+				ErrorObject::owned(BASE_ERROR + 5, e.to_string(), None::<()>),
 			e => ErrorObject::owned(BASE_ERROR + 3, e.to_string(), None::<()>),
 		}
 	}
@@ -30,3 +30,24 @@ pub struct ReadProof<Hash> {
 	/// A proof used to prove that storage entries are included in the storage trie
 	pub proof: Vec<Bytes>,
 }
+
+/// Arguments passed to a pallet view function.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ViewFunctionArgs {
+	/// The SCALE encoded arguments, concatenated in the order of the function parameters.
+	Scale(Bytes),
+	/// One JSON value per function parameter, encoded using the type information found in the
+	/// runtime metadata.
+	Json(Vec<serde_json::Value>),
+}
+
+/// Result of a pallet view function call.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewFunctionResult {
+	/// The SCALE encoded output of the view function.
+	pub output: Bytes,
+	/// The output decoded to JSON, if the type could be decoded.
+	pub decoded: Option<serde_json::Value>,
+}
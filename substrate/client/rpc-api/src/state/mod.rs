@@ -28,7 +28,7 @@ use sp_version::RuntimeVersion;
 pub mod error;
 pub mod helpers;
 
-pub use self::helpers::ReadProof;
+pub use self::helpers::{ReadProof, ViewFunctionArgs, ViewFunctionResult};
 pub use error::Error;
 
 /// Substrate state API
@@ -38,6 +38,20 @@ pub trait StateApi<Hash> {
 	#[method(name = "state_call", aliases = ["state_callAt"], blocking)]
 	fn call(&self, name: String, bytes: Bytes, hash: Option<Hash>) -> Result<Bytes, Error>;
 
+	/// Call a pallet view function at a block's state.
+	///
+	/// The view function is looked up by pallet and function name in the runtime metadata. The
+	/// arguments are either SCALE encoded or given as one JSON value per function parameter.
+	/// Returns the SCALE encoded output together with its JSON representation.
+	#[method(name = "state_callViewFunction", blocking)]
+	fn call_view_function(
+		&self,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+		hash: Option<Hash>,
+	) -> Result<ViewFunctionResult, Error>;
+
 	/// Returns the keys with prefix, leave empty to get all the keys.
 	#[method(name = "state_getKeys", blocking)]
 	#[deprecated(since = "2.0.0", note = "Please use `getKeysPaged` with proper paging support")]
@@ -300,4 +314,23 @@ pub trait StateApi<Hash> {
 		storage_keys: Option<String>,
 		methods: Option<String>,
 	) -> Result<sp_rpc::tracing::TraceBlockResponse, Error>;
+
+	/// Re-execute a block and return the storage keys read and written by every extrinsic.
+	///
+	/// The block is executed extrinsic by extrinsic on top of the state of its parent, so the
+	/// accesses of the block initialization, of every extrinsic and of the block finalization are
+	/// returned separately, each with the size of the values read and written. In contrast to
+	/// `state_traceBlock`, this doesn't require a runtime compiled with tracing support.
+	///
+	/// Keys that are only iterated over are not part of the reads. Accesses of storage
+	/// transactions that are rolled back are included.
+	///
+	/// ## Node requirements
+	///
+	/// - An archive node, or a node that still has the state of the parent of the block.
+	#[method(name = "state_traceBlockStorageAccess", blocking, with_extensions)]
+	fn trace_block_storage_access(
+		&self,
+		block: Hash,
+	) -> Result<sp_rpc::tracing::BlockStorageAccesses<Hash>, Error>;
 }
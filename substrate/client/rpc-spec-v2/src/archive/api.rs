@@ -25,6 +25,7 @@ use crate::{
 	MethodResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sc_rpc::view_functions::ViewFunctionArgs;
 
 #[rpc(client, server)]
 pub trait ArchiveApi<Hash> {
@@ -94,6 +95,24 @@ pub trait ArchiveApi<Hash> {
 		call_parameters: String,
 	) -> RpcResult<MethodResult>;
 
+	/// Call a pallet view function at a specified block's state.
+	///
+	/// The view function is looked up by pallet and function name in the runtime metadata. The
+	/// arguments are either SCALE encoded or given as one JSON value per function parameter.
+	/// On success, the result contains the hexadecimal-encoded SCALE-codec-encoded output.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[method(name = "archive_unstable_callViewFunction")]
+	fn archive_unstable_call_view_function(
+		&self,
+		hash: Hash,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+	) -> RpcResult<MethodResult>;
+
 	/// Returns storage entries at a specific block's state.
 	///
 	/// # Unstable
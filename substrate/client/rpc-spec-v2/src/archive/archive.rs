@@ -41,7 +41,10 @@ use sc_client_api::{
 	Backend, BlockBackend, BlockchainEvents, CallExecutor, ChildInfo, ExecutorProvider, StorageKey,
 	StorageProvider,
 };
-use sc_rpc::utils::Subscription;
+use sc_rpc::{
+	utils::Subscription,
+	view_functions::{self, MetadataCache, ViewFunctionArgs},
+};
 use sp_api::{CallApiAt, CallContext};
 use sp_blockchain::{
 	Backend as BlockChainBackend, Error as BlockChainError, HeaderBackend, HeaderMetadata,
@@ -73,6 +76,8 @@ pub struct Archive<BE: Backend<Block>, Block: BlockT, Client> {
 	executor: SubscriptionTaskExecutor,
 	/// The hexadecimal encoded hash of the genesis block.
 	genesis_hash: String,
+	/// The metadata used to call view functions.
+	view_function_metadata: MetadataCache,
 	/// Phantom member to pin the block type.
 	_phantom: PhantomData<Block>,
 }
@@ -86,7 +91,23 @@ impl<BE: Backend<Block>, Block: BlockT, Client> Archive<BE, Block, Client> {
 		executor: SubscriptionTaskExecutor,
 	) -> Self {
 		let genesis_hash = hex_string(&genesis_hash.as_ref());
-		Self { client, backend, executor, genesis_hash, _phantom: PhantomData }
+		Self {
+			client,
+			backend,
+			executor,
+			genesis_hash,
+			view_function_metadata: MetadataCache::default(),
+			_phantom: PhantomData,
+		}
+	}
+
+	/// Returns an error if the block is known, but its state was pruned or is not kept.
+	fn ensure_state_available(&self, hash: Block::Hash) -> Result<(), ArchiveError> {
+		match self.backend.state_at(hash) {
+			Err(BlockChainError::StateUnavailable(message)) =>
+				Err(ArchiveError::StateUnavailable(message)),
+			_ => Ok(()),
+		}
 	}
 }
 
@@ -202,6 +223,7 @@ where
 		call_parameters: String,
 	) -> RpcResult<MethodResult> {
 		let call_parameters = Bytes::from(parse_hex_param(call_parameters)?);
+		self.ensure_state_available(hash)?;
 
 		let result =
 			self.client
@@ -214,6 +236,36 @@ where
 		})
 	}
 
+	fn archive_unstable_call_view_function(
+		&self,
+		hash: Block::Hash,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+	) -> RpcResult<MethodResult> {
+		self.ensure_state_available(hash)?;
+		let call = |method: &str, call_data: &[u8]| {
+			self.client
+				.executor()
+				.call(hash, method, call_data, CallContext::Offchain)
+				.map_err(|error| error.to_string())
+		};
+
+		let version = match self.client.runtime_version_at(hash) {
+			Ok(version) => version,
+			Err(error) => return Ok(MethodResult::err(error.to_string())),
+		};
+		let result = self.view_function_metadata.metadata(&version, &call).and_then(|metadata| {
+			view_functions::call_view_function(call, &metadata, &pallet, &function, args)
+		});
+
+		match result {
+			Ok(result) => Ok(MethodResult::ok(hex_string(&result.output.0))),
+			Err(error) if error.is_runtime_error() => Ok(MethodResult::err(error.to_string())),
+			Err(error) => Err(ArchiveError::InvalidParam(error.to_string()).into()),
+		}
+	}
+
 	fn archive_unstable_storage(
 		&self,
 		pending: PendingSubscriptionSink,
@@ -223,8 +275,13 @@ where
 	) {
 		let mut storage_client =
 			StorageSubscriptionClient::<Client, Block, BE>::new(self.client.clone());
+		let state_available = self.ensure_state_available(hash);
 
 		let fut = async move {
+			if let Err(error) = state_available {
+				pending.reject(error).await;
+				return
+			}
 			let Ok(mut sink) = pending.accept().await.map(Subscription::from) else { return };
 
 			let items = match items
@@ -274,10 +331,17 @@ where
 	) {
 		let storage_client = ArchiveStorageDiff::new(self.client.clone());
 		let client = self.client.clone();
+		let state_available = std::iter::once(hash)
+			.chain(previous_hash)
+			.try_for_each(|hash| self.ensure_state_available(hash));
 
 		log::trace!(target: LOG_TARGET, "Storage diff subscription started");
 
 		let fut = async move {
+			if let Err(error) = state_available {
+				pending.reject(error).await;
+				return
+			}
 			let Ok(mut sink) = pending.accept().await.map(Subscription::from) else { return };
 
 			let previous_hash = if let Some(previous_hash) = previous_hash {
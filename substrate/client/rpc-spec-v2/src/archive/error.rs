@@ -32,6 +32,9 @@ pub enum Error {
 	/// Failed to fetch leaves.
 	#[error("Failed to fetch leaves of the chain: {0}")]
 	FetchLeaves(String),
+	/// The state of the block was pruned or is not kept by the node.
+	#[error("State unavailable: {0}")]
+	StateUnavailable(String),
 }
 
 // Base code for all `archive` errors.
@@ -42,6 +45,8 @@ const INVALID_PARAM_ERROR: i32 = BASE_ERROR + 1;
 const RUNTIME_CALL_ERROR: i32 = BASE_ERROR + 2;
 /// Failed to fetch leaves.
 const FETCH_LEAVES_ERROR: i32 = BASE_ERROR + 3;
+/// State unavailable error.
+const STATE_UNAVAILABLE_ERROR: i32 = BASE_ERROR + 4;
 
 impl From<Error> for ErrorObject<'static> {
 	fn from(e: Error) -> Self {
@@ -51,6 +56,8 @@ impl From<Error> for ErrorObject<'static> {
 			Error::InvalidParam(_) => ErrorObject::owned(INVALID_PARAM_ERROR, msg, None::<()>),
 			Error::RuntimeCall(_) => ErrorObject::owned(RUNTIME_CALL_ERROR, msg, None::<()>),
 			Error::FetchLeaves(_) => ErrorObject::owned(FETCH_LEAVES_ERROR, msg, None::<()>),
+			Error::StateUnavailable(_) =>
+				ErrorObject::owned(STATE_UNAVAILABLE_ERROR, msg, None::<()>),
 		}
 		.into()
 	}
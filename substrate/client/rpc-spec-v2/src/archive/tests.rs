@@ -36,7 +36,7 @@ use jsonrpsee::{
 
 use sc_block_builder::BlockBuilderBuilder;
 use sc_client_api::ChildInfo;
-use sc_rpc::testing::TokioTestExecutor;
+use sc_rpc::{testing::TokioTestExecutor, view_functions::ViewFunctionArgs};
 use sp_blockchain::HeaderBackend;
 use sp_consensus::BlockOrigin;
 use sp_core::{Blake2Hasher, Hasher};
@@ -344,6 +344,34 @@ async fn archive_call() {
 	assert_eq!(result, expected);
 }
 
+#[tokio::test]
+async fn archive_call_view_function() {
+	let (client, api) = setup_api();
+	let genesis_hash = format!("{:?}", client.genesis_hash());
+	let invalid_hash = hex_string(&INVALID_HASH);
+	let args = ViewFunctionArgs::Json(vec![]);
+
+	// Invalid hash.
+	let result: MethodResult = api
+		.call(
+			"archive_unstable_callViewFunction",
+			rpc_params![&invalid_hash, "System", "unknown", &args],
+		)
+		.await
+		.unwrap();
+	assert_matches!(result, MethodResult::Err(_));
+
+	// Unknown view function.
+	let err = api
+		.call::<_, serde_json::Value>(
+			"archive_unstable_callViewFunction",
+			rpc_params![&genesis_hash, "System", "unknown", &args],
+		)
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == 3001 && err.message().contains("not found"));
+}
+
 #[tokio::test]
 async fn archive_storage_hashes_values() {
 	let (client, api) = setup_api();
@@ -1003,3 +1031,72 @@ async fn archive_storage_diff_invalid_params() {
 		ArchiveStorageDiffEvent::StorageDiffError(ref err) if err.error.contains("Header was not found")
 	);
 }
+
+#[tokio::test]
+async fn archive_state_unavailable() {
+	let backend = Arc::new(Backend::new_test(1, 0));
+	let client = Arc::new(TestClientBuilder::with_backend(backend.clone()).build());
+	let api = Archive::new(
+		client.clone(),
+		backend,
+		CHAIN_GENESIS,
+		Arc::new(TokioTestExecutor::default()),
+	)
+	.into_rpc();
+
+	// Finalize enough blocks for the state of the first one to be pruned.
+	let mut hashes = vec![client.chain_info().genesis_hash];
+	for number in 0..4 {
+		let block = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(hashes[number as usize])
+			.with_parent_block_number(number)
+			.build()
+			.unwrap()
+			.build()
+			.unwrap()
+			.block;
+		hashes.push(block.hash());
+		client.import_as_final(BlockOrigin::Own, block).await.unwrap();
+	}
+	let pruned_hash = format!("{:?}", hashes[1]);
+	let best_hash = format!("{:?}", hashes[4]);
+
+	let alice_id = Sr25519Keyring::Alice.to_account_id();
+	let call_parameters = hex_string(&alice_id.encode());
+	let err = api
+		.call::<_, serde_json::Value>(
+			"archive_unstable_call",
+			[&pruned_hash, "AccountNonceApi_account_nonce", &call_parameters],
+		)
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == 3004 && err.message().contains("State unavailable"));
+
+	let items: Vec<StorageQuery<String>> =
+		vec![StorageQuery { key: hex_string(&KEY), query_type: StorageQueryType::Value }];
+	let err = api
+		.subscribe_unbounded("archive_unstable_storage", rpc_params![&pruned_hash, items])
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == 3004);
+
+	let items: Vec<ArchiveStorageDiffItem<String>> = Vec::new();
+	let err = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
+			rpc_params![&best_hash, items, &pruned_hash],
+		)
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == 3004);
+
+	// The state of the finalized block is still available.
+	let result: MethodResult = api
+		.call(
+			"archive_unstable_call",
+			[&best_hash, "AccountNonceApi_account_nonce", &call_parameters],
+		)
+		.await
+		.unwrap();
+	assert_eq!(result, MethodResult::ok("0x0000000000000000"));
+}
@@ -27,6 +27,7 @@ use crate::{
 	common::events::StorageQuery,
 };
 use jsonrpsee::{proc_macros::rpc, server::ResponsePayload};
+use sc_rpc::view_functions::ViewFunctionArgs;
 pub use sp_rpc::list::ListOrValue;
 
 #[rpc(client, server)]
@@ -108,6 +109,26 @@ pub trait ChainHeadApi<Hash> {
 		call_parameters: String,
 	) -> ResponsePayload<'static, MethodResponse>;
 
+	/// Call a pallet view function at a specified block's state.
+	///
+	/// The view function is looked up by pallet and function name in the runtime metadata. The
+	/// arguments are either SCALE encoded or given as one JSON value per function parameter.
+	/// The hexadecimal-encoded SCALE-codec-encoded output is reported by the
+	/// `operationCallDone` event of the `follow` subscription.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[method(name = "chainHead_unstable_callViewFunction", with_extensions)]
+	async fn chain_head_unstable_call_view_function(
+		&self,
+		follow_subscription: String,
+		hash: Hash,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+	) -> ResponsePayload<'static, MethodResponse>;
+
 	/// Unpin a block or multiple blocks reported by the `follow` method.
 	///
 	/// Ongoing operations that require the provided block
@@ -28,7 +28,9 @@ use crate::{
 		chain_head_follow::ChainHeadFollower,
 		error::Error as ChainHeadRpcError,
 		event::{FollowEvent, MethodResponse, OperationError, OperationId, OperationStorageItems},
-		subscription::{StopHandle, SubscriptionManagement, SubscriptionManagementError},
+		subscription::{
+			BlockGuard, StopHandle, SubscriptionManagement, SubscriptionManagementError,
+		},
 		FollowEventSendError, FollowEventSender,
 	},
 	common::{events::StorageQuery, storage::QueryResult},
@@ -45,7 +47,10 @@ use sc_client_api::{
 	Backend, BlockBackend, BlockchainEvents, CallExecutor, ChildInfo, ExecutorProvider, StorageKey,
 	StorageProvider,
 };
-use sc_rpc::utils::Subscription;
+use sc_rpc::{
+	utils::Subscription,
+	view_functions::{self, MetadataCache, ViewFunctionArgs},
+};
 use sp_api::CallApiAt;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use sp_core::{traits::CallContext, Bytes};
@@ -131,6 +136,8 @@ pub struct ChainHead<BE: Backend<Block>, Block: BlockT, Client> {
 	_phantom: PhantomData<Block>,
 	/// The maximum number of pending messages per subscription.
 	subscription_buffer_cap: usize,
+	/// The metadata used to call view functions.
+	view_function_metadata: Arc<MetadataCache>,
 }
 
 impl<BE: Backend<Block>, Block: BlockT, Client> ChainHead<BE, Block, Client> {
@@ -154,9 +161,54 @@ impl<BE: Backend<Block>, Block: BlockT, Client> ChainHead<BE, Block, Client> {
 			),
 			max_lagging_distance: config.max_lagging_distance,
 			subscription_buffer_cap: config.subscription_buffer_cap,
+			view_function_metadata: Default::default(),
 			_phantom: PhantomData,
 		}
 	}
+
+	/// Lock `hash` for a runtime call operation of the `follow_subscription`.
+	///
+	/// Returns the response of the method if the block can not be locked.
+	fn lock_block_for_call(
+		&self,
+		ext: &Extensions,
+		follow_subscription: &str,
+		hash: Block::Hash,
+	) -> Result<BlockGuard<Block, BE>, ResponsePayload<'static, MethodResponse>> {
+		let conn_id = ext
+			.get::<ConnectionId>()
+			.copied()
+			.expect("ConnectionId is always set by jsonrpsee; qed");
+
+		if !self.subscriptions.contains_subscription(conn_id, follow_subscription) {
+			// The spec says to return `LimitReached` if the follow subscription is invalid or
+			// stale.
+			return Err(ResponsePayload::success(MethodResponse::LimitReached));
+		}
+
+		let block_guard = match self.subscriptions.lock_block(follow_subscription, hash, 1) {
+			Ok(block) => block,
+			Err(SubscriptionManagementError::SubscriptionAbsent) |
+			Err(SubscriptionManagementError::ExceededLimits) => {
+				// Invalid invalid subscription ID.
+				return Err(ResponsePayload::success(MethodResponse::LimitReached))
+			},
+			Err(SubscriptionManagementError::BlockHashAbsent) => {
+				// Block is not part of the subscription.
+				return Err(ResponsePayload::error(ChainHeadRpcError::InvalidBlock))
+			},
+			Err(_) => return Err(ResponsePayload::error(ChainHeadRpcError::InvalidBlock)),
+		};
+
+		// Reject subscription if with_runtime is false.
+		if !block_guard.has_runtime() {
+			return Err(ResponsePayload::error(ChainHeadRpcError::InvalidRuntimeCall(
+				"The runtime updates flag must be set".to_string(),
+			)));
+		}
+
+		Ok(block_guard)
+	}
 }
 
 /// Helper to convert the `subscription ID` to a string.
@@ -473,38 +525,11 @@ where
 			Err(err) => return ResponsePayload::error(err),
 		};
 
-		let conn_id = ext
-			.get::<ConnectionId>()
-			.copied()
-			.expect("ConnectionId is always set by jsonrpsee; qed");
-
-		if !self.subscriptions.contains_subscription(conn_id, &follow_subscription) {
-			// The spec says to return `LimitReached` if the follow subscription is invalid or
-			// stale.
-			return ResponsePayload::success(MethodResponse::LimitReached);
-		}
-
-		let mut block_guard = match self.subscriptions.lock_block(&follow_subscription, hash, 1) {
-			Ok(block) => block,
-			Err(SubscriptionManagementError::SubscriptionAbsent) |
-			Err(SubscriptionManagementError::ExceededLimits) => {
-				// Invalid invalid subscription ID.
-				return ResponsePayload::success(MethodResponse::LimitReached)
-			},
-			Err(SubscriptionManagementError::BlockHashAbsent) => {
-				// Block is not part of the subscription.
-				return ResponsePayload::error(ChainHeadRpcError::InvalidBlock)
-			},
-			Err(_) => return ResponsePayload::error(ChainHeadRpcError::InvalidBlock),
+		let mut block_guard = match self.lock_block_for_call(ext, &follow_subscription, hash) {
+			Ok(block_guard) => block_guard,
+			Err(response) => return response,
 		};
 
-		// Reject subscription if with_runtime is false.
-		if !block_guard.has_runtime() {
-			return ResponsePayload::error(ChainHeadRpcError::InvalidRuntimeCall(
-				"The runtime updates flag must be set".to_string(),
-			));
-		}
-
 		let operation_id = block_guard.operation().operation_id();
 		let client = self.client.clone();
 
@@ -540,6 +565,65 @@ where
 		rp
 	}
 
+	async fn chain_head_unstable_call_view_function(
+		&self,
+		ext: &Extensions,
+		follow_subscription: String,
+		hash: Block::Hash,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+	) -> ResponsePayload<'static, MethodResponse> {
+		let mut block_guard = match self.lock_block_for_call(ext, &follow_subscription, hash) {
+			Ok(block_guard) => block_guard,
+			Err(response) => return response,
+		};
+
+		let operation_id = block_guard.operation().operation_id();
+		let client = self.client.clone();
+		let view_function_metadata = self.view_function_metadata.clone();
+
+		let (rp, rp_fut) = method_started_response(operation_id.clone(), None);
+		let fut = async move {
+			// Wait for the server to send out the response and if it produces an error no event
+			// should be generated.
+			if rp_fut.await.is_err() {
+				return
+			}
+
+			let call = |method: &str, call_data: &[u8]| {
+				client
+					.executor()
+					.call(hash, method, call_data, CallContext::Offchain)
+					.map_err(|error| error.to_string())
+			};
+			let result = client
+				.runtime_version_at(hash)
+				.map_err(|error| view_functions::Error::RuntimeCall(error.to_string()))
+				.and_then(|version| view_function_metadata.metadata(&version, &call))
+				.and_then(|metadata| {
+					view_functions::call_view_function(call, &metadata, &pallet, &function, args)
+				});
+
+			let event = match result {
+				Ok(result) => FollowEvent::<Block::Hash>::OperationCallDone(OperationCallDone {
+					operation_id: operation_id.clone(),
+					output: hex_string(&result.output.0),
+				}),
+				Err(error) => FollowEvent::<Block::Hash>::OperationError(OperationError {
+					operation_id: operation_id.clone(),
+					error: error.to_string(),
+				}),
+			};
+
+			let _ = block_guard.response_sender().send(event).await;
+		};
+		self.executor
+			.spawn_blocking("substrate-rpc-subscription", Some("rpc"), fut.boxed());
+
+		rp
+	}
+
 	async fn chain_head_unstable_unpin(
 		&self,
 		ext: &Extensions,
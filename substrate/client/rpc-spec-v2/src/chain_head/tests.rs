@@ -33,7 +33,7 @@ use jsonrpsee::{
 };
 use sc_block_builder::BlockBuilderBuilder;
 use sc_client_api::ChildInfo;
-use sc_rpc::testing::TokioTestExecutor;
+use sc_rpc::{testing::TokioTestExecutor, view_functions::ViewFunctionArgs};
 use sc_service::client::new_with_backend;
 use sp_blockchain::HeaderBackend;
 use sp_consensus::BlockOrigin;
@@ -619,6 +619,55 @@ async fn call_runtime() {
 	);
 }
 
+#[tokio::test]
+async fn call_view_function() {
+	let (_client, api, mut block_sub, sub_id, block) = setup_api().await;
+	let block_hash = format!("{:?}", block.header.hash());
+	let invalid_hash = hex_string(&INVALID_HASH);
+	let args = ViewFunctionArgs::Json(vec![]);
+
+	// Subscription ID is invalid.
+	let response: MethodResponse = api
+		.call(
+			"chainHead_unstable_callViewFunction",
+			rpc_params!["invalid_sub_id", &block_hash, "System", "unknown", &args],
+		)
+		.await
+		.unwrap();
+	assert_matches!(response, MethodResponse::LimitReached);
+
+	// Block hash is invalid.
+	let err = api
+		.call::<_, serde_json::Value>(
+			"chainHead_unstable_callViewFunction",
+			rpc_params![&sub_id, &invalid_hash, "System", "unknown", &args],
+		)
+		.await
+		.unwrap_err();
+	assert_matches!(err,
+		Error::JsonRpc(ref err) if err.code() == super::error::rpc_spec_v2::INVALID_BLOCK_ERROR
+	);
+
+	// Unknown view function.
+	let response: MethodResponse = api
+		.call(
+			"chainHead_unstable_callViewFunction",
+			rpc_params![&sub_id, &block_hash, "System", "unknown", &args],
+		)
+		.await
+		.unwrap();
+	let operation_id = match response {
+		MethodResponse::Started(started) => started.operation_id,
+		MethodResponse::LimitReached => panic!("Expected started response"),
+	};
+
+	// Error propagated to `chainHead_follow`.
+	assert_matches!(
+		get_next_event::<FollowEvent<String>>(&mut block_sub).await,
+		FollowEvent::OperationError(error) if error.operation_id == operation_id
+	);
+}
+
 #[tokio::test]
 async fn call_runtime_without_flag() {
 	let builder = TestClientBuilder::new();
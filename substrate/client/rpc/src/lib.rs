@@ -37,6 +37,7 @@ pub mod state;
 pub mod statement;
 pub mod system;
 pub mod utils;
+pub mod view_functions;
 
 #[cfg(any(test, feature = "test-helpers"))]
 pub mod testing;
@@ -59,6 +59,15 @@ where
 		call_data: Bytes,
 	) -> Result<Bytes, Error>;
 
+	/// Call a pallet view function at given block.
+	fn call_view_function(
+		&self,
+		block: Option<Block::Hash>,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+	) -> Result<ViewFunctionResult, Error>;
+
 	/// Returns the keys with prefix, leave empty to get all the keys.
 	fn storage_keys(
 		&self,
@@ -148,6 +157,12 @@ where
 		methods: Option<String>,
 	) -> Result<sp_rpc::tracing::TraceBlockResponse, Error>;
 
+	/// Trace the storage accesses of every extrinsic of a block
+	fn trace_block_storage_access(
+		&self,
+		block: Block::Hash,
+	) -> Result<sp_rpc::tracing::BlockStorageAccesses<Block::Hash>, Error>;
+
 	/// New runtime version subscription
 	fn subscribe_runtime_version(&self, pending: PendingSubscriptionSink);
 
@@ -209,6 +224,18 @@ where
 		self.backend.call(block, method, data).map_err(Into::into)
 	}
 
+	fn call_view_function(
+		&self,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+		block: Option<Block::Hash>,
+	) -> Result<ViewFunctionResult, Error> {
+		self.backend
+			.call_view_function(block, pallet, function, args)
+			.map_err(Into::into)
+	}
+
 	fn storage_keys(
 		&self,
 		key_prefix: StorageKey,
@@ -325,6 +352,18 @@ where
 			.map_err(Into::into)
 	}
 
+	/// Re-execute the given block extrinsic by extrinsic and capture the storage accesses.
+	///
+	/// Note: requires the node to run with `--rpc-methods=Unsafe`.
+	fn trace_block_storage_access(
+		&self,
+		ext: &Extensions,
+		block: Block::Hash,
+	) -> Result<sp_rpc::tracing::BlockStorageAccesses<Block::Hash>, Error> {
+		check_if_safe(ext)?;
+		self.backend.trace_block_storage_access(block).map_err(Into::into)
+	}
+
 	fn subscribe_runtime_version(&self, pending: PendingSubscriptionSink) {
 		self.backend.subscribe_runtime_version(pending)
 	}
@@ -492,5 +531,8 @@ where
 }
 
 fn client_err(err: sp_blockchain::Error) -> Error {
-	Error::Client(Box::new(err))
+	match err {
+		sp_blockchain::Error::StateUnavailable(message) => Error::StateUnavailable(message),
+		err => Error::Client(Box::new(err)),
+	}
 }
@@ -27,16 +27,18 @@ use super::{
 };
 use crate::{
 	utils::{spawn_subscription_task, BoundedVecDeque, PendingSubscription},
+	view_functions::{call_view_function, MetadataCache},
 	DenyUnsafe, SubscriptionTaskExecutor,
 };
 
 use futures::{future, stream, StreamExt};
 use jsonrpsee::{core::async_trait, types::ErrorObject, PendingSubscriptionSink};
 use sc_client_api::{
+	storage_access::{self, AccessedKey},
 	Backend, BlockBackend, BlockchainEvents, CallExecutor, ExecutorProvider, ProofProvider,
 	StorageProvider,
 };
-use sc_rpc_api::state::ReadProof;
+use sc_rpc_api::state::{ReadProof, ViewFunctionArgs, ViewFunctionResult};
 use sp_api::{CallApiAt, Metadata, ProvideRuntimeApi};
 use sp_blockchain::{
 	CachedHeaderMetadata, Error as ClientError, HeaderBackend, HeaderMetadata,
@@ -65,6 +67,7 @@ struct QueryStorageRange<Block: BlockT> {
 pub struct FullState<BE, Block: BlockT, Client> {
 	client: Arc<Client>,
 	executor: SubscriptionTaskExecutor,
+	view_function_metadata: MetadataCache,
 	_phantom: PhantomData<(BE, Block)>,
 }
 
@@ -79,7 +82,12 @@ where
 {
 	/// Create new state API backend for full nodes.
 	pub fn new(client: Arc<Client>, executor: SubscriptionTaskExecutor) -> Self {
-		Self { client, executor, _phantom: PhantomData }
+		Self {
+			client,
+			executor,
+			view_function_metadata: MetadataCache::default(),
+			_phantom: PhantomData,
+		}
 	}
 
 	/// Returns given block hash or best block hash if None is passed.
@@ -204,6 +212,29 @@ where
 			.map_err(client_err)
 	}
 
+	fn call_view_function(
+		&self,
+		block: Option<Block::Hash>,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+	) -> std::result::Result<ViewFunctionResult, Error> {
+		let block = self.block_or_best(block).map_err(client_err)?;
+		let call = |method: &str, call_data: &[u8]| {
+			self.client
+				.executor()
+				.call(block, method, call_data, CallContext::Offchain)
+				.map_err(|e| e.to_string())
+		};
+
+		let version =
+			self.client.runtime_version_at(block).map_err(|e| Error::Client(Box::new(e)))?;
+		self.view_function_metadata
+			.metadata(&version, &call)
+			.and_then(|metadata| call_view_function(call, &metadata, &pallet, &function, args))
+			.map_err(|e| Error::ViewFunction(e.to_string()))
+	}
+
 	// TODO: This is horribly broken; either remove it, or make it streaming.
 	fn storage_keys(
 		&self,
@@ -483,6 +514,57 @@ where
 		.trace_block()
 		.map_err(|e| invalid_block::<Block>(block, None, e.to_string()))
 	}
+
+	fn trace_block_storage_access(
+		&self,
+		block: Block::Hash,
+	) -> std::result::Result<sp_rpc::tracing::BlockStorageAccesses<Block::Hash>, Error> {
+		use sp_rpc::tracing::{ExecutionPhase, PhaseStorageAccesses, StorageKeyAccess};
+
+		let accesses =
+			sc_client_api::storage_access::trace_block_storage_accesses(&*self.client, block)
+				.map_err(|e| invalid_block::<Block>(block, None, e.to_string()))?;
+
+		let key_access = |key: AccessedKey, size: Option<u32>| StorageKeyAccess {
+			child_storage_key: key.child_storage_key.map(Into::into),
+			key: key.key.into(),
+			size,
+		};
+		let phases = accesses
+			.phases
+			.into_iter()
+			.map(|(phase, accesses)| PhaseStorageAccesses {
+				phase: match phase {
+					storage_access::ExecutionPhase::Initialization =>
+						ExecutionPhase::Initialization,
+					storage_access::ExecutionPhase::ApplyExtrinsic(index) =>
+						ExecutionPhase::ApplyExtrinsic(index),
+					storage_access::ExecutionPhase::Finalization => ExecutionPhase::Finalization,
+				},
+				reads: accesses
+					.reads
+					.into_iter()
+					.map(|(key, size)| key_access(key, Some(size)))
+					.collect(),
+				writes: accesses
+					.writes
+					.into_iter()
+					.map(|(key, size)| key_access(key, size))
+					.collect(),
+				cleared_prefixes: accesses
+					.cleared_prefixes
+					.into_iter()
+					.map(|prefix| key_access(prefix, None))
+					.collect(),
+			})
+			.collect();
+
+		Ok(sp_rpc::tracing::BlockStorageAccesses {
+			block_hash: accesses.block_hash,
+			parent_hash: accesses.parent_hash,
+			phases,
+		})
+	}
 }
 
 impl<BE, Block, Client> ChildStateBackend<Block, Client> for FullState<BE, Block, Client>
@@ -18,13 +18,14 @@
 
 use self::error::Error;
 use super::*;
-use crate::testing::{allow_unsafe, test_executor, timeout_secs};
+use crate::testing::{allow_unsafe, deny_unsafe, test_executor, timeout_secs};
 use assert_matches::assert_matches;
 use futures::executor;
 use jsonrpsee::{core::EmptyServerParams as EmptyParams, MethodsError as RpcError};
 use sc_block_builder::BlockBuilderBuilder;
 use sp_consensus::BlockOrigin;
 use sp_core::{hash::H256, storage::ChildInfo};
+use sp_rpc::tracing::{ExecutionPhase, StorageKeyAccess};
 use std::sync::Arc;
 use substrate_test_runtime_client::{
 	prelude::*,
@@ -207,6 +208,69 @@ async fn should_call_contract() {
 	)
 }
 
+#[tokio::test]
+async fn should_reject_unknown_view_function() {
+	let client = Arc::new(substrate_test_runtime_client::new());
+	let genesis_hash = client.genesis_hash();
+	let (client, _child) = new_full(client, test_executor());
+
+	assert_matches!(
+		client.call_view_function(
+			"System".into(),
+			"unknown".into(),
+			ViewFunctionArgs::Json(vec![]),
+			Some(genesis_hash).into(),
+		),
+		Err(Error::ViewFunction(_))
+	)
+}
+
+#[tokio::test]
+async fn should_trace_block_storage_access() {
+	let client = Arc::new(substrate_test_runtime_client::new());
+	let (api, _child) = new_full(client.clone(), test_executor());
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().best_hash)
+		.with_parent_block_number(client.chain_info().best_number)
+		.build()
+		.unwrap();
+	builder
+		.push(ExtrinsicBuilder::new_storage_change(vec![1], Some(vec![1, 2])).build())
+		.unwrap();
+	builder
+		.push(ExtrinsicBuilder::new_storage_change(vec![2], None).build())
+		.unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = block.hash();
+	client.import(BlockOrigin::Own, block).await.unwrap();
+
+	assert_matches!(
+		api.trace_block_storage_access(&deny_unsafe(), block_hash),
+		Err(Error::UnsafeRpcCalled(_))
+	);
+
+	let trace = api.trace_block_storage_access(&allow_unsafe(), block_hash).unwrap();
+	assert_eq!(trace.block_hash, block_hash);
+	assert_eq!(trace.parent_hash, client.genesis_hash());
+	assert_eq!(
+		trace.phases.iter().map(|phase| phase.phase).collect::<Vec<_>>(),
+		vec![
+			ExecutionPhase::Initialization,
+			ExecutionPhase::ApplyExtrinsic(0),
+			ExecutionPhase::ApplyExtrinsic(1),
+			ExecutionPhase::Finalization,
+		],
+	);
+	assert!(!trace.phases[0].writes.is_empty());
+
+	let write =
+		|key: Vec<u8>, size| StorageKeyAccess { child_storage_key: None, key: Bytes(key), size };
+	assert!(trace.phases[1].writes.contains(&write(vec![1], Some(2))));
+	assert!(!trace.phases[1].writes.iter().any(|write| write.key.0 == vec![2]));
+	assert!(trace.phases[2].writes.contains(&write(vec![2], None)));
+}
+
 #[tokio::test]
 async fn should_notify_about_storage_changes() {
 	let mut sub = {
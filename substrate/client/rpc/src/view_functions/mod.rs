@@ -0,0 +1,681 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Client side helpers for calling pallet view functions.
+//!
+//! View functions are identified by a 32 byte id that is derived from the pallet name and the
+//! function signature. The helpers in this module resolve that id from the runtime metadata,
+//! encode the arguments, execute the `RuntimeViewFunction` runtime API and decode the output.
+//! Arguments and outputs can be converted from and to JSON using the type registry of the
+//! metadata. Since decoding the metadata is expensive, RPC servers keep it in a
+//! [`MetadataCache`] keyed by the runtime version.
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Compact, Decode, Encode};
+use frame_metadata::{
+	v16::{PalletViewFunctionMetadata, RuntimeMetadataV16},
+	RuntimeMetadata, RuntimeMetadataPrefixed,
+};
+use parking_lot::Mutex;
+use scale_info::{form::PortableForm, Field, PortableRegistry, TypeDef, TypeDefPrimitive};
+use serde_json::{Map, Value};
+use sp_core::{Bytes, OpaqueMetadata, U256};
+use sp_version::RuntimeVersion;
+use std::{borrow::Cow, collections::VecDeque, sync::Arc};
+
+pub use sc_rpc_api::state::{ViewFunctionArgs, ViewFunctionResult};
+
+/// Runtime API method returning the metadata at a given version.
+const METADATA_AT_VERSION: &str = "Metadata_metadata_at_version";
+/// Runtime API method executing a view function.
+const EXECUTE_VIEW_FUNCTION: &str = "RuntimeViewFunction_execute_view_function";
+/// Metadata versions that contain view functions, in order of preference.
+///
+/// The unstable version is requested until V16 is stabilized.
+const VIEW_FUNCTION_METADATA_VERSIONS: [u32; 2] = [16, u32::MAX];
+/// Number of runtime versions whose metadata is kept by a [`MetadataCache`].
+const METADATA_CACHE_SIZE: usize = 4;
+
+/// View function errors.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// Calling into the runtime failed.
+	#[error("Runtime call failed: {0}")]
+	RuntimeCall(String),
+	/// The runtime does not provide metadata containing view functions.
+	#[error("Runtime does not provide view function metadata")]
+	MetadataUnavailable,
+	/// The pallet is not part of the runtime.
+	#[error("Pallet `{0}` not found")]
+	PalletNotFound(String),
+	/// The pallet does not have a view function with the given name.
+	#[error("View function `{0}::{1}` not found")]
+	FunctionNotFound(String, String),
+	/// The arguments could not be encoded.
+	#[error("Invalid arguments: {0}")]
+	InvalidArgs(String),
+	/// The runtime failed to dispatch the view function.
+	#[error("View function dispatch failed: {0}")]
+	Dispatch(String),
+	/// Decoding a value returned by the runtime failed.
+	#[error("Failed to decode {0}: {1}")]
+	Decode(&'static str, String),
+}
+
+impl Error {
+	/// Returns `true` if the error was raised while executing the runtime.
+	///
+	/// All other errors are caused by invalid input.
+	pub fn is_runtime_error(&self) -> bool {
+		matches!(self, Self::RuntimeCall(_) | Self::Dispatch(_) | Self::Decode(..))
+	}
+}
+
+/// Mirrors the runtime side `ViewFunctionDispatchError`.
+#[derive(Debug, Encode, Decode)]
+enum DispatchError {
+	NotImplemented,
+	NotFound([u8; 32]),
+	Codec,
+}
+
+impl core::fmt::Display for DispatchError {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		match self {
+			Self::NotImplemented => write!(f, "view functions are not implemented"),
+			Self::NotFound(id) =>
+				write!(f, "view function {} not found", array_bytes::bytes2hex("0x", id)),
+			Self::Codec => write!(f, "failed to decode the input"),
+		}
+	}
+}
+
+/// Runtime version fields that identify the metadata of a runtime.
+type RuntimeKey = (Cow<'static, str>, u32, u32);
+
+/// Caches the view function metadata of the most recently used runtimes.
+///
+/// Entries are keyed by the spec name, spec version and implementation version of the runtime,
+/// so a runtime upgrade that changes the metadata must bump one of them.
+#[derive(Default)]
+pub struct MetadataCache {
+	entries: Mutex<VecDeque<(RuntimeKey, Arc<RuntimeMetadataV16>)>>,
+}
+
+impl MetadataCache {
+	/// Returns the metadata of the runtime with the given `version`.
+	///
+	/// The metadata is fetched with `call` if it is not cached yet. `call` must execute the
+	/// runtime with the given `version`.
+	pub fn metadata<F>(
+		&self,
+		version: &RuntimeVersion,
+		call: &F,
+	) -> Result<Arc<RuntimeMetadataV16>, Error>
+	where
+		F: Fn(&str, &[u8]) -> Result<Vec<u8>, String>,
+	{
+		let key = (version.spec_name.clone(), version.spec_version, version.impl_version);
+		{
+			let mut entries = self.entries.lock();
+			if let Some(index) = entries.iter().position(|(k, _)| *k == key) {
+				let entry = entries.remove(index).expect("index was just found; qed");
+				let metadata = entry.1.clone();
+				entries.push_front(entry);
+				return Ok(metadata)
+			}
+		}
+
+		// Fetch without holding the lock, the runtime call can take a while.
+		let metadata = Arc::new(fetch_metadata(call)?);
+		let mut entries = self.entries.lock();
+		if !entries.iter().any(|(k, _)| *k == key) {
+			entries.push_front((key, metadata.clone()));
+			entries.truncate(METADATA_CACHE_SIZE);
+		}
+		Ok(metadata)
+	}
+}
+
+/// Call the view function `function` of `pallet` with the given arguments.
+///
+/// `call` executes the given runtime API method with the given SCALE encoded parameters and
+/// returns the SCALE encoded result. It must call into the state of a block whose runtime
+/// `metadata` belongs to.
+pub fn call_view_function<F>(
+	call: F,
+	metadata: &RuntimeMetadataV16,
+	pallet: &str,
+	function: &str,
+	args: ViewFunctionArgs,
+) -> Result<ViewFunctionResult, Error>
+where
+	F: Fn(&str, &[u8]) -> Result<Vec<u8>, String>,
+{
+	let view_function = resolve(metadata, pallet, function)?;
+	let input = encode_args(&metadata.types, view_function, args)?;
+
+	let result = call(EXECUTE_VIEW_FUNCTION, &(view_function.id, input).encode())
+		.map_err(Error::RuntimeCall)?;
+	let output = Result::<Vec<u8>, DispatchError>::decode(&mut &result[..])
+		.map_err(|e| Error::Decode("view function result", e.to_string()))?
+		.map_err(|e| Error::Dispatch(e.to_string()))?;
+
+	let decoded = decode_output(&metadata.types, view_function.output.id, &output).ok();
+	Ok(ViewFunctionResult { output: output.into(), decoded })
+}
+
+/// Fetch the latest metadata that contains view functions.
+pub fn fetch_metadata<F>(call: &F) -> Result<RuntimeMetadataV16, Error>
+where
+	F: Fn(&str, &[u8]) -> Result<Vec<u8>, String>,
+{
+	for version in VIEW_FUNCTION_METADATA_VERSIONS {
+		let result = call(METADATA_AT_VERSION, &version.encode()).map_err(Error::RuntimeCall)?;
+		let Some(metadata) = Option::<OpaqueMetadata>::decode(&mut &result[..])
+			.map_err(|e| Error::Decode("metadata", e.to_string()))?
+		else {
+			continue
+		};
+
+		let prefixed = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+			.map_err(|e| Error::Decode("metadata", e.to_string()))?;
+		if let RuntimeMetadata::V16(metadata) = prefixed.1 {
+			return Ok(metadata)
+		}
+	}
+
+	Err(Error::MetadataUnavailable)
+}
+
+/// Find the view function `function` of `pallet` in the metadata.
+pub fn resolve<'a>(
+	metadata: &'a RuntimeMetadataV16,
+	pallet: &str,
+	function: &str,
+) -> Result<&'a PalletViewFunctionMetadata<PortableForm>, Error> {
+	metadata
+		.pallets
+		.iter()
+		.find(|p| p.name == pallet)
+		.ok_or_else(|| Error::PalletNotFound(pallet.into()))?
+		.view_functions
+		.iter()
+		.find(|f| f.name == function)
+		.ok_or_else(|| Error::FunctionNotFound(pallet.into(), function.into()))
+}
+
+/// Encode the arguments of `view_function`.
+///
+/// SCALE encoded arguments are passed through as they are.
+pub fn encode_args(
+	registry: &PortableRegistry,
+	view_function: &PalletViewFunctionMetadata<PortableForm>,
+	args: ViewFunctionArgs,
+) -> Result<Vec<u8>, Error> {
+	let args = match args {
+		ViewFunctionArgs::Scale(Bytes(input)) => return Ok(input),
+		ViewFunctionArgs::Json(args) => args,
+	};
+
+	if args.len() != view_function.inputs.len() {
+		return Err(Error::InvalidArgs(format!(
+			"expected {} arguments, got {}",
+			view_function.inputs.len(),
+			args.len()
+		)))
+	}
+
+	let mut input = Vec::new();
+	for (param, arg) in view_function.inputs.iter().zip(args.iter()) {
+		encode_value(registry, param.ty.id, arg, &mut input)
+			.map_err(|e| Error::InvalidArgs(format!("`{}`: {e}", param.name)))?;
+	}
+	Ok(input)
+}
+
+/// Decode the SCALE encoded `output` of type `ty` to JSON.
+pub fn decode_output(registry: &PortableRegistry, ty: u32, output: &[u8]) -> Result<Value, Error> {
+	let input = &mut &output[..];
+	let value = decode_value(registry, ty, input).map_err(|e| Error::Decode("output", e))?;
+	if !input.is_empty() {
+		return Err(Error::Decode("output", format!("{} trailing bytes", input.len())))
+	}
+	Ok(value)
+}
+
+/// Encode the JSON `value` as type `ty`.
+pub fn encode_value(
+	registry: &PortableRegistry,
+	ty: u32,
+	value: &Value,
+	out: &mut Vec<u8>,
+) -> Result<(), String> {
+	match resolve_type(registry, ty)? {
+		TypeDef::Composite(composite) => encode_fields(registry, &composite.fields, value, out),
+		TypeDef::Variant(variant) => {
+			let (name, fields) = match value {
+				Value::Null => ("None", None),
+				Value::String(name) => (name.as_str(), None),
+				Value::Object(map) if map.len() == 1 => {
+					let (name, fields) = map.iter().next().expect("map has one entry; qed");
+					(name.as_str(), Some(fields))
+				},
+				_ => return Err(format!("expected a variant, got {value}")),
+			};
+			let variant = variant
+				.variants
+				.iter()
+				.find(|v| v.name == name)
+				.ok_or_else(|| format!("unknown variant `{name}`"))?;
+			out.push(variant.index);
+			match fields {
+				Some(fields) => encode_fields(registry, &variant.fields, fields, out),
+				None if variant.fields.is_empty() => Ok(()),
+				None => Err(format!("missing fields of variant `{name}`")),
+			}
+		},
+		TypeDef::Sequence(sequence) => {
+			if is_u8(registry, sequence.type_param.id) {
+				if let Value::String(hex) = value {
+					parse_hex(hex)?.encode_to(out);
+					return Ok(())
+				}
+			}
+			let items = as_array(value)?;
+			Compact(items.len() as u32).encode_to(out);
+			items
+				.iter()
+				.try_for_each(|item| encode_value(registry, sequence.type_param.id, item, out))
+		},
+		TypeDef::Array(array) => {
+			if is_u8(registry, array.type_param.id) {
+				if let Value::String(hex) = value {
+					let bytes = parse_hex(hex)?;
+					if bytes.len() != array.len as usize {
+						return Err(format!("expected {} bytes, got {}", array.len, bytes.len()))
+					}
+					out.extend(bytes);
+					return Ok(())
+				}
+			}
+			let items = as_array(value)?;
+			if items.len() != array.len as usize {
+				return Err(format!("expected {} items, got {}", array.len, items.len()))
+			}
+			items
+				.iter()
+				.try_for_each(|item| encode_value(registry, array.type_param.id, item, out))
+		},
+		TypeDef::Tuple(tuple) => {
+			let items = match value {
+				Value::Null if tuple.fields.is_empty() => &[][..],
+				_ if tuple.fields.len() == 1 => core::slice::from_ref(value),
+				_ => as_array(value)?,
+			};
+			if items.len() != tuple.fields.len() {
+				return Err(format!("expected {} items, got {}", tuple.fields.len(), items.len()))
+			}
+			tuple
+				.fields
+				.iter()
+				.zip(items)
+				.try_for_each(|(ty, item)| encode_value(registry, ty.id, item, out))
+		},
+		TypeDef::Primitive(primitive) => encode_primitive(primitive, value, out),
+		TypeDef::Compact(compact) => encode_compact(registry, compact.type_param.id, value, out),
+		TypeDef::BitSequence(_) => Err("bit sequences are not supported".into()),
+	}
+}
+
+/// Decode a value of type `ty` from `input` to JSON.
+pub fn decode_value(
+	registry: &PortableRegistry,
+	ty: u32,
+	input: &mut &[u8],
+) -> Result<Value, String> {
+	let value = match resolve_type(registry, ty)? {
+		TypeDef::Composite(composite) => decode_fields(registry, &composite.fields, input)?,
+		TypeDef::Variant(variant) => {
+			let index = u8::decode(input).map_err(|e| e.to_string())?;
+			let variant = variant
+				.variants
+				.iter()
+				.find(|v| v.index == index)
+				.ok_or_else(|| format!("unknown variant index {index}"))?;
+			if variant.fields.is_empty() {
+				Value::String(variant.name.clone())
+			} else {
+				let fields = decode_fields(registry, &variant.fields, input)?;
+				Value::Object(Map::from_iter([(variant.name.clone(), fields)]))
+			}
+		},
+		TypeDef::Sequence(sequence) => {
+			let len = Compact::<u32>::decode(input).map_err(|e| e.to_string())?.0 as usize;
+			decode_items(registry, sequence.type_param.id, len, input)?
+		},
+		TypeDef::Array(array) =>
+			decode_items(registry, array.type_param.id, array.len as usize, input)?,
+		TypeDef::Tuple(tuple) if tuple.fields.is_empty() => Value::Null,
+		TypeDef::Tuple(tuple) => Value::Array(
+			tuple
+				.fields
+				.iter()
+				.map(|ty| decode_value(registry, ty.id, input))
+				.collect::<Result<_, _>>()?,
+		),
+		TypeDef::Primitive(primitive) => decode_primitive(primitive, input)?,
+		TypeDef::Compact(compact) => decode_compact(registry, compact.type_param.id, input)?,
+		TypeDef::BitSequence(_) => return Err("bit sequences are not supported".into()),
+	};
+	Ok(value)
+}
+
+fn resolve_type(registry: &PortableRegistry, ty: u32) -> Result<&TypeDef<PortableForm>, String> {
+	registry
+		.resolve(ty)
+		.map(|ty| &ty.type_def)
+		.ok_or_else(|| format!("type {ty} not found in registry"))
+}
+
+fn is_u8(registry: &PortableRegistry, ty: u32) -> bool {
+	matches!(resolve_type(registry, ty), Ok(TypeDef::Primitive(TypeDefPrimitive::U8)))
+}
+
+/// Encode the fields of a struct or variant.
+///
+/// Named fields are taken from a JSON object, unnamed fields from a JSON array. A single field is
+/// encoded transparently unless it is given as an object.
+fn encode_fields(
+	registry: &PortableRegistry,
+	fields: &[Field<PortableForm>],
+	value: &Value,
+	out: &mut Vec<u8>,
+) -> Result<(), String> {
+	match value {
+		Value::Object(map) if fields.iter().all(|f| f.name.is_some()) => {
+			if map.len() != fields.len() {
+				return Err(format!("expected {} fields, got {}", fields.len(), map.len()))
+			}
+			fields.iter().try_for_each(|field| {
+				let name = field.name.as_ref().expect("all fields are named; qed");
+				let value = map.get(name).ok_or_else(|| format!("missing field `{name}`"))?;
+				encode_value(registry, field.ty.id, value, out)
+			})
+		},
+		Value::Null if fields.is_empty() => Ok(()),
+		_ if fields.len() == 1 => encode_value(registry, fields[0].ty.id, value, out),
+		_ => {
+			let items = as_array(value)?;
+			if items.len() != fields.len() {
+				return Err(format!("expected {} fields, got {}", fields.len(), items.len()))
+			}
+			fields
+				.iter()
+				.zip(items)
+				.try_for_each(|(field, item)| encode_value(registry, field.ty.id, item, out))
+		},
+	}
+}
+
+/// Decode the fields of a struct or variant, mirroring [`encode_fields`].
+fn decode_fields(
+	registry: &PortableRegistry,
+	fields: &[Field<PortableForm>],
+	input: &mut &[u8],
+) -> Result<Value, String> {
+	match fields {
+		[] => Ok(Value::Null),
+		[field] if field.name.is_none() => decode_value(registry, field.ty.id, input),
+		_ if fields.iter().all(|f| f.name.is_some()) => fields
+			.iter()
+			.map(|field| {
+				let name = field.name.clone().expect("all fields are named; qed");
+				Ok((name, decode_value(registry, field.ty.id, input)?))
+			})
+			.collect::<Result<Map<_, _>, String>>()
+			.map(Value::Object),
+		_ => fields
+			.iter()
+			.map(|field| decode_value(registry, field.ty.id, input))
+			.collect::<Result<_, _>>()
+			.map(Value::Array),
+	}
+}
+
+/// Decode `len` items of type `ty`. Byte sequences are returned as hex strings.
+fn decode_items(
+	registry: &PortableRegistry,
+	ty: u32,
+	len: usize,
+	input: &mut &[u8],
+) -> Result<Value, String> {
+	if is_u8(registry, ty) {
+		if input.len() < len {
+			return Err("not enough data to fill buffer".into())
+		}
+		let (bytes, rest) = input.split_at(len);
+		*input = rest;
+		return Ok(array_bytes::bytes2hex("0x", bytes).into())
+	}
+	(0..len)
+		.map(|_| decode_value(registry, ty, input))
+		.collect::<Result<_, _>>()
+		.map(Value::Array)
+}
+
+/// Encode `value` as the compact encoding of type `ty`.
+///
+/// Besides unsigned integers, this supports the types that are compact encoded through their
+/// single field, like `Perbill`.
+fn encode_compact(
+	registry: &PortableRegistry,
+	ty: u32,
+	value: &Value,
+	out: &mut Vec<u8>,
+) -> Result<(), String> {
+	match resolve_type(registry, ty)? {
+		TypeDef::Primitive(TypeDefPrimitive::U8) => Compact(as_number::<u8>(value)?).encode_to(out),
+		TypeDef::Primitive(TypeDefPrimitive::U16) =>
+			Compact(as_number::<u16>(value)?).encode_to(out),
+		TypeDef::Primitive(TypeDefPrimitive::U32) =>
+			Compact(as_number::<u32>(value)?).encode_to(out),
+		TypeDef::Primitive(TypeDefPrimitive::U64) =>
+			Compact(as_number::<u64>(value)?).encode_to(out),
+		TypeDef::Primitive(TypeDefPrimitive::U128) =>
+			Compact(as_number::<u128>(value)?).encode_to(out),
+		TypeDef::Tuple(tuple) if tuple.fields.is_empty() => (),
+		TypeDef::Composite(composite) if composite.fields.len() == 1 => {
+			let field = &composite.fields[0];
+			let value = match (&field.name, value) {
+				(Some(name), Value::Object(map)) =>
+					map.get(name).ok_or_else(|| format!("missing field `{name}`"))?,
+				_ => value,
+			};
+			return encode_compact(registry, field.ty.id, value, out)
+		},
+		_ => return Err(format!("type {ty} can not be compact encoded")),
+	}
+	Ok(())
+}
+
+/// Decode the compact encoding of type `ty`, mirroring [`encode_compact`].
+fn decode_compact(
+	registry: &PortableRegistry,
+	ty: u32,
+	input: &mut &[u8],
+) -> Result<Value, String> {
+	fn decode<T>(input: &mut &[u8]) -> Result<T, String>
+	where
+		Compact<T>: Decode,
+	{
+		Compact::<T>::decode(input).map(|c| c.0).map_err(|e| e.to_string())
+	}
+
+	let value = match resolve_type(registry, ty)? {
+		TypeDef::Primitive(TypeDefPrimitive::U8) => decode::<u8>(input)?.into(),
+		TypeDef::Primitive(TypeDefPrimitive::U16) => decode::<u16>(input)?.into(),
+		TypeDef::Primitive(TypeDefPrimitive::U32) => decode::<u32>(input)?.into(),
+		TypeDef::Primitive(TypeDefPrimitive::U64) => decode::<u64>(input)?.into(),
+		TypeDef::Primitive(TypeDefPrimitive::U128) => decode::<u128>(input)?.to_string().into(),
+		TypeDef::Tuple(tuple) if tuple.fields.is_empty() => Value::Null,
+		TypeDef::Composite(composite) if composite.fields.len() == 1 => {
+			let field = &composite.fields[0];
+			let value = decode_compact(registry, field.ty.id, input)?;
+			match &field.name {
+				Some(name) => Value::Object(Map::from_iter([(name.clone(), value)])),
+				None => value,
+			}
+		},
+		_ => return Err(format!("type {ty} can not be compact decoded")),
+	};
+	Ok(value)
+}
+
+fn encode_primitive(
+	primitive: &TypeDefPrimitive,
+	value: &Value,
+	out: &mut Vec<u8>,
+) -> Result<(), String> {
+	match primitive {
+		TypeDefPrimitive::Bool => value
+			.as_bool()
+			.ok_or_else(|| format!("expected a bool, got {value}"))?
+			.encode_to(out),
+		TypeDefPrimitive::Char => {
+			let mut chars = value.as_str().into_iter().flat_map(str::chars);
+			match (chars.next(), chars.next()) {
+				(Some(c), None) => (c as u32).encode_to(out),
+				_ => return Err(format!("expected a char, got {value}")),
+			}
+		},
+		TypeDefPrimitive::Str => value
+			.as_str()
+			.ok_or_else(|| format!("expected a string, got {value}"))?
+			.encode_to(out),
+		TypeDefPrimitive::U8 => as_number::<u8>(value)?.encode_to(out),
+		TypeDefPrimitive::U16 => as_number::<u16>(value)?.encode_to(out),
+		TypeDefPrimitive::U32 => as_number::<u32>(value)?.encode_to(out),
+		TypeDefPrimitive::U64 => as_number::<u64>(value)?.encode_to(out),
+		TypeDefPrimitive::U128 => as_number::<u128>(value)?.encode_to(out),
+		TypeDefPrimitive::I8 => as_number::<i8>(value)?.encode_to(out),
+		TypeDefPrimitive::I16 => as_number::<i16>(value)?.encode_to(out),
+		TypeDefPrimitive::I32 => as_number::<i32>(value)?.encode_to(out),
+		TypeDefPrimitive::I64 => as_number::<i64>(value)?.encode_to(out),
+		TypeDefPrimitive::I128 => as_number::<i128>(value)?.encode_to(out),
+		TypeDefPrimitive::U256 => out.extend(as_u256(value, false)?.to_little_endian()),
+		TypeDefPrimitive::I256 => out.extend(as_u256(value, true)?.to_little_endian()),
+	}
+	Ok(())
+}
+
+fn decode_primitive(primitive: &TypeDefPrimitive, input: &mut &[u8]) -> Result<Value, String> {
+	fn decode<T: Decode>(input: &mut &[u8]) -> Result<T, String> {
+		T::decode(input).map_err(|e| e.to_string())
+	}
+
+	let value = match primitive {
+		TypeDefPrimitive::Bool => decode::<bool>(input)?.into(),
+		TypeDefPrimitive::Char => char::from_u32(decode::<u32>(input)?)
+			.ok_or_else(|| "invalid char".to_string())?
+			.to_string()
+			.into(),
+		TypeDefPrimitive::Str => decode::<String>(input)?.into(),
+		TypeDefPrimitive::U8 => decode::<u8>(input)?.into(),
+		TypeDefPrimitive::U16 => decode::<u16>(input)?.into(),
+		TypeDefPrimitive::U32 => decode::<u32>(input)?.into(),
+		TypeDefPrimitive::U64 => decode::<u64>(input)?.into(),
+		// Not every JSON implementation can represent 128 bit integers.
+		TypeDefPrimitive::U128 => decode::<u128>(input)?.to_string().into(),
+		TypeDefPrimitive::I8 => decode::<i8>(input)?.into(),
+		TypeDefPrimitive::I16 => decode::<i16>(input)?.into(),
+		TypeDefPrimitive::I32 => decode::<i32>(input)?.into(),
+		TypeDefPrimitive::I64 => decode::<i64>(input)?.into(),
+		TypeDefPrimitive::I128 => decode::<i128>(input)?.to_string().into(),
+		TypeDefPrimitive::U256 =>
+			U256::from_little_endian(&decode::<[u8; 32]>(input)?).to_string().into(),
+		TypeDefPrimitive::I256 => {
+			let value = U256::from_little_endian(&decode::<[u8; 32]>(input)?);
+			if value.bit(255) {
+				// Two's complement, the magnitude of `I256::MIN` still fits into a `U256`.
+				format!("-{}", (!value).overflowing_add(U256::one()).0).into()
+			} else {
+				value.to_string().into()
+			}
+		},
+	};
+	Ok(value)
+}
+
+fn as_array(value: &Value) -> Result<&[Value], String> {
+	value
+		.as_array()
+		.map(Vec::as_slice)
+		.ok_or_else(|| format!("expected an array, got {value}"))
+}
+
+/// Parse an integer from a JSON number or a decimal string.
+fn as_number<T>(value: &Value) -> Result<T, String>
+where
+	T: TryFrom<u64> + TryFrom<i64> + core::str::FromStr,
+{
+	let number = match value {
+		Value::Number(n) => n
+			.as_u64()
+			.and_then(|n| <T as TryFrom<u64>>::try_from(n).ok())
+			.or_else(|| n.as_i64().and_then(|n| <T as TryFrom<i64>>::try_from(n).ok())),
+		Value::String(s) => s.parse().ok(),
+		_ => None,
+	};
+	number.ok_or_else(|| format!("expected a {}, got {value}", core::any::type_name::<T>()))
+}
+
+/// Parse a 256 bit integer from a JSON number or a decimal string.
+///
+/// Signed integers are returned in two's complement.
+fn as_u256(value: &Value, signed: bool) -> Result<U256, String> {
+	let (negative, magnitude) = match value {
+		Value::Number(n) => match (n.as_u64(), n.as_i64()) {
+			(Some(n), _) => (false, Some(U256::from(n))),
+			(None, Some(n)) => (true, Some(U256::from(n.unsigned_abs()))),
+			_ => (false, None),
+		},
+		Value::String(s) => match s.strip_prefix('-') {
+			Some(s) => (true, U256::from_dec_str(s).ok()),
+			None => (false, U256::from_dec_str(s).ok()),
+		},
+		_ => (false, None),
+	};
+	let expected = || format!("expected a {}, got {value}", if signed { "i256" } else { "u256" });
+	let magnitude = magnitude.ok_or_else(expected)?;
+
+	let min = U256::one() << 255;
+	match (signed, negative) {
+		(false, false) => Ok(magnitude),
+		(false, true) if magnitude.is_zero() => Ok(magnitude),
+		(true, false) if magnitude < min => Ok(magnitude),
+		(true, true) if magnitude <= min => Ok((!magnitude).overflowing_add(U256::one()).0),
+		_ => Err(expected()),
+	}
+}
+
+fn parse_hex(hex: &str) -> Result<Vec<u8>, String> {
+	array_bytes::hex2bytes(hex).map_err(|e| format!("invalid hex string `{hex}`: {e:?}"))
+}
@@ -0,0 +1,313 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use assert_matches::assert_matches;
+use codec::CompactAs;
+use scale_info::{meta_type, Registry, TypeInfo};
+use serde_json::json;
+use sp_metadata_ir::{
+	DeprecationStatusIR, ExtrinsicMetadataIR, MetadataIR, OuterEnumsIR, PalletMetadataIR,
+	PalletViewFunctionMetadataIR, PalletViewFunctionParamMetadataIR,
+};
+use std::cell::Cell;
+
+#[derive(Debug, PartialEq, Encode, Decode, TypeInfo)]
+struct Point {
+	x: u32,
+	y: i64,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, TypeInfo)]
+enum Shape {
+	Empty,
+	Circle(u128),
+	Polygon { points: Vec<Point>, label: Option<String> },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, CompactAs, TypeInfo)]
+struct Percent(u8);
+
+/// Stands in for the 256 bit integer primitives, which no Rust type maps to.
+struct Wide<const SIGNED: bool>;
+
+impl<const SIGNED: bool> TypeInfo for Wide<SIGNED> {
+	type Identity = Self;
+
+	fn type_info() -> scale_info::Type {
+		if SIGNED { TypeDefPrimitive::I256 } else { TypeDefPrimitive::U256 }.into()
+	}
+}
+
+/// The id of the `Counter::add` view function.
+const ADD: [u8; 32] = [7; 32];
+
+/// Metadata of a runtime whose `Counter` pallet provides `add(a: u32, b: Compact<u64>) -> u64`.
+fn metadata() -> OpaqueMetadata {
+	let view_function = PalletViewFunctionMetadataIR {
+		name: "add",
+		id: ADD,
+		inputs: vec![
+			PalletViewFunctionParamMetadataIR { name: "a", ty: meta_type::<u32>() },
+			PalletViewFunctionParamMetadataIR { name: "b", ty: meta_type::<Compact<u64>>() },
+		],
+		output: meta_type::<u64>(),
+		docs: vec![],
+		deprecation_info: DeprecationStatusIR::NotDeprecated,
+	};
+	let pallet = PalletMetadataIR {
+		name: "Counter",
+		storage: None,
+		calls: None,
+		view_functions: vec![view_function],
+		event: None,
+		constants: vec![],
+		error: None,
+		associated_types: vec![],
+		index: 0,
+		docs: vec![],
+		deprecation_info: DeprecationStatusIR::NotDeprecated,
+	};
+	let metadata = MetadataIR {
+		pallets: vec![pallet],
+		extrinsic: ExtrinsicMetadataIR {
+			ty: meta_type::<()>(),
+			versions: vec![4],
+			address_ty: meta_type::<()>(),
+			call_ty: meta_type::<()>(),
+			signature_ty: meta_type::<()>(),
+			extra_ty: meta_type::<()>(),
+			extensions: vec![],
+		},
+		ty: meta_type::<()>(),
+		apis: vec![],
+		outer_enums: OuterEnumsIR {
+			call_enum_ty: meta_type::<()>(),
+			event_enum_ty: meta_type::<()>(),
+			error_enum_ty: meta_type::<()>(),
+		},
+	};
+	OpaqueMetadata::new(sp_metadata_ir::into_unstable(metadata).encode())
+}
+
+/// Executes runtime API calls against a runtime with the [`metadata`].
+///
+/// Only the unstable metadata is provided, like by runtimes before V16 was stabilized.
+fn runtime(metadata_calls: &Cell<u32>) -> impl Fn(&str, &[u8]) -> Result<Vec<u8>, String> + '_ {
+	move |method, mut data| match method {
+		METADATA_AT_VERSION => {
+			metadata_calls.set(metadata_calls.get() + 1);
+			let version = u32::decode(&mut data).map_err(|e| e.to_string())?;
+			Ok((version == u32::MAX).then(metadata).encode())
+		},
+		EXECUTE_VIEW_FUNCTION => {
+			let (id, input) =
+				<([u8; 32], Vec<u8>)>::decode(&mut data).map_err(|e| e.to_string())?;
+			let result = match <(u32, Compact<u64>)>::decode(&mut &input[..]) {
+				_ if id != ADD => Err(DispatchError::NotFound(id)),
+				Ok((a, b)) => Ok((a as u64 + b.0).encode()),
+				Err(_) => Err(DispatchError::Codec),
+			};
+			Ok(result.encode())
+		},
+		_ => Err(format!("unknown method {method}")),
+	}
+}
+
+fn registry_with<T: TypeInfo + 'static>() -> (PortableRegistry, u32) {
+	let mut registry = Registry::new();
+	let ty = registry.register_type(&meta_type::<T>()).id;
+	(registry.into(), ty)
+}
+
+fn encode_json<T: TypeInfo + 'static>(value: Value) -> Result<Vec<u8>, String> {
+	let (registry, ty) = registry_with::<T>();
+	let mut out = Vec::new();
+	encode_value(&registry, ty, &value, &mut out).map(|_| out)
+}
+
+fn decode_json<T: TypeInfo + 'static>(bytes: &[u8]) -> Result<Value, Error> {
+	let (registry, ty) = registry_with::<T>();
+	decode_output(&registry, ty, bytes)
+}
+
+#[test]
+fn encodes_json_values() {
+	assert_eq!(encode_json::<u32>(json!(42)).unwrap(), 42u32.encode());
+	assert_eq!(
+		encode_json::<u128>(json!("340282366920938463463374607431768211455")).unwrap(),
+		u128::MAX.encode()
+	);
+	assert_eq!(encode_json::<[u8; 4]>(json!("0x01020304")).unwrap(), [1u8, 2, 3, 4].encode());
+	assert_eq!(encode_json::<Vec<u8>>(json!("0x0102")).unwrap(), vec![1u8, 2].encode());
+	assert_eq!(encode_json::<(bool, String)>(json!([true, "hi"])).unwrap(), (true, "hi").encode());
+	assert_eq!(encode_json::<Option<u16>>(json!(null)).unwrap(), None::<u16>.encode());
+	assert_eq!(encode_json::<Option<u16>>(json!({ "Some": 7 })).unwrap(), Some(7u16).encode());
+	assert_eq!(
+		encode_json::<Point>(json!({ "x": 1, "y": -2 })).unwrap(),
+		Point { x: 1, y: -2 }.encode()
+	);
+	assert_eq!(encode_json::<Shape>(json!("Empty")).unwrap(), Shape::Empty.encode());
+	assert_eq!(encode_json::<Shape>(json!({ "Circle": "5" })).unwrap(), Shape::Circle(5).encode());
+
+	let polygon = Shape::Polygon {
+		points: vec![Point { x: 0, y: 0 }, Point { x: 3, y: 4 }],
+		label: Some("line".into()),
+	};
+	let json = json!({
+		"Polygon": {
+			"points": [{ "x": 0, "y": 0 }, { "x": 3, "y": 4 }],
+			"label": { "Some": "line" },
+		}
+	});
+	assert_eq!(encode_json::<Shape>(json).unwrap(), polygon.encode());
+}
+
+#[test]
+fn rejects_invalid_json_values() {
+	assert!(encode_json::<u8>(json!(256)).is_err());
+	assert!(encode_json::<u32>(json!(-1)).is_err());
+	assert!(encode_json::<[u8; 4]>(json!("0x010203")).is_err());
+	assert!(encode_json::<Point>(json!({ "x": 1 })).is_err());
+	assert!(encode_json::<Shape>(json!("Square")).is_err());
+	assert!(encode_json::<Shape>(json!("Circle")).is_err());
+}
+
+#[test]
+fn decodes_to_json() {
+	assert_eq!(decode_json::<u64>(&7u64.encode()).unwrap(), json!(7));
+	assert_eq!(decode_json::<u128>(&u128::MAX.encode()).unwrap(), json!(u128::MAX.to_string()));
+	assert_eq!(decode_json::<[u8; 2]>(&[1u8, 2].encode()).unwrap(), json!("0x0102"));
+	assert_eq!(decode_json::<Option<u16>>(&None::<u16>.encode()).unwrap(), json!("None"));
+	assert_eq!(decode_json::<()>(&().encode()).unwrap(), json!(null));
+	assert_eq!(decode_json::<Shape>(&Shape::Circle(5).encode()).unwrap(), json!({ "Circle": "5" }));
+
+	// Trailing bytes are rejected.
+	assert!(decode_json::<u8>(&[1, 2]).is_err());
+}
+
+#[test]
+fn json_round_trips() {
+	let polygon = Shape::Polygon { points: vec![Point { x: 1, y: -1 }], label: None };
+	let json = decode_json::<Shape>(&polygon.encode()).unwrap();
+	assert_eq!(json, json!({ "Polygon": { "points": [{ "x": 1, "y": -1 }], "label": "None" } }));
+	assert_eq!(encode_json::<Shape>(json).unwrap(), polygon.encode());
+}
+
+#[test]
+fn converts_wide_integers_as_numbers() {
+	let i256_min = U256::one() << 255;
+	let mut i256_min_bytes = [0u8; 32];
+	i256_min_bytes[31] = 0x80;
+
+	assert_eq!(encode_json::<Wide<false>>(json!(5)).unwrap(), U256::from(5).to_little_endian());
+	assert_eq!(encode_json::<Wide<false>>(json!(U256::MAX.to_string())).unwrap(), [0xff; 32]);
+	assert_eq!(encode_json::<Wide<true>>(json!(-1)).unwrap(), [0xff; 32]);
+	assert_eq!(encode_json::<Wide<true>>(json!(format!("-{i256_min}"))).unwrap(), i256_min_bytes);
+
+	assert!(encode_json::<Wide<false>>(json!(-1)).is_err());
+	assert!(encode_json::<Wide<false>>(json!("0x05")).is_err());
+	assert!(encode_json::<Wide<true>>(json!(i256_min.to_string())).is_err());
+
+	assert_eq!(decode_json::<Wide<false>>(&[0xff; 32]).unwrap(), json!(U256::MAX.to_string()));
+	assert_eq!(decode_json::<Wide<true>>(&[0xff; 32]).unwrap(), json!("-1"));
+	assert_eq!(decode_json::<Wide<true>>(&i256_min_bytes).unwrap(), json!(format!("-{i256_min}")));
+}
+
+#[test]
+fn compact_values_respect_the_inner_type() {
+	assert_eq!(encode_json::<Compact<u32>>(json!(42)).unwrap(), Compact(42u32).encode());
+	assert_eq!(encode_json::<Compact<Percent>>(json!(50)).unwrap(), Compact(Percent(50)).encode());
+	assert_eq!(encode_json::<Compact<()>>(json!(null)).unwrap(), Compact(()).encode());
+	assert!(encode_json::<Compact<u8>>(json!(256)).is_err());
+	assert!(encode_json::<Compact<Percent>>(json!(300)).is_err());
+
+	assert_eq!(decode_json::<Compact<Percent>>(&Compact(Percent(50)).encode()).unwrap(), json!(50));
+	assert_eq!(
+		decode_json::<Compact<u128>>(&Compact(u128::MAX).encode()).unwrap(),
+		json!(u128::MAX.to_string())
+	);
+	assert!(decode_json::<Compact<u8>>(&Compact(300u32).encode()).is_err());
+}
+
+#[test]
+fn caches_metadata_per_runtime_version() {
+	let metadata_calls = Cell::new(0);
+	let call = runtime(&metadata_calls);
+	let cache = MetadataCache::default();
+	let version = |spec_version| RuntimeVersion { spec_version, ..Default::default() };
+
+	// Fetching the metadata falls back from V16 to the unstable version.
+	cache.metadata(&version(1), &call).unwrap();
+	assert_eq!(metadata_calls.get(), 2);
+	cache.metadata(&version(1), &call).unwrap();
+	assert_eq!(metadata_calls.get(), 2);
+
+	// A new runtime version fetches the metadata again.
+	cache.metadata(&version(2), &call).unwrap();
+	assert_eq!(metadata_calls.get(), 4);
+
+	// Only the most recently used runtime versions are kept.
+	for spec_version in 3..=METADATA_CACHE_SIZE as u32 + 1 {
+		cache.metadata(&version(spec_version), &call).unwrap();
+	}
+	let calls = metadata_calls.get();
+	cache.metadata(&version(2), &call).unwrap();
+	assert_eq!(metadata_calls.get(), calls);
+	cache.metadata(&version(1), &call).unwrap();
+	assert_eq!(metadata_calls.get(), calls + 2);
+}
+
+#[test]
+fn calls_view_functions() {
+	let metadata_calls = Cell::new(0);
+	let call = runtime(&metadata_calls);
+	let metadata = fetch_metadata(&call).unwrap();
+	let expected = ViewFunctionResult { output: 3u64.encode().into(), decoded: Some(json!(3)) };
+
+	let args = ViewFunctionArgs::Json(vec![json!(1), json!(2)]);
+	assert_eq!(call_view_function(&call, &metadata, "Counter", "add", args).unwrap(), expected);
+
+	let args = ViewFunctionArgs::Scale((1u32, Compact(2u64)).encode().into());
+	assert_eq!(call_view_function(&call, &metadata, "Counter", "add", args).unwrap(), expected);
+
+	// Invalid calls.
+	let args = || ViewFunctionArgs::Json(vec![json!(1), json!(2)]);
+	assert_matches!(
+		call_view_function(&call, &metadata, "Unknown", "add", args()),
+		Err(Error::PalletNotFound(_))
+	);
+	assert_matches!(
+		call_view_function(&call, &metadata, "Counter", "sub", args()),
+		Err(Error::FunctionNotFound(..))
+	);
+	assert_matches!(
+		call_view_function(&call, &metadata, "Counter", "add", ViewFunctionArgs::Json(vec![])),
+		Err(Error::InvalidArgs(_))
+	);
+	assert_matches!(
+		call_view_function(
+			&call,
+			&metadata,
+			"Counter",
+			"add",
+			ViewFunctionArgs::Scale(vec![1].into())
+		),
+		Err(Error::Dispatch(_))
+	);
+}
@@ -40,7 +40,7 @@ use sc_executor::{
 	sp_wasm_interface::HostFunctions, HeapAllocStrategy, NativeExecutionDispatch, RuntimeVersionOf,
 	WasmExecutor, DEFAULT_HEAP_ALLOC_STRATEGY,
 };
-use sc_keystore::LocalKeystore;
+use sc_keystore::{LocalKeystore, RemoteKeystore};
 use sc_network::{
 	config::{FullNetworkConfiguration, ProtocolId, SyncMode},
 	multiaddr::Protocol,
@@ -110,28 +110,38 @@ type TFullParts<TBl, TRtApi, TExec> =
 	(TFullClient<TBl, TRtApi, TExec>, Arc<TFullBackend<TBl>>, KeystoreContainer, TaskManager);
 
 /// Construct a local keystore shareable container
-pub struct KeystoreContainer(Arc<LocalKeystore>);
+pub struct KeystoreContainer {
+	keystore: KeystorePtr,
+	local_keystore: Option<Arc<LocalKeystore>>,
+}
 
 impl KeystoreContainer {
 	/// Construct KeystoreContainer
 	pub fn new(config: &KeystoreConfig) -> Result<Self, Error> {
-		let keystore = Arc::new(match config {
+		let local_keystore = match config {
 			KeystoreConfig::Path { path, password } =>
-				LocalKeystore::open(path.clone(), password.clone())?,
-			KeystoreConfig::InMemory => LocalKeystore::in_memory(),
-		});
+				Arc::new(LocalKeystore::open(path.clone(), password.clone())?),
+			KeystoreConfig::InMemory => Arc::new(LocalKeystore::in_memory()),
+			KeystoreConfig::Remote { endpoint } => {
+				info!("🔑 Using the remote signer at {}", endpoint);
+				let keystore = Arc::new(RemoteKeystore::new(endpoint.clone()));
+				return Ok(Self { keystore, local_keystore: None })
+			},
+		};
 
-		Ok(Self(keystore))
+		Ok(Self { keystore: local_keystore.clone(), local_keystore: Some(local_keystore) })
 	}
 
 	/// Returns a shared reference to a dynamic `Keystore` trait implementation.
 	pub fn keystore(&self) -> KeystorePtr {
-		self.0.clone()
+		self.keystore.clone()
 	}
 
-	/// Returns a shared reference to the local keystore .
-	pub fn local_keystore(&self) -> Arc<LocalKeystore> {
-		self.0.clone()
+	/// Returns a shared reference to the local keystore.
+	///
+	/// Returns `None` with a remote keystore, whose keys are not available locally.
+	pub fn local_keystore(&self) -> Option<Arc<LocalKeystore>> {
+		self.local_keystore.clone()
 	}
 }
 
@@ -0,0 +1,509 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Export and import of finalized state snapshots.
+//!
+//! A snapshot starts with [`SNAPSHOT_MAGIC`] and the format version, followed by frames. Each
+//! frame is the length of its payload as a little endian `u32`, the blake2-256 hash of the payload
+//! and the payload itself: a compressed, SCALE encoded [`Frame`]. The first frame holds the
+//! header and justifications of the block, followed by the key-values of the main trie and the
+//! default child tries in chunks, and a final frame with the totals.
+//!
+//! The state is imported the same way as the state downloaded by state sync: the block is passed
+//! to the import queue together with its state, so the consensus engines initialize themselves
+//! from the imported state.
+
+use crate::error::Error;
+use codec::{Decode, Encode};
+use futures::{future, prelude::*};
+use futures_timer::Delay;
+use log::info;
+use parking_lot::Mutex;
+use sc_client_api::{BlockBackend, HeaderBackend, ProofProvider, StorageProvider, UsageProvider};
+use sc_consensus::{
+	import_queue::{BlockImportError, BlockImportStatus, ImportQueue, IncomingBlock, Link},
+	ImportedState,
+};
+use sc_network_sync::{
+	strategy::state_sync::{ImportResult, StateSync, StateSyncProvider},
+	KeyValueStateEntry, StateEntry, StateResponse,
+};
+use sp_consensus::BlockOrigin;
+use sp_core::{
+	hashing::blake2_256,
+	storage::{well_known_keys, ChildInfo},
+};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, NumberFor, Zero},
+	Justifications,
+};
+use std::{
+	collections::HashSet,
+	io::{Read, Write},
+	pin::Pin,
+	sync::Arc,
+	task::Poll,
+	time::Duration,
+};
+
+/// The first bytes of every snapshot.
+pub const SNAPSHOT_MAGIC: [u8; 8] = *b"SUBSNAP\0";
+
+/// The version of the snapshot format.
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// Frames decompressing to more than this are rejected.
+const FRAME_BOMB_LIMIT: usize = 512 * 1024 * 1024;
+
+/// Frames longer than this are rejected before they are read.
+const MAX_FRAME_LEN: u32 = FRAME_BOMB_LIMIT as u32;
+
+/// Number of milliseconds to wait until next poll.
+const DELAY_TIME: u64 = 200;
+
+/// A frame of a snapshot.
+#[derive(Encode, Decode)]
+enum Frame<B: BlockT> {
+	/// The block the state belongs to, always the first frame.
+	Block { genesis_hash: B::Hash, header: B::Header, justifications: Option<Justifications> },
+	/// Key-values of the main trie or of a default child trie.
+	Storage {
+		/// The prefixed storage key and the root of the child trie, `None` for the main trie.
+		child: Option<(Vec<u8>, Vec<u8>)>,
+		key_values: Vec<(Vec<u8>, Vec<u8>)>,
+	},
+	/// The totals of the snapshot, always the last frame.
+	End { frames: u32, key_values: u64 },
+}
+
+/// Writes the frames of a snapshot.
+struct FrameWriter<W> {
+	output: W,
+	frames: u32,
+}
+
+impl<W: Write> FrameWriter<W> {
+	fn new(mut output: W) -> Result<Self, Error> {
+		output.write_all(&SNAPSHOT_MAGIC)?;
+		output.write_all(&SNAPSHOT_VERSION.to_le_bytes())?;
+		Ok(Self { output, frames: 0 })
+	}
+
+	fn write<B: BlockT>(&mut self, frame: &Frame<B>) -> Result<(), Error> {
+		let encoded = frame.encode();
+		let payload = sp_maybe_compressed_blob::compress(&encoded, FRAME_BOMB_LIMIT)
+			.ok_or_else(|| Error::Other("Snapshot frame is too large to compress".into()))?;
+		let len = u32::try_from(payload.len())
+			.ok()
+			.filter(|len| *len <= MAX_FRAME_LEN)
+			.ok_or_else(|| Error::Other("Snapshot frame is too large".into()))?;
+		self.output.write_all(&len.to_le_bytes())?;
+		self.output.write_all(&blake2_256(&payload))?;
+		self.output.write_all(&payload)?;
+		self.frames += 1;
+		Ok(())
+	}
+}
+
+/// Reads and verifies the frames of a snapshot.
+struct FrameReader<R> {
+	input: R,
+	frames: u32,
+}
+
+impl<R: Read> FrameReader<R> {
+	fn new(mut input: R) -> Result<Self, Error> {
+		let mut magic = [0; SNAPSHOT_MAGIC.len()];
+		let mut version = [0; 4];
+		input.read_exact(&mut magic)?;
+		input.read_exact(&mut version)?;
+		if magic != SNAPSHOT_MAGIC {
+			return Err(Error::Other("Input is not a snapshot".into()))
+		}
+		let version = u32::from_le_bytes(version);
+		if version != SNAPSHOT_VERSION {
+			return Err(Error::Other(format!("Unsupported snapshot version {version}")))
+		}
+		Ok(Self { input, frames: 0 })
+	}
+
+	fn read<B: BlockT>(&mut self) -> Result<Frame<B>, Error> {
+		let mut len = [0; 4];
+		let mut checksum = [0; 32];
+		self.input.read_exact(&mut len)?;
+		self.input.read_exact(&mut checksum)?;
+		let len = u32::from_le_bytes(len);
+		if len > MAX_FRAME_LEN {
+			return Err(Error::Other(format!(
+				"Snapshot frame {} is too large: {len} bytes",
+				self.frames
+			)))
+		}
+		let mut payload = vec![0; len as usize];
+		self.input.read_exact(&mut payload)?;
+		if blake2_256(&payload) != checksum {
+			return Err(Error::Other(format!("Checksum mismatch in snapshot frame {}", self.frames)))
+		}
+		let encoded = sp_maybe_compressed_blob::decompress(&payload, FRAME_BOMB_LIMIT)
+			.map_err(|e| Error::Other(format!("Invalid snapshot frame {}: {e}", self.frames)))?;
+		let frame = Frame::decode(&mut &encoded[..])
+			.map_err(|e| Error::Other(format!("Invalid snapshot frame {}: {e}", self.frames)))?;
+		self.frames += 1;
+		Ok(frame)
+	}
+}
+
+/// Collects key-values into frames of about `chunk_size` bytes.
+struct Chunker<'a, W> {
+	writer: &'a mut FrameWriter<W>,
+	chunk_size: usize,
+	child: Option<(Vec<u8>, Vec<u8>)>,
+	key_values: Vec<(Vec<u8>, Vec<u8>)>,
+	size: usize,
+	total: u64,
+}
+
+impl<'a, W: Write> Chunker<'a, W> {
+	fn push<B: BlockT>(&mut self, key: Vec<u8>, value: Vec<u8>) -> Result<(), Error> {
+		self.size += key.len() + value.len();
+		self.total += 1;
+		self.key_values.push((key, value));
+		if self.size >= self.chunk_size {
+			self.flush::<B>()?;
+		}
+		Ok(())
+	}
+
+	fn flush<B: BlockT>(&mut self) -> Result<(), Error> {
+		if !self.key_values.is_empty() {
+			let key_values = std::mem::take(&mut self.key_values);
+			self.writer
+				.write::<B>(&Frame::Storage { child: self.child.clone(), key_values })?;
+			self.size = 0;
+		}
+		Ok(())
+	}
+}
+
+/// Export the finalized state at `hash` into a snapshot written to `output`.
+///
+/// The key-values are written in chunks of about `chunk_size` bytes.
+pub fn export_snapshot<B, BA, C>(
+	client: Arc<C>,
+	hash: B::Hash,
+	output: impl Write,
+	chunk_size: usize,
+) -> Result<(), Error>
+where
+	C: UsageProvider<B> + StorageProvider<B, BA> + HeaderBackend<B> + BlockBackend<B>,
+	B: BlockT,
+	BA: sc_client_api::backend::Backend<B>,
+{
+	let info = client.usage_info().chain;
+	let header = client.expect_header(hash)?;
+	let number = *header.number();
+	if number > info.finalized_number || client.hash(number)? != Some(hash) {
+		return Err(Error::Other(format!("Block {hash} is not finalized")))
+	}
+
+	let mut writer = FrameWriter::new(output)?;
+	writer.write::<B>(&Frame::Block {
+		genesis_hash: info.genesis_hash,
+		header,
+		justifications: client.justifications(hash)?,
+	})?;
+
+	let mut chunker = Chunker {
+		writer: &mut writer,
+		chunk_size,
+		child: None,
+		key_values: Vec::new(),
+		size: 0,
+		total: 0,
+	};
+	let mut children = Vec::new();
+	for (key, value) in client.storage_pairs(hash, None, None)? {
+		// The roots of the child tries are recalculated on import.
+		if key.0.starts_with(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+			children.push((key.0, value.0));
+			continue
+		}
+		chunker.push::<B>(key.0, value.0)?;
+	}
+	chunker.flush::<B>()?;
+
+	for (prefixed_storage_key, root) in children {
+		let child_info = ChildInfo::new_default(
+			&prefixed_storage_key[well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX.len()..],
+		);
+		chunker.child = Some((prefixed_storage_key, root));
+		for key in client.child_storage_keys(hash, child_info.clone(), None, None)? {
+			if let Some(value) = client.child_storage(hash, &child_info, &key)? {
+				chunker.push::<B>(key.0, value.0)?;
+			}
+		}
+		chunker.flush::<B>()?;
+	}
+
+	let key_values = chunker.total;
+	let frames = writer.frames + 1;
+	writer.write::<B>(&Frame::End { frames, key_values })?;
+	writer.output.flush()?;
+
+	info!("Exported the state of #{number} ({hash}): {key_values} key-values in {frames} frames");
+	Ok(())
+}
+
+/// Reads the state following the block frame of a snapshot.
+///
+/// Every chunk is passed to [`StateSync`] as soon as it is read, the same way state responses of
+/// peers are, so only the collected key-values are kept in memory and not the frames.
+fn read_state<B, C>(
+	reader: &mut FrameReader<impl Read>,
+	mut state_sync: StateSync<B, C>,
+) -> Result<(B::Header, ImportedState<B>, Option<Justifications>), Error>
+where
+	B: BlockT,
+	C: ProofProvider<B> + Send + Sync + 'static,
+{
+	let entry = |state_root, key_values: Vec<(Vec<u8>, Vec<u8>)>, complete| KeyValueStateEntry {
+		state_root,
+		entries: key_values.into_iter().map(|(key, value)| StateEntry { key, value }).collect(),
+		complete,
+	};
+
+	let mut children = HashSet::new();
+	let mut total = 0;
+	loop {
+		let entries = match reader.read::<B>()? {
+			Frame::Storage { child: None, key_values } => {
+				total += key_values.len() as u64;
+				vec![entry(Vec::new(), key_values, false)]
+			},
+			Frame::Storage { child: Some((prefixed_storage_key, root)), key_values } => {
+				total += key_values.len() as u64;
+				// The child trie is announced in the main trie once, with its first chunk.
+				let parent = if children.insert(prefixed_storage_key.clone()) {
+					vec![(prefixed_storage_key, root.clone())]
+				} else {
+					Vec::new()
+				};
+				vec![entry(Vec::new(), parent, false), entry(root, key_values, false)]
+			},
+			Frame::End { frames, key_values } => {
+				if frames != reader.frames || key_values != total {
+					return Err(Error::Other("Snapshot is incomplete".into()))
+				}
+				vec![entry(Vec::new(), Vec::new(), true)]
+			},
+			Frame::Block { .. } =>
+				return Err(Error::Other("Snapshot contains more than one block".into())),
+		};
+		match state_sync.import(StateResponse { entries, proof: Vec::new() }) {
+			ImportResult::Import(_, header, state, _, justifications) =>
+				return Ok((header, state, justifications)),
+			ImportResult::Continue => (),
+			ImportResult::BadResponse =>
+				return Err(Error::Other(format!("Invalid snapshot frame {}", reader.frames))),
+		}
+	}
+}
+
+/// Reads a snapshot of the chain of `client` to be imported into its fresh database.
+fn read_snapshot<B, C>(
+	client: Arc<C>,
+	input: impl Read,
+) -> Result<(B::Header, ImportedState<B>, Option<Justifications>), Error>
+where
+	B: BlockT,
+	C: HeaderBackend<B> + ProofProvider<B> + Send + Sync + 'static,
+{
+	let mut reader = FrameReader::new(input)?;
+	let Frame::<B>::Block { genesis_hash, header, justifications } = reader.read()? else {
+		return Err(Error::Other("Snapshot does not start with a block".into()))
+	};
+
+	let info = client.info();
+	if genesis_hash != info.genesis_hash {
+		return Err(Error::Other(format!(
+			"Snapshot of chain with genesis {genesis_hash} does not match genesis {}",
+			info.genesis_hash,
+		)))
+	}
+	if !info.best_number.is_zero() {
+		return Err(Error::Other("Snapshots can only be imported into a fresh database".into()))
+	}
+
+	read_state(&mut reader, StateSync::new(client, header, None, justifications, true))
+}
+
+/// Import a snapshot read from `input` into a fresh database.
+///
+/// As with state sync, the key-values are collected in memory before the state is imported.
+pub fn import_snapshot<B, IQ, C>(
+	client: Arc<C>,
+	mut import_queue: IQ,
+	input: impl Read,
+) -> Pin<Box<dyn Future<Output = Result<(), Error>> + Send>>
+where
+	C: HeaderBackend<B> + ProofProvider<B> + Send + Sync + 'static,
+	B: BlockT,
+	IQ: ImportQueue<B> + 'static,
+{
+	struct WaitLink<B: BlockT> {
+		result: Mutex<Option<Result<(), String>>>,
+		_phantom: std::marker::PhantomData<B>,
+	}
+
+	impl<B: BlockT> Link<B> for WaitLink<B> {
+		fn blocks_processed(
+			&self,
+			_imported: usize,
+			_num_expected_blocks: usize,
+			results: Vec<(Result<BlockImportStatus<NumberFor<B>>, BlockImportError>, B::Hash)>,
+		) {
+			if let Some((result, _)) = results.into_iter().next() {
+				*self.result.lock() = Some(result.map(|_| ()).map_err(|e| e.to_string()));
+			}
+		}
+	}
+
+	let (header, state, justifications) = match read_snapshot(client, input) {
+		Ok(snapshot) => snapshot,
+		Err(e) => return future::ready(Err(e)).boxed(),
+	};
+
+	let hash = header.hash();
+	let number = *header.number();
+	import_queue.service_ref().import_blocks(
+		BlockOrigin::File,
+		vec![IncomingBlock {
+			hash,
+			header: Some(header),
+			body: None,
+			indexed_body: None,
+			justifications,
+			origin: None,
+			allow_missing_state: true,
+			import_existing: true,
+			skip_execution: true,
+			state: Some(state),
+		}],
+	);
+
+	let mut link = WaitLink { result: Mutex::new(None), _phantom: Default::default() };
+	let mut delay = Delay::new(Duration::from_millis(DELAY_TIME));
+	future::poll_fn(move |cx| {
+		import_queue.poll_actions(cx, &mut link);
+		match link.result.lock().take() {
+			Some(Ok(())) => {
+				info!("🎉 Imported the state of #{number} ({hash})");
+				return Poll::Ready(Ok(()))
+			},
+			Some(Err(e)) =>
+				return Poll::Ready(Err(Error::Other(format!("Failed to import snapshot: {e}")))),
+			None => (),
+		}
+		while Pin::new(&mut delay).poll(cx).is_ready() {
+			delay.reset(Duration::from_millis(DELAY_TIME));
+		}
+		Poll::Pending
+	})
+	.boxed()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_state_machine::KeyValueStates;
+	use substrate_test_runtime_client::runtime::{Block, Header};
+
+	fn write_snapshot(frames: &[Frame<Block>]) -> Vec<u8> {
+		let mut output = Vec::new();
+		let mut writer = FrameWriter::new(&mut output).unwrap();
+		for frame in frames {
+			writer.write(frame).unwrap();
+		}
+		output
+	}
+
+	fn read(snapshot: &[u8]) -> Result<KeyValueStates, Error> {
+		let mut reader = FrameReader::new(snapshot)?;
+		let Frame::<Block>::Block { header, .. } = reader.read()? else {
+			return Err(Error::Other("Snapshot does not start with a block".into()))
+		};
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let state_sync = StateSync::new(client, header, None, None, true);
+		read_state(&mut reader, state_sync).map(|(_, state, _)| state.state)
+	}
+
+	fn storage(child: Option<(Vec<u8>, Vec<u8>)>, key: u8) -> Frame<Block> {
+		Frame::Storage { child, key_values: vec![(vec![key], vec![key; 4])] }
+	}
+
+	#[test]
+	fn snapshots_are_verified() {
+		let block = || Frame::Block {
+			genesis_hash: Default::default(),
+			header: Header::new(
+				0,
+				Default::default(),
+				Default::default(),
+				Default::default(),
+				Default::default(),
+			),
+			justifications: None,
+		};
+		let child = Some((b":child_storage:default:child".to_vec(), vec![7; 32]));
+		let snapshot = write_snapshot(&[
+			block(),
+			storage(None, 1),
+			storage(None, 2),
+			storage(child.clone(), 3),
+			storage(child.clone(), 4),
+			Frame::End { frames: 6, key_values: 4 },
+		]);
+
+		let mut levels = read(&snapshot).unwrap().0;
+		levels.sort_by(|a, b| a.state_root.cmp(&b.state_root));
+		assert_eq!(levels.len(), 2);
+		assert!(levels[0].state_root.is_empty());
+		assert_eq!(levels[0].key_values, vec![(vec![1], vec![1; 4]), (vec![2], vec![2; 4])]);
+		assert_eq!(levels[1].state_root, vec![7; 32]);
+		assert_eq!(levels[1].parent_storage_keys, vec![child.unwrap().0]);
+		assert_eq!(levels[1].key_values, vec![(vec![3], vec![3; 4]), (vec![4], vec![4; 4])]);
+
+		// A corrupted frame fails the checksum.
+		let mut corrupted = snapshot.clone();
+		let last = corrupted.len() - 1;
+		corrupted[last] ^= 1;
+		assert!(read(&corrupted).is_err());
+
+		// Missing frames are detected.
+		let truncated =
+			write_snapshot(&[block(), storage(None, 1), Frame::End { frames: 6, key_values: 4 }]);
+		assert!(read(&truncated).is_err());
+		assert!(read(&snapshot[..snapshot.len() - 1]).is_err());
+
+		// Oversized frames are rejected before they are read.
+		let mut oversized = write_snapshot(&[block()]);
+		oversized.extend((MAX_FRAME_LEN + 1).to_le_bytes());
+		oversized.extend([0; 32]);
+		assert!(read(&oversized).unwrap_err().to_string().contains("too large"));
+	}
+}
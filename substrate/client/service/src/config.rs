@@ -23,6 +23,7 @@ use prometheus_endpoint::Registry;
 use sc_chain_spec::ChainSpec;
 pub use sc_client_db::{BlocksPruning, Database, DatabaseSource, PruningMode};
 pub use sc_executor::{WasmExecutionMethod, WasmtimeInstantiationStrategy};
+pub use sc_keystore::remote::RemoteEndpoint;
 pub use sc_network::{
 	config::{
 		MultiaddrWithPeerId, NetworkConfiguration, NodeKeyConfig, NonDefaultSetConfig, ProtocolId,
@@ -136,6 +137,11 @@ pub enum KeystoreConfig {
 	},
 	/// In-memory keystore. Recommended for in-browser nodes.
 	InMemory,
+	/// Keystore forwarding all operations to a signer in a separate process.
+	Remote {
+		/// Where the signer listens for requests.
+		endpoint: RemoteEndpoint,
+	},
 }
 
 impl KeystoreConfig {
@@ -143,7 +149,7 @@ impl KeystoreConfig {
 	pub fn path(&self) -> Option<&Path> {
 		match self {
 			Self::Path { path, .. } => Some(path),
-			Self::InMemory => None,
+			Self::InMemory | Self::Remote { .. } => None,
 		}
 	}
 }
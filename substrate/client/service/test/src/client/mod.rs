@@ -53,6 +53,7 @@ use substrate_test_runtime_client::{
 };
 
 mod db;
+mod snapshot;
 
 const TEST_ENGINE_ID: ConsensusEngineId = *b"TEST";
 
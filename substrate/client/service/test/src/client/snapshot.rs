@@ -0,0 +1,122 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use futures::executor::block_on;
+use sc_block_builder::BlockBuilderBuilder;
+use sc_client_api::{HeaderBackend, StorageProvider};
+use sc_consensus::{BasicQueue, BlockImportParams, ForkChoiceStrategy, Verifier};
+use sc_service::chain_ops::{export_snapshot, import_snapshot};
+use sp_consensus::BlockOrigin;
+use sp_core::testing::TaskExecutor;
+use sp_storage::{ChildInfo, StorageKey};
+use std::sync::Arc;
+use substrate_test_runtime_client::{
+	runtime::{currency::DOLLARS, Block, Transfer},
+	BlockBuilderExt, ClientBlockImportExt, ClientExt, DefaultTestClientBuilderExt, Sr25519Keyring,
+	TestClientBuilder, TestClientBuilderExt,
+};
+
+/// Finalizes the imported blocks, as the snapshot contains a finalized block.
+struct FinalizingVerifier;
+
+#[async_trait::async_trait]
+impl Verifier<Block> for FinalizingVerifier {
+	async fn verify(
+		&self,
+		mut block: BlockImportParams<Block>,
+	) -> Result<BlockImportParams<Block>, String> {
+		block.fork_choice = Some(ForkChoiceStrategy::LongestChain);
+		block.finalized = true;
+		Ok(block)
+	}
+}
+
+#[test]
+fn snapshot_export_and_import_works() {
+	sp_tracing::try_init_simple();
+
+	let child_info = ChildInfo::new_default(b"child");
+	let builder = || {
+		TestClientBuilder::new()
+			.add_extra_child_storage(&child_info, b"first".to_vec(), vec![1; 32])
+			.add_extra_child_storage(&child_info, b"second".to_vec(), vec![2; 32])
+	};
+	let client = Arc::new(builder().build());
+
+	let mut parent = client.chain_info().genesis_hash;
+	for (number, nonce) in (0..2).zip(0..) {
+		let mut block_builder = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(parent)
+			.with_parent_block_number(number)
+			.build()
+			.unwrap();
+		block_builder
+			.push_transfer(Transfer {
+				from: Sr25519Keyring::Alice.into(),
+				to: Sr25519Keyring::Ferdie.into(),
+				amount: 42 * DOLLARS,
+				nonce,
+			})
+			.unwrap();
+		let block = block_builder.build().unwrap().block;
+		parent = block.hash();
+		block_on(client.import(BlockOrigin::Own, block)).unwrap();
+	}
+	client.finalize_block(parent, None).unwrap();
+
+	// A chunk size of one byte writes every key-value into its own frame.
+	let mut snapshot = Vec::new();
+	export_snapshot(client.clone(), parent, &mut snapshot, 1).unwrap();
+
+	let imported = Arc::new(builder().build());
+	let import_queue = BasicQueue::new(
+		FinalizingVerifier,
+		Box::new(imported.clone()),
+		None,
+		&TaskExecutor::new(),
+		None,
+	);
+	block_on(import_snapshot(imported.clone(), import_queue, &snapshot[..])).unwrap();
+
+	let info = imported.info();
+	assert_eq!(info.best_hash, parent);
+	assert_eq!(info.finalized_hash, parent);
+	assert_eq!(imported.header(parent).unwrap(), client.header(parent).unwrap());
+	assert_eq!(
+		imported.storage_pairs(parent, None, None).unwrap().collect::<Vec<_>>(),
+		client.storage_pairs(parent, None, None).unwrap().collect::<Vec<_>>(),
+	);
+	assert_eq!(
+		imported
+			.child_storage(parent, &child_info, &StorageKey(b"second".to_vec()))
+			.unwrap(),
+		client
+			.child_storage(parent, &child_info, &StorageKey(b"second".to_vec()))
+			.unwrap(),
+	);
+
+	// The database is not fresh anymore.
+	let import_queue = BasicQueue::new(
+		FinalizingVerifier,
+		Box::new(imported.clone()),
+		None,
+		&TaskExecutor::new(),
+		None,
+	);
+	assert!(block_on(import_snapshot(imported, import_queue, &snapshot[..])).is_err());
+}
@@ -0,0 +1,275 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checkpoints.
+//!
+//! The state of every canonical block with a number that is a multiple of the checkpoint interval
+//! is kept forever, regardless of the pruning window.
+//!
+//! Nodes are deleted when the block that removed them from the state leaves the pruning window.
+//! To keep the state of the latest checkpoint, the deletions of its descendants are filtered
+//! before they enter the window: a deleted key is only kept if it was inserted after the
+//! checkpoint, otherwise it belongs to the checkpoint and is dropped from the deletions for good.
+//! Once the next checkpoint is canonicalized, the filter starts over.
+//!
+//! The keys inserted and deleted since the latest checkpoint are journaled per block, so the
+//! filter can be rebuilt on startup.
+//!
+//! The filter tracks at most [`MAX_FILTER_KEYS`] keys. Once it is full, inserted keys are not
+//! tracked anymore until the next checkpoint and all deletions of untracked keys are dropped.
+//! This never deletes a node of the checkpoint, but keeps the nodes inserted and deleted in the
+//! meantime in the database forever.
+
+use crate::{to_meta_key, CommitSet, Error, Hash, MetaDb, LOG_TARGET};
+use codec::{Decode, Encode};
+use log::{trace, warn};
+use std::collections::HashSet;
+
+const CHECKPOINTS: &[u8] = b"checkpoints";
+const CHECKPOINT_JOURNAL: &[u8] = b"checkpoint_journal";
+
+/// Maximum number of keys tracked by the filter of the latest checkpoint.
+const MAX_FILTER_KEYS: usize = 1024 * 1024;
+
+/// See module documentation.
+pub struct Checkpoints<BlockHash: Hash, Key: Hash> {
+	/// Canonical blocks with a number that is a multiple of this keep their state.
+	interval: u32,
+	/// Number and hash of the kept blocks, ordered by number.
+	blocks: Vec<(u64, BlockHash)>,
+	/// Keys inserted since the latest checkpoint.
+	inserted: HashSet<Key>,
+	/// Keys of the latest checkpoint deleted since then, which are never deleted from the
+	/// database.
+	protected: HashSet<Key>,
+	/// Maximum number of keys in `inserted` and `protected` together.
+	max_keys: usize,
+	/// The filter reached `max_keys` since the latest checkpoint and stopped tracking keys.
+	saturated: bool,
+}
+
+#[derive(Encode, Decode)]
+struct JournalRecord<Key: Hash> {
+	inserted: Vec<Key>,
+	deleted: Vec<Key>,
+}
+
+fn to_journal_key(block: u64) -> Vec<u8> {
+	to_meta_key(CHECKPOINT_JOURNAL, &block)
+}
+
+impl<BlockHash: Hash, Key: Hash> Checkpoints<BlockHash, Key> {
+	pub fn new<D: MetaDb>(db: &D, interval: u32) -> Result<Self, Error<D::Error>> {
+		Self::with_max_keys(db, interval, MAX_FILTER_KEYS)
+	}
+
+	fn with_max_keys<D: MetaDb>(
+		db: &D,
+		interval: u32,
+		max_keys: usize,
+	) -> Result<Self, Error<D::Error>> {
+		let blocks: Vec<(u64, BlockHash)> =
+			match db.get_meta(&to_meta_key(CHECKPOINTS, &())).map_err(Error::Db)? {
+				Some(buffer) => Decode::decode(&mut buffer.as_slice())?,
+				None => Vec::new(),
+			};
+		let mut checkpoints = Checkpoints {
+			interval,
+			blocks,
+			inserted: HashSet::new(),
+			protected: HashSet::new(),
+			max_keys,
+			saturated: false,
+		};
+		if let Some(&(latest, _)) = checkpoints.blocks.last() {
+			let mut block = latest + 1;
+			while let Some(record) = db.get_meta(&to_journal_key(block)).map_err(Error::Db)? {
+				let JournalRecord { inserted, mut deleted } =
+					Decode::decode(&mut record.as_slice())?;
+				checkpoints.filter(inserted.iter(), &mut deleted);
+				block += 1;
+			}
+			trace!(
+				target: LOG_TARGET,
+				"Restored checkpoint #{} with {} blocks since ({} inserted, {} protected)",
+				latest,
+				block - latest - 1,
+				checkpoints.inserted.len(),
+				checkpoints.protected.len(),
+			);
+		}
+		Ok(checkpoints)
+	}
+
+	/// Check if the state of the given block is kept as a checkpoint.
+	pub fn have_block(&self, hash: &BlockHash, number: u64) -> bool {
+		self.blocks
+			.binary_search_by_key(&number, |(number, _)| *number)
+			.map_or(false, |index| self.blocks[index].1 == *hash)
+	}
+
+	/// Note a canonicalized block, before its deletions are added to the pruning window.
+	///
+	/// Removes the nodes of the latest checkpoint from the deletions in `commit`.
+	pub fn note_canonical(&mut self, hash: &BlockHash, number: u64, commit: &mut CommitSet<Key>) {
+		if number % self.interval as u64 == 0 {
+			let previous = self.blocks.last().map_or(number, |(previous, _)| *previous);
+			commit.meta.deleted.extend((previous + 1..number).map(to_journal_key));
+			self.inserted.clear();
+			self.protected.clear();
+			self.saturated = false;
+			self.blocks.push((number, hash.clone()));
+			commit.meta.inserted.push((to_meta_key(CHECKPOINTS, &()), self.blocks.encode()));
+			trace!(target: LOG_TARGET, "Adding checkpoint {:?} (#{})", hash, number);
+		} else if self.blocks.last().map_or(false, |(latest, _)| *latest < number) {
+			let record = JournalRecord {
+				inserted: commit.data.inserted.iter().map(|(k, _)| k.clone()).collect(),
+				deleted: commit.data.deleted.clone(),
+			};
+			commit.meta.inserted.push((to_journal_key(number), record.encode()));
+			self.filter(record.inserted.iter(), &mut commit.data.deleted);
+		}
+	}
+
+	fn filter<'a>(&mut self, inserted: impl Iterator<Item = &'a Key>, deleted: &mut Vec<Key>)
+	where
+		Key: 'a,
+	{
+		for k in inserted {
+			if self.saturated {
+				break
+			}
+			if !self.protected.contains(k) {
+				self.inserted.insert(k.clone());
+				self.check_saturated();
+			}
+		}
+		deleted.retain(|k| {
+			if self.inserted.remove(k) {
+				true
+			} else {
+				// Once saturated, untracked re-inserted keys are never deleted, so there is no
+				// need to remember the protected ones.
+				if !self.saturated {
+					self.protected.insert(k.clone());
+					self.check_saturated();
+				}
+				false
+			}
+		});
+	}
+
+	/// Stops tracking keys until the next checkpoint once the filter is full.
+	///
+	/// Tracking must not resume when keys are removed from the filter: the protected keys are
+	/// not recorded while saturated, so re-inserting and deleting one would delete it.
+	fn check_saturated(&mut self) {
+		if self.inserted.len() + self.protected.len() >= self.max_keys {
+			warn!(
+				target: LOG_TARGET,
+				"Checkpoint filter is full, nodes deleted before the next checkpoint are kept",
+			);
+			self.protected.clear();
+			self.saturated = true;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Checkpoints;
+	use crate::test::{make_commit, make_db, TestDb};
+	use sp_core::H256;
+
+	fn note(
+		checkpoints: &mut Checkpoints<H256, H256>,
+		db: &mut TestDb,
+		number: u64,
+		inserted: &[u64],
+		deleted: &[u64],
+	) -> Vec<H256> {
+		let mut commit = make_commit(inserted, deleted);
+		checkpoints.note_canonical(&H256::from_low_u64_be(number), number, &mut commit);
+		db.commit(&commit);
+		commit.data.deleted
+	}
+
+	#[test]
+	fn deletions_of_checkpoint_nodes_are_dropped() {
+		let mut db = make_db(&[]);
+		let mut checkpoints = Checkpoints::new(&db, 4).unwrap();
+
+		// Nothing is filtered before the first checkpoint.
+		assert_eq!(note(&mut checkpoints, &mut db, 3, &[1, 2], &[]), vec![]);
+		assert_eq!(note(&mut checkpoints, &mut db, 4, &[3], &[1]), vec![H256::from_low_u64_be(1)]);
+		assert!(checkpoints.have_block(&H256::from_low_u64_be(4), 4));
+		assert!(!checkpoints.have_block(&H256::from_low_u64_be(5), 4));
+
+		// 2 and 3 belong to the checkpoint, 5 does not.
+		assert_eq!(note(&mut checkpoints, &mut db, 5, &[5], &[2]), vec![]);
+		assert_eq!(
+			note(&mut checkpoints, &mut db, 6, &[6, 2], &[5, 3]),
+			vec![H256::from_low_u64_be(5)]
+		);
+
+		// Restarting restores what was inserted and protected since the checkpoint.
+		let mut checkpoints = Checkpoints::new(&db, 4).unwrap();
+		assert_eq!(
+			note(&mut checkpoints, &mut db, 7, &[], &[2, 6]),
+			vec![H256::from_low_u64_be(6)]
+		);
+
+		// The next checkpoint starts over and discards the journal.
+		let meta_len = db.meta_len();
+		assert_eq!(note(&mut checkpoints, &mut db, 8, &[], &[3]), vec![H256::from_low_u64_be(3)]);
+		assert_eq!(db.meta_len(), meta_len - 3);
+		assert_eq!(note(&mut checkpoints, &mut db, 9, &[], &[4]), vec![]);
+
+		let checkpoints = Checkpoints::<H256, H256>::new(&db, 4).unwrap();
+		assert!(checkpoints.have_block(&H256::from_low_u64_be(4), 4));
+		assert!(checkpoints.have_block(&H256::from_low_u64_be(8), 8));
+		assert!(checkpoints.protected.contains(&H256::from_low_u64_be(4)));
+	}
+
+	#[test]
+	fn full_filter_keeps_untracked_deletions() {
+		let mut db = make_db(&[]);
+		let mut checkpoints = Checkpoints::with_max_keys(&db, 4, 3).unwrap();
+		note(&mut checkpoints, &mut db, 4, &[1], &[]);
+
+		// 5 and 6 are tracked and 1 is protected, which fills the filter.
+		assert_eq!(note(&mut checkpoints, &mut db, 5, &[5, 6], &[1]), vec![]);
+		assert!(checkpoints.saturated);
+
+		// Tracked keys are still deleted, the deletions of untracked keys are dropped.
+		assert_eq!(
+			note(&mut checkpoints, &mut db, 6, &[7, 1], &[5, 7, 1]),
+			vec![H256::from_low_u64_be(5)]
+		);
+
+		// Restarting restores the full filter.
+		let mut checkpoints = Checkpoints::with_max_keys(&db, 4, 3).unwrap();
+		assert!(checkpoints.saturated);
+		assert_eq!(note(&mut checkpoints, &mut db, 7, &[], &[6]), vec![H256::from_low_u64_be(6)]);
+
+		// The next checkpoint starts over.
+		note(&mut checkpoints, &mut db, 8, &[], &[]);
+		assert!(!checkpoints.saturated);
+		assert_eq!(note(&mut checkpoints, &mut db, 9, &[9], &[9]), vec![H256::from_low_u64_be(9)]);
+	}
+}
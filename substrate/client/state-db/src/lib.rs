@@ -40,14 +40,22 @@
 //! # Pruning.
 //! See `RefWindow` for pruning algorithm details. `StateDb` prunes on each canonicalization until
 //! pruning constraints are satisfied.
+//!
+//! # Checkpoints.
+//! With a checkpoint interval in the pruning constraints, the state of every canonical block with
+//! a number that is a multiple of the interval is kept forever alongside the pruning window. See
+//! `Checkpoints` for details.
 
+mod checkpoint;
 mod noncanonical;
 mod pruning;
 #[cfg(test)]
 mod test;
 
-use codec::Codec;
+use checkpoint::Checkpoints;
+use codec::{Codec, Decode, Encode};
 use log::trace;
+pub use noncanonical::map_journal_keys;
 use noncanonical::NonCanonicalOverlay;
 use parking_lot::RwLock;
 use pruning::{HaveBlock, RefWindow};
@@ -62,6 +70,7 @@ const PRUNING_MODE: &[u8] = b"mode";
 const PRUNING_MODE_ARCHIVE: &[u8] = b"archive";
 const PRUNING_MODE_ARCHIVE_CANON: &[u8] = b"archive_canonical";
 const PRUNING_MODE_CONSTRAINED: &[u8] = b"constrained";
+const CHECKPOINT_INTERVAL: &[u8] = b"checkpoint_interval";
 pub(crate) const DEFAULT_MAX_BLOCK_CONSTRAINT: u32 = 256;
 
 /// Database value type.
@@ -224,6 +233,10 @@ pub struct Constraints {
 	/// Maximum blocks. Defaults to 0 when unspecified, effectively keeping only non-canonical
 	/// states.
 	pub max_blocks: Option<u32>,
+	/// Keep the state of every canonical block with a number that is a multiple of this interval,
+	/// starting with the first such block canonicalized after the interval is set. Once stored in
+	/// the database, the interval is kept when reopened without one. Zero keeps no checkpoints.
+	pub checkpoint_interval: Option<u32>,
 }
 
 /// Pruning mode.
@@ -240,7 +253,15 @@ pub enum PruningMode {
 impl PruningMode {
 	/// Create a mode that keeps given number of blocks.
 	pub fn blocks_pruning(n: u32) -> PruningMode {
-		PruningMode::Constrained(Constraints { max_blocks: Some(n) })
+		PruningMode::Constrained(Constraints { max_blocks: Some(n), checkpoint_interval: None })
+	}
+
+	/// Create a mode that keeps given number of blocks and the state of every `interval`-th block.
+	pub fn blocks_pruning_with_checkpoints(n: u32, interval: u32) -> PruningMode {
+		PruningMode::Constrained(Constraints {
+			max_blocks: Some(n),
+			checkpoint_interval: Some(interval),
+		})
 	}
 
 	/// Is this an archive (either ArchiveAll or ArchiveCanonical) pruning mode?
@@ -278,7 +299,7 @@ impl Default for PruningMode {
 
 impl Default for Constraints {
 	fn default() -> Self {
-		Self { max_blocks: Some(DEFAULT_MAX_BLOCK_CONSTRAINT) }
+		Self { max_blocks: Some(DEFAULT_MAX_BLOCK_CONSTRAINT), checkpoint_interval: None }
 	}
 }
 
@@ -303,6 +324,7 @@ pub struct StateDbSync<BlockHash: Hash, Key: Hash, D: MetaDb> {
 	mode: PruningMode,
 	non_canonical: NonCanonicalOverlay<BlockHash, Key>,
 	pruning: Option<RefWindow<BlockHash, Key, D>>,
+	checkpoints: Option<Checkpoints<BlockHash, Key>>,
 	pinned: HashMap<BlockHash, u32>,
 	ref_counting: bool,
 }
@@ -316,13 +338,26 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 		trace!(target: LOG_TARGET, "StateDb settings: {:?}. Ref-counting: {}", mode, ref_counting);
 
 		let non_canonical: NonCanonicalOverlay<BlockHash, Key> = NonCanonicalOverlay::new(&db)?;
+		let checkpoints = match mode {
+			PruningMode::Constrained(Constraints {
+				checkpoint_interval: Some(interval), ..
+			}) if interval > 0 => Some(Checkpoints::new(&db, interval)?),
+			_ => None,
+		};
 		let pruning: Option<RefWindow<BlockHash, Key, D>> = match mode {
-			PruningMode::Constrained(Constraints { max_blocks }) =>
+			PruningMode::Constrained(Constraints { max_blocks, .. }) =>
 				Some(RefWindow::new(db, max_blocks.unwrap_or(0), ref_counting)?),
 			PruningMode::ArchiveAll | PruningMode::ArchiveCanonical => None,
 		};
 
-		Ok(StateDbSync { mode, non_canonical, pruning, pinned: Default::default(), ref_counting })
+		Ok(StateDbSync {
+			mode,
+			non_canonical,
+			pruning,
+			checkpoints,
+			pinned: Default::default(),
+			ref_counting,
+		})
 	}
 
 	fn insert_block(
@@ -358,6 +393,9 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 		if self.mode == PruningMode::ArchiveCanonical {
 			commit.data.deleted.clear();
 		}
+		if let Some(ref mut checkpoints) = self.checkpoints {
+			checkpoints.note_canonical(hash, number, &mut commit);
+		}
 		if let Some(ref mut pruning) = self.pruning {
 			pruning.note_canonical(hash, number, &mut commit)?;
 		}
@@ -392,6 +430,8 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 					} else {
 						IsPruned::Pruned
 					}
+				} else if self.checkpoints.as_ref().map_or(false, |c| c.have_block(hash, number)) {
+					IsPruned::NotPruned
 				} else {
 					match self.pruning.as_ref() {
 						// We don't know for sure.
@@ -463,6 +503,7 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 			PruningMode::ArchiveAll => Ok(()),
 			PruningMode::ArchiveCanonical | PruningMode::Constrained(_) => {
 				let have_block = self.non_canonical.have_block(hash) ||
+					self.checkpoints.as_ref().map_or(false, |c| c.have_block(hash, number)) ||
 					self.pruning.as_ref().map_or_else(
 						|| hint(),
 						|pruning| match pruning.have_block(hash, number) {
@@ -539,7 +580,7 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDb<BlockHash, Key, D> {
 	) -> Result<(CommitSet<Key>, StateDb<BlockHash, Key, D>), Error<D::Error>> {
 		let stored_mode = fetch_stored_pruning_mode(&db)?;
 
-		let selected_mode = match (should_init, stored_mode, requested_mode) {
+		let selected_mode = match (should_init, stored_mode.clone(), requested_mode) {
 			(true, stored_mode, requested_mode) => {
 				assert!(stored_mode.is_none(), "The storage has just been initialized. No meta-data is expected to be found in it.");
 				requested_mode.unwrap_or_default()
@@ -556,7 +597,7 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDb<BlockHash, Key, D> {
 			(false, Some(stored), Some(requested)) => choose_pruning_mode(stored, requested)?,
 		};
 
-		let db_init_commit_set = if should_init {
+		let mut db_init_commit_set = if should_init {
 			let mut cs: CommitSet<Key> = Default::default();
 
 			let key = to_meta_key(PRUNING_MODE, &());
@@ -569,6 +610,18 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDb<BlockHash, Key, D> {
 			Default::default()
 		};
 
+		if let PruningMode::Constrained(Constraints {
+			checkpoint_interval: Some(interval), ..
+		}) = selected_mode
+		{
+			if stored_mode.map_or(true, |stored| checkpoint_interval(&stored) != Some(interval)) {
+				db_init_commit_set
+					.meta
+					.inserted
+					.push((to_meta_key(CHECKPOINT_INTERVAL, &()), interval.encode()));
+			}
+		}
+
 		let state_db =
 			StateDb { db: RwLock::new(StateDbSync::new(selected_mode, ref_counting, db)?) };
 
@@ -674,7 +727,14 @@ pub enum IsPruned {
 fn fetch_stored_pruning_mode<D: MetaDb>(db: &D) -> Result<Option<PruningMode>, Error<D::Error>> {
 	let meta_key_mode = to_meta_key(PRUNING_MODE, &());
 	if let Some(stored_mode) = db.get_meta(&meta_key_mode).map_err(Error::Db)? {
-		if let Some(mode) = PruningMode::from_id(&stored_mode) {
+		if let Some(mut mode) = PruningMode::from_id(&stored_mode) {
+			if let PruningMode::Constrained(ref mut constraints) = mode {
+				let meta_key_interval = to_meta_key(CHECKPOINT_INTERVAL, &());
+				if let Some(interval) = db.get_meta(&meta_key_interval).map_err(Error::Db)? {
+					constraints.checkpoint_interval =
+						Some(Decode::decode(&mut interval.as_slice())?);
+				}
+			}
 			Ok(Some(mode))
 		} else {
 			Err(StateDbError::Metadata(format!(
@@ -696,17 +756,27 @@ fn choose_pruning_mode(
 		(PruningMode::ArchiveAll, PruningMode::ArchiveAll) => Ok(PruningMode::ArchiveAll),
 		(PruningMode::ArchiveCanonical, PruningMode::ArchiveCanonical) =>
 			Ok(PruningMode::ArchiveCanonical),
-		(PruningMode::Constrained(_), PruningMode::Constrained(requested)) =>
-			Ok(PruningMode::Constrained(requested)),
+		(PruningMode::Constrained(stored), PruningMode::Constrained(requested)) =>
+			Ok(PruningMode::Constrained(Constraints {
+				max_blocks: requested.max_blocks,
+				checkpoint_interval: requested.checkpoint_interval.or(stored.checkpoint_interval),
+			})),
 		(stored, requested) => Err(StateDbError::IncompatiblePruningModes { requested, stored }),
 	}
 }
 
+fn checkpoint_interval(mode: &PruningMode) -> Option<u32> {
+	match mode {
+		PruningMode::Constrained(constraints) => constraints.checkpoint_interval,
+		PruningMode::ArchiveAll | PruningMode::ArchiveCanonical => None,
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::{
 		test::{make_changeset, make_db, TestDb},
-		Constraints, Error, IsPruned, PruningMode, StateDb, StateDbError,
+		Error, IsPruned, PruningMode, StateDb, StateDbError,
 	};
 	use sp_core::H256;
 
@@ -788,8 +858,7 @@ mod tests {
 
 	#[test]
 	fn block_record_unavailable() {
-		let (mut db, state_db) =
-			make_test_db(PruningMode::Constrained(Constraints { max_blocks: Some(1) }));
+		let (mut db, state_db) = make_test_db(PruningMode::blocks_pruning(1));
 		// import 2 blocks
 		for i in &[5, 6] {
 			db.commit(
@@ -823,13 +892,13 @@ mod tests {
 
 	#[test]
 	fn prune_window_0() {
-		let (db, _) = make_test_db(PruningMode::Constrained(Constraints { max_blocks: Some(0) }));
+		let (db, _) = make_test_db(PruningMode::blocks_pruning(0));
 		assert!(db.data_eq(&make_db(&[21, 3, 922, 94])));
 	}
 
 	#[test]
 	fn prune_window_1() {
-		let (db, sdb) = make_test_db(PruningMode::Constrained(Constraints { max_blocks: Some(1) }));
+		let (db, sdb) = make_test_db(PruningMode::blocks_pruning(1));
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(0), 0), IsPruned::Pruned);
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(1), 1), IsPruned::Pruned);
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(21), 2), IsPruned::Pruned);
@@ -839,7 +908,7 @@ mod tests {
 
 	#[test]
 	fn prune_window_2() {
-		let (db, sdb) = make_test_db(PruningMode::Constrained(Constraints { max_blocks: Some(2) }));
+		let (db, sdb) = make_test_db(PruningMode::blocks_pruning(2));
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(0), 0), IsPruned::Pruned);
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(1), 1), IsPruned::Pruned);
 		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(21), 2), IsPruned::NotPruned);
@@ -847,6 +916,26 @@ mod tests {
 		assert!(db.data_eq(&make_db(&[1, 21, 3, 921, 922, 93, 94])));
 	}
 
+	#[test]
+	fn prune_window_0_with_checkpoints() {
+		let (db, sdb) = make_test_db(PruningMode::blocks_pruning_with_checkpoints(0, 2));
+		assert!(db.data_eq(&make_db(&[21, 3, 922, 93, 94])));
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(1), 1), IsPruned::Pruned);
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(21), 2), IsPruned::NotPruned);
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(22), 2), IsPruned::Pruned);
+		assert!(sdb.pin(&H256::from_low_u64_be(21), 2, || false).is_ok());
+
+		let (_, sdb) = StateDb::<H256, H256, TestDb>::open(
+			db,
+			Some(PruningMode::blocks_pruning(0)),
+			false,
+			false,
+		)
+		.unwrap();
+		assert_eq!(sdb.pruning_mode(), PruningMode::blocks_pruning_with_checkpoints(0, 2));
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(21), 2), IsPruned::NotPruned);
+	}
+
 	#[test]
 	fn detects_incompatible_mode() {
 		let mut db = make_db(&[]);
@@ -863,7 +952,7 @@ mod tests {
 				)
 				.unwrap(),
 		);
-		let new_mode = PruningMode::Constrained(Constraints { max_blocks: Some(2) });
+		let new_mode = PruningMode::blocks_pruning(2);
 		let state_db_open_result: Result<(_, StateDb<H256, H256, TestDb>), _> =
 			StateDb::open(db.clone(), Some(new_mode), false, false);
 		assert!(state_db_open_result.is_err());
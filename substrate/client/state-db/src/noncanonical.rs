@@ -81,6 +81,28 @@ fn to_journal_key(block: u64, index: u64) -> Vec<u8> {
 	to_meta_key(NON_CANONICAL_JOURNAL, &(block, index))
 }
 
+/// Apply `f` to the keys of the nodes recorded in a journal entry of the overlay.
+///
+/// Returns `None` if `meta_key` is not the key of a journal entry.
+pub fn map_journal_keys<BlockHash: Hash, Key: Hash>(
+	meta_key: &[u8],
+	value: &[u8],
+	mut f: impl FnMut(Key) -> Key,
+) -> Option<Result<DBValue, codec::Error>> {
+	if meta_key.len() != to_journal_key(0, 0).len() || !meta_key.ends_with(NON_CANONICAL_JOURNAL) {
+		return None
+	}
+	Some(JournalRecord::<BlockHash, Key>::decode(&mut &value[..]).map(|record| {
+		JournalRecord {
+			hash: record.hash,
+			parent_hash: record.parent_hash,
+			inserted: record.inserted.into_iter().map(|(k, v)| (f(k), v)).collect(),
+			deleted: record.deleted.into_iter().map(&mut f).collect(),
+		}
+		.encode()
+	}))
+}
+
 #[cfg_attr(test, derive(PartialEq, Debug))]
 struct BlockOverlay<BlockHash: Hash, Key: Hash> {
 	hash: BlockHash,
@@ -570,7 +592,7 @@ impl<BlockHash: Hash, Key: Hash> NonCanonicalOverlay<BlockHash, Key> {
 
 #[cfg(test)]
 mod tests {
-	use super::{to_journal_key, NonCanonicalOverlay};
+	use super::{map_journal_keys, to_journal_key, NonCanonicalOverlay};
 	use crate::{
 		test::{make_changeset, make_db},
 		ChangeSet, CommitSet, MetaDb, StateDbError,
@@ -699,6 +721,36 @@ mod tests {
 		assert_eq!(overlay.last_canonicalized, overlay2.last_canonicalized);
 	}
 
+	#[test]
+	fn restore_from_journal_with_mapped_keys() {
+		let h1 = H256::random();
+		let mut db = make_db(&[1, 2]);
+		let mut overlay = NonCanonicalOverlay::<H256, H256>::new(&db).unwrap();
+		db.commit(
+			&overlay
+				.insert(&h1, 10, &H256::default(), make_changeset(&[3, 4], &[2]))
+				.unwrap(),
+		);
+
+		let shift = |key: H256| H256::from_low_u64_be(key.to_low_u64_be() + 10);
+		assert!(map_journal_keys::<H256, H256>(b"last_canonical", &[], shift).is_none());
+		let key = to_journal_key(10, 0);
+		let value = db.get_meta(&key).unwrap().unwrap();
+		let mapped = map_journal_keys::<H256, H256>(&key, &value, shift).unwrap().unwrap();
+		let mut commit = CommitSet::default();
+		commit.meta.inserted.push((key, mapped));
+		db.commit(&commit);
+
+		let overlay = NonCanonicalOverlay::<H256, H256>::new(&db).unwrap();
+		for key in [3, 4] {
+			assert_eq!(
+				overlay.get(&H256::from_low_u64_be(key + 10)),
+				Some(H256::from_low_u64_be(key).as_bytes().to_vec())
+			);
+		}
+		assert!(!contains(&overlay, 3));
+	}
+
 	#[test]
 	fn restore_from_journal_after_canonicalize() {
 		let h1 = H256::random();
@@ -69,6 +69,9 @@ pub enum Error {
 
 	#[error("The pool is not accepting future transactions")]
 	RejectedFutureTransaction,
+
+	#[error("The sender has too many transactions in the pool")]
+	SenderLimitReached,
 }
 
 impl Error {
@@ -87,7 +90,9 @@ impl Error {
 			// The node might be lagging behind, or during a warp sync.
 			Error::InvalidBlockId(_) |
 			// The pool is configured to not accept future transactions.
-			Error::RejectedFutureTransaction => {
+			Error::RejectedFutureTransaction |
+			// The transactions of the sender have to leave the pool first.
+			Error::SenderLimitReached => {
 				true
 			}
 			_ => false
@@ -98,6 +98,7 @@ impl TransactionPoolOptions {
 				},
 				reject_future_transactions: false,
 				ban_time: Duration::from_secs(30 * 60),
+				..Default::default()
 			},
 			txpool_type: TransactionPoolType::SingleState,
 		}
@@ -38,7 +38,7 @@ use crate::{
 	graph::{
 		self,
 		base_pool::{TimedTransactionSource, Transaction},
-		ExtrinsicFor, ExtrinsicHash, IsValidator, Options,
+		ExtrinsicFor, ExtrinsicHash, IsValidator, Options, ValidatedTransaction,
 	},
 	ReadyIteratorFor, LOG_TARGET,
 };
@@ -52,16 +52,17 @@ use futures::{
 use parking_lot::Mutex;
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_transaction_pool_api::{
-	error::Error as TxPoolApiError, ChainEvent, ImportNotificationStream,
-	MaintainedTransactionPool, PoolStatus, TransactionFor, TransactionPool, TransactionPriority,
-	TransactionSource, TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
+	error::{Error as TxPoolApiError, IntoPoolError},
+	ChainEvent, ImportNotificationStream, MaintainedTransactionPool, PoolStatus, TransactionFor,
+	TransactionPool, TransactionPriority, TransactionSource, TransactionStatusStreamFor, TxHash,
+	TxInvalidityReportMap,
 };
 use sp_blockchain::{HashAndNumber, TreeRoute};
 use sp_core::traits::SpawnEssentialNamed;
 use sp_runtime::{
 	generic::BlockId,
 	traits::{Block as BlockT, NumberFor},
-	transaction_validity::{TransactionValidityError, ValidTransaction},
+	transaction_validity::{TransactionTag, TransactionValidityError, ValidTransaction},
 };
 use std::{
 	collections::{HashMap, HashSet},
@@ -195,6 +196,24 @@ where
 		ready_limits: crate::PoolLimit,
 		future_limits: crate::PoolLimit,
 		mempool_max_transactions_count: usize,
+	) -> (Self, ForkAwareTxPoolTask) {
+		Self::new_test_with_options(
+			pool_api,
+			best_block_hash,
+			finalized_hash,
+			Options { ready: ready_limits, future: future_limits, ..Default::default() },
+			mempool_max_transactions_count,
+		)
+	}
+
+	/// Create new fork aware transaction pool with given options and with provided shared instance
+	/// of `ChainApi` intended for tests.
+	pub fn new_test_with_options(
+		pool_api: Arc<ChainApi>,
+		best_block_hash: Block::Hash,
+		finalized_hash: Block::Hash,
+		options: Options,
+		mempool_max_transactions_count: usize,
 	) -> (Self, ForkAwareTxPoolTask) {
 		let (listener, listener_task) = MultiViewListener::new_with_worker(Default::default());
 		let listener = Arc::new(listener);
@@ -207,7 +226,7 @@ where
 			listener.clone(),
 			Default::default(),
 			mempool_max_transactions_count,
-			ready_limits.total_bytes + future_limits.total_bytes,
+			options.ready.total_bytes + options.future.total_bytes,
 		));
 
 		let (dropped_stream_controller, dropped_stream) =
@@ -221,6 +240,7 @@ where
 			mempool.clone(),
 			view_store.clone(),
 			import_notification_sink.clone(),
+			Default::default(),
 		);
 
 		let combined_tasks = async move {
@@ -232,8 +252,6 @@ where
 		}
 		.boxed();
 
-		let options = Options { ready: ready_limits, future: future_limits, ..Default::default() };
-
 		(
 			Self {
 				mempool,
@@ -270,6 +288,7 @@ where
 			Block::Hash,
 			ExtrinsicHash<ChainApi>,
 		>,
+		metrics: PrometheusMetrics,
 	) {
 		loop {
 			let Some(dropped) = dropped_stream.next().await else {
@@ -285,6 +304,7 @@ where
 			);
 			match dropped.reason {
 				DroppedReason::Usurped(new_tx_hash) => {
+					metrics.report(|metrics| metrics.usurped_txs.inc());
 					if let Some(new_tx) = mempool.get_by_hash(new_tx_hash) {
 						view_store.replace_transaction(new_tx.source(), new_tx.tx(), tx_hash).await;
 					} else {
@@ -295,7 +315,11 @@ where
 						);
 					};
 				},
-				DroppedReason::LimitsEnforced | DroppedReason::Invalid => {
+				DroppedReason::LimitsEnforced => {
+					metrics.report(|metrics| metrics.dropped_by_limits_txs.inc());
+					view_store.remove_transaction_subtree(tx_hash, |_, _| {});
+				},
+				DroppedReason::Invalid => {
 					view_store.remove_transaction_subtree(tx_hash, |_, _| {});
 				},
 			};
@@ -352,6 +376,7 @@ where
 			mempool.clone(),
 			view_store.clone(),
 			import_notification_sink.clone(),
+			metrics.clone(),
 		);
 
 		let combined_tasks = async move {
@@ -771,9 +796,13 @@ where
 			.into_iter()
 			.map(|result| {
 				result.map_err(Into::into).and_then(|insertion| {
-					submission_results.next().expect(RESULTS_ASSUMPTION).inspect_err(|_| {
-						mempool.remove_transactions(&[insertion.hash]);
-					})
+					submission_results
+						.next()
+						.expect(RESULTS_ASSUMPTION)
+						.inspect_err(|_| {
+							mempool.remove_transactions(&[insertion.hash]);
+						})
+						.map_err(|error| self.report_rejected(error))
 				})
 			})
 			.map(|r| {
@@ -841,6 +870,7 @@ where
 			.inspect_err(|_| {
 				self.mempool.remove_transactions(&[insertion.hash]);
 			})
+			.map_err(|error| self.report_rejected(error))
 			.map(|mut outcome| {
 				self.mempool.update_transaction_priority(&outcome);
 				outcome.expect_watcher()
@@ -1005,6 +1035,7 @@ where
 			.inspect_err(|_| {
 				self.mempool.remove_transactions(&[insertion.hash]);
 			})
+			.map_err(|error| self.report_rejected(error))
 			.map(|outcome| {
 				self.mempool.update_transaction_priority(&outcome);
 				outcome.hash()
@@ -1460,9 +1491,12 @@ where
 			)
 			.await;
 
-		let Some(priority) = validated_tx.priority() else {
+		let ValidatedTransaction::Valid(Transaction { priority, requires, provides, .. }) =
+			validated_tx
+		else {
 			return Err(TxPoolApiError::ImmediatelyDropped)
 		};
+		self.check_sender_limit(&best_view, &requires, &provides)?;
 
 		self.attempt_transaction_replacement_inner(xt, xt_hash, priority, source, watched)
 	}
@@ -1480,7 +1514,7 @@ where
 			.read()
 			.ok_or(TxPoolApiError::ImmediatelyDropped)?;
 
-		let ValidTransaction { priority, .. } = self
+		let ValidTransaction { priority, requires, provides, .. } = self
 			.api
 			.validate_transaction_blocking(at, TransactionSource::Local, Arc::from(xt.clone()))
 			.map_err(|_| TxPoolApiError::ImmediatelyDropped)?
@@ -1488,6 +1522,9 @@ where
 				TransactionValidityError::Invalid(i) => TxPoolApiError::InvalidTransaction(i),
 				TransactionValidityError::Unknown(u) => TxPoolApiError::UnknownTransaction(u),
 			})?;
+		if let Some((view, _)) = self.view_store.get_view_at(at, false) {
+			self.check_sender_limit(&view, &requires, &provides)?;
+		}
 		let xt_hash = self.hash_of(&xt);
 		self.attempt_transaction_replacement_inner(xt, xt_hash, priority, source, watched)
 	}
@@ -1502,6 +1539,9 @@ where
 	) -> Result<InsertionInfo<ExtrinsicHash<ChainApi>>, TxPoolApiError> {
 		let insertion_info =
 			self.mempool.try_insert_with_replacement(xt, priority, source, watched)?;
+		self.metrics.report(|metrics| {
+			metrics.mempool_evicted_txs.inc_by(insertion_info.removed.len() as _)
+		});
 
 		for worst_hash in &insertion_info.removed {
 			trace!(
@@ -1522,6 +1562,39 @@ where
 
 		return Ok(insertion_info)
 	}
+
+	/// Rejects a transaction whose sender has reached its limit in the given view.
+	///
+	/// Checked before the mempool evicts other transactions to make room for the new one, so
+	/// transactions that the views would reject anyway cannot push other transactions out.
+	fn check_sender_limit(
+		&self,
+		view: &View<ChainApi>,
+		requires: &[TransactionTag],
+		provides: &[TransactionTag],
+	) -> Result<(), TxPoolApiError> {
+		if view.pool.validated_pool().is_sender_limit_reached(requires, provides) {
+			self.metrics.report(|metrics| metrics.rejected_sender_limit_txs.inc());
+			return Err(TxPoolApiError::SenderLimitReached)
+		}
+		Ok(())
+	}
+
+	/// Reports the rejection of a transaction by the views in the eviction metrics.
+	fn report_rejected(&self, error: ChainApi::Error) -> ChainApi::Error {
+		match error.into_pool_error() {
+			Ok(error) => {
+				self.metrics.report(|metrics| match error {
+					TxPoolApiError::TooLowPriority { .. } =>
+						metrics.rejected_low_priority_txs.inc(),
+					TxPoolApiError::SenderLimitReached => metrics.rejected_sender_limit_txs.inc(),
+					_ => {},
+				});
+				error.into()
+			},
+			Err(error) => error,
+		}
+	}
 }
 
 #[async_trait]
@@ -83,6 +83,18 @@ pub struct Metrics {
 	pub view_revalidation_duration: Histogram,
 	/// Total number of the views created w/o cloning existing view.
 	pub non_cloned_views: Counter<U64>,
+	/// Total number of transactions replaced by a transaction providing the same tags.
+	pub usurped_txs: Counter<U64>,
+	/// Total number of transactions dropped from the views to enforce the limits.
+	pub dropped_by_limits_txs: Counter<U64>,
+	/// Total number of transactions evicted from the mempool to make room for transactions with
+	/// higher priority.
+	pub mempool_evicted_txs: Counter<U64>,
+	/// Total number of transactions rejected for not bumping the priority of the transactions
+	/// providing the same tags enough.
+	pub rejected_low_priority_txs: Counter<U64>,
+	/// Total number of transactions rejected because their sender reached its limit.
+	pub rejected_sender_limit_txs: Counter<U64>,
 	/// Histograms to track the timing distribution of individual transaction pool events.
 	pub events_histograms: EventsHistograms,
 }
@@ -348,6 +360,41 @@ impl MetricsRegistrant for Metrics {
 				)?,
 				registry,
 			)?,
+			usurped_txs: register(
+				Counter::new(
+					"substrate_sub_txpool_usurped_txs_total",
+					"Total number of transactions replaced by a transaction providing the same tags.",
+				)?,
+				registry,
+			)?,
+			dropped_by_limits_txs: register(
+				Counter::new(
+					"substrate_sub_txpool_dropped_by_limits_txs_total",
+					"Total number of transactions dropped from the views to enforce the limits.",
+				)?,
+				registry,
+			)?,
+			mempool_evicted_txs: register(
+				Counter::new(
+					"substrate_sub_txpool_mempool_evicted_txs_total",
+					"Total number of transactions evicted from the mempool to make room for transactions with higher priority.",
+				)?,
+				registry,
+			)?,
+			rejected_low_priority_txs: register(
+				Counter::new(
+					"substrate_sub_txpool_rejected_low_priority_txs_total",
+					"Total number of transactions rejected for not bumping the priority of the transactions providing the same tags enough.",
+				)?,
+				registry,
+			)?,
+			rejected_sender_limit_txs: register(
+				Counter::new(
+					"substrate_sub_txpool_rejected_sender_limit_txs_total",
+					"Total number of transactions rejected because their sender reached its limit.",
+				)?,
+				registry,
+			)?,
 			events_histograms: EventsHistograms::register(registry)?,
 		}))
 	}
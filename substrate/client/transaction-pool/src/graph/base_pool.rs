@@ -20,7 +20,13 @@
 //!
 //! For a more full-featured pool, have a look at the `pool` module.
 
-use std::{cmp::Ordering, collections::HashSet, fmt, hash, sync::Arc, time::Instant};
+use std::{
+	cmp::Ordering,
+	collections::{HashMap, HashSet},
+	fmt, hash,
+	sync::Arc,
+	time::Instant,
+};
 
 use crate::LOG_TARGET;
 use log::{trace, warn};
@@ -33,6 +39,7 @@ use sp_runtime::{
 		TransactionLongevity as Longevity, TransactionPriority as Priority, TransactionSource,
 		TransactionTag as Tag,
 	},
+	Percent,
 };
 
 use super::{
@@ -256,6 +263,8 @@ pub struct BasePool<Hash: hash::Hash + Eq, Ex> {
 	/// transactions to future in case they were just stuck in verification.
 	recently_pruned: [HashSet<Tag>; RECENTLY_PRUNED_TAGS],
 	recently_pruned_index: usize,
+	/// Limits on the number of transactions of a single sender, not enforced if `None`.
+	sender_limit: Option<SenderLimit>,
 }
 
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> Default for BasePool<Hash, Ex> {
@@ -273,9 +282,24 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			ready: Default::default(),
 			recently_pruned: Default::default(),
 			recently_pruned_index: 0,
+			sender_limit: None,
 		}
 	}
 
+	/// Sets the minimal priority increase, relative to the collective priority of the replaced
+	/// transactions, required to replace transactions providing the same tags.
+	pub fn set_min_priority_bump(&mut self, min_priority_bump: Percent) {
+		self.ready.set_min_priority_bump(min_priority_bump);
+	}
+
+	/// Sets the limits on the number of transactions of a single sender.
+	pub fn set_sender_limit(&mut self, sender_limit: Option<SenderLimit>) {
+		let tag_prefix_len = sender_limit.as_ref().map(|limit| limit.tag_prefix_len);
+		self.ready.count_senders(tag_prefix_len);
+		self.future.count_senders(tag_prefix_len);
+		self.sender_limit = sender_limit;
+	}
+
 	/// Clears buffer keeping recently pruned transaction.
 	pub fn clear_recently_pruned(&mut self) {
 		self.recently_pruned = Default::default();
@@ -330,14 +354,52 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 				return Err(error::Error::RejectedFutureTransaction)
 			}
 
+			if self.is_sender_limit_reached_in(false, &tx.transaction.provides) {
+				return Err(error::Error::SenderLimitReached)
+			}
+
 			let hash = tx.transaction.hash.clone();
 			self.future.import(tx);
 			return Ok(Imported::Future { hash })
 		}
 
+		if self.is_sender_limit_reached_in(true, &tx.transaction.provides) {
+			return Err(error::Error::SenderLimitReached)
+		}
+
 		self.import_to_ready(tx)
 	}
 
+	/// Checks if the sender of a transaction with the given tags already has as many transactions
+	/// as allowed in the queue the transaction would enter.
+	///
+	/// Ready transactions replacing some transaction providing the same tag do not count against
+	/// the limit.
+	pub fn is_sender_limit_reached(&self, requires: &[Tag], provides: &[Tag]) -> bool {
+		let is_ready = requires.iter().all(|tag| {
+			self.ready.provided_tags().contains_key(tag) ||
+				self.recently_pruned.iter().any(|pruned| pruned.contains(tag))
+		});
+		self.is_sender_limit_reached_in(is_ready, provides)
+	}
+
+	fn is_sender_limit_reached_in(&self, is_ready: bool, provides: &[Tag]) -> bool {
+		let Some(limit) = &self.sender_limit else { return false };
+		let Some(sender) = limit.sender(provides) else { return false };
+
+		let reached = if is_ready {
+			let provided_tags = self.ready.provided_tags();
+			!provides.iter().any(|tag| provided_tags.contains_key(tag)) &&
+				self.ready.sender_count(sender) >= limit.ready
+		} else {
+			self.future.sender_count(sender) >= limit.future
+		};
+		if reached {
+			trace!(target: LOG_TARGET, "Sender limit reached: 0x{}", HexDisplay::from(&sender));
+		}
+		reached
+	}
+
 	/// Imports transaction to ready queue.
 	///
 	/// NOTE the transaction has to have all requirements satisfied.
@@ -612,6 +674,82 @@ impl Limit {
 	}
 }
 
+/// Limits on the number of transactions of a single sender.
+///
+/// The sender of a transaction is identified by the prefix of its first `provides` tag, e.g. the
+/// encoded account id of the `(AccountId, Nonce)` tag provided by FRAME's `CheckNonce`.
+#[derive(Debug, Clone)]
+pub struct SenderLimit {
+	/// Maximal number of ready transactions of a single sender.
+	pub ready: usize,
+	/// Maximal number of future transactions of a single sender.
+	pub future: usize,
+	/// Length of the `provides` tag prefix identifying the sender.
+	pub tag_prefix_len: usize,
+}
+
+impl SenderLimit {
+	/// Returns the sender of the transaction, `None` if its tags are too short to identify it.
+	pub fn sender<'a>(&self, provides: &'a [Tag]) -> Option<&'a [u8]> {
+		provides.first()?.get(..self.tag_prefix_len)
+	}
+}
+
+/// Number of transactions of every sender in a queue.
+///
+/// Updated whenever a transaction enters or leaves the queue, so checking the [`SenderLimit`]
+/// does not require iterating the queue.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SenderCounts {
+	/// Length of the `provides` tag prefix identifying the sender, nothing is counted if `None`.
+	tag_prefix_len: Option<usize>,
+	counts: HashMap<Vec<u8>, usize>,
+}
+
+impl SenderCounts {
+	/// Creates counts for the given transactions.
+	pub(crate) fn new<'a, Hash: 'a, Ex: 'a>(
+		tag_prefix_len: Option<usize>,
+		transactions: impl Iterator<Item = &'a Transaction<Hash, Ex>>,
+	) -> Self {
+		let mut counts = Self { tag_prefix_len, counts: Default::default() };
+		transactions.for_each(|tx| counts.insert(tx));
+		counts
+	}
+
+	fn sender<'a, Hash, Ex>(&self, tx: &'a Transaction<Hash, Ex>) -> Option<&'a [u8]> {
+		tx.provides.first()?.get(..self.tag_prefix_len?)
+	}
+
+	/// Returns the number of transactions of the given sender.
+	pub(crate) fn get(&self, sender: &[u8]) -> usize {
+		self.counts.get(sender).copied().unwrap_or_default()
+	}
+
+	/// Counts a transaction entering the queue.
+	pub(crate) fn insert<Hash, Ex>(&mut self, tx: &Transaction<Hash, Ex>) {
+		if let Some(sender) = self.sender(tx) {
+			*self.counts.entry(sender.to_vec()).or_default() += 1;
+		}
+	}
+
+	/// Forgets all counted transactions.
+	pub(crate) fn clear(&mut self) {
+		self.counts.clear();
+	}
+
+	/// Counts a transaction leaving the queue.
+	pub(crate) fn remove<Hash, Ex>(&mut self, tx: &Transaction<Hash, Ex>) {
+		let Some(sender) = self.sender(tx) else { return };
+		if let Some(count) = self.counts.get_mut(sender) {
+			*count -= 1;
+			if *count == 0 {
+				self.counts.remove(sender);
+			}
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -710,6 +848,53 @@ mod tests {
 		assert_eq!(pool.ready.len(), 1);
 	}
 
+	#[test]
+	fn should_enforce_sender_limits() {
+		// given
+		let mut pool = pool();
+		pool.set_sender_limit(Some(SenderLimit { ready: 2, future: 1, tag_prefix_len: 1 }));
+		let tx = |hash: Hash, requires: Vec<Tag>, provides: Tag| Transaction {
+			hash,
+			requires,
+			provides: vec![provides],
+			..default_tx().clone()
+		};
+
+		// when
+		pool.import(tx(1, vec![], vec![1, 0])).unwrap();
+		pool.import(tx(2, vec![vec![1, 0]], vec![1, 1])).unwrap();
+		let err = pool.import(tx(3, vec![vec![1, 1]], vec![1, 2])).unwrap_err();
+		assert!(matches!(err, error::Error::SenderLimitReached));
+
+		// replacements and other senders are not limited
+		pool.import(Transaction { priority: 10, ..tx(4, vec![vec![1, 0]], vec![1, 1]) })
+			.unwrap();
+		pool.import(tx(5, vec![], vec![2, 0])).unwrap();
+
+		// the future queue has its own limit
+		pool.import(tx(6, vec![vec![1, 5]], vec![1, 6])).unwrap();
+		let err = pool.import(tx(7, vec![vec![1, 6]], vec![1, 7])).unwrap_err();
+		assert!(matches!(err, error::Error::SenderLimitReached));
+
+		// then
+		let mut ready = pool.ready().map(|tx| tx.hash).collect::<Vec<_>>();
+		ready.sort();
+		assert_eq!(ready, vec![1, 4, 5]);
+		assert_eq!(pool.futures().map(|tx| tx.hash).collect::<Vec<_>>(), vec![6]);
+
+		// removed transactions free the limit
+		pool.remove_subtree(&[6]);
+		pool.import(tx(7, vec![vec![1, 6]], vec![1, 7])).unwrap();
+		pool.prune_tags(vec![vec![1, 0]]);
+		pool.import(tx(8, vec![vec![1, 1]], vec![1, 2])).unwrap();
+		assert!(matches!(
+			pool.import(tx(9, vec![vec![1, 2]], vec![1, 3])).unwrap_err(),
+			error::Error::SenderLimitReached
+		));
+		assert!(pool.is_sender_limit_reached(&[vec![1, 2]], &[vec![1, 3]]));
+		assert!(!pool.is_sender_limit_reached(&[vec![2, 0]], &[vec![2, 1]]));
+	}
+
 	#[test]
 	fn should_not_import_same_transaction_twice() {
 		// given
@@ -26,7 +26,7 @@ use sp_core::hexdisplay::HexDisplay;
 use sp_runtime::transaction_validity::TransactionTag as Tag;
 use std::time::Instant;
 
-use super::base_pool::Transaction;
+use super::base_pool::{SenderCounts, Transaction};
 use crate::{common::tracing_log_xt::log_xt_trace, LOG_TARGET};
 
 /// Transaction with partially satisfied dependencies.
@@ -114,11 +114,17 @@ pub struct FutureTransactions<Hash: hash::Hash + Eq, Ex> {
 	wanted_tags: HashMap<Tag, HashSet<Hash>>,
 	/// Transactions waiting for a particular other transaction
 	waiting: HashMap<Hash, WaitingTransaction<Hash, Ex>>,
+	/// Number of future transactions of every sender.
+	senders: SenderCounts,
 }
 
 impl<Hash: hash::Hash + Eq, Ex> Default for FutureTransactions<Hash, Ex> {
 	fn default() -> Self {
-		Self { wanted_tags: Default::default(), waiting: Default::default() }
+		Self {
+			wanted_tags: Default::default(),
+			waiting: Default::default(),
+			senders: Default::default(),
+		}
 	}
 }
 
@@ -132,6 +138,17 @@ qed
 impl<Hash: hash::Hash + Eq + Clone + std::fmt::Debug, Ex: std::fmt::Debug>
 	FutureTransactions<Hash, Ex>
 {
+	/// Starts counting the transactions of every sender, identified by the given `provides` tag
+	/// prefix length, or stops counting if `None`.
+	pub(crate) fn count_senders(&mut self, tag_prefix_len: Option<usize>) {
+		self.senders = SenderCounts::new(tag_prefix_len, self.all());
+	}
+
+	/// Returns the number of future transactions of the given sender.
+	pub(crate) fn sender_count(&self, sender: &[u8]) -> usize {
+		self.senders.get(sender)
+	}
+
 	/// Import transaction to Future queue.
 	///
 	/// Only transactions that don't have all their tags satisfied should occupy
@@ -152,6 +169,7 @@ impl<Hash: hash::Hash + Eq + Clone + std::fmt::Debug, Ex: std::fmt::Debug>
 		}
 
 		// Add the transaction to a by-hash waiting map
+		self.senders.insert(&tx.transaction);
 		self.waiting.insert(tx.transaction.hash.clone(), tx);
 	}
 
@@ -209,6 +227,7 @@ impl<Hash: hash::Hash + Eq + Clone + std::fmt::Debug, Ex: std::fmt::Debug>
 
 					if is_ready {
 						let tx = self.waiting.remove(&hash).expect(WAITING_PROOF);
+						self.senders.remove(&tx.transaction);
 						became_ready.push(tx);
 					}
 				}
@@ -225,6 +244,7 @@ impl<Hash: hash::Hash + Eq + Clone + std::fmt::Debug, Ex: std::fmt::Debug>
 		let mut removed = vec![];
 		for hash in hashes {
 			if let Some(waiting_tx) = self.waiting.remove(hash) {
+				self.senders.remove(&waiting_tx.transaction);
 				// remove from wanted_tags as well
 				for tag in waiting_tx.missing_tags {
 					let remove = if let Some(wanted) = self.wanted_tags.get_mut(&tag) {
@@ -261,6 +281,7 @@ impl<Hash: hash::Hash + Eq + Clone + std::fmt::Debug, Ex: std::fmt::Debug>
 	/// Removes and returns all future transactions.
 	pub fn clear(&mut self) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		self.wanted_tags.clear();
+		self.senders.clear();
 		self.waiting.drain().map(|(_, tx)| tx.transaction).collect()
 	}
 
@@ -27,6 +27,7 @@ use sp_runtime::{
 	transaction_validity::{
 		TransactionSource, TransactionTag as Tag, TransactionValidity, TransactionValidityError,
 	},
+	PerThing, Percent,
 };
 use std::{
 	sync::Arc,
@@ -144,6 +145,11 @@ pub struct Options {
 	pub reject_future_transactions: bool,
 	/// How long the extrinsic is banned for.
 	pub ban_time: Duration,
+	/// Minimal priority increase, relative to the collective priority of the replaced
+	/// transactions, required to replace transactions providing the same tags.
+	pub min_priority_bump: Percent,
+	/// Limits on the number of transactions of a single sender, not enforced if `None`.
+	pub sender_limit: Option<base::SenderLimit>,
 }
 
 impl Default for Options {
@@ -153,6 +159,8 @@ impl Default for Options {
 			future: base::Limit { count: 512, total_bytes: 1 * 1024 * 1024 },
 			reject_future_transactions: false,
 			ban_time: Duration::from_secs(60 * 30),
+			min_priority_bump: Percent::zero(),
+			sender_limit: None,
 		}
 	}
 }
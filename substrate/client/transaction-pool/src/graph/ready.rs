@@ -27,10 +27,10 @@ use crate::LOG_TARGET;
 use log::trace;
 use sc_transaction_pool_api::error;
 use serde::Serialize;
-use sp_runtime::{traits::Member, transaction_validity::TransactionTag as Tag};
+use sp_runtime::{traits::Member, transaction_validity::TransactionTag as Tag, PerThing, Percent};
 
 use super::{
-	base_pool::Transaction,
+	base_pool::{SenderCounts, Transaction},
 	future::WaitingTransaction,
 	tracked_map::{self, TrackedMap},
 };
@@ -118,6 +118,11 @@ pub struct ReadyTransactions<Hash: hash::Hash + Eq, Ex> {
 	/// Best transactions that are ready to be included to the block without any other previous
 	/// transaction.
 	best: BTreeSet<TransactionRef<Hash, Ex>>,
+	/// Minimal priority increase, relative to the collective priority of the replaced
+	/// transactions, required to replace transactions providing the same tags.
+	min_priority_bump: Percent,
+	/// Number of ready transactions of every sender.
+	senders: SenderCounts,
 }
 
 impl<Hash, Ex> tracked_map::Size for ReadyTx<Hash, Ex> {
@@ -133,11 +138,33 @@ impl<Hash: hash::Hash + Eq, Ex> Default for ReadyTransactions<Hash, Ex> {
 			provided_tags: Default::default(),
 			ready: Default::default(),
 			best: Default::default(),
+			min_priority_bump: Percent::zero(),
+			senders: Default::default(),
 		}
 	}
 }
 
 impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
+	/// Sets the minimal priority increase required to replace transactions providing the same
+	/// tags.
+	pub fn set_min_priority_bump(&mut self, min_priority_bump: Percent) {
+		self.min_priority_bump = min_priority_bump;
+	}
+
+	/// Starts counting the transactions of every sender, identified by the given `provides` tag
+	/// prefix length, or stops counting if `None`.
+	pub(crate) fn count_senders(&mut self, tag_prefix_len: Option<usize>) {
+		self.senders = SenderCounts::new(
+			tag_prefix_len,
+			self.ready.read().values().map(|tx| &*tx.transaction.transaction),
+		);
+	}
+
+	/// Returns the number of ready transactions of the given sender.
+	pub(crate) fn sender_count(&self, sender: &[u8]) -> usize {
+		self.senders.get(sender)
+	}
+
 	/// Borrows a map of tags that are provided by transactions in this queue.
 	pub fn provided_tags(&self) -> &HashMap<Tag, Hash> {
 		&self.provided_tags
@@ -219,6 +246,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 			self.provided_tags.insert(tag.clone(), hash.clone());
 		}
 
+		self.senders.insert(&transaction);
 		let transaction = TransactionRef { insertion_id, transaction };
 
 		// insert to best if it doesn't require any other transaction to be included before it
@@ -281,6 +309,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 		let mut ready = self.ready.write();
 		while let Some(hash) = to_remove.pop() {
 			if let Some(mut tx) = ready.remove(&hash) {
+				self.senders.remove(&tx.transaction.transaction);
 				let invalidated = tx.transaction.transaction.provides.iter().filter(|tag| {
 					provides_tag_filter
 						.as_ref()
@@ -337,6 +366,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 				.and_then(|hash| self.ready.write().remove(&hash));
 
 			if let Some(tx) = res {
+				self.senders.remove(&tx.transaction.transaction);
 				let unlocks = tx.unlocks;
 
 				// Make sure we remove it from best txs
@@ -445,7 +475,9 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 			};
 
 			// bail - the transaction has too low priority to replace the old ones
-			if old_priority >= tx.priority {
+			let min_priority =
+				old_priority.saturating_add(self.min_priority_bump.mul_ceil(old_priority));
+			if old_priority >= tx.priority || min_priority > tx.priority {
 				return Err(error::Error::TooLowPriority { old: old_priority, new: tx.priority })
 			}
 
@@ -638,6 +670,33 @@ mod tests {
 		assert_eq!(ready.get().count(), 1);
 	}
 
+	#[test]
+	fn should_require_min_priority_bump_to_replace() {
+		// given
+		let mut ready = ReadyTransactions::default();
+		ready.set_min_priority_bump(Percent::from_percent(10));
+		let mut tx1 = tx(1);
+		tx1.requires.clear();
+		tx1.priority = 100;
+		import(&mut ready, tx1).unwrap();
+
+		// when
+		let mut tx2 = tx(2);
+		tx2.requires.clear();
+		tx2.priority = 109;
+		let mut tx3 = tx2.clone();
+		tx3.hash = 3;
+		tx3.priority = 110;
+
+		// then
+		assert!(matches!(
+			import(&mut ready, tx2),
+			Err(error::Error::TooLowPriority { old: 100, new: 109 })
+		));
+		assert_eq!(import(&mut ready, tx3).unwrap()[0].hash, 1);
+		assert_eq!(ready.get().map(|tx| tx.hash).collect::<Vec<_>>(), vec![3]);
+	}
+
 	#[test]
 	fn should_replace_multiple_transactions_correctly() {
 		// given
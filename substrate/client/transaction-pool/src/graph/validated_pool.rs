@@ -238,7 +238,9 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 		rotator: PoolRotator<ExtrinsicHash<B>>,
 		event_handler: Option<L>,
 	) -> Self {
-		let base_pool = base::BasePool::new(options.reject_future_transactions);
+		let mut base_pool = base::BasePool::new(options.reject_future_transactions);
+		base_pool.set_min_priority_bump(options.min_priority_bump);
+		base_pool.set_sender_limit(options.sender_limit.clone());
 		Self {
 			is_validator,
 			options,
@@ -759,6 +761,11 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 		self.pool.read().status()
 	}
 
+	/// Checks if the sender of a transaction with the given tags has reached its limit in the pool.
+	pub fn is_sender_limit_reached(&self, requires: &[Tag], provides: &[Tag]) -> bool {
+		self.pool.read().is_sender_limit_reached(requires, provides)
+	}
+
 	/// Notify all watchers that transactions in the block with hash have been finalized
 	pub async fn on_block_finalized(&self, block_hash: BlockHash<B>) -> Result<(), B::Error> {
 		log::trace!(
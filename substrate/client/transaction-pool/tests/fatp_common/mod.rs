@@ -18,9 +18,9 @@
 
 //! Tests for fork-aware transaction pool.
 
-use sc_transaction_pool::{ChainApi, PoolLimit};
+use sc_transaction_pool::{ChainApi, Options, PoolLimit, SenderLimit};
 use sc_transaction_pool_api::ChainEvent;
-use sp_runtime::transaction_validity::TransactionSource;
+use sp_runtime::{transaction_validity::TransactionSource, PerThing, Percent};
 use std::sync::Arc;
 use substrate_test_runtime_client::{
 	runtime::{Block, Hash, Header},
@@ -70,6 +70,8 @@ pub struct TestPoolBuilder {
 	ready_limits: sc_transaction_pool::PoolLimit,
 	future_limits: sc_transaction_pool::PoolLimit,
 	mempool_max_transactions_count: usize,
+	min_priority_bump: Percent,
+	sender_limit: Option<SenderLimit>,
 }
 
 impl Default for TestPoolBuilder {
@@ -80,6 +82,8 @@ impl Default for TestPoolBuilder {
 			ready_limits: PoolLimit { count: 8192, total_bytes: 20 * 1024 * 1024 },
 			future_limits: PoolLimit { count: 512, total_bytes: 1 * 1024 * 1024 },
 			mempool_max_transactions_count: usize::MAX,
+			min_priority_bump: Percent::zero(),
+			sender_limit: None,
 		}
 	}
 }
@@ -124,6 +128,18 @@ impl TestPoolBuilder {
 		self
 	}
 
+	pub fn with_min_priority_bump(mut self, min_priority_bump: Percent) -> Self {
+		self.min_priority_bump = min_priority_bump;
+		self.use_default_limits = false;
+		self
+	}
+
+	pub fn with_sender_limit(mut self, sender_limit: SenderLimit) -> Self {
+		self.sender_limit = Some(sender_limit);
+		self.use_default_limits = false;
+		self
+	}
+
 	pub fn build(
 		self,
 	) -> (ForkAwareTxPool<TestApi, Block>, Arc<TestApi>, futures::executor::ThreadPool) {
@@ -142,12 +158,17 @@ impl TestPoolBuilder {
 		let (pool, txpool_task) = if self.use_default_limits {
 			ForkAwareTxPool::new_test(api.clone(), genesis_hash, genesis_hash)
 		} else {
-			ForkAwareTxPool::new_test_with_limits(
+			ForkAwareTxPool::new_test_with_options(
 				api.clone(),
 				genesis_hash,
 				genesis_hash,
-				self.ready_limits,
-				self.future_limits,
+				Options {
+					ready: self.ready_limits,
+					future: self.future_limits,
+					min_priority_bump: self.min_priority_bump,
+					sender_limit: self.sender_limit,
+					..Default::default()
+				},
 				self.mempool_max_transactions_count,
 			)
 		};
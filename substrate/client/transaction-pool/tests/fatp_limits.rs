@@ -24,7 +24,7 @@ use fatp_common::{
 	finalized_block_event, invalid_hash, new_best_block_event, TestPoolBuilder, LOG_TARGET, SOURCE,
 };
 use futures::{executor::block_on, FutureExt};
-use sc_transaction_pool::ChainApi;
+use sc_transaction_pool::{ChainApi, SenderLimit};
 use sc_transaction_pool_api::{
 	error::Error as TxPoolError, MaintainedTransactionPool, TransactionPool, TransactionStatus,
 };
@@ -829,3 +829,85 @@ fn fatp_limits_watcher_future_transactions_are_droped_when_view_is_dropped() {
 		assert_eq!(x_status, vec![TransactionStatus::Future]);
 	}
 }
+
+#[test]
+fn fatp_limits_sender_limit_works() {
+	sp_tracing::try_init_simple();
+
+	let builder = TestPoolBuilder::new();
+	let (pool, api, _) = builder
+		.with_sender_limit(SenderLimit { ready: 2, future: 1, tag_prefix_len: 1 })
+		.build();
+	api.set_nonce(api.genesis_hash(), Bob.into(), 300);
+
+	let header01 = api.push_block(1, vec![], true);
+
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	let xt2 = uxt(Alice, 202);
+	let xt3 = uxt(Alice, 205);
+	let xt4 = uxt(Alice, 206);
+	let xt5 = uxt(Bob, 300);
+
+	let results = [&xt0, &xt1, &xt2, &xt3, &xt4, &xt5]
+		.into_iter()
+		.map(|xt| block_on(pool.submit_one(invalid_hash(), SOURCE, xt.clone())))
+		.collect::<Vec<_>>();
+
+	assert!(results[0].is_ok());
+	assert!(results[1].is_ok());
+	assert!(matches!(results[2].as_ref().unwrap_err().0, TxPoolError::SenderLimitReached));
+	assert!(results[3].is_ok());
+	assert!(matches!(results[4].as_ref().unwrap_err().0, TxPoolError::SenderLimitReached));
+	assert!(results[5].is_ok());
+
+	// rejected transactions do not stay in the mempool
+	assert_eq!(pool.mempool_len(), (4, 0));
+	assert_pool_status!(header01.hash(), &pool, 3, 1);
+	assert_ready_iterator!(header01.hash(), pool, [xt0, xt1, xt5]);
+}
+
+#[test]
+fn fatp_limits_sender_limit_checked_before_mempool_eviction() {
+	sp_tracing::try_init_simple();
+
+	let builder = TestPoolBuilder::new();
+	let (pool, api, _) = builder
+		.with_mempool_count_limit(3)
+		.with_sender_limit(SenderLimit { ready: 2, future: 1, tag_prefix_len: 1 })
+		.build();
+	api.set_nonce(api.genesis_hash(), Bob.into(), 300);
+
+	let header01 = api.push_block(1, vec![], true);
+
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	let xt2 = uxt(Bob, 300);
+	let xt3 = uxt(Alice, 202);
+
+	api.set_priority(&xt0, 2);
+	api.set_priority(&xt1, 2);
+	api.set_priority(&xt2, 1);
+	api.set_priority(&xt3, 10);
+
+	let results = [&xt0, &xt1, &xt2, &xt3]
+		.into_iter()
+		.map(|xt| block_on(pool.submit_one(invalid_hash(), SOURCE, xt.clone())))
+		.collect::<Vec<_>>();
+
+	assert!(results[0].is_ok());
+	assert!(results[1].is_ok());
+	assert!(results[2].is_ok());
+	assert!(matches!(results[3].as_ref().unwrap_err().0, TxPoolError::SenderLimitReached));
+
+	// the low priority transaction was not evicted for a transaction that is rejected anyway
+	assert_eq!(pool.mempool_len(), (3, 0));
+	assert_pool_status!(header01.hash(), &pool, 3, 0);
+	assert_ready_iterator!(header01.hash(), pool, [xt0, xt1, xt2]);
+}
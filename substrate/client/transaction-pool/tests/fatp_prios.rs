@@ -27,6 +27,7 @@ use sc_transaction_pool_api::{
 	error::Error as TxPoolError, LocalTransactionPool, MaintainedTransactionPool, TransactionPool,
 	TransactionStatus,
 };
+use sp_runtime::Percent;
 use substrate_test_runtime_client::Sr25519Keyring::*;
 use substrate_test_runtime_transaction_pool::uxt;
 #[test]
@@ -558,3 +559,34 @@ fn fatp_prios_submit_local_full_mempool_higher_prio_is_accepted() {
 	assert_ready_iterator!(header02.hash(), pool, [xt3, xt2]);
 	assert_ready_iterator!(header03.hash(), pool, [xt5, xt4]);
 }
+
+#[test]
+fn fatp_prio_replacement_requires_min_priority_bump() {
+	sp_tracing::try_init_simple();
+
+	let builder = TestPoolBuilder::new();
+	let (pool, api, _) = builder.with_min_priority_bump(Percent::from_percent(50)).build();
+
+	let header01 = api.push_block(1, vec![], true);
+
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 200);
+	let xt2 = uxt(Alice, 200);
+
+	api.set_priority(&xt0, 10);
+	api.set_priority(&xt1, 14);
+	api.set_priority(&xt2, 15);
+
+	let result0 = block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone()));
+	let result1 = block_on(pool.submit_one(header01.hash(), SOURCE, xt1.clone()));
+	let result2 = block_on(pool.submit_one(header01.hash(), SOURCE, xt2.clone()));
+
+	assert!(result0.is_ok());
+	assert!(matches!(result1.unwrap_err().0, TxPoolError::TooLowPriority { old: 10, new: 14 }));
+	assert!(result2.is_ok());
+	assert_ready_iterator!(header01.hash(), pool, [xt2]);
+	assert_pool_status!(header01.hash(), &pool, 1, 0);
+}
@@ -20,8 +20,6 @@
 
 use frame_support::dispatch::DispatchResult;
 use frame_system::offchain::CreateInherent;
-#[cfg(feature = "experimental")]
-use frame_system::offchain::SubmitTransaction;
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
@@ -37,6 +35,10 @@ pub use weights::*;
 #[cfg(feature = "experimental")]
 const LOG_TARGET: &str = "pallet-example-tasks";
 
+/// The maximum number of tasks submitted by the offchain worker in a block.
+#[cfg(feature = "experimental")]
+const MAX_SUBMITTED_TASKS: usize = 16;
+
 #[frame_support::pallet(dev_mode)]
 pub mod pallet {
 	use super::*;
@@ -70,20 +72,12 @@ pub mod pallet {
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		#[cfg(feature = "experimental")]
 		fn offchain_worker(_block_number: BlockNumberFor<T>) {
-			if let Some(key) = Numbers::<T>::iter_keys().next() {
-				// Create a valid task
-				let task = Task::<T>::AddNumberIntoTotal { i: key };
-				let runtime_task = <T as Config>::RuntimeTask::from(task);
-				let call = frame_system::Call::<T>::do_task { task: runtime_task.into() };
-
-				// Submit the task as an unsigned transaction
-				let xt = <T as CreateInherent<frame_system::Call<T>>>::create_inherent(call.into());
-				let res = SubmitTransaction::<T, frame_system::Call<T>>::submit_transaction(xt);
-				match res {
-					Ok(_) => log::info!(target: LOG_TARGET, "Submitted the task."),
-					Err(e) => log::error!(target: LOG_TARGET, "Error submitting task: {:?}", e),
-				}
-			}
+			// Submit the valid tasks as unsigned transactions.
+			let submitted = frame_system::offchain::submit_valid_tasks::<
+				T,
+				<T as frame_system::Config>::RuntimeTask,
+			>(MAX_SUBMITTED_TASKS);
+			log::info!(target: LOG_TARGET, "Submitted {submitted} tasks.");
 		}
 
 		#[cfg(not(feature = "experimental"))]
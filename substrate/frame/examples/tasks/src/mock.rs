@@ -19,7 +19,7 @@
 #![cfg(test)]
 
 use crate::{self as pallet_example_tasks};
-use frame_support::derive_impl;
+use frame_support::{derive_impl, traits::ConstU32};
 use sp_runtime::testing::TestXt;
 
 pub type AccountId = u32;
@@ -38,6 +38,7 @@ pub type Extrinsic = TestXt<RuntimeCall, ()>;
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
 impl frame_system::Config for Runtime {
 	type Block = Block;
+	type MaxIdleTasks = ConstU32<2>;
 }
 
 impl<LocalCall> frame_system::offchain::CreateTransactionBase<LocalCall> for Runtime
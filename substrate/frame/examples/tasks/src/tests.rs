@@ -135,6 +135,33 @@ fn task_execution_fails_for_invalid_task() {
 	});
 }
 
+#[cfg(feature = "experimental")]
+#[test]
+fn tasks_run_on_idle() {
+	use crate::WeightInfo;
+	use frame_support::{traits::Hooks, weights::Weight};
+	use frame_system::pallet_prelude::BlockNumberFor;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Numbers::<Runtime>::insert(0, 1);
+		Numbers::<Runtime>::insert(1, 4);
+		Numbers::<Runtime>::insert(2, 9);
+		let task_weight = <() as WeightInfo>::add_number_into_total();
+
+		// Only one task fits into the remaining weight.
+		let weight = <System as Hooks<BlockNumberFor<Runtime>>>::on_idle(1, task_weight);
+		assert_eq!(weight, task_weight);
+		assert_eq!(Numbers::<Runtime>::iter().count(), 2);
+
+		// At most `MaxIdleTasks` tasks run in a block.
+		let weight = <System as Hooks<BlockNumberFor<Runtime>>>::on_idle(1, Weight::MAX);
+		assert_eq!(weight, task_weight.saturating_mul(2));
+		assert_eq!(Numbers::<Runtime>::iter().count(), 0);
+		assert_eq!(crate::Total::<Runtime>::get(), (3, 14));
+	});
+}
+
 #[cfg(feature = "experimental")]
 #[test]
 fn task_with_offchain_worker() {
@@ -161,3 +188,61 @@ fn task_with_offchain_worker() {
 		assert!(tx.is_bare());
 	});
 }
+
+#[cfg(feature = "experimental")]
+#[test]
+fn offchain_worker_submits_valid_tasks_up_to_the_bound() {
+	use frame_support::traits::Get;
+	use sp_runtime::traits::ExtrinsicLike;
+
+	let (offchain, _offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+
+	let mut t = new_test_ext();
+	t.register_extension(OffchainWorkerExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+
+	// Decodes the submitted transactions into the numbers of the tasks they run.
+	let submitted = || {
+		let mut numbers = core::mem::take(&mut pool_state.write().transactions)
+			.into_iter()
+			.map(|tx| {
+				let tx = Extrinsic::decode(&mut &*tx).unwrap();
+				assert!(tx.is_bare());
+				match tx.function {
+					RuntimeCall::System(frame_system::Call::do_task {
+						task:
+							RuntimeTask::TasksExample(
+								crate::pallet::Task::<Runtime>::AddNumberIntoTotal { i },
+							),
+					}) => i,
+					call => panic!("unexpected call submitted: {call:?}"),
+				}
+			})
+			.collect::<Vec<_>>();
+		numbers.sort();
+		numbers
+	};
+
+	t.execute_with(|| {
+		Numbers::<Runtime>::insert(0, 1);
+		Numbers::<Runtime>::insert(1, 4);
+		Numbers::<Runtime>::insert(2, 9);
+
+		// Only `MaxIdleTasks` of the three valid tasks are submitted.
+		let max = <Runtime as frame_system::Config>::MaxIdleTasks::get() as usize;
+		assert_eq!(frame_system::offchain::submit_valid_tasks::<Runtime, RuntimeTask>(max), max);
+		let numbers = submitted();
+		assert_eq!(numbers.len(), max);
+		assert!(numbers.iter().all(|i| Numbers::<Runtime>::contains_key(i)));
+
+		// The offchain worker submits every valid task when below its bound.
+		advance_to(1);
+		assert_eq!(submitted(), vec![0, 1, 2]);
+
+		// Tasks that became invalid are not submitted anymore.
+		Numbers::<Runtime>::remove(1);
+		advance_to(2);
+		assert_eq!(submitted(), vec![0, 2]);
+	});
+}
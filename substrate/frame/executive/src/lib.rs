@@ -117,6 +117,15 @@
 //!
 //! pub type Executive = executive::Executive<Runtime, Block, Context, Runtime, AllPalletsWithSystem, CustomOnRuntimeUpgrade>;
 //! ```
+//!
+//! ### Sampled `try_state` checks
+//!
+//! On test networks, the `try_state` checks of the pallets can also run on the live chain. With
+//! the `try-state` feature enabled, which doesn't require the rest of the try-runtime tooling,
+//! setting the optional `TryStateSampler` generic parameter to a `SampledTryState` runs the checks
+//! of some of the pallets with the weight that is left after `on_idle`, rotating through the
+//! pallets from block to block. A failing check emits a `frame_system::Event::TryStateFailed`
+//! event and is reported to an [`OnTryStateFailure`], e.g. to count the failures in a metric.
 
 #[cfg(doc)]
 #[cfg_attr(doc, aquamarine::aquamarine)]
@@ -148,9 +157,12 @@
 /// ```
 pub mod block_flowchart {}
 
+mod sampled_try_state;
 #[cfg(test)]
 mod tests;
 
+pub use sampled_try_state::*;
+
 extern crate alloc;
 
 use codec::{Codec, Encode};
@@ -207,6 +219,8 @@ pub type OriginOf<E, C> = <CallOf<E, C> as Dispatchable>::RuntimeOrigin;
 ///   used to call hooks e.g. `on_initialize`.
 /// - `OnRuntimeUpgrade`: Custom logic that should be called after a runtime upgrade. Modules are
 ///   already called by `AllPalletsWithSystem`. It will be called before all modules will be called.
+/// - `TryStateSampler`: Runs sampled `try_state` checks with the weight left after `on_idle`. See
+///   [`SampleTryState`].
 pub struct Executive<
 	System,
 	Block,
@@ -214,6 +228,7 @@ pub struct Executive<
 	UnsignedValidator,
 	AllPalletsWithSystem,
 	OnRuntimeUpgrade = (),
+	TryStateSampler = (),
 >(
 	PhantomData<(
 		System,
@@ -222,6 +237,7 @@ pub struct Executive<
 		UnsignedValidator,
 		AllPalletsWithSystem,
 		OnRuntimeUpgrade,
+		TryStateSampler,
 	)>,
 );
 
@@ -241,8 +257,17 @@ impl<
 			+ OffchainWorker<BlockNumberFor<System>>
 			+ OnPoll<BlockNumberFor<System>>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
+		TryStateSampler: SampleTryState<System, AllPalletsWithSystem>,
 	> ExecuteBlock<Block>
-	for Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+	for Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		TryStateSampler,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
@@ -259,6 +284,7 @@ where
 			UnsignedValidator,
 			AllPalletsWithSystem,
 			COnRuntimeUpgrade,
+			TryStateSampler,
 		>::execute_block(block);
 	}
 }
@@ -282,7 +308,17 @@ impl<
 			+ TryState<BlockNumberFor<System>>
 			+ TryDecodeEntireStorage,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
-	> Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+		TryStateSampler: SampleTryState<System, AllPalletsWithSystem>,
+	>
+	Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		TryStateSampler,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
@@ -515,7 +551,17 @@ impl<
 			+ OffchainWorker<BlockNumberFor<System>>
 			+ OnPoll<BlockNumberFor<System>>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
-	> Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+		TryStateSampler: SampleTryState<System, AllPalletsWithSystem>,
+	>
+	Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		TryStateSampler,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
@@ -736,6 +782,15 @@ where
 				used_weight,
 				DispatchClass::Mandatory,
 			);
+
+			let sampled_weight = TryStateSampler::sample_try_state(
+				block_number,
+				remaining_weight.saturating_sub(used_weight),
+			);
+			<frame_system::Pallet<System>>::register_extra_weight_unchecked(
+				sampled_weight,
+				DispatchClass::Mandatory,
+			);
 		}
 	}
 
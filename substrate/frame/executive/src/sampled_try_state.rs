@@ -0,0 +1,158 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sampled execution of the `try_state` checks of the pallets at the end of `on_idle`.
+//!
+//! [`SampledTryState`] is available with either the `try-runtime` or the `try-state` feature. The
+//! latter only enables the `try_state` hooks of the pallets and not the rest of the try-runtime
+//! tooling, so that a test network runtime can run the checks on the live chain. The pallets
+//! whose checks should run must compile their `try_state` hook with the `try-state` feature as
+//! well.
+
+use frame_support::weights::Weight;
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::TryRuntimeError;
+
+#[cfg(any(feature = "try-runtime", feature = "try-state"))]
+use ::{
+	alloc::{vec, vec::Vec},
+	core::marker::PhantomData,
+	frame_support::{
+		storage::{transactional::with_transaction_unchecked, TransactionOutcome},
+		traits::{Get, PalletsInfoAccess, TryState, TryStateSelect},
+		weights::WeightMeter,
+	},
+	sp_runtime::traits::UniqueSaturatedInto,
+};
+
+/// Runs the `try_state` checks of some of the `AllPallets` with the weight that is left at the end
+/// of `on_idle`.
+///
+/// The unit type runs no checks.
+pub trait SampleTryState<System: frame_system::Config, AllPallets> {
+	/// Run the checks selected for block `n` within `remaining_weight`.
+	///
+	/// Returns the weight consumed.
+	fn sample_try_state(n: BlockNumberFor<System>, remaining_weight: Weight) -> Weight;
+}
+
+impl<System: frame_system::Config, AllPallets> SampleTryState<System, AllPallets> for () {
+	fn sample_try_state(_: BlockNumberFor<System>, _: Weight) -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Called when the sampled `try_state` checks of a pallet fail, in addition to the
+/// [`frame_system::Event::TryStateFailed`] event.
+///
+/// This is where a runtime feeds its metrics, e.g. a counter of the failures per pallet.
+pub trait OnTryStateFailure {
+	/// The checks of the pallet named `pallet` failed with `error`.
+	fn on_try_state_failure(pallet: &'static str, error: &TryRuntimeError);
+}
+
+impl OnTryStateFailure for () {
+	fn on_try_state_failure(_: &'static str, _: &TryRuntimeError) {}
+}
+
+/// Runs the `try_state` checks of the pallets selected by `Select` one pallet at a time, rotating
+/// through them from block to block.
+///
+/// - [`TryStateSelect::None`] runs no checks.
+/// - [`TryStateSelect::All`] runs the checks of as many pallets as the weight allows.
+/// - [`TryStateSelect::RoundRobin`] runs the checks of at most the given number of pallets.
+/// - [`TryStateSelect::Only`] runs the checks of as many of the given pallets as the weight allows.
+///   Names that are not pallets of the runtime are ignored.
+///
+/// Each pallet is accounted with `WeightPerCheck` and checks only run while the remaining weight
+/// covers it. Any changes to the storage made by the checks are discarded.
+///
+/// A failing check is logged, reported to `OnFailure` and emits a
+/// [`frame_system::Event::TryStateFailed`]. Block execution is never aborted.
+///
+/// This is meant for test networks only: the checks are usually not benchmarked and may be
+/// arbitrarily expensive.
+#[cfg(any(feature = "try-runtime", feature = "try-state"))]
+pub struct SampledTryState<Select, WeightPerCheck, OnFailure = ()>(
+	PhantomData<(Select, WeightPerCheck, OnFailure)>,
+);
+
+#[cfg(any(feature = "try-runtime", feature = "try-state"))]
+impl<System, AllPallets, Select, WeightPerCheck, OnFailure> SampleTryState<System, AllPallets>
+	for SampledTryState<Select, WeightPerCheck, OnFailure>
+where
+	System: frame_system::Config,
+	AllPallets: TryState<BlockNumberFor<System>> + PalletsInfoAccess,
+	Select: Get<TryStateSelect>,
+	WeightPerCheck: Get<Weight>,
+	OnFailure: OnTryStateFailure,
+{
+	fn sample_try_state(n: BlockNumberFor<System>, remaining_weight: Weight) -> Weight {
+		let names = AllPallets::infos().into_iter().map(|info| info.name);
+		let (pallets, max_checks): (Vec<&'static str>, _) = match Select::get() {
+			TryStateSelect::None => return Weight::zero(),
+			TryStateSelect::All => (names.collect(), usize::MAX),
+			TryStateSelect::RoundRobin(count) => (names.collect(), count as usize),
+			TryStateSelect::Only(only) => (
+				names.filter(|name| only.iter().any(|o| o == name.as_bytes())).collect(),
+				usize::MAX,
+			),
+		};
+		if pallets.is_empty() {
+			return Weight::zero()
+		}
+
+		let skip = UniqueSaturatedInto::<u64>::unique_saturated_into(n) % pallets.len() as u64;
+		let mut meter = WeightMeter::with_limit(remaining_weight);
+		for pallet in pallets
+			.iter()
+			.copied()
+			.cycle()
+			.skip(skip as usize)
+			.take(max_checks.min(pallets.len()))
+		{
+			if meter.try_consume(WeightPerCheck::get()).is_err() {
+				break
+			}
+
+			let result = with_transaction_unchecked(|| {
+				TransactionOutcome::Rollback(AllPallets::try_state(
+					n,
+					TryStateSelect::Only(vec![pallet.as_bytes().to_vec()]),
+				))
+			});
+			if let Err(error) = result {
+				log::error!(
+					target: crate::LOG_TARGET,
+					"sampled try-state checks of {:?} failed in block {:?}: {:?}",
+					pallet,
+					n,
+					error,
+				);
+				OnFailure::on_try_state_failure(pallet, &error);
+				frame_system::Pallet::<System>::deposit_event(
+					frame_system::Event::<System>::TryStateFailed {
+						pallet: pallet.as_bytes().to_vec(),
+						error,
+					},
+				);
+			}
+		}
+
+		meter.consumed()
+	}
+}
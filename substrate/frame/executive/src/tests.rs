@@ -43,6 +43,8 @@ use frame_system::{pallet_prelude::*, ChainContext, LastRuntimeUpgrade, LastRunt
 use pallet_balances::Call as BalancesCall;
 
 const TEST_KEY: &[u8] = b":test:key:";
+#[cfg(any(feature = "try-runtime", feature = "try-state"))]
+const TRY_STATE_KEY: &[u8] = b":try_state:key:";
 
 #[frame_support::pallet(dev_mode)]
 mod custom {
@@ -78,6 +80,16 @@ mod custom {
 		fn offchain_worker(n: BlockNumberFor<T>) {
 			assert_eq!(BlockNumberFor::<T>::from(1u32), n);
 		}
+
+		#[cfg(any(feature = "try-runtime", feature = "try-state"))]
+		fn try_state(_: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			// Checks must not be able to change the state.
+			sp_io::storage::set(super::TRY_STATE_KEY, b"changed");
+			if super::CustomTryStateFails::get() {
+				return Err("custom invariant violated".into())
+			}
+			Ok(())
+		}
 	}
 
 	#[pallet::call]
@@ -1282,6 +1294,137 @@ fn try_execute_block_works() {
 	});
 }
 
+#[cfg(any(feature = "try-runtime", feature = "try-state"))]
+parameter_types! {
+	pub static CustomTryStateFails: bool = false;
+	pub static SampledTryStateSelect: frame_support::traits::TryStateSelect =
+		frame_support::traits::TryStateSelect::None;
+	pub const TryStateCheckWeight: Weight = Weight::from_parts(10, 0);
+	pub static TryStateFailures: Vec<&'static str> = vec![];
+}
+
+/// Records the failures like a metric counting them per pallet would.
+#[cfg(any(feature = "try-runtime", feature = "try-state"))]
+pub struct RecordTryStateFailures;
+
+#[cfg(any(feature = "try-runtime", feature = "try-state"))]
+impl super::OnTryStateFailure for RecordTryStateFailures {
+	fn on_try_state_failure(pallet: &'static str, _: &sp_runtime::TryRuntimeError) {
+		TryStateFailures::mutate(|failures| failures.push(pallet));
+	}
+}
+
+#[test]
+#[cfg(any(feature = "try-runtime", feature = "try-state"))]
+fn sampled_try_state_runs_in_on_idle() {
+	use super::{SampleTryState, SampledTryState};
+	use frame_support::traits::TryStateSelect;
+
+	type Sampler =
+		SampledTryState<SampledTryStateSelect, TryStateCheckWeight, RecordTryStateFailures>;
+	type SamplingExecutive = super::Executive<
+		Runtime,
+		Block<UncheckedXt>,
+		ChainContext<Runtime>,
+		Runtime,
+		AllPalletsWithSystem,
+		CustomOnRuntimeUpgrade,
+		Sampler,
+	>;
+	let sample = |n: u64, remaining_weight: Weight| {
+		System::reset_events();
+		<Sampler as SampleTryState<Runtime, AllPalletsWithSystem>>::sample_try_state(
+			n,
+			remaining_weight,
+		)
+	};
+	let failed = || {
+		System::events()
+			.into_iter()
+			.filter(|record| {
+				record.event ==
+					RuntimeEvent::System(frame_system::Event::TryStateFailed {
+						pallet: b"Custom".to_vec(),
+						error: "custom invariant violated".into(),
+					})
+			})
+			.count()
+	};
+	let check = TryStateCheckWeight::get();
+
+	new_test_ext(1).execute_with(|| {
+		System::set_block_number(1);
+		CustomTryStateFails::set(true);
+
+		// Nothing is selected by default.
+		assert_eq!(sample(1, check * 10), Weight::zero());
+		assert_eq!(failed(), 0);
+
+		SampledTryStateSelect::set(TryStateSelect::Only(vec![b"Custom".to_vec()]));
+		assert_eq!(sample(1, check * 10), check);
+		assert_eq!(failed(), 1);
+		assert!(!sp_io::storage::exists(TRY_STATE_KEY));
+
+		// Not enough weight left for a single check.
+		assert_eq!(sample(1, check - Weight::from_parts(1, 0)), Weight::zero());
+		assert_eq!(failed(), 0);
+
+		// One pallet per block, rotating through all of them. `Custom` is the fourth one.
+		SampledTryStateSelect::set(TryStateSelect::RoundRobin(1));
+		for n in 0..10 {
+			assert_eq!(sample(n, check * 10), check);
+			assert_eq!(failed(), if n % 5 == 3 { 1 } else { 0 });
+		}
+
+		// All pallets, as far as the weight allows.
+		SampledTryStateSelect::set(TryStateSelect::All);
+		assert_eq!(sample(0, check * 3), check * 3);
+		assert_eq!(failed(), 0);
+		assert_eq!(sample(2, check * 3), check * 3);
+		assert_eq!(failed(), 1);
+		assert_eq!(sample(0, check * 10), check * 5);
+		assert_eq!(failed(), 1);
+
+		// Passing checks emit nothing.
+		CustomTryStateFails::set(false);
+		assert_eq!(sample(0, check * 10), check * 5);
+		assert_eq!(failed(), 0);
+
+		// Every failure above was reported for `Custom`.
+		assert_eq!(TryStateFailures::take(), vec!["Custom"; 5]);
+	});
+
+	// The executive runs the checks with the weight left after `on_idle`.
+	new_test_ext(1).execute_with(|| {
+		CustomTryStateFails::set(true);
+		SampledTryStateSelect::set(TryStateSelect::Only(vec![b"Custom".to_vec()]));
+
+		SamplingExecutive::initialize_block(&Header::new_from_number(1));
+		let weight_before = System::block_weight().total();
+		SamplingExecutive::finalize_block();
+
+		assert_eq!(failed_in_block(), 1);
+		assert!(!sp_io::storage::exists(TRY_STATE_KEY));
+		// `on_idle` of `Custom` and one check.
+		assert_eq!(
+			System::block_weight().total(),
+			weight_before + Weight::from_parts(175, 0) + check
+		);
+	});
+
+	fn failed_in_block() -> usize {
+		System::events()
+			.into_iter()
+			.filter(|record| {
+				matches!(
+					record.event,
+					RuntimeEvent::System(frame_system::Event::TryStateFailed { .. })
+				)
+			})
+			.count()
+	}
+}
+
 /// Same as `extrinsic_while_exts_forbidden_errors` but using the try-runtime function.
 #[test]
 #[cfg(feature = "try-runtime")]
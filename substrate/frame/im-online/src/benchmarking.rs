@@ -20,12 +20,9 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use frame_benchmarking::v2::*;
-use frame_support::{traits::UnfilteredDispatchable, WeakBoundedVec};
+use frame_support::{dispatch::Authorize, WeakBoundedVec};
 use frame_system::RawOrigin;
-use sp_runtime::{
-	traits::{ValidateUnsigned, Zero},
-	transaction_validity::TransactionSource,
-};
+use sp_runtime::{traits::Zero, transaction_validity::TransactionSource};
 
 use crate::*;
 
@@ -66,43 +63,26 @@ pub fn create_heartbeat<T: Config>(
 mod benchmarks {
 	use super::*;
 
-	#[benchmark(extra)]
+	#[benchmark]
 	fn heartbeat(k: Linear<1, MAX_KEYS>) -> Result<(), BenchmarkError> {
 		let (input_heartbeat, signature) = create_heartbeat::<T>(k)?;
 
 		#[extrinsic_call]
-		_(RawOrigin::None, input_heartbeat, signature);
-
-		Ok(())
-	}
-
-	#[benchmark(extra)]
-	fn validate_unsigned(k: Linear<1, MAX_KEYS>) -> Result<(), BenchmarkError> {
-		let (input_heartbeat, signature) = create_heartbeat::<T>(k)?;
-		let call = Call::heartbeat { heartbeat: input_heartbeat, signature };
-
-		#[block]
-		{
-			Pallet::<T>::validate_unsigned(TransactionSource::InBlock, &call)
-				.map_err(<&str>::from)?;
-		}
+		_(RawOrigin::Authorized, input_heartbeat, signature);
 
 		Ok(())
 	}
 
 	#[benchmark]
-	fn validate_unsigned_and_then_heartbeat(k: Linear<1, MAX_KEYS>) -> Result<(), BenchmarkError> {
+	fn authorize_heartbeat(k: Linear<1, MAX_KEYS>) -> Result<(), BenchmarkError> {
 		let (input_heartbeat, signature) = create_heartbeat::<T>(k)?;
-		let call = Call::heartbeat { heartbeat: input_heartbeat, signature };
-		let call_enc = call.encode();
+		let call = Call::<T>::heartbeat { heartbeat: input_heartbeat, signature };
 
 		#[block]
 		{
-			Pallet::<T>::validate_unsigned(TransactionSource::InBlock, &call)
+			call.authorize(TransactionSource::External)
+				.ok_or("the call authorizes itself")?
 				.map_err(<&str>::from)?;
-			<Call<T> as Decode>::decode(&mut &*call_enc)
-				.expect("call is encoded above, encoding must be correct")
-				.dispatch_bypass_filter(RawOrigin::None.into())?;
 		}
 
 		Ok(())
@@ -27,7 +27,9 @@
 //!
 //! The heartbeat is a signed transaction, which was signed using the session key
 //! and includes the recent best block number of the local validators chain.
-//! It is submitted as an Unsigned Transaction via off-chain workers.
+//! It is submitted via off-chain workers as a general transaction, which the call authorizes
+//! itself by checking the signature, see [`frame_support::pallet_macros::authorize`]. The
+//! runtime must thus include the [`frame_system::AuthorizeCall`] transaction extension.
 //!
 //! - [`Config`]
 //! - [`Call`]
@@ -95,7 +97,7 @@ use frame_support::{
 	BoundedSlice, WeakBoundedVec,
 };
 use frame_system::{
-	offchain::{CreateInherent, SubmitTransaction},
+	offchain::{CreateAuthorizedTransaction, SubmitTransaction},
 	pallet_prelude::*,
 };
 pub use pallet::*;
@@ -261,7 +263,7 @@ pub mod pallet {
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
-	pub trait Config: CreateInherent<Call<Self>> + frame_system::Config {
+	pub trait Config: CreateAuthorizedTransaction<Call<Self>> + frame_system::Config {
 		/// The identifier type for an authority.
 		type AuthorityId: Member
 			+ Parameter
@@ -298,10 +300,10 @@ pub mod pallet {
 			UnresponsivenessOffence<IdentificationTuple<Self>>,
 		>;
 
-		/// A configuration for base priority of unsigned transactions.
+		/// A configuration for base priority of heartbeat transactions.
 		///
 		/// This is exposed so that it can be tuned for particular runtime, when
-		/// multiple pallets send unsigned transactions.
+		/// multiple pallets send unsigned or authorized transactions.
 		#[pallet::constant]
 		type UnsignedPriority: Get<TransactionPriority>;
 
@@ -383,20 +385,20 @@ pub mod pallet {
 		/// ## Complexity:
 		/// - `O(K)` where K is length of `Keys` (heartbeat.validators_len)
 		///   - `O(K)`: decoding of length `K`
-		// NOTE: the weight includes the cost of validate_unsigned as it is part of the cost to
-		// import block with such an extrinsic.
 		#[pallet::call_index(0)]
-		#[pallet::weight(<T as Config>::WeightInfo::validate_unsigned_and_then_heartbeat(
+		#[pallet::weight(<T as Config>::WeightInfo::heartbeat(heartbeat.validators_len))]
+		#[pallet::authorize(Pallet::<T>::authorize_heartbeat)]
+		#[pallet::weight_of_authorize(<T as Config>::WeightInfo::authorize_heartbeat(
 			heartbeat.validators_len,
 		))]
 		pub fn heartbeat(
 			origin: OriginFor<T>,
 			heartbeat: Heartbeat<BlockNumberFor<T>>,
-			// since signature verification is done in `validate_unsigned`
+			// since signature verification is done in `authorize_heartbeat`
 			// we can skip doing it here again.
 			_signature: <T::AuthorityId as RuntimeAppPublic>::Signature,
 		) -> DispatchResult {
-			ensure_none(origin)?;
+			ensure_authorized(origin)?;
 
 			let current_session = T::ValidatorSet::session_index();
 			let exists =
@@ -445,59 +447,6 @@ pub mod pallet {
 	/// Invalid transaction custom error. Returned when validators_len field in heartbeat is
 	/// incorrect.
 	pub(crate) const INVALID_VALIDATORS_LEN: u8 = 10;
-
-	#[pallet::validate_unsigned]
-	impl<T: Config> ValidateUnsigned for Pallet<T> {
-		type Call = Call<T>;
-
-		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-			if let Call::heartbeat { heartbeat, signature } = call {
-				if <Pallet<T>>::is_online(heartbeat.authority_index) {
-					// we already received a heartbeat for this authority
-					return InvalidTransaction::Stale.into()
-				}
-
-				// check if session index from heartbeat is recent
-				let current_session = T::ValidatorSet::session_index();
-				if heartbeat.session_index != current_session {
-					return InvalidTransaction::Stale.into()
-				}
-
-				// verify that the incoming (unverified) pubkey is actually an authority id
-				let keys = Keys::<T>::get();
-				if keys.len() as u32 != heartbeat.validators_len {
-					return InvalidTransaction::Custom(INVALID_VALIDATORS_LEN).into()
-				}
-				let authority_id = match keys.get(heartbeat.authority_index as usize) {
-					Some(id) => id,
-					None => return InvalidTransaction::BadProof.into(),
-				};
-
-				// check signature (this is expensive so we do it last).
-				let signature_valid = heartbeat.using_encoded(|encoded_heartbeat| {
-					authority_id.verify(&encoded_heartbeat, signature)
-				});
-
-				if !signature_valid {
-					return InvalidTransaction::BadProof.into()
-				}
-
-				ValidTransaction::with_tag_prefix("ImOnline")
-					.priority(T::UnsignedPriority::get())
-					.and_provides((current_session, authority_id))
-					.longevity(
-						TryInto::<u64>::try_into(
-							T::NextSessionRotation::average_session_length() / 2u32.into(),
-						)
-						.unwrap_or(64_u64),
-					)
-					.propagate(true)
-					.build()
-			} else {
-				InvalidTransaction::Call.into()
-			}
-		}
-	}
 }
 
 /// Keep track of number of authored blocks per authority, uncles are counted as
@@ -511,6 +460,57 @@ impl<T: Config + pallet_authorship::Config>
 }
 
 impl<T: Config> Pallet<T> {
+	/// Authorize a heartbeat transaction, see [`Call::heartbeat`].
+	///
+	/// Checks that the heartbeat is recent and signed by the authority it claims to be from.
+	fn authorize_heartbeat(
+		_source: TransactionSource,
+		heartbeat: &Heartbeat<BlockNumberFor<T>>,
+		signature: &<T::AuthorityId as RuntimeAppPublic>::Signature,
+	) -> TransactionValidityWithRefund {
+		if Self::is_online(heartbeat.authority_index) {
+			// we already received a heartbeat for this authority
+			return Err(InvalidTransaction::Stale.into())
+		}
+
+		// check if session index from heartbeat is recent
+		let current_session = T::ValidatorSet::session_index();
+		if heartbeat.session_index != current_session {
+			return Err(InvalidTransaction::Stale.into())
+		}
+
+		// verify that the incoming (unverified) pubkey is actually an authority id
+		let keys = Keys::<T>::get();
+		if keys.len() as u32 != heartbeat.validators_len {
+			return Err(InvalidTransaction::Custom(INVALID_VALIDATORS_LEN).into())
+		}
+		let authority_id = match keys.get(heartbeat.authority_index as usize) {
+			Some(id) => id,
+			None => return Err(InvalidTransaction::BadProof.into()),
+		};
+
+		// check signature (this is expensive so we do it last).
+		let signature_valid = heartbeat
+			.using_encoded(|encoded_heartbeat| authority_id.verify(&encoded_heartbeat, signature));
+
+		if !signature_valid {
+			return Err(InvalidTransaction::BadProof.into())
+		}
+
+		let valid = ValidTransaction::with_tag_prefix("ImOnline")
+			.priority(T::UnsignedPriority::get())
+			.and_provides((current_session, authority_id))
+			.longevity(
+				TryInto::<u64>::try_into(
+					T::NextSessionRotation::average_session_length() / 2u32.into(),
+				)
+				.unwrap_or(64_u64),
+			)
+			.propagate(true)
+			.build()?;
+		Ok((valid, Weight::zero()))
+	}
+
 	/// Returns `true` if a heartbeat has been received for the authority at
 	/// `authority_index` in the authorities series or if the authority has
 	/// authored at least one block, during the current session. Otherwise
@@ -642,7 +642,7 @@ impl<T: Config> Pallet<T> {
 				call,
 			);
 
-			let xt = T::create_inherent(call.into());
+			let xt = T::create_authorized_transaction(call.into());
 			SubmitTransaction::<T, Call<T>>::submit_transaction(xt)
 				.map_err(|_| OffchainErr::SubmitTransaction)?;
 
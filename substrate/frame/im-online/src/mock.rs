@@ -196,15 +196,24 @@ where
 	type Extrinsic = Extrinsic;
 }
 
-impl<LocalCall> frame_system::offchain::CreateInherent<LocalCall> for Runtime
+impl<LocalCall> frame_system::offchain::CreateTransaction<LocalCall> for Runtime
 where
 	RuntimeCall: From<LocalCall>,
 {
-	fn create_inherent(call: Self::RuntimeCall) -> Self::Extrinsic {
-		Extrinsic::new_bare(call)
+	type Extension = ();
+
+	fn create_transaction(call: Self::RuntimeCall, _extension: Self::Extension) -> Self::Extrinsic {
+		Extrinsic::new_transaction(call, ())
 	}
 }
 
+impl<LocalCall> frame_system::offchain::CreateAuthorizedTransaction<LocalCall> for Runtime
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_extension() -> Self::Extension {}
+}
+
 pub fn advance_session() {
 	let now = System::block_number().max(1);
 	System::set_block_number(now + 1);
@@ -21,12 +21,15 @@
 
 use super::*;
 use crate::mock::*;
-use frame_support::{assert_noop, dispatch};
+use frame_support::{
+	assert_noop,
+	dispatch::{self, Authorize},
+};
 use sp_core::offchain::{
 	testing::{TestOffchainExt, TestTransactionPoolExt},
 	OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
 };
-use sp_runtime::testing::UintAuthorityId;
+use sp_runtime::{generic::Preamble, testing::UintAuthorityId, DispatchError};
 
 #[test]
 fn test_unresponsiveness_slash_fraction() {
@@ -120,16 +123,35 @@ fn heartbeat(
 	};
 	let signature = id.sign(&heartbeat.encode()).unwrap();
 
-	ImOnline::pre_dispatch(&crate::Call::heartbeat {
+	crate::Call::<Runtime>::heartbeat {
 		heartbeat: heartbeat.clone(),
 		signature: signature.clone(),
-	})
+	}
+	.authorize(TransactionSource::External)
+	.expect("heartbeats authorize themselves")
 	.map_err(|e| match e {
 		TransactionValidityError::Invalid(InvalidTransaction::Custom(INVALID_VALIDATORS_LEN)) =>
 			"invalid validators len",
 		e @ _ => <&'static str>::from(e),
 	})?;
-	ImOnline::heartbeat(RuntimeOrigin::none(), heartbeat, signature)
+	ImOnline::heartbeat(frame_system::RawOrigin::Authorized.into(), heartbeat, signature)
+}
+
+#[test]
+fn heartbeats_require_the_authorized_origin() {
+	new_test_ext().execute_with(|| {
+		advance_session();
+		Validators::mutate(|l| *l = Some(vec![1, 2, 3]));
+		advance_session();
+
+		let heartbeat =
+			Heartbeat { block_number: 1, session_index: 2, authority_index: 0, validators_len: 3 };
+		let signature = UintAuthorityId(1).sign(&heartbeat.encode()).unwrap();
+		assert_noop!(
+			ImOnline::heartbeat(RuntimeOrigin::none(), heartbeat, signature),
+			DispatchError::BadOrigin
+		);
+	});
 }
 
 #[test]
@@ -225,6 +247,7 @@ fn should_generate_heartbeats() {
 
 		// check stuff about the transaction.
 		let ex: Extrinsic = Decode::decode(&mut &*transaction).unwrap();
+		assert!(matches!(ex.preamble, Preamble::General(..)));
 		let heartbeat = match ex.function {
 			crate::mock::RuntimeCall::ImOnline(crate::Call::heartbeat { heartbeat, .. }) =>
 				heartbeat,
@@ -177,6 +177,24 @@ where
 	}
 }
 
+impl<T> frame_system::offchain::CreateTransaction<T> for Test
+where
+	RuntimeCall: From<T>,
+{
+	type Extension = ();
+
+	fn create_transaction(call: Self::RuntimeCall, _extension: Self::Extension) -> Self::Extrinsic {
+		UncheckedExtrinsic::new_transaction(call, ())
+	}
+}
+
+impl<T> frame_system::offchain::CreateAuthorizedTransaction<T> for Test
+where
+	RuntimeCall: From<T>,
+{
+	fn create_extension() -> Self::Extension {}
+}
+
 impl crate::Config for Test {}
 
 pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
@@ -189,7 +207,7 @@ frame_support::construct_runtime!(
 		Balances: pallet_balances,
 		Staking: pallet_staking,
 		Session: pallet_session,
-		ImOnline: pallet_im_online::{Pallet, Call, Storage, Event<T>, ValidateUnsigned, Config<T>},
+		ImOnline: pallet_im_online::{Pallet, Call, Storage, Event<T>, Config<T>},
 		Offences: pallet_offences::{Pallet, Storage, Event},
 		Historical: pallet_session_historical::{Pallet},
 	}
@@ -0,0 +1,328 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Verification of the [`AccountProof`]s returned by the `eth_getProof` method of the
+//! pallet-revive Ethereum JSON-RPC server.
+//!
+//! The state of a contract is spread over the state trie of the chain:
+//! - The `ContractInfoOf` entry of the pallet, holding the code hash and the child trie id.
+//! - The `AddressSuffix` entry of the pallet, used to map the address to its account id.
+//! - The `Account` entry of the system pallet, holding the nonce and the balance.
+//! - The root of the child trie of the contract, holding its storage.
+//!
+//! The key derivation assumes that the pallets are named `Revive` and `System` in the runtime,
+//! and that the runtime uses 32 bytes account ids.
+
+use codec::Decode;
+use pallet_revive::evm::{AccountProof, Bytes, H160, H256};
+use sp_core::storage::ChildInfo;
+use sp_crypto_hashing::{blake2_128, blake2_256, twox_128};
+use sp_state_machine::{read_child_proof_check, read_proof_check, StorageProof};
+use std::collections::HashMap;
+
+/// The name of pallet-revive in the runtime.
+pub const REVIVE_PALLET_NAME: &[u8] = b"Revive";
+
+/// The name of frame-system in the runtime.
+pub const SYSTEM_PALLET_NAME: &[u8] = b"System";
+
+/// The hasher of the state trie.
+type Hasher = sp_core::Blake2Hasher;
+
+/// Errors that can occur while verifying a proof.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// The account proof is invalid for the given state root.
+	#[error("Invalid account proof: {0}")]
+	InvalidAccountProof(String),
+	/// The proof of a storage slot is invalid for the given state root.
+	#[error("Invalid storage proof for key {0:?}: {1}")]
+	InvalidStorageProof(H256, String),
+	/// A proven value could not be decoded.
+	#[error("Failed to decode a proven value: {0}")]
+	Decode(#[from] codec::Error),
+	/// A proven value doesn't match the value reported by the proof.
+	#[error("Proven {0} doesn't match the reported value")]
+	Mismatch(&'static str),
+}
+
+/// The leading fields of the `ContractInfo` stored by pallet-revive.
+#[derive(Debug, Clone, Decode, PartialEq, Eq)]
+pub struct ContractInfoPrefix {
+	/// The id of the child trie holding the storage of the contract.
+	pub trie_id: Vec<u8>,
+	/// The hash of the code of the contract.
+	pub code_hash: H256,
+}
+
+impl ContractInfoPrefix {
+	/// The child trie holding the storage of the contract.
+	pub fn child_info(&self) -> ChildInfo {
+		ChildInfo::new_default(&self.trie_id)
+	}
+}
+
+/// The account state proven by an [`AccountProof`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedAccount {
+	/// The id of the account the address maps to.
+	pub account_id: [u8; 32],
+	/// The encoded `frame_system::AccountInfo` of the account, if any.
+	///
+	/// It is left encoded, as its type depends on the runtime.
+	pub account_info: Option<Vec<u8>>,
+	/// The contract info of the account, if it is a contract.
+	pub contract_info: Option<ContractInfoPrefix>,
+}
+
+/// The storage key of a storage value of a pallet.
+fn storage_value_key(pallet: &[u8], item: &[u8]) -> Vec<u8> {
+	[twox_128(pallet), twox_128(item)].concat()
+}
+
+/// The storage key of the `AddressSuffix` entry of `address`.
+pub fn address_suffix_key(address: &H160) -> Vec<u8> {
+	[storage_value_key(REVIVE_PALLET_NAME, b"AddressSuffix"), address.as_bytes().to_vec()].concat()
+}
+
+/// The storage key of the `ContractInfoOf` entry of `address`.
+pub fn contract_info_key(address: &H160) -> Vec<u8> {
+	[storage_value_key(REVIVE_PALLET_NAME, b"ContractInfoOf"), address.as_bytes().to_vec()].concat()
+}
+
+/// The storage key of the `frame_system::Account` entry of `account_id`.
+pub fn system_account_key(account_id: &[u8; 32]) -> Vec<u8> {
+	[
+		storage_value_key(SYSTEM_PALLET_NAME, b"Account"),
+		blake2_128(account_id).to_vec(),
+		account_id.to_vec(),
+	]
+	.concat()
+}
+
+/// The key of a storage slot in the child trie of a contract.
+pub fn contract_storage_key(key: &H256) -> Vec<u8> {
+	blake2_256(key.as_bytes()).to_vec()
+}
+
+/// The account id `address` maps to, given its `AddressSuffix` entry.
+pub fn account_id(address: &H160, suffix: Option<[u8; 12]>) -> [u8; 32] {
+	let mut account_id = [0xEE; 32];
+	account_id[..20].copy_from_slice(address.as_bytes());
+	if let Some(suffix) = suffix {
+		account_id[20..].copy_from_slice(&suffix);
+	}
+	account_id
+}
+
+/// Check `proof` against `state_root` and return the proven values of `keys`.
+fn check_proof(
+	state_root: H256,
+	proof: &[Bytes],
+	keys: &[Vec<u8>],
+	child_info: Option<&ChildInfo>,
+) -> Result<HashMap<Vec<u8>, Option<Vec<u8>>>, String> {
+	let proof = StorageProof::new(proof.iter().map(|node| node.0.clone()));
+	let result = match child_info {
+		None => read_proof_check::<Hasher, _>(state_root, proof, keys),
+		Some(child_info) =>
+			read_child_proof_check::<Hasher, _>(state_root, proof, child_info, keys),
+	};
+	result.map_err(|err| err.to_string())
+}
+
+/// Verify `proof` against the state root of a block.
+///
+/// Checks that the reported code hash, storage hash and storage values are the ones stored in
+/// the state. The balance and nonce are derived from the returned
+/// [`VerifiedAccount::account_info`], whose layout depends on the runtime.
+pub fn verify_proof(state_root: H256, proof: &AccountProof) -> Result<VerifiedAccount, Error> {
+	let check_account_proof = |keys: &[Vec<u8>]| {
+		check_proof(state_root, &proof.account_proof, keys, None)
+			.map_err(Error::InvalidAccountProof)
+	};
+
+	let suffix_key = address_suffix_key(&proof.address);
+	let contract_key = contract_info_key(&proof.address);
+	let values = check_account_proof(&[suffix_key.clone(), contract_key.clone()])?;
+
+	let suffix = values
+		.get(&suffix_key)
+		.cloned()
+		.flatten()
+		.map(|suffix| <[u8; 12]>::decode(&mut &suffix[..]))
+		.transpose()?;
+	let contract_info = values
+		.get(&contract_key)
+		.cloned()
+		.flatten()
+		.map(|info| ContractInfoPrefix::decode(&mut &info[..]))
+		.transpose()?;
+
+	let account_id = account_id(&proof.address, suffix);
+	let account_key = system_account_key(&account_id);
+	let account_info = check_account_proof(&[account_key.clone()])?.remove(&account_key).flatten();
+
+	let Some(contract_info) = contract_info else {
+		if !proof.code_hash.is_zero() {
+			return Err(Error::Mismatch("code hash"));
+		}
+		if !proof.storage_hash.is_zero() {
+			return Err(Error::Mismatch("storage hash"));
+		}
+		if proof.storage_proof.iter().any(|slot| !slot.value.0.is_empty()) {
+			return Err(Error::Mismatch("storage value"));
+		}
+		return Ok(VerifiedAccount { account_id, account_info, contract_info: None });
+	};
+
+	if contract_info.code_hash != proof.code_hash {
+		return Err(Error::Mismatch("code hash"));
+	}
+
+	let child_info = contract_info.child_info();
+	let root_key = child_info.prefixed_storage_key().into_inner();
+	let storage_hash = check_account_proof(&[root_key.clone()])?
+		.remove(&root_key)
+		.flatten()
+		.map(|root| H256::decode(&mut &root[..]))
+		.transpose()?
+		.unwrap_or_default();
+	if storage_hash != proof.storage_hash {
+		return Err(Error::Mismatch("storage hash"));
+	}
+
+	for slot in &proof.storage_proof {
+		let key = contract_storage_key(&slot.key);
+		let value = check_proof(state_root, &slot.proof, &[key.clone()], Some(&child_info))
+			.map_err(|err| Error::InvalidStorageProof(slot.key, err))?
+			.remove(&key)
+			.flatten()
+			.unwrap_or_default();
+		if value != slot.value.0 {
+			return Err(Error::Mismatch("storage value"));
+		}
+	}
+
+	Ok(VerifiedAccount { account_id, account_info, contract_info: Some(contract_info) })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use pallet_revive::evm::StorageProof as SlotProof;
+	use sp_core::storage::StateVersion;
+	use sp_state_machine::{prove_child_read, prove_read, Backend, InMemoryBackend};
+	use std::collections::BTreeMap;
+
+	const ADDRESS: H160 = H160([1u8; 20]);
+	const CODE_HASH: H256 = H256([2u8; 32]);
+
+	fn contract_info() -> ContractInfoPrefix {
+		ContractInfoPrefix { trie_id: vec![3u8; 32], code_hash: CODE_HASH }
+	}
+
+	fn backend() -> InMemoryBackend<Hasher> {
+		let info = contract_info();
+		let top: BTreeMap<_, _> = [
+			// The trailing fields of the contract info are ignored by the verifier.
+			(contract_info_key(&ADDRESS), (info.trie_id.clone(), CODE_HASH, 42u32).encode()),
+			(system_account_key(&account_id(&ADDRESS, None)), vec![7u8; 16]),
+		]
+		.into_iter()
+		.collect();
+		let child: BTreeMap<_, _> =
+			[(contract_storage_key(&H256::zero()), vec![4u8; 4])].into_iter().collect();
+		let storage: HashMap<_, _> =
+			[(None, top), (Some(info.child_info()), child)].into_iter().collect();
+		(storage, StateVersion::V1).into()
+	}
+
+	fn account_proof() -> (H256, AccountProof) {
+		let info = contract_info();
+		let backend = backend();
+		let state_root = *backend.root();
+		let storage_hash = backend
+			.storage(&info.child_info().prefixed_storage_key().into_inner())
+			.unwrap()
+			.map(|root| H256::from_slice(&root))
+			.unwrap();
+
+		let keys = [
+			address_suffix_key(&ADDRESS),
+			contract_info_key(&ADDRESS),
+			system_account_key(&account_id(&ADDRESS, None)),
+			info.child_info().prefixed_storage_key().into_inner(),
+		];
+		let into_nodes =
+			|proof: StorageProof| proof.into_iter_nodes().map(Bytes).collect::<Vec<_>>();
+		let account_proof = into_nodes(prove_read(backend, &keys).unwrap());
+
+		let storage_proof = [H256::zero(), H256::repeat_byte(1)]
+			.into_iter()
+			.map(|key| {
+				let backend = self::backend();
+				let value = backend
+					.child_storage(&info.child_info(), &contract_storage_key(&key))
+					.unwrap()
+					.unwrap_or_default();
+				let proof =
+					prove_child_read(backend, &info.child_info(), [contract_storage_key(&key)])
+						.unwrap();
+				SlotProof { key, value: Bytes(value), proof: into_nodes(proof) }
+			})
+			.collect();
+
+		let proof = AccountProof {
+			address: ADDRESS,
+			account_proof,
+			code_hash: CODE_HASH,
+			storage_hash,
+			storage_proof,
+			..Default::default()
+		};
+		(state_root, proof)
+	}
+
+	#[test]
+	fn verify_proof_works() {
+		let (state_root, proof) = account_proof();
+		let account = verify_proof(state_root, &proof).unwrap();
+		assert_eq!(account.account_id, account_id(&ADDRESS, None));
+		assert_eq!(account.account_info, Some(vec![7u8; 16]));
+		assert_eq!(account.contract_info, Some(contract_info()));
+	}
+
+	#[test]
+	fn verify_proof_rejects_wrong_values() {
+		let (state_root, proof) = account_proof();
+
+		let mut wrong = proof.clone();
+		wrong.code_hash = H256::zero();
+		assert!(matches!(verify_proof(state_root, &wrong), Err(Error::Mismatch("code hash"))));
+
+		let mut wrong = proof.clone();
+		wrong.storage_proof[0].value = Bytes(vec![5u8; 4]);
+		assert!(matches!(verify_proof(state_root, &wrong), Err(Error::Mismatch("storage value"))));
+
+		let mut wrong = proof.clone();
+		wrong.storage_proof[1].proof.clear();
+		assert!(matches!(verify_proof(state_root, &wrong), Err(Error::InvalidStorageProof(..))));
+
+		assert!(matches!(verify_proof(H256::zero(), &proof), Err(Error::InvalidAccountProof(_))));
+	}
+}
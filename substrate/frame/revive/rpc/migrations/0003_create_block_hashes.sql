@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS block_hashes (
+	block_number INTEGER NOT NULL PRIMARY KEY,
+	block_hash BLOB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_block_hashes_block_hash ON block_hashes (
+	block_hash
+);
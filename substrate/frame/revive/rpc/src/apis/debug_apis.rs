@@ -42,7 +42,7 @@ pub trait DebugRpc {
 		&self,
 		transaction_hash: H256,
 		tracer_config: TracerConfig,
-	) -> RpcResult<CallTrace>;
+	) -> RpcResult<Trace>;
 
 	/// Dry run a call and returns the transaction's traces.
 	///
@@ -55,7 +55,7 @@ pub trait DebugRpc {
 		transaction: GenericTransaction,
 		block: BlockNumberOrTag,
 		tracer_config: TracerConfig,
-	) -> RpcResult<CallTrace>;
+	) -> RpcResult<Trace>;
 }
 
 pub struct DebugRpcServerImpl {
@@ -84,7 +84,7 @@ impl DebugRpcServer for DebugRpcServerImpl {
 		&self,
 		transaction_hash: H256,
 		tracer_config: TracerConfig,
-	) -> RpcResult<CallTrace> {
+	) -> RpcResult<Trace> {
 		let trace = self.client.trace_transaction(transaction_hash, tracer_config).await?;
 		Ok(trace)
 	}
@@ -94,7 +94,7 @@ impl DebugRpcServer for DebugRpcServerImpl {
 		transaction: GenericTransaction,
 		block: BlockNumberOrTag,
 		tracer_config: TracerConfig,
-	) -> RpcResult<CallTrace> {
+	) -> RpcResult<Trace> {
 		log::debug!(target: crate::LOG_TARGET, "trace_call: {transaction:?} block: {block:?} config: {tracer_config:?}");
 		let trace = self.client.trace_call(transaction, block, tracer_config).await?;
 		Ok(trace)
@@ -37,12 +37,22 @@ pub trait EthRpc {
 		&self,
 		transaction: GenericTransaction,
 		block: Option<BlockNumberOrTagOrHash>,
+		state_overrides: Option<StateOverride>,
+		block_overrides: Option<BlockOverrides>,
 	) -> RpcResult<Bytes>;
 
 	/// Returns the chain ID of the current network.
 	#[method(name = "eth_chainId")]
 	async fn chain_id(&self) -> RpcResult<U256>;
 
+	/// Generates an access list for a transaction.
+	#[method(name = "eth_createAccessList")]
+	async fn create_access_list(
+		&self,
+		transaction: GenericTransaction,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<AccessListResult>;
+
 	/// Generates and returns an estimate of how much gas is necessary to allow the transaction to
 	/// complete.
 	#[method(name = "eth_estimateGas")]
@@ -50,8 +60,19 @@ pub trait EthRpc {
 		&self,
 		transaction: GenericTransaction,
 		block: Option<BlockNumberOrTag>,
+		state_overrides: Option<StateOverride>,
+		block_overrides: Option<BlockOverrides>,
 	) -> RpcResult<U256>;
 
+	/// Transaction fee history
+	#[method(name = "eth_feeHistory")]
+	async fn fee_history(
+		&self,
+		block_count: U256,
+		newest_block: BlockNumberOrTag,
+		reward_percentiles: Option<Vec<f64>>,
+	) -> RpcResult<FeeHistoryResult>;
+
 	/// Returns the current price per gas in wei.
 	#[method(name = "eth_gasPrice")]
 	async fn gas_price(&self) -> RpcResult<U256>;
@@ -95,10 +116,29 @@ pub trait EthRpc {
 	#[method(name = "eth_getCode")]
 	async fn get_code(&self, address: Address, block: BlockNumberOrTagOrHash) -> RpcResult<Bytes>;
 
+	/// Polling method for a filter, which returns an array of logs, or block hashes, which
+	/// occurred since last poll.
+	#[method(name = "eth_getFilterChanges")]
+	async fn get_filter_changes(&self, filter_id: U256) -> RpcResult<FilterResults>;
+
+	/// Returns an array of all logs matching filter with given id.
+	#[method(name = "eth_getFilterLogs")]
+	async fn get_filter_logs(&self, filter_id: U256) -> RpcResult<FilterResults>;
+
 	/// Returns an array of all logs matching filter with given id.
 	#[method(name = "eth_getLogs")]
 	async fn get_logs(&self, filter: Option<Filter>) -> RpcResult<FilterResults>;
 
+	/// Returns the account and storage values of the specified account, including the Merkle
+	/// proofs.
+	#[method(name = "eth_getProof")]
+	async fn get_proof(
+		&self,
+		address: Address,
+		storage_keys: Vec<H256>,
+		block: BlockNumberOrTagOrHash,
+	) -> RpcResult<AccountProof>;
+
 	/// Returns the value from a storage position at a given address.
 	#[method(name = "eth_getStorageAt")]
 	async fn get_storage_at(
@@ -150,6 +190,14 @@ pub trait EthRpc {
 	#[method(name = "eth_maxPriorityFeePerGas")]
 	async fn max_priority_fee_per_gas(&self) -> RpcResult<U256>;
 
+	/// Creates a filter in the node, to notify when a new block arrives.
+	#[method(name = "eth_newBlockFilter")]
+	async fn new_block_filter(&self) -> RpcResult<U256>;
+
+	/// Creates a filter object, based on filter options, to notify when the state changes (logs).
+	#[method(name = "eth_newFilter")]
+	async fn new_filter(&self, filter: Filter) -> RpcResult<U256>;
+
 	/// Submits a raw transaction. For EIP-4844 transactions, the raw form must be the network form.
 	/// This means it includes the blobs, KZG commitments, and KZG proofs.
 	#[method(name = "eth_sendRawTransaction")]
@@ -163,6 +211,10 @@ pub trait EthRpc {
 	#[method(name = "eth_syncing")]
 	async fn syncing(&self) -> RpcResult<SyncingStatus>;
 
+	/// Uninstalls a filter with given id.
+	#[method(name = "eth_uninstallFilter")]
+	async fn uninstall_filter(&self, filter_id: U256) -> RpcResult<bool>;
+
 	/// The string value of current network id
 	#[method(name = "net_version")]
 	async fn net_version(&self) -> RpcResult<String>;
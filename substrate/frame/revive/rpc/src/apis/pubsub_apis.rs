@@ -0,0 +1,164 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::*;
+use jsonrpsee::{
+	core::SubscriptionResult, proc_macros::rpc, types::error::INVALID_PARAMS_CODE,
+	PendingSubscriptionSink, SubscriptionMessage,
+};
+use std::future::Future;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// The maximum number of topics a logs subscription filter can have.
+const MAX_TOPICS: usize = 4;
+
+/// Ethereum pub-sub JSON-RPC apis.
+#[rpc(server, client)]
+pub trait EthPubSubRpc {
+	/// Creates a subscription for the given kind of events.
+	///
+	/// The `filter` is only used by `logs` subscriptions, its block range and block hash are
+	/// ignored.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/pubsub>
+	#[subscription(
+		name = "eth_subscribe" => "eth_subscription",
+		unsubscribe = "eth_unsubscribe",
+		item = SubscriptionItem
+	)]
+	async fn subscribe(&self, kind: SubscriptionKind, filter: Option<Filter>)
+		-> SubscriptionResult;
+}
+
+pub struct EthPubSubRpcServerImpl {
+	client: client::Client,
+}
+
+impl EthPubSubRpcServerImpl {
+	pub fn new(client: client::Client) -> Self {
+		Self { client }
+	}
+}
+
+/// Accept the subscription and send the items produced by `f` for each value received on
+/// `receiver`, until either the subscription or the channel is closed.
+async fn pipe_from_receiver<T, F, Fut>(
+	pending: PendingSubscriptionSink,
+	mut receiver: broadcast::Receiver<T>,
+	f: F,
+) -> SubscriptionResult
+where
+	T: Clone,
+	F: Fn(T) -> Fut,
+	Fut: Future<Output = Vec<SubscriptionItem>>,
+{
+	let sink = pending.accept().await?;
+	loop {
+		let value = tokio::select! {
+			_ = sink.closed() => return Ok(()),
+			value = receiver.recv() => value,
+		};
+
+		let value = match value {
+			Ok(value) => value,
+			Err(RecvError::Lagged(skipped)) => {
+				log::debug!(target: LOG_TARGET, "Subscription {:?} skipped {skipped} notifications", sink.subscription_id());
+				continue;
+			},
+			Err(RecvError::Closed) => return Ok(()),
+		};
+
+		for item in f(value).await {
+			sink.send(SubscriptionMessage::from_json(&item)?).await?;
+		}
+	}
+}
+
+#[async_trait]
+impl EthPubSubRpcServer for EthPubSubRpcServerImpl {
+	async fn subscribe(
+		&self,
+		pending: PendingSubscriptionSink,
+		kind: SubscriptionKind,
+		filter: Option<Filter>,
+	) -> SubscriptionResult {
+		log::debug!(target: LOG_TARGET, "subscribe: {kind:?} filter: {filter:?}");
+		let client = self.client.clone();
+		match kind {
+			SubscriptionKind::NewHeads => {
+				let receiver = client.subscribe_new_block_hashes();
+				pipe_from_receiver(pending, receiver, |hash| {
+					let client = client.clone();
+					async move {
+						match client.block_by_hash(&hash).await {
+							Ok(Some(block)) =>
+								vec![SubscriptionItem::Header(client.evm_block(block, false).await)],
+							Ok(None) => vec![],
+							Err(err) => {
+								log::debug!(target: LOG_TARGET, "Failed to fetch block {hash:?}: {err:?}");
+								vec![]
+							},
+						}
+					}
+				})
+				.await
+			},
+			SubscriptionKind::Logs => {
+				let filter = filter.unwrap_or_default();
+				if filter.topics.as_ref().is_some_and(|topics| topics.len() > MAX_TOPICS) {
+					pending
+						.reject(ErrorObjectOwned::owned::<()>(
+							INVALID_PARAMS_CODE,
+							"exceed max topics",
+							None,
+						))
+						.await;
+					return Ok(());
+				}
+
+				let receiver = client.subscribe_new_block_hashes();
+				pipe_from_receiver(pending, receiver, |hash| {
+					let client = client.clone();
+					let filter = Filter {
+						block_hash: Some(hash),
+						from_block: None,
+						to_block: None,
+						..filter.clone()
+					};
+					async move {
+						match client.logs(Some(filter)).await {
+							Ok(logs) => logs.into_iter().map(SubscriptionItem::Log).collect(),
+							Err(err) => {
+								log::debug!(target: LOG_TARGET, "Failed to filter logs of {hash:?}: {err:?}");
+								vec![]
+							},
+						}
+					}
+				})
+				.await
+			},
+			SubscriptionKind::NewPendingTransactions => {
+				let receiver = client.subscribe_pending_transactions();
+				pipe_from_receiver(pending, receiver, |hash| async move {
+					vec![SubscriptionItem::TransactionHash(hash)]
+				})
+				.await
+			},
+		}
+	}
+}
@@ -18,8 +18,9 @@
 use crate::{
 	client::{connect, native_to_eth_ratio, Client, SubscriptionType, SubstrateBlockNumber},
 	BlockInfoProvider, BlockInfoProviderImpl, CacheReceiptProvider, DBReceiptProvider,
-	DebugRpcServer, DebugRpcServerImpl, EthRpcServer, EthRpcServerImpl, ReceiptExtractor,
-	ReceiptProvider, SystemHealthRpcServer, SystemHealthRpcServerImpl, LOG_TARGET,
+	DebugRpcServer, DebugRpcServerImpl, EthPubSubRpcServer, EthPubSubRpcServerImpl, EthRpcServer,
+	EthRpcServerImpl, FilterManager, ReceiptExtractor, ReceiptProvider, SystemHealthRpcServer,
+	SystemHealthRpcServerImpl, DEFAULT_FILTER_TIMEOUT, DEFAULT_MAX_FILTERS, LOG_TARGET,
 };
 use clap::Parser;
 use futures::{pin_mut, FutureExt};
@@ -65,6 +66,11 @@ pub struct CliCommand {
 	#[clap(long)]
 	pub index_until_block: Option<SubstrateBlockNumber>,
 
+	/// The maximum number of filters installed with `eth_newFilter` and `eth_newBlockFilter`
+	/// at the same time, shared by all connections.
+	#[clap(long, default_value_t = DEFAULT_MAX_FILTERS)]
+	pub max_filters: usize,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub shared_params: SharedParams,
@@ -76,6 +82,36 @@ pub struct CliCommand {
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub prometheus_params: PrometheusParams,
+
+	#[allow(missing_docs)]
+	#[clap(subcommand)]
+	pub subcommand: Option<Subcommand>,
+}
+
+/// The subcommands of the Ethereum JSON-RPC server.
+#[derive(Debug, clap::Subcommand)]
+pub enum Subcommand {
+	/// Index the receipts of a historical block range into the database, and exit.
+	Index(IndexCommand),
+}
+
+/// Index the receipts of a historical block range from an archive node.
+///
+/// Blocks that are already indexed with their canonical hash are skipped, so the command can be
+/// used to fill the gaps left by restarts, or be resumed after an interruption.
+#[derive(Debug, clap::Args)]
+pub struct IndexCommand {
+	/// The first block to index. Defaults to `--earliest-receipt-block`, or the genesis block.
+	#[clap(long)]
+	pub from_block: Option<SubstrateBlockNumber>,
+
+	/// The last block to index. Defaults to the latest finalized block.
+	#[clap(long)]
+	pub to_block: Option<SubstrateBlockNumber>,
+
+	/// The number of blocks fetched concurrently.
+	#[clap(long, default_value = "16")]
+	pub concurrency: usize,
 }
 
 /// Initialize the logger
@@ -158,12 +194,25 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		database_url,
 		earliest_receipt_block,
 		index_until_block,
+		max_filters,
 		shared_params,
+		subcommand,
 		..
 	} = cmd;
 
 	#[cfg(not(test))]
 	init_logger(&shared_params)?;
+
+	if let Some(Subcommand::Index(index_cmd)) = subcommand {
+		return run_indexer(
+			index_cmd,
+			cache_size,
+			earliest_receipt_block,
+			&node_rpc_url,
+			&database_url,
+		);
+	}
+
 	let is_dev = shared_params.dev;
 	let rpc_addrs: Option<Vec<sc_service::config::RpcEndpoint>> = rpc_params
 		.rpc_addr(is_dev, false, 8545)?
@@ -216,7 +265,7 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		&rpc_config,
 		prometheus_registry,
 		tokio_handle,
-		|| rpc_module(is_dev, client.clone()),
+		|| rpc_module(is_dev, max_filters, client.clone()),
 		None,
 	)?;
 
@@ -224,11 +273,12 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		.spawn_essential_handle()
 		.spawn("block-subscription", None, async move {
 			let fut1 = client.subscribe_and_cache_new_blocks(SubscriptionType::BestBlocks);
+			let fut2 = client.subscribe_and_index_finalized_blocks();
 			if let Some(index_until_block) = index_until_block {
-				let fut2 = client.cache_old_blocks(index_until_block);
-				tokio::join!(fut1, fut2);
+				let fut3 = client.cache_old_blocks(index_until_block);
+				tokio::join!(fut1, fut2, fut3);
 			} else {
-				fut1.await;
+				tokio::join!(fut1, fut2);
 			}
 		});
 
@@ -238,18 +288,67 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 	Ok(())
 }
 
+/// Index the receipts of the block range of the `index` subcommand, and exit.
+fn run_indexer(
+	cmd: IndexCommand,
+	cache_size: usize,
+	earliest_receipt_block: Option<SubstrateBlockNumber>,
+	node_rpc_url: &str,
+	database_url: &str,
+) -> anyhow::Result<()> {
+	if database_url == IN_MEMORY_DB {
+		anyhow::bail!("Indexing requires a persistent database, use --database-url");
+	}
+
+	let tokio_runtime = sc_cli::build_runtime()?;
+	let tokio_handle = tokio_runtime.handle();
+	let client = build_client(
+		tokio_handle,
+		cache_size,
+		earliest_receipt_block,
+		node_rpc_url,
+		database_url,
+		tokio_runtime.block_on(async { Signals::capture() })?,
+	)?;
+
+	let fut = async {
+		let from_block = cmd.from_block.or(earliest_receipt_block).unwrap_or_default();
+		let to_block = match cmd.to_block {
+			Some(to_block) => to_block,
+			None => client.latest_finalized_block_number().await?,
+		};
+		client.backfill_blocks(from_block, to_block, cmd.concurrency).await
+	}
+	.fuse();
+	pin_mut!(fut);
+
+	let signals = tokio_runtime.block_on(async { Signals::capture() })?;
+	match tokio_runtime.block_on(signals.try_until_signal(fut)) {
+		Ok(Ok(())) => Ok(()),
+		Ok(Err(err)) => Err(err.into()),
+		Err(_) => anyhow::bail!("Process interrupted"),
+	}
+}
+
 /// Create the JSON-RPC module.
-fn rpc_module(is_dev: bool, client: Client) -> Result<RpcModule<()>, sc_service::Error> {
+fn rpc_module(
+	is_dev: bool,
+	max_filters: usize,
+	client: Client,
+) -> Result<RpcModule<()>, sc_service::Error> {
 	let eth_api = EthRpcServerImpl::new(client.clone())
 		.with_accounts(if is_dev { vec![crate::Account::default()] } else { vec![] })
+		.with_filters(FilterManager::new(DEFAULT_FILTER_TIMEOUT, max_filters))
 		.into_rpc();
 
 	let health_api = SystemHealthRpcServerImpl::new(client.clone()).into_rpc();
+	let pubsub_api = EthPubSubRpcServerImpl::new(client.clone()).into_rpc();
 	let debug_api = DebugRpcServerImpl::new(client).into_rpc();
 
 	let mut module = RpcModule::new(());
 	module.merge(eth_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module.merge(health_api).map_err(|e| sc_service::Error::Application(e.into()))?;
+	module.merge(pubsub_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module.merge(debug_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	Ok(module)
 }
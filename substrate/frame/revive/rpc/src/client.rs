@@ -23,15 +23,22 @@ use crate::{
 	BlockInfoProvider, ReceiptExtractor, ReceiptProvider, TransactionInfo, LOG_TARGET,
 };
 use codec::{Decode, Encode};
+use futures::{StreamExt, TryStreamExt};
 use jsonrpsee::types::{error::CALL_EXECUTION_FAILED_CODE, ErrorObjectOwned};
 use pallet_revive::{
 	evm::{
-		decode_revert_reason, Block, BlockNumberOrTag, BlockNumberOrTagOrHash, CallTrace, Filter,
-		GenericTransaction, Log, ReceiptInfo, SyncingProgress, SyncingStatus, TracerConfig,
-		TransactionSigned, TransactionTrace, H160, H256, U256,
+		decode_revert_reason, AccessList, AccountProof, Block, BlockNumberOrTag,
+		BlockNumberOrTagOrHash, BlockOverrides, Bytes, CallTrace, FeeHistoryResult, Filter,
+		GenericTransaction, Log, ReceiptInfo, StateOverride, StorageProof, SyncingProgress,
+		SyncingStatus, Trace, TracerConfig, TransactionSigned, TransactionTrace, H160, H256, U256,
 	},
 	EthTransactError, EthTransactInfo,
 };
+use pallet_revive_proof_verifier::{
+	account_id, address_suffix_key, contract_info_key, contract_storage_key, system_account_key,
+	ContractInfoPrefix,
+};
+use sp_core::storage::ChildInfo;
 use sp_runtime::OpaqueExtrinsic;
 use sp_weights::Weight;
 use std::{ops::ControlFlow, sync::Arc, time::Duration};
@@ -40,7 +47,7 @@ use subxt::{
 		legacy::{rpc_methods::SystemHealth, LegacyRpcMethods},
 		rpc::{
 			reconnecting_rpc_client::{ExponentialBackoff, RpcClient as ReconnectingRpcClient},
-			RpcClient,
+			rpc_params, RpcClient,
 		},
 	},
 	config::Header,
@@ -49,7 +56,7 @@ use subxt::{
 	Config, OnlineClient,
 };
 use thiserror::Error;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 
 use crate::subxt_client::{self, SrcChainConfig};
 
@@ -68,6 +75,12 @@ pub type Shared<T> = Arc<RwLock<T>>;
 /// The runtime balance type.
 pub type Balance = u128;
 
+/// The number of notifications buffered for the subscribers of the client notifiers.
+const NOTIFICATION_BUFFER_SIZE: usize = 256;
+
+/// The maximum number of retracted ancestors re-indexed when a reorg is detected.
+const MAX_REORG_DEPTH: usize = 256;
+
 /// The subscription type used to listen to new blocks.
 pub enum SubscriptionType {
 	/// Subscribe to the best blocks.
@@ -137,8 +150,18 @@ pub enum ClientError {
 	/// Failed to filter logs.
 	#[error("Failed to filter logs")]
 	LogFilterFailed(#[from] anyhow::Error),
+	/// The runtime does not implement the required version of the `ReviveApi`.
+	#[error("runtime API not supported")]
+	RuntimeApiNotSupported,
 }
 
+/// The first version of the `ReviveApi` returning a [`Trace`] for every tracer, previous
+/// versions only support the call tracer.
+const TRACE_API_VERSION: u32 = 2;
+
+/// The first version of the `ReviveApi` supporting state and block overrides and access lists.
+const OVERRIDES_API_VERSION: u32 = 3;
+
 const REVERT_CODE: i32 = 3;
 impl From<ClientError> for ErrorObjectOwned {
 	fn from(err: ClientError) -> Self {
@@ -181,6 +204,10 @@ pub struct Client {
 	receipt_extractor: ReceiptExtractor,
 	chain_id: u64,
 	max_block_weight: Weight,
+	/// Notify subscribers of the new best blocks, once their receipts are indexed.
+	block_notifier: broadcast::Sender<SubstrateBlockHash>,
+	/// Notify subscribers of the transactions submitted through this client.
+	transaction_notifier: broadcast::Sender<H256>,
 }
 
 /// Fetch the chain ID from the substrate chain.
@@ -213,6 +240,15 @@ async fn extract_block_timestamp(block: &SubstrateBlock) -> Option<u64> {
 	Some(ext.value.now / 1000)
 }
 
+/// Ensure the given tracer can be used with the given version of the `ReviveApi`.
+fn ensure_tracer_supported(api_version: u32, config: &TracerConfig) -> Result<(), ClientError> {
+	match config {
+		TracerConfig::CallTracer { .. } => Ok(()),
+		_ if api_version >= TRACE_API_VERSION => Ok(()),
+		_ => Err(ClientError::RuntimeApiNotSupported),
+	}
+}
+
 /// Connect to a node at the given URL, and return the underlying API, RPC client, and legacy RPC
 /// clients.
 pub async fn connect(
@@ -255,9 +291,29 @@ impl Client {
 			receipt_extractor,
 			chain_id,
 			max_block_weight,
+			block_notifier: broadcast::channel(NOTIFICATION_BUFFER_SIZE).0,
+			transaction_notifier: broadcast::channel(NOTIFICATION_BUFFER_SIZE).0,
 		})
 	}
 
+	/// Subscribe to the hashes of the new best blocks.
+	///
+	/// A block is notified once its receipts are available from the receipt provider.
+	pub fn subscribe_new_block_hashes(&self) -> broadcast::Receiver<SubstrateBlockHash> {
+		self.block_notifier.subscribe()
+	}
+
+	/// Subscribe to the hashes of the transactions submitted through this client.
+	pub fn subscribe_pending_transactions(&self) -> broadcast::Receiver<H256> {
+		self.transaction_notifier.subscribe()
+	}
+
+	/// Notify the subscribers that the transaction with the given hash was submitted.
+	pub fn notify_pending_transaction(&self, transaction_hash: H256) {
+		// An error only means that there are no subscribers.
+		let _ = self.transaction_notifier.send(transaction_hash);
+	}
+
 	/// Subscribe to past blocks executing the callback for each block.
 	/// The subscription continues iterating past blocks until the closure returns
 	/// `ControlFlow::Break`. Blocks are iterated starting from the latest block and moving
@@ -347,17 +403,76 @@ impl Client {
 		Ok(())
 	}
 
+	/// Index the receipts of the given block, and record it as the canonical block at its height.
+	async fn index_block(&self, block: &SubstrateBlock) -> Result<(), ClientError> {
+		let receipts = self.receipt_extractor.extract_from_block(block).await?;
+		let block_hash = block.hash();
+		self.receipt_provider.set_canonical(block.number(), &block_hash).await;
+		self.receipt_provider.insert(&block_hash, &receipts).await;
+		Ok(())
+	}
+
+	/// Similar to `index_block`, but intended for archiving the receipts of historical blocks.
+	async fn archive_block(&self, block: &SubstrateBlock) -> Result<(), ClientError> {
+		let receipts = self.receipt_extractor.extract_from_block(block).await?;
+		let block_hash = block.hash();
+		self.receipt_provider.set_canonical(block.number(), &block_hash).await;
+		self.receipt_provider.archive(&block_hash, &receipts).await;
+		Ok(())
+	}
+
+	/// Re-index the ancestors of the given block that were retracted by a reorg.
+	///
+	/// Walks back the parents of the block until one matches the canonical block recorded at its
+	/// height, re-indexing at most [`MAX_REORG_DEPTH`] blocks.
+	async fn reindex_retracted_ancestors(&self, block: &SubstrateBlock) -> Result<(), ClientError> {
+		let mut ancestors = Vec::new();
+		let mut parent_hash = block.header().parent_hash;
+		let mut block_number = block.number();
+
+		while block_number > 0 && ancestors.len() < MAX_REORG_DEPTH {
+			block_number -= 1;
+			match self.receipt_provider.block_hash(block_number).await {
+				Some(hash) if hash != parent_hash => {},
+				_ => break,
+			}
+
+			let Some(parent) = self.block_provider.block_by_hash(&parent_hash).await? else {
+				break;
+			};
+			parent_hash = parent.header().parent_hash;
+			ancestors.push(parent);
+		}
+
+		if !ancestors.is_empty() {
+			log::info!(target: LOG_TARGET, "🔀 Reorg detected, re-indexing {} retracted blocks", ancestors.len());
+		}
+
+		for block in ancestors.iter().rev() {
+			self.index_block(block).await?;
+		}
+
+		Ok(())
+	}
+
 	/// Start the block subscription, and populate the block cache.
+	///
+	/// Receipts of blocks retracted by a reorg are removed, and the new canonical blocks are
+	/// re-indexed.
 	pub async fn subscribe_and_cache_new_blocks(&self, subscription_type: SubscriptionType) {
 		let res = self
 			.subscribe_new_blocks(subscription_type, |block| async {
-				let receipts = self.receipt_extractor.extract_from_block(&block).await?;
+				let block_hash = block.hash();
+				self.receipt_provider.retract_after(block.number()).await;
+				self.reindex_retracted_ancestors(&block).await?;
+				self.index_block(&block).await?;
 
-				self.receipt_provider.insert(&block.hash(), &receipts).await;
 				if let Some(pruned) = self.block_provider.cache_block(block).await {
 					self.receipt_provider.remove(&pruned).await;
 				}
 
+				// An error only means that there are no subscribers.
+				let _ = self.block_notifier.send(block_hash);
 				Ok(())
 			})
 			.await;
@@ -367,12 +482,85 @@ impl Client {
 		}
 	}
 
+	/// Subscribe to the finalized blocks, and make sure the finalized chain is indexed.
+	///
+	/// Finalized blocks that were missed, or whose height still records a retracted block, are
+	/// indexed together with their retracted ancestors.
+	pub async fn subscribe_and_index_finalized_blocks(&self) {
+		let res = self
+			.subscribe_new_blocks(SubscriptionType::FinalizedBlocks, |block| async move {
+				let block_hash = block.hash();
+				if self.receipt_provider.block_hash(block.number()).await != Some(block_hash) {
+					self.reindex_retracted_ancestors(&block).await?;
+					self.index_block(&block).await?;
+				}
+				Ok(())
+			})
+			.await;
+
+		if let Err(err) = res {
+			log::error!(target: LOG_TARGET, "Finalized block subscription error: {err:?}");
+		}
+	}
+
+	/// Index the canonical blocks in the given range from the node, which needs to be an archive
+	/// node for historical blocks.
+	///
+	/// Blocks whose canonical hash is already recorded are skipped, blocks recorded with a
+	/// different hash are re-indexed. Up to `concurrency` blocks are fetched in parallel.
+	pub async fn backfill_blocks(
+		&self,
+		from_block: SubstrateBlockNumber,
+		to_block: SubstrateBlockNumber,
+		concurrency: usize,
+	) -> Result<(), ClientError> {
+		log::info!(target: LOG_TARGET, "🗄️ Indexing blocks #{from_block} to #{to_block}");
+		futures::stream::iter(from_block..=to_block)
+			.map(|block_number| async move {
+				let Some(block_hash) =
+					self.rpc.chain_get_block_hash(Some(block_number.into())).await?
+				else {
+					return Err(ClientError::BlockNotFound);
+				};
+
+				if self.receipt_provider.block_hash(block_number).await == Some(block_hash) {
+					return Ok(block_number);
+				}
+
+				let block = self
+					.block_provider
+					.block_by_hash(&block_hash)
+					.await?
+					.ok_or(ClientError::BlockNotFound)?;
+				self.archive_block(&block).await?;
+				Ok(block_number)
+			})
+			.buffer_unordered(concurrency.max(1))
+			.try_for_each(|block_number| async move {
+				if block_number % 1000 == 0 {
+					log::info!(target: LOG_TARGET, "🗄️ Indexed block #{block_number}");
+				}
+				Ok(())
+			})
+			.await?;
+
+		log::info!(target: LOG_TARGET, "🗄️ Indexed blocks #{from_block} to #{to_block}");
+		Ok(())
+	}
+
+	/// Get the number of the latest finalized block of the node.
+	pub async fn latest_finalized_block_number(&self) -> Result<SubstrateBlockNumber, ClientError> {
+		let hash = self.rpc.chain_get_finalized_head().await?;
+		let header =
+			self.rpc.chain_get_header(Some(hash)).await?.ok_or(ClientError::BlockNotFound)?;
+		Ok(header.number)
+	}
+
 	/// Cache old blocks up to the given block number.
 	pub async fn cache_old_blocks(&self, oldest_block: SubstrateBlockNumber) {
 		let res = self
 			.subscribe_past_blocks(|block| async move {
-				let receipts = self.receipt_extractor.extract_from_block(&block).await?;
-				self.receipt_provider.archive(&block.hash(), &receipts).await;
+				self.archive_block(&block).await?;
 				if block.number() <= oldest_block {
 					Ok(ControlFlow::Break(()))
 				} else {
@@ -542,6 +730,94 @@ impl Client {
 		Ok(result)
 	}
 
+	/// Get the [`AccountProof`] of the given address and storage keys.
+	///
+	/// See [`pallet_revive_proof_verifier::verify_proof`] for verifying the returned proof.
+	pub async fn get_proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<H256>,
+		block: BlockNumberOrTagOrHash,
+	) -> Result<AccountProof, ClientError> {
+		// Pin the block, so that all the values and proofs are read from the same state.
+		let block_hash = self.block_hash_for(&block).await?;
+		let block = block_hash.map_or(block, BlockNumberOrTagOrHash::H256);
+		let storage_api = self.storage_api(&block).await?;
+
+		let suffix_key = address_suffix_key(&address);
+		let suffix = storage_api
+			.fetch_raw(suffix_key.clone())
+			.await?
+			.map(|suffix| <[u8; 12]>::decode(&mut &suffix[..]))
+			.transpose()?;
+		let contract_key = contract_info_key(&address);
+		let contract_info = storage_api
+			.fetch_raw(contract_key.clone())
+			.await?
+			.map(|info| ContractInfoPrefix::decode(&mut &info[..]))
+			.transpose()?;
+
+		let mut keys =
+			vec![suffix_key, contract_key, system_account_key(&account_id(&address, suffix))];
+		let child_info = contract_info.as_ref().map(|info| info.child_info());
+		let mut storage_hash = H256::zero();
+		if let Some(child_info) = &child_info {
+			let root_key = child_info.prefixed_storage_key().into_inner();
+			if let Some(root) = storage_api.fetch_raw(root_key.clone()).await? {
+				storage_hash = H256::decode(&mut &root[..])?;
+			}
+			keys.push(root_key);
+		}
+		let account_proof = self.read_proof(keys, None, block_hash).await?;
+
+		let mut storage_proof = Vec::with_capacity(storage_keys.len());
+		for key in storage_keys {
+			let Some(child_info) = &child_info else {
+				storage_proof.push(StorageProof { key, ..Default::default() });
+				continue;
+			};
+
+			let value = self
+				.get_contract_storage(address, U256::from_big_endian(key.as_bytes()), block.clone())
+				.await?;
+			let proof = self
+				.read_proof(vec![contract_storage_key(&key)], Some(child_info), block_hash)
+				.await?;
+			storage_proof.push(StorageProof { key, value: value.into(), proof });
+		}
+
+		Ok(AccountProof {
+			address,
+			account_proof,
+			balance: self.balance(address, &block).await?,
+			code_hash: contract_info.map(|info| info.code_hash).unwrap_or_default(),
+			nonce: self.nonce(address, block).await?,
+			storage_hash,
+			storage_proof,
+		})
+	}
+
+	/// Get the trie nodes proving `keys` in the state trie, or in the given child trie.
+	async fn read_proof(
+		&self,
+		keys: Vec<Vec<u8>>,
+		child_info: Option<&ChildInfo>,
+		at: Option<SubstrateBlockHash>,
+	) -> Result<Vec<Bytes>, ClientError> {
+		let client = RpcClient::new(self.rpc_client.clone());
+		let keys = keys.into_iter().map(sp_core::Bytes).collect::<Vec<_>>();
+		let proof: sc_rpc_api::state::ReadProof<SubstrateBlockHash> = match child_info {
+			None => client.request("state_getReadProof", rpc_params![keys, at]).await?,
+			Some(child_info) => {
+				let child_key = sp_core::Bytes(child_info.prefixed_storage_key().into_inner());
+				client
+					.request("state_getChildReadProof", rpc_params![child_key, keys, at])
+					.await?
+			},
+		};
+		Ok(proof.proof.into_iter().map(|node| Bytes(node.0)).collect())
+	}
+
 	/// Get the contract code for the given contract address.
 	pub async fn get_contract_code(
 		&self,
@@ -564,11 +840,26 @@ impl Client {
 	}
 
 	/// Dry run a transaction and returns the [`EthTransactInfo`] for the transaction.
+	///
+	/// The state and block overrides, if any, are applied before executing the transaction.
 	pub async fn dry_run(
 		&self,
 		tx: GenericTransaction,
 		block: BlockNumberOrTagOrHash,
+		state_overrides: Option<StateOverride>,
+		block_overrides: Option<BlockOverrides>,
 	) -> Result<EthTransactInfo<Balance>, ClientError> {
+		if state_overrides.is_some() || block_overrides.is_some() {
+			return self
+				.dry_run_with_overrides(
+					tx,
+					block,
+					state_overrides.unwrap_or_default(),
+					block_overrides.unwrap_or_default(),
+				)
+				.await;
+		}
+
 		let runtime_api = self.runtime_api(&block).await?;
 		let payload = subxt_client::apis().revive_api().eth_transact(tx.into());
 
@@ -582,6 +873,57 @@ impl Client {
 		}
 	}
 
+	/// Dry run a transaction, after applying the given state and block overrides.
+	async fn dry_run_with_overrides(
+		&self,
+		tx: GenericTransaction,
+		block: BlockNumberOrTagOrHash,
+		state_overrides: StateOverride,
+		block_overrides: BlockOverrides,
+	) -> Result<EthTransactInfo<Balance>, ClientError> {
+		let block_hash = self.block_hash_for(&block).await?;
+		self.ensure_revive_api_version(block_hash, OVERRIDES_API_VERSION).await?;
+		let params = (tx, state_overrides, block_overrides).encode();
+		let bytes = self
+			.rpc
+			.state_call("ReviveApi_eth_transact_with_overrides", Some(&params), block_hash)
+			.await
+			.inspect_err(|err| {
+				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
+			})?;
+
+		Result::<EthTransactInfo<Balance>, EthTransactError>::decode(&mut &bytes[..])?.map_err(
+			|err| {
+				log::debug!(target: LOG_TARGET, "Dry run failed {err:?}");
+				ClientError::TransactError(err)
+			},
+		)
+	}
+
+	/// Dry run a transaction and return the access list of the accounts and storage slots it
+	/// touched.
+	pub async fn create_access_list(
+		&self,
+		tx: GenericTransaction,
+		block: &BlockNumberOrTagOrHash,
+	) -> Result<AccessList, ClientError> {
+		let block_hash = self.block_hash_for(block).await?;
+		self.ensure_revive_api_version(block_hash, OVERRIDES_API_VERSION).await?;
+		let params = tx.encode();
+		let bytes = self
+			.rpc
+			.state_call("ReviveApi_create_access_list", Some(&params), block_hash)
+			.await
+			.inspect_err(|err| {
+				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
+			})?;
+
+		Result::<AccessList, EthTransactError>::decode(&mut &bytes[..])?.map_err(|err| {
+			log::debug!(target: LOG_TARGET, "Dry run failed {err:?}");
+			ClientError::TransactError(err)
+		})
+	}
+
 	/// Get the nonce of the given address.
 	pub async fn nonce(
 		&self,
@@ -612,6 +954,27 @@ impl Client {
 		Ok(maybe_block.map(|block| block.hash()))
 	}
 
+	/// Get the hash of the given block.
+	///
+	/// Returns `None` for a block tag if no block was cached yet, in which case the node uses its
+	/// best block.
+	async fn block_hash_for(
+		&self,
+		block: &BlockNumberOrTagOrHash,
+	) -> Result<Option<SubstrateBlockHash>, ClientError> {
+		match block {
+			BlockNumberOrTagOrHash::U256(n) => {
+				let block_number: SubstrateBlockNumber =
+					(*n).try_into().map_err(|_| ClientError::ConversionFailed)?;
+				let hash =
+					self.get_block_hash(block_number).await?.ok_or(ClientError::BlockNotFound)?;
+				Ok(Some(hash))
+			},
+			BlockNumberOrTagOrHash::H256(hash) => Ok(Some(*hash)),
+			BlockNumberOrTagOrHash::BlockTag(_) => Ok(self.latest_block().await.map(|b| b.hash())),
+		}
+	}
+
 	/// Get a block for the specified hash or number.
 	pub async fn block_by_number_or_tag(
 		&self,
@@ -682,6 +1045,8 @@ impl Client {
 			.filter_map(|e| OpaqueExtrinsic::decode(&mut &e[..]).ok())
 			.collect::<Vec<_>>();
 
+		let api_version = self.revive_api_version(Some(parent_hash)).await?;
+		ensure_tracer_supported(api_version, &tracer_config)?;
 		let params = ((header, exts), tracer_config).encode();
 
 		let bytes = self
@@ -692,7 +1057,14 @@ impl Client {
 				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
 			})?;
 
-		let traces = Vec::<(u32, CallTrace)>::decode(&mut &bytes[..])?;
+		let traces = if api_version >= TRACE_API_VERSION {
+			Vec::<(u32, Trace)>::decode(&mut &bytes[..])?
+		} else {
+			Vec::<(u32, CallTrace)>::decode(&mut &bytes[..])?
+				.into_iter()
+				.map(|(index, trace)| (index, Trace::Call(trace)))
+				.collect()
+		};
 
 		let mut hashes = self
 			.receipt_provider
@@ -715,7 +1087,7 @@ impl Client {
 		&self,
 		transaction_hash: H256,
 		tracer_config: TracerConfig,
-	) -> Result<CallTrace, ClientError> {
+	) -> Result<Trace, ClientError> {
 		let ReceiptInfo { block_hash, transaction_index, .. } = self
 			.receipt_provider
 			.receipt_by_hash(&transaction_hash)
@@ -740,6 +1112,8 @@ impl Client {
 			.filter_map(|e| OpaqueExtrinsic::decode(&mut &e[..]).ok())
 			.collect::<Vec<_>>();
 
+		let api_version = self.revive_api_version(Some(parent_hash)).await?;
+		ensure_tracer_supported(api_version, &tracer_config)?;
 		let params = ((header, exts), transaction_index.as_u32(), tracer_config).encode();
 		let bytes = self
 			.rpc
@@ -749,7 +1123,11 @@ impl Client {
 				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
 			})?;
 
-		let trace = Option::<CallTrace>::decode(&mut &bytes[..])?;
+		let trace = if api_version >= TRACE_API_VERSION {
+			Option::<Trace>::decode(&mut &bytes[..])?
+		} else {
+			Option::<CallTrace>::decode(&mut &bytes[..])?.map(Trace::Call)
+		};
 		trace.ok_or(ClientError::EthExtrinsicNotFound)
 	}
 
@@ -759,7 +1137,7 @@ impl Client {
 		transaction: GenericTransaction,
 		block: BlockNumberOrTag,
 		tracer_config: TracerConfig,
-	) -> Result<CallTrace, ClientError> {
+	) -> Result<Trace, ClientError> {
 		let block_hash = match block {
 			BlockNumberOrTag::U256(n) => {
 				let block_number: SubstrateBlockNumber =
@@ -769,6 +1147,8 @@ impl Client {
 			BlockNumberOrTag::BlockTag(_) => self.latest_block().await.map(|b| b.hash()),
 		};
 
+		let api_version = self.revive_api_version(block_hash).await?;
+		ensure_tracer_supported(api_version, &tracer_config)?;
 		let params = (transaction, tracer_config).encode();
 		let bytes = self
 			.rpc
@@ -778,8 +1158,48 @@ impl Client {
 				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
 			})?;
 
-		Result::<CallTrace, EthTransactError>::decode(&mut &bytes[..])?
-			.map_err(ClientError::TransactError)
+		if api_version >= TRACE_API_VERSION {
+			Result::<Trace, EthTransactError>::decode(&mut &bytes[..])?
+				.map_err(ClientError::TransactError)
+		} else {
+			Result::<CallTrace, EthTransactError>::decode(&mut &bytes[..])?
+				.map(Trace::Call)
+				.map_err(ClientError::TransactError)
+		}
+	}
+
+	/// Get the version of the `ReviveApi` implemented by the runtime at the given block.
+	async fn revive_api_version(
+		&self,
+		block_hash: Option<SubstrateBlockHash>,
+	) -> Result<u32, ClientError> {
+		let api_id = format!("0x{}", hex::encode(sp_crypto_hashing::blake2_64(b"ReviveApi")));
+		let runtime_version = self.rpc.state_get_runtime_version(block_hash).await?;
+		runtime_version
+			.other
+			.get("apis")
+			.and_then(|apis| apis.as_array())
+			.into_iter()
+			.flatten()
+			.find_map(|api| match api.as_array()?.as_slice() {
+				[id, version] if id.as_str() == Some(api_id.as_str()) => version.as_u64(),
+				_ => None,
+			})
+			.and_then(|version| version.try_into().ok())
+			.ok_or(ClientError::RuntimeApiNotSupported)
+	}
+
+	/// Ensure the runtime at the given block implements at least the given version of the
+	/// `ReviveApi`.
+	async fn ensure_revive_api_version(
+		&self,
+		block_hash: Option<SubstrateBlockHash>,
+		version: u32,
+	) -> Result<(), ClientError> {
+		if self.revive_api_version(block_hash).await? < version {
+			return Err(ClientError::RuntimeApiNotSupported);
+		}
+		Ok(())
 	}
 	/// Get the EVM block for the given hash.
 	pub async fn evm_block(
@@ -832,6 +1252,75 @@ impl Client {
 		}
 	}
 
+	/// Get the fee history of the `block_count` blocks ending at `newest_block`.
+	///
+	/// The base fee of a block is the gas price at that block, and the rewards are the effective
+	/// priority fees of its transactions, at the given percentiles of the gas used by the block.
+	pub async fn fee_history(
+		&self,
+		block_count: u32,
+		newest_block: BlockNumberOrTag,
+		reward_percentiles: Option<Vec<f64>>,
+	) -> Result<FeeHistoryResult, ClientError> {
+		let newest_block = self
+			.block_by_number_or_tag(&newest_block)
+			.await?
+			.ok_or(ClientError::BlockNotFound)?;
+		let newest_number = newest_block.number();
+		let block_count = block_count.min(newest_number.saturating_add(1));
+		let oldest_number = newest_number.saturating_add(1).saturating_sub(block_count);
+
+		let mut result = FeeHistoryResult {
+			oldest_block: oldest_number.into(),
+			reward: reward_percentiles.as_ref().map(|_| Vec::new()),
+			..Default::default()
+		};
+
+		for number in oldest_number..=newest_number {
+			let block = self.block_by_number(number).await?.ok_or(ClientError::BlockNotFound)?;
+			let block_hash = block.hash();
+			let runtime_api = self.api.runtime_api().at(block_hash);
+			let gas_limit = Self::block_gas_limit(&runtime_api).await?;
+			let base_fee = self.gas_price(&block_hash.into()).await?;
+
+			let count = self.receipt_provider.receipts_count_per_block(&block_hash).await;
+			let mut receipts = Vec::with_capacity(count.unwrap_or_default());
+			for index in 0..count.unwrap_or_default() {
+				if let Some(receipt) =
+					self.receipt_provider.receipt_by_block_hash_and_index(&block_hash, index).await
+				{
+					receipts.push(receipt);
+				}
+			}
+
+			let gas_used =
+				receipts.iter().fold(U256::zero(), |acc, receipt| acc + receipt.gas_used);
+			result.base_fee_per_gas.push(base_fee);
+			result.gas_used_ratio.push(if gas_limit.is_zero() {
+				0.0
+			} else {
+				gas_used.low_u128() as f64 / gas_limit.low_u128() as f64
+			});
+
+			if let (Some(rewards), Some(percentiles)) = (&mut result.reward, &reward_percentiles) {
+				let fees = receipts
+					.iter()
+					.map(|receipt| {
+						(receipt.effective_gas_price.saturating_sub(base_fee), receipt.gas_used)
+					})
+					.collect();
+				rewards.push(block_rewards(fees, gas_used, percentiles));
+			}
+		}
+
+		// The base fee of the next block is the same as the one of the newest block.
+		if let Some(base_fee) = result.base_fee_per_gas.last().copied() {
+			result.base_fee_per_gas.push(base_fee);
+		}
+
+		Ok(result)
+	}
+
 	/// Convert a weight to a fee.
 	async fn block_gas_limit(
 		runtime_api: &subxt::runtime_api::RuntimeApi<SrcChainConfig, OnlineClient<SrcChainConfig>>,
@@ -858,3 +1347,50 @@ impl Client {
 		Ok(logs)
 	}
 }
+
+/// Compute the rewards of a block at the given percentiles.
+///
+/// `fees` are the effective priority fees and gas used of the block transactions, and
+/// `percentiles` are increasing values between 0 and 100. The reward at a percentile is the fee of
+/// the first transaction at which the cumulative gas used, sorted by fee, reaches that percentile
+/// of the block gas used.
+fn block_rewards(mut fees: Vec<(U256, U256)>, gas_used: U256, percentiles: &[f64]) -> Vec<U256> {
+	if fees.is_empty() {
+		return vec![U256::zero(); percentiles.len()];
+	}
+
+	fees.sort_by(|(a, _), (b, _)| a.cmp(b));
+	let mut index = 0;
+	let mut cumulative_gas = fees[0].1;
+	percentiles
+		.iter()
+		.map(|percentile| {
+			let threshold = U256::from((gas_used.low_u128() as f64 * percentile / 100.0) as u128);
+			while cumulative_gas < threshold && index < fees.len() - 1 {
+				index += 1;
+				cumulative_gas += fees[index].1;
+			}
+			fees[index].0
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn block_rewards_works() {
+		assert_eq!(block_rewards(vec![], U256::zero(), &[10.0, 50.0]), vec![U256::zero(); 2]);
+
+		let fees = vec![
+			(U256::from(30u64), U256::from(50u64)),
+			(U256::from(10u64), U256::from(25u64)),
+			(U256::from(20u64), U256::from(25u64)),
+		];
+		assert_eq!(
+			block_rewards(fees, U256::from(100u64), &[0.0, 25.0, 40.0, 50.0, 100.0]),
+			vec![10u64, 10, 20, 20, 30].into_iter().map(U256::from).collect::<Vec<_>>()
+		);
+	}
+}
@@ -129,6 +129,8 @@ impl<Client: EthRpcClient + Send + Sync> TransactionBuilder<Client> {
 					..Default::default()
 				},
 				None,
+				None,
+				None,
 			)
 			.await
 			.with_context(|| "eth_call failed")?;
@@ -158,6 +160,8 @@ impl<Client: EthRpcClient + Send + Sync> TransactionBuilder<Client> {
 					..Default::default()
 				},
 				None,
+				None,
+				None,
 			)
 			.await
 			.with_context(|| "Failed to fetch gas estimate")?;
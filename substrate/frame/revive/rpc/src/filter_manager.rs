@@ -0,0 +1,198 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::client::SubstrateBlockNumber;
+use pallet_revive::evm::Filter;
+use sp_core::U256;
+use std::{
+	collections::HashMap,
+	ops::RangeInclusive,
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+/// Filters that are not polled within this duration are uninstalled.
+pub const DEFAULT_FILTER_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// The maximum number of filters that can be installed at the same time.
+pub const DEFAULT_MAX_FILTERS: usize = 1024;
+
+/// The kind of an installed filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterKind {
+	/// A filter created with `eth_newFilter`, reporting the logs matching the filter.
+	Logs(Filter),
+	/// A filter created with `eth_newBlockFilter`, reporting the hashes of the new blocks.
+	Blocks,
+}
+
+/// A filter installed in the [`FilterManager`].
+struct InstalledFilter {
+	/// The kind of the filter.
+	kind: FilterKind,
+	/// The first block whose changes have not been polled yet.
+	next_block: SubstrateBlockNumber,
+	/// The last time the filter was accessed.
+	last_access: Instant,
+}
+
+/// The state of the [`FilterManager`].
+#[derive(Default)]
+struct Filters {
+	/// The installed filters, by id.
+	filters: HashMap<U256, InstalledFilter>,
+	/// The id of the last installed filter.
+	last_id: u64,
+}
+
+/// Keeps track of the filters installed by the clients of the RPC server.
+///
+/// Filters that are not accessed within the configured timeout are uninstalled. The number of
+/// installed filters is bounded, since they are shared by all the clients of the server.
+pub struct FilterManager {
+	/// The installed filters.
+	filters: Mutex<Filters>,
+	/// The duration after which a filter that is not accessed expires.
+	timeout: Duration,
+	/// The maximum number of installed filters.
+	max_filters: usize,
+}
+
+impl Default for FilterManager {
+	fn default() -> Self {
+		Self::new(DEFAULT_FILTER_TIMEOUT, DEFAULT_MAX_FILTERS)
+	}
+}
+
+impl FilterManager {
+	/// Create a new [`FilterManager`], with the given expiry timeout and maximum number of
+	/// installed filters.
+	pub fn new(timeout: Duration, max_filters: usize) -> Self {
+		Self { filters: Default::default(), timeout, max_filters }
+	}
+
+	/// Install a new filter and return its id.
+	///
+	/// Changes are reported starting from the block following `latest_block`. Returns `None` if
+	/// the maximum number of filters is already installed.
+	pub fn install(&self, kind: FilterKind, latest_block: SubstrateBlockNumber) -> Option<U256> {
+		let mut state = self.lock();
+		if state.filters.len() >= self.max_filters {
+			return None
+		}
+		state.last_id += 1;
+		let id = U256::from(state.last_id);
+		let filter = InstalledFilter {
+			kind,
+			next_block: latest_block.saturating_add(1),
+			last_access: Instant::now(),
+		};
+		state.filters.insert(id, filter);
+		Some(id)
+	}
+
+	/// Uninstall the filter with the given id.
+	///
+	/// Returns `false` if the filter was not found.
+	pub fn uninstall(&self, id: &U256) -> bool {
+		self.lock().filters.remove(id).is_some()
+	}
+
+	/// Get the filter with the given id.
+	pub fn get(&self, id: &U256) -> Option<FilterKind> {
+		let mut state = self.lock();
+		let filter = state.filters.get_mut(id)?;
+		filter.last_access = Instant::now();
+		Some(filter.kind.clone())
+	}
+
+	/// Poll the filter with the given id.
+	///
+	/// Returns the filter and the range of blocks whose changes were not polled yet, up to
+	/// `latest_block`. The range is empty if there are no new blocks.
+	pub fn poll(
+		&self,
+		id: &U256,
+		latest_block: SubstrateBlockNumber,
+	) -> Option<(FilterKind, RangeInclusive<SubstrateBlockNumber>)> {
+		let mut state = self.lock();
+		let filter = state.filters.get_mut(id)?;
+		filter.last_access = Instant::now();
+
+		let range = filter.next_block..=latest_block;
+		filter.next_block = filter.next_block.max(latest_block.saturating_add(1));
+		Some((filter.kind.clone(), range))
+	}
+
+	/// Lock the state, uninstalling the expired filters.
+	fn lock(&self) -> std::sync::MutexGuard<'_, Filters> {
+		let mut state = self.filters.lock().unwrap_or_else(|err| err.into_inner());
+		let timeout = self.timeout;
+		state.filters.retain(|_, filter| filter.last_access.elapsed() < timeout);
+		state
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn poll_works() {
+		let manager = FilterManager::default();
+		let id = manager.install(FilterKind::Blocks, 10).unwrap();
+
+		assert_eq!(manager.poll(&id, 10), Some((FilterKind::Blocks, 11..=10)));
+		assert_eq!(manager.poll(&id, 12), Some((FilterKind::Blocks, 11..=12)));
+		assert_eq!(manager.poll(&id, 12), Some((FilterKind::Blocks, 13..=12)));
+		assert_eq!(manager.poll(&id, 13), Some((FilterKind::Blocks, 13..=13)));
+	}
+
+	#[test]
+	fn uninstall_works() {
+		let manager = FilterManager::default();
+		let id = manager.install(FilterKind::Logs(Default::default()), 1).unwrap();
+		let other_id = manager.install(FilterKind::Blocks, 1).unwrap();
+		assert_ne!(id, other_id);
+
+		assert!(manager.uninstall(&id));
+		assert!(!manager.uninstall(&id));
+		assert_eq!(manager.get(&id), None);
+		assert_eq!(manager.get(&other_id), Some(FilterKind::Blocks));
+	}
+
+	#[test]
+	fn filters_expire() {
+		let manager = FilterManager::new(Duration::from_millis(10), DEFAULT_MAX_FILTERS);
+		let id = manager.install(FilterKind::Blocks, 1).unwrap();
+		assert_eq!(manager.get(&id), Some(FilterKind::Blocks));
+
+		std::thread::sleep(Duration::from_millis(20));
+		assert_eq!(manager.poll(&id, 2), None);
+	}
+
+	#[test]
+	fn installed_filters_are_bounded() {
+		let manager = FilterManager::new(DEFAULT_FILTER_TIMEOUT, 2);
+		let id = manager.install(FilterKind::Blocks, 1).unwrap();
+		manager.install(FilterKind::Blocks, 1).unwrap();
+		assert_eq!(manager.install(FilterKind::Blocks, 1), None);
+
+		// Uninstalling a filter makes room for a new one.
+		assert!(manager.uninstall(&id));
+		assert!(manager.install(FilterKind::Blocks, 1).is_some());
+	}
+}
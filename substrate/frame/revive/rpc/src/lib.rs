@@ -38,6 +38,9 @@ mod tests;
 mod block_info_provider;
 pub use block_info_provider::*;
 
+mod filter_manager;
+pub use filter_manager::*;
+
 mod receipt_provider;
 pub use receipt_provider::*;
 
@@ -49,6 +52,9 @@ pub use apis::*;
 
 pub const LOG_TARGET: &str = "eth-rpc";
 
+/// The maximum number of blocks that can be requested with `eth_feeHistory`.
+const MAX_FEE_HISTORY_BLOCK_COUNT: u32 = 1024;
+
 /// An EVM RPC server implementation.
 pub struct EthRpcServerImpl {
 	/// The client used to interact with the substrate node.
@@ -56,12 +62,15 @@ pub struct EthRpcServerImpl {
 
 	/// The accounts managed by the server.
 	accounts: Vec<Account>,
+
+	/// The filters installed by `eth_newFilter` and `eth_newBlockFilter`.
+	filters: FilterManager,
 }
 
 impl EthRpcServerImpl {
 	/// Creates a new [`EthRpcServerImpl`].
 	pub fn new(client: client::Client) -> Self {
-		Self { client, accounts: vec![] }
+		Self { client, accounts: vec![], filters: Default::default() }
 	}
 
 	/// Sets the accounts managed by the server.
@@ -69,6 +78,12 @@ impl EthRpcServerImpl {
 		self.accounts = accounts;
 		self
 	}
+
+	/// Sets the manager of the filters installed by the clients of the server.
+	pub fn with_filters(mut self, filters: FilterManager) -> Self {
+		self.filters = filters;
+		self
+	}
 }
 
 /// The error type for the EVM RPC server.
@@ -95,6 +110,18 @@ pub enum EthRpcError {
 	/// Received an invalid transaction
 	#[error("Invalid transaction {0:?}")]
 	TransactionTypeNotSupported(Byte),
+	/// The filter was not found, or it expired.
+	#[error("filter not found")]
+	FilterNotFound,
+	/// The maximum number of filters is already installed.
+	#[error("too many filters installed")]
+	TooManyFilters,
+	/// Received an invalid filter.
+	#[error("Invalid filter: {0}")]
+	InvalidFilter(&'static str),
+	/// Received invalid `eth_feeHistory` parameters.
+	#[error("Invalid fee history parameters: {0}")]
+	InvalidFeeHistoryParams(&'static str),
 }
 
 // TODO use https://eips.ethereum.org/EIPS/eip-1474#error-codes
@@ -140,19 +167,50 @@ impl EthRpcServer for EthRpcServerImpl {
 		&self,
 		transaction: GenericTransaction,
 		block: Option<BlockNumberOrTag>,
+		state_overrides: Option<StateOverride>,
+		block_overrides: Option<BlockOverrides>,
 	) -> RpcResult<U256> {
-		let dry_run = self.client.dry_run(transaction, block.unwrap_or_default().into()).await?;
+		let dry_run = self
+			.client
+			.dry_run(
+				transaction,
+				block.unwrap_or_default().into(),
+				state_overrides,
+				block_overrides,
+			)
+			.await?;
 		Ok(dry_run.eth_gas)
 	}
 
+	async fn create_access_list(
+		&self,
+		mut transaction: GenericTransaction,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<AccessListResult> {
+		let block = block.unwrap_or_else(|| BlockTag::Latest.into());
+		let access_list = self.client.create_access_list(transaction.clone(), &block).await?;
+
+		// Dry run the transaction again with the access list, to report the gas it would use.
+		transaction.access_list = Some(access_list.clone());
+		let dry_run = self.client.dry_run(transaction, block, None, None).await?;
+		Ok(AccessListResult { access_list, gas_used: dry_run.eth_gas })
+	}
+
 	async fn call(
 		&self,
 		transaction: GenericTransaction,
 		block: Option<BlockNumberOrTagOrHash>,
+		state_overrides: Option<StateOverride>,
+		block_overrides: Option<BlockOverrides>,
 	) -> RpcResult<Bytes> {
 		let dry_run = self
 			.client
-			.dry_run(transaction, block.unwrap_or_else(|| BlockTag::Latest.into()))
+			.dry_run(
+				transaction,
+				block.unwrap_or_else(|| BlockTag::Latest.into()),
+				state_overrides,
+				block_overrides,
+			)
 			.await?;
 		Ok(dry_run.data.into())
 	}
@@ -166,6 +224,7 @@ impl EthRpcServer for EthRpcServerImpl {
 		})?;
 
 		log::debug!(target: LOG_TARGET, "send_raw_transaction hash: {hash:?}");
+		self.client.notify_pending_transaction(hash);
 		Ok(hash)
 	}
 
@@ -184,7 +243,7 @@ impl EthRpcServer for EthRpcServerImpl {
 			.ok_or(EthRpcError::AccountNotFound(from))?;
 
 		if transaction.gas.is_none() {
-			transaction.gas = Some(self.estimate_gas(transaction.clone(), None).await?);
+			transaction.gas = Some(self.estimate_gas(transaction.clone(), None, None, None).await?);
 		}
 
 		if transaction.gas_price.is_none() {
@@ -289,6 +348,107 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(FilterResults::Logs(logs))
 	}
 
+	async fn new_filter(&self, filter: Filter) -> RpcResult<U256> {
+		if filter.block_hash.is_some() {
+			return Err(EthRpcError::InvalidFilter("blockHash is not supported").into());
+		}
+		let latest_block = self.client.block_number().await?;
+		Ok(self
+			.filters
+			.install(FilterKind::Logs(filter), latest_block)
+			.ok_or(EthRpcError::TooManyFilters)?)
+	}
+
+	async fn new_block_filter(&self) -> RpcResult<U256> {
+		let latest_block = self.client.block_number().await?;
+		Ok(self
+			.filters
+			.install(FilterKind::Blocks, latest_block)
+			.ok_or(EthRpcError::TooManyFilters)?)
+	}
+
+	async fn get_filter_changes(&self, filter_id: U256) -> RpcResult<FilterResults> {
+		let latest_block = self.client.block_number().await?;
+		let (kind, blocks) =
+			self.filters.poll(&filter_id, latest_block).ok_or(EthRpcError::FilterNotFound)?;
+
+		match kind {
+			FilterKind::Blocks => {
+				let mut hashes = Vec::new();
+				for number in blocks {
+					if let Some(hash) = self.client.get_block_hash(number).await? {
+						hashes.push(hash);
+					}
+				}
+				Ok(FilterResults::Hashes(hashes))
+			},
+			FilterKind::Logs(filter) => {
+				let from_block = filter
+					.from_block
+					.map_or(U256::from(*blocks.start()), |from| from.max((*blocks.start()).into()));
+				let to_block = filter
+					.to_block
+					.map_or(U256::from(*blocks.end()), |to| to.min((*blocks.end()).into()));
+				if from_block > to_block {
+					return Ok(FilterResults::Logs(vec![]));
+				}
+
+				let filter =
+					Filter { from_block: Some(from_block), to_block: Some(to_block), ..filter };
+				let logs = self.client.logs(Some(filter)).await?;
+				Ok(FilterResults::Logs(logs))
+			},
+		}
+	}
+
+	async fn get_filter_logs(&self, filter_id: U256) -> RpcResult<FilterResults> {
+		let Some(FilterKind::Logs(filter)) = self.filters.get(&filter_id) else {
+			return Err(EthRpcError::FilterNotFound.into());
+		};
+		let logs = self.client.logs(Some(filter)).await?;
+		Ok(FilterResults::Logs(logs))
+	}
+
+	async fn uninstall_filter(&self, filter_id: U256) -> RpcResult<bool> {
+		Ok(self.filters.uninstall(&filter_id))
+	}
+
+	async fn fee_history(
+		&self,
+		block_count: U256,
+		newest_block: BlockNumberOrTag,
+		reward_percentiles: Option<Vec<f64>>,
+	) -> RpcResult<FeeHistoryResult> {
+		let block_count: u32 = block_count
+			.try_into()
+			.ok()
+			.filter(|count| (1..=MAX_FEE_HISTORY_BLOCK_COUNT).contains(count))
+			.ok_or(EthRpcError::InvalidFeeHistoryParams("invalid block count"))?;
+
+		if let Some(percentiles) = &reward_percentiles {
+			let is_valid = percentiles.iter().all(|p| (0.0..=100.0).contains(p)) &&
+				percentiles.windows(2).all(|w| w[0] <= w[1]);
+			if !is_valid {
+				return Err(
+					EthRpcError::InvalidFeeHistoryParams("invalid reward percentiles").into()
+				);
+			}
+		}
+
+		let result = self.client.fee_history(block_count, newest_block, reward_percentiles).await?;
+		Ok(result)
+	}
+
+	async fn get_proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<H256>,
+		block: BlockNumberOrTagOrHash,
+	) -> RpcResult<AccountProof> {
+		let proof = self.client.get_proof(address, storage_keys, block).await?;
+		Ok(proof)
+	}
+
 	async fn get_storage_at(
 		&self,
 		address: H160,
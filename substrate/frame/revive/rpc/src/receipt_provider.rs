@@ -15,6 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::client::SubstrateBlockNumber;
 use jsonrpsee::core::async_trait;
 use pallet_revive::evm::{Filter, Log, ReceiptInfo, TransactionSigned, H256};
 use std::collections::HashMap;
@@ -41,6 +42,25 @@ pub trait ReceiptProvider: Send + Sync {
 	/// Deletes receipts associated with the specified block hash.
 	async fn remove(&self, block_hash: &H256);
 
+	/// Return the hash of the canonical block indexed at the given block number.
+	async fn block_hash(&self, block_number: SubstrateBlockNumber) -> Option<H256>;
+
+	/// Record the given block hash as the canonical block at the given block number.
+	///
+	/// A different block previously recorded at this height was retracted by a reorg: its
+	/// receipts are deleted and its hash is returned.
+	async fn set_canonical(
+		&self,
+		block_number: SubstrateBlockNumber,
+		block_hash: &H256,
+	) -> Option<H256>;
+
+	/// Delete the receipts of all the canonical blocks above the given block number, and return
+	/// their hashes.
+	///
+	/// This is used when a new best block retracts blocks of a longer fork.
+	async fn retract_after(&self, block_number: SubstrateBlockNumber) -> Vec<H256>;
+
 	/// Return all transaction hashes for the given block hash.
 	async fn block_transaction_hashes(&self, block_hash: &H256) -> Option<HashMap<usize, H256>>;
 
@@ -75,6 +95,30 @@ impl<Cache: ReceiptProvider, Archive: ReceiptProvider> ReceiptProvider for (Cach
 		join!(self.0.remove(block_hash), self.1.remove(block_hash));
 	}
 
+	async fn block_hash(&self, block_number: SubstrateBlockNumber) -> Option<H256> {
+		self.1.block_hash(block_number).await
+	}
+
+	async fn set_canonical(
+		&self,
+		block_number: SubstrateBlockNumber,
+		block_hash: &H256,
+	) -> Option<H256> {
+		let retracted = self.1.set_canonical(block_number, block_hash).await;
+		if let Some(retracted) = &retracted {
+			self.0.remove(retracted).await;
+		}
+		retracted
+	}
+
+	async fn retract_after(&self, block_number: SubstrateBlockNumber) -> Vec<H256> {
+		let retracted = self.1.retract_after(block_number).await;
+		for block_hash in &retracted {
+			self.0.remove(block_hash).await;
+		}
+		retracted
+	}
+
 	async fn receipt_by_block_hash_and_index(
 		&self,
 		block_hash: &H256,
@@ -15,6 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use super::ReceiptProvider;
+use crate::client::SubstrateBlockNumber;
 use jsonrpsee::core::async_trait;
 use pallet_revive::evm::{Filter, Log, ReceiptInfo, TransactionSigned, H256};
 use std::{collections::HashMap, sync::Arc};
@@ -51,6 +52,22 @@ impl ReceiptProvider for CacheReceiptProvider {
 		cache.remove(block_hash);
 	}
 
+	async fn block_hash(&self, _block_number: SubstrateBlockNumber) -> Option<H256> {
+		None
+	}
+
+	async fn set_canonical(
+		&self,
+		_block_number: SubstrateBlockNumber,
+		_block_hash: &H256,
+	) -> Option<H256> {
+		None
+	}
+
+	async fn retract_after(&self, _block_number: SubstrateBlockNumber) -> Vec<H256> {
+		vec![]
+	}
+
 	async fn receipt_by_block_hash_and_index(
 		&self,
 		block_hash: &H256,
@@ -17,14 +17,15 @@
 
 use super::*;
 use crate::{
-	Address, AddressOrAddresses, BlockInfoProvider, Bytes, FilterTopic, ReceiptExtractor,
-	LOG_TARGET,
+	client::SubstrateBlockNumber, Address, AddressOrAddresses, BlockInfoProvider, Bytes,
+	FilterTopic, ReceiptExtractor, LOG_TARGET,
 };
 use jsonrpsee::core::async_trait;
 use pallet_revive::evm::{Filter, Log, ReceiptInfo, TransactionSigned};
 use sp_core::{H256, U256};
-use sqlx::{query, QueryBuilder, Row, Sqlite, SqlitePool};
+use sqlx::{query, query_scalar, QueryBuilder, Row, Sqlite, SqlitePool};
 use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
 
 /// A `[ReceiptProvider]` that stores receipts in a SQLite database.
 #[derive(Clone)]
@@ -37,6 +38,9 @@ pub struct DBReceiptProvider {
 	receipt_extractor: ReceiptExtractor,
 	/// Whether to prune old blocks.
 	prune_old_blocks: bool,
+	/// Serializes updates of the canonical block hashes, so that concurrent best and finalized
+	/// block notifications don't interleave their read-then-write of the `block_hashes` table.
+	canonical_lock: Arc<Mutex<()>>,
 }
 
 impl DBReceiptProvider {
@@ -49,7 +53,60 @@ impl DBReceiptProvider {
 	) -> Result<Self, sqlx::Error> {
 		let pool = SqlitePool::connect(database_url).await?;
 		sqlx::migrate!().run(&pool).await?;
-		Ok(Self { pool, block_provider, receipt_extractor, prune_old_blocks })
+		Ok(Self {
+			pool,
+			block_provider,
+			receipt_extractor,
+			prune_old_blocks,
+			canonical_lock: Default::default(),
+		})
+	}
+
+	/// Delete the receipts and logs of the given block, and its canonical block hash record.
+	async fn delete_block(&self, block_hash: &H256) {
+		let block_hash = block_hash.as_ref();
+
+		let delete_transaction_hashes = query!(
+			r#"
+        DELETE FROM transaction_hashes
+        WHERE block_hash = $1
+        "#,
+			block_hash
+		)
+		.execute(&self.pool);
+
+		let delete_logs = query!(
+			r#"
+        DELETE FROM logs
+        WHERE block_hash = $1
+        "#,
+			block_hash
+		)
+		.execute(&self.pool);
+
+		let delete_block_hash = query!(
+			r#"
+        DELETE FROM block_hashes
+        WHERE block_hash = $1
+        "#,
+			block_hash
+		)
+		.execute(&self.pool);
+
+		let (tx_result, logs_result, block_hash_result) =
+			tokio::join!(delete_transaction_hashes, delete_logs, delete_block_hash);
+
+		if let Err(err) = tx_result {
+			log::error!(target: LOG_TARGET, "Error removing transaction hashes for block hash {block_hash:?}: {err:?}");
+		}
+
+		if let Err(err) = logs_result {
+			log::error!(target: LOG_TARGET, "Error removing logs for block hash {block_hash:?}: {err:?}");
+		}
+
+		if let Err(err) = block_hash_result {
+			log::error!(target: LOG_TARGET, "Error removing block hash {block_hash:?}: {err:?}");
+		}
 	}
 
 	async fn fetch_row(&self, transaction_hash: &H256) -> Option<(H256, usize)> {
@@ -79,35 +136,91 @@ impl ReceiptProvider for DBReceiptProvider {
 			return;
 		}
 
-		let block_hash = block_hash.as_ref();
+		self.delete_block(block_hash).await;
+	}
 
-		let delete_transaction_hashes = query!(
+	async fn block_hash(&self, block_number: SubstrateBlockNumber) -> Option<H256> {
+		let number = block_number as i64;
+		let block_hash = query_scalar!(
 			r#"
-        DELETE FROM transaction_hashes
-        WHERE block_hash = $1
-        "#,
-			block_hash
+			SELECT block_hash
+			FROM block_hashes
+			WHERE block_number = $1
+			"#,
+			number
 		)
-		.execute(&self.pool);
+		.fetch_optional(&self.pool)
+		.await
+		.inspect_err(|err| {
+			log::error!(target: LOG_TARGET, "Error fetching block hash of #{block_number}: {err:?}");
+		})
+		.ok()??;
 
-		let delete_logs = query!(
+		Some(H256::from_slice(&block_hash))
+	}
+
+	async fn set_canonical(
+		&self,
+		block_number: SubstrateBlockNumber,
+		block_hash: &H256,
+	) -> Option<H256> {
+		let _guard = self.canonical_lock.lock().await;
+
+		let retracted = self.block_hash(block_number).await.filter(|hash| hash != block_hash);
+		if let Some(retracted) = &retracted {
+			log::debug!(target: LOG_TARGET, "Block #{block_number} {retracted:?} retracted by {block_hash:?}");
+			self.delete_block(retracted).await;
+		}
+
+		let number = block_number as i64;
+		let hash = block_hash.as_ref();
+		let result = query!(
 			r#"
-        DELETE FROM logs
-        WHERE block_hash = $1
-        "#,
-			block_hash
+			INSERT OR REPLACE INTO block_hashes (block_number, block_hash)
+			VALUES ($1, $2)
+			"#,
+			number,
+			hash
 		)
-		.execute(&self.pool);
+		.execute(&self.pool)
+		.await;
 
-		let (tx_result, logs_result) = tokio::join!(delete_transaction_hashes, delete_logs);
-
-		if let Err(err) = tx_result {
-			log::error!(target: LOG_TARGET, "Error removing transaction hashes for block hash {block_hash:?}: {err:?}");
+		if let Err(err) = result {
+			log::error!(target: LOG_TARGET, "Error recording block hash of #{block_number}: {err:?}");
 		}
 
-		if let Err(err) = logs_result {
-			log::error!(target: LOG_TARGET, "Error removing logs for block hash {block_hash:?}: {err:?}");
+		retracted
+	}
+
+	async fn retract_after(&self, block_number: SubstrateBlockNumber) -> Vec<H256> {
+		let _guard = self.canonical_lock.lock().await;
+
+		let number = block_number as i64;
+		let retracted = match query_scalar!(
+			r#"
+			SELECT block_hash
+			FROM block_hashes
+			WHERE block_number > $1
+			"#,
+			number
+		)
+		.fetch_all(&self.pool)
+		.await
+		{
+			Ok(hashes) => hashes,
+			Err(err) => {
+				log::error!(target: LOG_TARGET, "Error fetching blocks above #{block_number}: {err:?}");
+				return vec![];
+			},
+		};
+
+		let retracted = retracted.iter().map(|hash| H256::from_slice(hash)).collect::<Vec<_>>();
+		for block_hash in &retracted {
+			log::debug!(target: LOG_TARGET, "Block {block_hash:?} above #{block_number} retracted");
+			self.delete_block(block_hash).await;
 		}
+
+		retracted
 	}
 
 	async fn archive(&self, block_hash: &H256, receipts: &[(TransactionSigned, ReceiptInfo)]) {
@@ -412,6 +525,7 @@ mod tests {
 			block_provider: Arc::new(MockBlockInfoProvider {}),
 			receipt_extractor: ReceiptExtractor::new(1_000_000, None),
 			prune_old_blocks: true,
+			canonical_lock: Default::default(),
 		}
 	}
 
@@ -460,6 +574,68 @@ mod tests {
 		assert_eq!(logs_count, 0);
 	}
 
+	#[sqlx::test]
+	async fn test_reorg_removes_retracted_blocks(pool: SqlitePool) {
+		let provider = setup_sqlite_provider(pool).await;
+		let receipts = |block_hash: H256, transaction_hash: H256| {
+			vec![(
+				TransactionSigned::default(),
+				ReceiptInfo {
+					block_hash,
+					transaction_hash,
+					logs: vec![Log { block_hash, transaction_hash, ..Default::default() }],
+					..Default::default()
+				},
+			)]
+		};
+
+		let (block_a, block_b, block_c) =
+			(H256::from([1u8; 32]), H256::from([2u8; 32]), H256::from([3u8; 32]));
+		let (tx_a, tx_b, tx_c) =
+			(H256::from([11u8; 32]), H256::from([12u8; 32]), H256::from([13u8; 32]));
+
+		assert_eq!(provider.set_canonical(1, &block_a).await, None);
+		provider.insert(&block_a, &receipts(block_a, tx_a)).await;
+		assert_eq!(provider.set_canonical(2, &block_c).await, None);
+		provider.insert(&block_c, &receipts(block_c, tx_c)).await;
+		assert_eq!(provider.block_hash(1).await, Some(block_a));
+
+		// Recording the same block again is a no-op
+		assert_eq!(provider.set_canonical(1, &block_a).await, None);
+		assert_eq!(provider.fetch_row(&tx_a).await, Some((block_a, 0)));
+
+		// A reorg at height 1 removes the receipts of the retracted block
+		assert_eq!(provider.set_canonical(1, &block_b).await, Some(block_a));
+		provider.insert(&block_b, &receipts(block_b, tx_b)).await;
+		assert_eq!(provider.block_hash(1).await, Some(block_b));
+		assert_eq!(provider.fetch_row(&tx_a).await, None);
+		assert_eq!(provider.fetch_row(&tx_b).await, Some((block_b, 0)));
+		assert_eq!(provider.block_transaction_hashes(&block_a).await, Some(HashMap::new()));
+
+		// Blocks above the new best block are retracted
+		assert_eq!(provider.retract_after(1).await, vec![block_c]);
+		assert_eq!(provider.block_hash(2).await, None);
+		assert_eq!(provider.fetch_row(&tx_c).await, None);
+		assert_eq!(provider.block_hash(1).await, Some(block_b));
+	}
+
+	#[sqlx::test]
+	async fn test_concurrent_set_canonical_retracts_once(pool: SqlitePool) {
+		let provider = setup_sqlite_provider(pool).await;
+		let (block_a, block_b) = (H256::from([1u8; 32]), H256::from([2u8; 32]));
+		assert_eq!(provider.set_canonical(1, &block_a).await, None);
+
+		// The best and finalized notifications may race to record the same height.
+		let (first, second) =
+			tokio::join!(provider.set_canonical(1, &block_b), provider.set_canonical(1, &block_b));
+
+		// Only one of them observes (and removes) the retracted block.
+		let mut retracted = [first, second];
+		retracted.sort();
+		assert_eq!(retracted, [None, Some(block_a)]);
+		assert_eq!(provider.block_hash(1).await, Some(block_b));
+	}
+
 	#[sqlx::test]
 	async fn test_receipts_count_per_block(pool: SqlitePool) {
 		let provider = setup_sqlite_provider(pool).await;
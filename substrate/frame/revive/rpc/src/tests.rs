@@ -21,13 +21,19 @@
 use crate::{
 	cli::{self, CliCommand},
 	example::TransactionBuilder,
-	EthRpcClient,
+	EthPubSubRpcClient, EthRpcClient,
 };
 use clap::Parser;
-use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
+use jsonrpsee::{
+	core::client::Subscription,
+	ws_client::{WsClient, WsClientBuilder},
+};
 use pallet_revive::{
 	create1,
-	evm::{Account, BlockTag, U256},
+	evm::{
+		Account, BlockNumberOrTagOrHash, BlockTag, Bytes, Filter, FilterTopic, Log,
+		SubscriptionItem, SubscriptionKind, H256, U256,
+	},
 };
 use static_init::dynamic;
 use std::{sync::Arc, thread};
@@ -219,3 +225,145 @@ async fn invalid_transaction() -> anyhow::Result<()> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn subscriptions() -> anyhow::Result<()> {
+	let _lock = SHARED_RESOURCES.write();
+	let client = Arc::new(SharedResources::client().await);
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+
+	let mut heads = client.subscribe(SubscriptionKind::NewHeads, None).await?;
+	let mut pending_txs = client.subscribe(SubscriptionKind::NewPendingTransactions, None).await?;
+
+	let tx = TransactionBuilder::new(&client)
+		.value(U256::from(1_000_000_000_000u128))
+		.to(ethan.address())
+		.send()
+		.await?;
+
+	let item = pending_txs.next().await.expect("subscription should not be closed")?;
+	assert_eq!(item, SubscriptionItem::TransactionHash(tx.hash()));
+
+	let receipt = tx.wait_for_receipt().await?;
+	loop {
+		let item = heads.next().await.expect("subscription should not be closed")?;
+		let SubscriptionItem::Header(block) = item else { panic!("Expected a block header") };
+		if block.hash == receipt.block_hash {
+			break;
+		}
+	}
+
+	heads.unsubscribe().await?;
+	pending_txs.unsubscribe().await?;
+	Ok(())
+}
+
+/// Wait for the next log of a `logs` subscription.
+async fn next_log(subscription: &mut Subscription<SubscriptionItem>) -> anyhow::Result<Log> {
+	let item = subscription.next().await.expect("subscription should not be closed")?;
+	let SubscriptionItem::Log(log) = item else { panic!("Expected a log") };
+	Ok(log)
+}
+
+#[tokio::test]
+async fn logs_subscription_filters() -> anyhow::Result<()> {
+	let _lock = SHARED_RESOURCES.write();
+	let client = Arc::new(SharedResources::client().await);
+	let account = Account::default();
+
+	// The fixture deposits an event with a single `[42; 32]` topic when deployed.
+	let (bytes, _) = pallet_revive_fixtures::compile_module("event_and_return_on_deploy")?;
+	let topic = H256([42u8; 32]);
+	let nonce: u64 = client
+		.get_transaction_count(account.address(), BlockTag::Latest.into())
+		.await?
+		.try_into()
+		.unwrap();
+	let first = create1(&account.address(), nonce);
+	let second = create1(&account.address(), nonce + 1);
+
+	let mut by_address = client
+		.subscribe(
+			SubscriptionKind::Logs,
+			Some(Filter { address: Some(second.into()), ..Default::default() }),
+		)
+		.await?;
+	let mut by_topic = client
+		.subscribe(
+			SubscriptionKind::Logs,
+			Some(Filter {
+				topics: Some(vec![FilterTopic::Multiple(vec![H256::zero(), topic])]),
+				..Default::default()
+			}),
+		)
+		.await?;
+	let mut by_other_topic = client
+		.subscribe(
+			SubscriptionKind::Logs,
+			Some(Filter {
+				topics: Some(vec![FilterTopic::Single(H256::zero())]),
+				..Default::default()
+			}),
+		)
+		.await?;
+
+	let mut receipts = Vec::new();
+	for _ in 0..2 {
+		let tx = TransactionBuilder::new(&client).input(bytes.clone()).send().await?;
+		receipts.push(tx.wait_for_receipt().await?);
+	}
+
+	// Both deployments match the topic filter.
+	for (receipt, address) in receipts.iter().zip([first, second]) {
+		let log = next_log(&mut by_topic).await?;
+		assert_eq!(log.address, address);
+		assert_eq!(log.topics, vec![topic]);
+		assert_eq!(log.data, Some(Bytes(vec![1, 2, 3, 4])));
+		assert_eq!(log.transaction_hash, receipt.transaction_hash);
+	}
+
+	// The log of the first deployment is filtered out by its address.
+	let log = next_log(&mut by_address).await?;
+	assert_eq!(log.address, second);
+	assert_eq!(log.transaction_hash, receipts[1].transaction_hash);
+
+	// No log matches the other topic.
+	let timeout = tokio::time::Duration::from_secs(5);
+	assert!(tokio::time::timeout(timeout, by_other_topic.next()).await.is_err());
+
+	by_address.unsubscribe().await?;
+	by_topic.unsubscribe().await?;
+	by_other_topic.unsubscribe().await?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn get_proof() -> anyhow::Result<()> {
+	let _lock = SHARED_RESOURCES.write();
+	let client = Arc::new(SharedResources::client().await);
+	let account = Account::default();
+
+	let (bytes, _) = pallet_revive_fixtures::compile_module("dummy")?;
+	let nonce = client.get_transaction_count(account.address(), BlockTag::Latest.into()).await?;
+	let tx = TransactionBuilder::new(&client).input(bytes).send().await?;
+	let receipt = tx.wait_for_receipt().await?;
+	let contract_address = create1(&account.address(), nonce.try_into().unwrap());
+
+	let block = client
+		.get_block_by_hash(receipt.block_hash, false)
+		.await?
+		.expect("block should exist");
+	let proof = client
+		.get_proof(
+			contract_address,
+			vec![H256::zero()],
+			BlockNumberOrTagOrHash::H256(receipt.block_hash),
+		)
+		.await?;
+	assert!(!proof.code_hash.is_zero());
+	assert!(proof.storage_proof[0].value.0.is_empty());
+
+	let verified = pallet_revive_proof_verifier::verify_proof(block.state_root, &proof)?;
+	assert_eq!(verified.contract_info.map(|info| info.code_hash), Some(proof.code_hash));
+	Ok(())
+}
@@ -24,19 +24,26 @@ mod code;
 use self::{call_builder::CallSetup, code::WasmModule};
 use crate::{
 	evm::runtime::GAS_PRICE,
-	exec::{Ext, Key, MomentOf},
+	exec::{ExecResult, Ext, Key, MomentOf, Origin},
+	gas::GasMeter,
 	limits,
-	pure_precompiles::Precompile,
+	pure_precompiles::{
+		IXcm, NativeErc20, Precompile, PrecompileContext, PrecompileSet, XcmPrecompile, IERC20,
+	},
 	storage::WriteOutcome,
 	ConversionPrecision, Pallet as Contracts, *,
 };
 use alloc::{vec, vec::Vec};
+use alloy_core::{
+	primitives::{Address, U256 as SolU256},
+	sol_types::SolCall,
+};
 use codec::{Encode, MaxEncodedLen};
 use frame_benchmarking::v2::*;
 use frame_support::{
-	self, assert_ok,
+	self, assert_ok, parameter_types,
 	storage::child,
-	traits::fungible::InspectHold,
+	traits::{fungible::InspectHold, ConstU8},
 	weights::{Weight, WeightMeter},
 };
 use frame_system::RawOrigin;
@@ -51,6 +58,10 @@ use sp_runtime::{
 	generic::{Digest, DigestItem},
 	traits::{Bounded, Hash},
 };
+use xcm::{
+	latest::{AssetId, Instruction, Location, Xcm, MAX_INSTRUCTIONS_TO_DECODE},
+	VersionedAssetId, VersionedLocation, VersionedXcm,
+};
 
 /// How many runs we do per API benchmark.
 ///
@@ -225,6 +236,76 @@ fn default_deposit_limit<T: Config>() -> BalanceOf<T> {
 		T::DepositPerItem::get() * 1024u32.into()
 }
 
+parameter_types! {
+	/// The address of the ERC-20 precompile the benchmarks are run against.
+	pub Erc20BenchmarkAddress: H160 = H160::from_low_u64_be(0x1001);
+}
+
+/// The ERC-20 precompile of the native currency.
+///
+/// The weights of all ERC-20 precompiles are measured against this one.
+type BenchmarkErc20 = NativeErc20<Erc20BenchmarkAddress, ConstU8<18>>;
+
+/// Create a mapped account funded with native currency and return it with its address.
+fn erc20_account<T: Config>(name: &'static str) -> (T::AccountId, Address) {
+	let account_id: T::AccountId = account(name, 0, 0);
+	T::Currency::set_balance(&account_id, caller_funding::<T>());
+	Contracts::<T>::map_account(RawOrigin::Signed(account_id.clone()).into()).unwrap();
+	let address = Address::from(T::AddressMapper::to_address(&account_id).0);
+	(account_id, address)
+}
+
+/// Execute `input` with [`BenchmarkErc20`] on behalf of `caller`.
+fn erc20_call<T>(gas_meter: &mut GasMeter<T>, caller: &T::AccountId, input: &[u8]) -> ExecResult
+where
+	T: Config,
+	BalanceOf<T>: Into<U256> + TryFrom<U256>,
+{
+	let mut context = PrecompileContext {
+		address: Erc20BenchmarkAddress::get(),
+		caller: Origin::from_account_id(caller.clone()),
+		is_read_only: false,
+		is_delegate: false,
+		gas_meter,
+	};
+	<BenchmarkErc20 as PrecompileSet<T>>::execute(&mut context, input)
+		.expect("the precompile is called at its own address; qed")
+}
+
+parameter_types! {
+	/// The address of the XCM precompile the benchmarks are run against.
+	pub XcmBenchmarkAddress: H160 = H160::from_low_u64_be(0x1002);
+}
+
+/// A SCALE encoded `VersionedXcm` of `n` instructions.
+fn xcm_message(n: u32) -> Vec<u8> {
+	VersionedXcm::<()>::from(Xcm::<()>(vec![Instruction::ClearOrigin; n as usize])).encode()
+}
+
+/// Execute the read-only `input` with the [`XcmPrecompile`].
+///
+/// Whether the queries succeed depends on [`Config::XcmPayment`].
+fn xcm_query<T: Config>(gas_meter: &mut GasMeter<T>, input: &[u8]) -> ExecResult {
+	let caller: T::AccountId = account("caller", 0, 0);
+	let mut context = PrecompileContext {
+		address: XcmBenchmarkAddress::get(),
+		caller: Origin::from_account_id(caller),
+		is_read_only: true,
+		is_delegate: false,
+		gas_meter,
+	};
+	<XcmPrecompile<XcmBenchmarkAddress> as PrecompileSet<T>>::execute(&mut context, input)
+		.expect("the precompile is called at its own address; qed")
+}
+
+/// The smallest amount of native currency that can be transferred to a new account.
+fn erc20_value<T: Config>() -> SolU256
+where
+	BalanceOf<T>: Into<U256>,
+{
+	SolU256::from_be_bytes(Pallet::<T>::min_balance().into().to_big_endian())
+}
+
 #[benchmarks(
 	where
 		BalanceOf<T>: Into<U256> + TryFrom<U256>,
@@ -1963,6 +2044,335 @@ mod benchmarks {
 		assert_eq!(result.unwrap().data, expected);
 	}
 
+	// `n`: Input to hash in bytes
+	#[benchmark(pov_mode = Measured)]
+	fn ripemd_160(n: Linear<0, { limits::code::BLOB_BYTES }>) {
+		let input = vec![0u8; n as usize];
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = pure_precompiles::Ripemd160::execute(ext.gas_meter_mut(), &input);
+		}
+
+		assert_eq!(result.unwrap().data.len(), 32);
+	}
+
+	// `n`: Input to copy in bytes
+	#[benchmark(pov_mode = Measured)]
+	fn identity(n: Linear<0, { limits::code::BLOB_BYTES }>) {
+		let input = vec![0u8; n as usize];
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = pure_precompiles::Identity::execute(ext.gas_meter_mut(), &input);
+		}
+
+		assert_eq!(result.unwrap().data, input);
+	}
+
+	// `n`: The EIP-2565 gas cost of the operation.
+	//
+	// The gas cost grows with the square of the modulus length while the work grows slower, so
+	// the cheapest operations per unit of gas use the shortest modulus that reaches `n` with the
+	// longest exponent. We use that shape for every `n`, from a single word modulus up to the
+	// maximum cost allowed by the EIP-7823 length limit of 1024 bytes.
+	#[benchmark(pov_mode = Measured)]
+	fn modexp(n: Linear<200, 44_733_781>) {
+		// The iteration count of a 1024 byte exponent with all bits set.
+		const MAX_ITERATION_COUNT: u64 = 8 * (1024 - 32) + 255;
+		let gas = u64::from(n);
+		let mut words = 1u64;
+		while words * words * MAX_ITERATION_COUNT < gas * 3 {
+			words += 1;
+		}
+		let iteration_count = (gas * 3).div_ceil(words * words);
+		let exponent = if iteration_count <= 255 {
+			// All bits up to and including bit `iteration_count` are set.
+			(U256::MAX >> (255 - iteration_count)).to_big_endian().to_vec()
+		} else {
+			vec![0xffu8; 32 + (iteration_count as usize - 255).div_ceil(8)]
+		};
+		let len = words as usize * 8;
+
+		let mut input = Vec::new();
+		input.extend_from_slice(&U256::from(len).to_big_endian());
+		input.extend_from_slice(&U256::from(exponent.len()).to_big_endian());
+		input.extend_from_slice(&U256::from(len).to_big_endian());
+		input.extend_from_slice(&vec![0xffu8; len]);
+		input.extend_from_slice(&exponent);
+		input.extend_from_slice(&vec![0xfeu8; len]);
+
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = pure_precompiles::Modexp::execute(ext.gas_meter_mut(), &input);
+		}
+
+		assert_eq!(result.unwrap().data.len(), len);
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn bn128_add() {
+		use hex_literal::hex;
+		let input = hex!(
+			"0000000000000000000000000000000000000000000000000000000000000001"
+			"0000000000000000000000000000000000000000000000000000000000000002"
+			"0000000000000000000000000000000000000000000000000000000000000001"
+			"0000000000000000000000000000000000000000000000000000000000000002"
+		);
+		let expected = hex!(
+			"1b386c209eabea1777ad2736a8d8c1b4a538cbf1cac001ef71d0923a4ed31b35"
+			"1bf9fd5a6a30b0981e8e684d82dec7161b96d19c1061c919a6c1081142cabf86"
+		);
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = pure_precompiles::Bn128Add::execute(ext.gas_meter_mut(), &input);
+		}
+
+		assert_eq!(result.unwrap().data, expected);
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn bn128_mul() {
+		use hex_literal::hex;
+		let input = hex!(
+			"0000000000000000000000000000000000000000000000000000000000000001"
+			"0000000000000000000000000000000000000000000000000000000000000002"
+			"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+		);
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = pure_precompiles::Bn128Mul::execute(ext.gas_meter_mut(), &input);
+		}
+
+		assert_eq!(result.unwrap().data.len(), 64);
+	}
+
+	// `n`: Number of (G1, G2) pairs to check.
+	#[benchmark(pov_mode = Measured)]
+	fn bn128_pairing(n: Linear<0, 20>) {
+		use bn::{AffineG1, AffineG2, Group, G1, G2};
+		let g1 = AffineG1::from_jacobian(G1::one()).unwrap();
+		let g2 = AffineG2::from_jacobian(G2::one()).unwrap();
+		let mut pair = [0u8; 192];
+		for (i, fq) in
+			[g1.x(), g1.y(), g2.x().imaginary(), g2.x().real(), g2.y().imaginary(), g2.y().real()]
+				.into_iter()
+				.enumerate()
+		{
+			fq.to_big_endian(&mut pair[i * 32..(i + 1) * 32]).unwrap();
+		}
+		let input = pair.repeat(n as usize);
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = pure_precompiles::Bn128Pairing::execute(ext.gas_meter_mut(), &input);
+		}
+
+		assert_eq!(result.unwrap().data.len(), 32);
+	}
+
+	// `n`: Number of rounds
+	//
+	// Every round does the same work, so the linear weight is also used for more rounds than
+	// benchmarked. EIP-152 allows any `u32` and the gas meter bounds the rounds actually executed.
+	#[benchmark(pov_mode = Measured)]
+	fn blake2f(n: Linear<0, 65_536>) {
+		let mut input = vec![0u8; 213];
+		input[0..4].copy_from_slice(&n.to_be_bytes());
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = pure_precompiles::Blake2F::execute(ext.gas_meter_mut(), &input);
+		}
+
+		assert_eq!(result.unwrap().data.len(), 64);
+	}
+
+	// Reading a balance stands in for all getters of the ERC-20 precompiles.
+	#[benchmark(pov_mode = Measured)]
+	fn erc20_balance_of() {
+		let (caller, address) = erc20_account::<T>("caller");
+		let input = IERC20::balanceOfCall { account: address }.abi_encode();
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = erc20_call::<T>(ext.gas_meter_mut(), &caller, &input);
+		}
+
+		assert!(!result.unwrap().did_revert());
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn erc20_allowance() {
+		let (caller, owner) = erc20_account::<T>("caller");
+		let spender = Address::from([0x42; 20]);
+		Erc20Allowances::<T>::insert(
+			(Erc20BenchmarkAddress::get(), H160(owner.into_array()), H160(spender.into_array())),
+			U256::one(),
+		);
+		let input = IERC20::allowanceCall { owner, spender }.abi_encode();
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = erc20_call::<T>(ext.gas_meter_mut(), &caller, &input);
+		}
+
+		assert_eq!(
+			result.unwrap().data,
+			IERC20::allowanceCall::abi_encode_returns(&(SolU256::from(1),))
+		);
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn erc20_approve() {
+		let (caller, owner) = erc20_account::<T>("caller");
+		let spender = Address::from([0x42; 20]);
+		let input = IERC20::approveCall { spender, value: SolU256::from(1) }.abi_encode();
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = erc20_call::<T>(ext.gas_meter_mut(), &caller, &input);
+		}
+
+		assert!(!result.unwrap().did_revert());
+		assert_eq!(
+			Erc20Allowances::<T>::get((
+				Erc20BenchmarkAddress::get(),
+				H160(owner.into_array()),
+				H160(spender.into_array())
+			)),
+			U256::one(),
+		);
+	}
+
+	// The recipient doesn't exist yet.
+	#[benchmark(pov_mode = Measured)]
+	fn erc20_transfer() {
+		let (caller, _) = erc20_account::<T>("caller");
+		let to = Address::from([0x42; 20]);
+		let input = IERC20::transferCall { to, value: erc20_value::<T>() }.abi_encode();
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = erc20_call::<T>(ext.gas_meter_mut(), &caller, &input);
+		}
+
+		assert_eq!(result.unwrap().data, IERC20::transferCall::abi_encode_returns(&(true,)));
+	}
+
+	// The recipient doesn't exist yet.
+	#[benchmark(pov_mode = Measured)]
+	fn erc20_transfer_from() {
+		let (caller, spender) = erc20_account::<T>("caller");
+		let (_, from) = erc20_account::<T>("owner");
+		let to = Address::from([0x42; 20]);
+		let value = erc20_value::<T>();
+		Erc20Allowances::<T>::insert(
+			(Erc20BenchmarkAddress::get(), H160(from.into_array()), H160(spender.into_array())),
+			U256::from_big_endian(&value.to_be_bytes::<32>()),
+		);
+		let input = IERC20::transferFromCall { from, to, value }.abi_encode();
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = erc20_call::<T>(ext.gas_meter_mut(), &caller, &input);
+		}
+
+		assert_eq!(result.unwrap().data, IERC20::transferFromCall::abi_encode_returns(&(true,)));
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn xcm_weigh_message(n: Linear<0, { MAX_INSTRUCTIONS_TO_DECODE as u32 }>) {
+		let input = IXcm::weighMessageCall { message: xcm_message(n).into() }.abi_encode();
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = xcm_query::<T>(ext.gas_meter_mut(), &input);
+		}
+
+		assert!(result.is_ok());
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn xcm_weight_to_asset_fee() {
+		let input = IXcm::weightToAssetFeeCall {
+			weight: IXcm::Weight { refTime: 1_000_000_000, proofSize: 64 * 1024 },
+			assetId: VersionedAssetId::from(AssetId(Location::parent())).encode().into(),
+		}
+		.abi_encode();
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = xcm_query::<T>(ext.gas_meter_mut(), &input);
+		}
+
+		assert!(result.is_ok());
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn xcm_delivery_fees(n: Linear<0, { MAX_INSTRUCTIONS_TO_DECODE as u32 }>) {
+		let input = IXcm::deliveryFeesCall {
+			destination: VersionedLocation::from(Location::parent()).encode().into(),
+			message: xcm_message(n).into(),
+		}
+		.abi_encode();
+		let mut call_setup = CallSetup::<T>::default();
+		let (mut ext, _) = call_setup.ext();
+
+		let result;
+		#[block]
+		{
+			result = xcm_query::<T>(ext.gas_meter_mut(), &input);
+		}
+
+		assert!(result.is_ok());
+	}
+
 	// Only calling the function itself for the list of
 	// generated different ECDSA keys.
 	// This is a slow call: We reduce the number of runs.
@@ -30,6 +30,21 @@ pub use type_id::*;
 mod debug_rpc_types;
 pub use debug_rpc_types::*;
 
+mod proof_rpc_types;
+pub use proof_rpc_types::*;
+
+mod pubsub_rpc_types;
+pub use pubsub_rpc_types::*;
+
+mod state_override_rpc_types;
+pub use state_override_rpc_types::*;
+
+mod access_list_rpc_types;
+pub use access_list_rpc_types::*;
+
+mod fee_history_rpc_types;
+pub use fee_history_rpc_types::*;
+
 mod rpc_types;
 mod rpc_types_gen;
 pub use rpc_types_gen::*;
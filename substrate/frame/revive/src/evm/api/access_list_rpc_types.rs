@@ -0,0 +1,41 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types used by `eth_createAccessList`.
+//!
+//! Not part of `rpc_types_gen`, which only contains the types generated from the Ethereum
+//! JSON-RPC specification.
+
+use crate::evm::{AccessList, AccessListEntry};
+use codec::{Decode, DecodeWithMemTracking, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_core::U256;
+
+/// The result of `eth_createAccessList`.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+	/// The accounts and storage slots accessed by the transaction.
+	pub access_list: AccessList,
+	/// The gas used by the transaction, when sent with the access list.
+	pub gas_used: U256,
+}
+
+// Access lists are passed to the dispatchables an Ethereum transaction is converted into.
+impl DecodeWithMemTracking for AccessListEntry {}
@@ -15,8 +15,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::evm::{Bytes, CallTracer};
-use alloc::{fmt, string::String, vec::Vec};
+use crate::evm::Bytes;
+use alloc::{collections::BTreeMap, fmt, string::String, vec::Vec};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
 use serde::{
@@ -36,15 +36,34 @@ pub enum TracerConfig {
 		#[serde(rename = "withLog")]
 		with_logs: bool,
 	},
-}
 
-impl TracerConfig {
-	/// Build the tracer associated to this config.
-	pub fn build<G>(self, gas_mapper: G) -> CallTracer<U256, G> {
-		match self {
-			Self::CallTracer { with_logs } => CallTracer::new(with_logs, gas_mapper),
-		}
-	}
+	/// A tracer that captures the state of the accounts touched by a transaction.
+	#[serde(rename = "prestateTracer")]
+	PrestateTracer {
+		/// Whether to return the state before and after the execution instead of the prestate.
+		#[serde(rename = "diffMode")]
+		diff_mode: bool,
+		/// Whether to omit the code of the accounts.
+		#[serde(rename = "disableCode")]
+		disable_code: bool,
+		/// Whether to omit the storage of the accounts.
+		#[serde(rename = "disableStorage")]
+		disable_storage: bool,
+	},
+
+	/// A tracer logging every executed host function of a transaction.
+	#[serde(rename = "structLogger")]
+	StructLogger {
+		/// Whether to omit the arguments of the host functions.
+		#[serde(rename = "disableStack")]
+		disable_stack: bool,
+		/// Whether to omit the storage accessed by the host functions.
+		#[serde(rename = "disableStorage")]
+		disable_storage: bool,
+		/// Whether to return the output of the transaction.
+		#[serde(rename = "enableReturnData")]
+		enable_return_data: bool,
+	},
 }
 
 /// Custom deserializer to support the following JSON format:
@@ -56,18 +75,42 @@ impl TracerConfig {
 /// ```json
 /// { "tracer": "callTracer" }
 /// ```
+///
+/// ```json
+/// { "tracer": "prestateTracer", "tracerConfig": { "diffMode": true } }
+/// ```
+///
+/// The options of the struct logger are read from the top level object:
+///
+/// ```json
+/// { "tracer": "structLogger", "disableStack": true, "enableReturnData": true }
+/// ```
+///
+/// When no tracer is specified the call tracer is used. Memory capture is not supported, hence
+/// `enableMemory` is rejected.
 impl<'de> Deserialize<'de> for TracerConfig {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where
 		D: Deserializer<'de>,
 	{
+		#[derive(Deserialize, Default)]
+		#[serde(rename_all = "camelCase")]
+		struct Options {
+			with_logs: Option<bool>,
+			diff_mode: Option<bool>,
+			disable_code: Option<bool>,
+			disable_storage: Option<bool>,
+			disable_stack: Option<bool>,
+			enable_return_data: Option<bool>,
+		}
+
 		struct TracerConfigVisitor;
 
 		impl<'de> Visitor<'de> for TracerConfigVisitor {
 			type Value = TracerConfig;
 
 			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-				formatter.write_str("a map with tracer and optional tracerConfig")
+				formatter.write_str("a map with an optional tracer and tracerConfig")
 			}
 
 			fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
@@ -75,7 +118,7 @@ impl<'de> Deserialize<'de> for TracerConfig {
 				M: MapAccess<'de>,
 			{
 				let mut tracer_type: Option<String> = None;
-				let mut with_logs = None;
+				let mut options = Options::default();
 
 				while let Some(key) = map.next_key::<String>()? {
 					match key.as_str() {
@@ -83,22 +126,51 @@ impl<'de> Deserialize<'de> for TracerConfig {
 							tracer_type = map.next_value()?;
 						},
 						"tracerConfig" => {
-							#[derive(Deserialize)]
-							struct CallTracerConfig {
-								#[serde(rename = "withLogs")]
-								with_logs: Option<bool>,
-							}
-							let inner: CallTracerConfig = map.next_value()?;
-							with_logs = inner.with_logs;
+							let inner: Option<Options> = map.next_value()?;
+							let inner = inner.unwrap_or_default();
+							options.with_logs = inner.with_logs.or(options.with_logs);
+							options.diff_mode = inner.diff_mode.or(options.diff_mode);
+							options.disable_code = inner.disable_code.or(options.disable_code);
+							options.disable_storage =
+								inner.disable_storage.or(options.disable_storage);
+							options.disable_stack = inner.disable_stack.or(options.disable_stack);
+							options.enable_return_data =
+								inner.enable_return_data.or(options.enable_return_data);
+						},
+						"disableStorage" => {
+							options.disable_storage = map.next_value()?;
+						},
+						"disableStack" => {
+							options.disable_stack = map.next_value()?;
+						},
+						"enableReturnData" => {
+							options.enable_return_data = map.next_value()?;
+						},
+						"enableMemory" =>
+							if map.next_value::<Option<bool>>()?.unwrap_or_default() {
+								return Err(de::Error::custom("enableMemory is not supported"));
+							},
+						_ => {
+							let _ = map.next_value::<de::IgnoredAny>()?;
 						},
-						_ => {},
 					}
 				}
 
 				match tracer_type.as_deref() {
-					Some("callTracer") =>
-						Ok(TracerConfig::CallTracer { with_logs: with_logs.unwrap_or(true) }),
-					_ => Err(de::Error::custom("Unsupported or missing tracer type")),
+					None | Some("callTracer") => Ok(TracerConfig::CallTracer {
+						with_logs: options.with_logs.unwrap_or(true),
+					}),
+					Some("prestateTracer") => Ok(TracerConfig::PrestateTracer {
+						diff_mode: options.diff_mode.unwrap_or_default(),
+						disable_code: options.disable_code.unwrap_or_default(),
+						disable_storage: options.disable_storage.unwrap_or_default(),
+					}),
+					Some("structLogger") => Ok(TracerConfig::StructLogger {
+						disable_stack: options.disable_stack.unwrap_or_default(),
+						disable_storage: options.disable_storage.unwrap_or_default(),
+						enable_return_data: options.enable_return_data.unwrap_or_default(),
+					}),
+					_ => Err(de::Error::custom("Unsupported tracer type")),
 				}
 			}
 		}
@@ -119,6 +191,39 @@ fn test_tracer_config_serialization() {
 			r#"{"tracer": "callTracer", "tracerConfig": { "withLogs": false }}"#,
 			TracerConfig::CallTracer { with_logs: false },
 		),
+		(
+			r#"{"tracer": "prestateTracer"}"#,
+			TracerConfig::PrestateTracer {
+				diff_mode: false,
+				disable_code: false,
+				disable_storage: false,
+			},
+		),
+		(
+			r#"{"tracer": "prestateTracer", "tracerConfig": { "diffMode": true, "disableCode": true }}"#,
+			TracerConfig::PrestateTracer {
+				diff_mode: true,
+				disable_code: true,
+				disable_storage: false,
+			},
+		),
+		(r#"{}"#, TracerConfig::CallTracer { with_logs: true }),
+		(
+			r#"{"tracer": "structLogger"}"#,
+			TracerConfig::StructLogger {
+				disable_stack: false,
+				disable_storage: false,
+				enable_return_data: false,
+			},
+		),
+		(
+			r#"{"tracer": "structLogger", "disableStack": true, "enableReturnData": true}"#,
+			TracerConfig::StructLogger {
+				disable_stack: true,
+				disable_storage: false,
+				enable_return_data: true,
+			},
+		),
 	];
 
 	for (json_data, expected) in tracers {
@@ -126,6 +231,13 @@ fn test_tracer_config_serialization() {
 			serde_json::from_str(json_data).expect("Deserialization should succeed");
 		assert_eq!(result, expected);
 	}
+
+	let result: TracerConfig =
+		serde_json::from_str(r#"{"tracer": "structLogger", "enableMemory": false}"#).unwrap();
+	assert!(matches!(result, TracerConfig::StructLogger { .. }));
+	let result =
+		serde_json::from_str::<TracerConfig>(r#"{"tracer": "structLogger", "enableMemory": true}"#);
+	assert!(result.is_err());
 }
 
 impl Default for TracerConfig {
@@ -134,6 +246,18 @@ impl Default for TracerConfig {
 	}
 }
 
+/// The trace of a transaction, as returned by the tracer selected in [`TracerConfig`].
+#[derive(TypeInfo, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum Trace {
+	/// A call trace.
+	Call(CallTrace),
+	/// A prestate trace.
+	Prestate(PrestateTrace),
+	/// A struct logger trace.
+	StructLog(StructLogTrace),
+}
+
 /// The type of call that was executed.
 #[derive(
 	Default, TypeInfo, Encode, Decode, Serialize, Deserialize, Eq, PartialEq, Clone, Debug,
@@ -206,6 +330,91 @@ pub struct CallLog {
 	pub position: u32,
 }
 
+/// The result of the prestate tracer.
+#[derive(TypeInfo, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum PrestateTrace {
+	/// The state of the accounts touched by the transaction, before its execution.
+	Prestate(BTreeMap<H160, PrestateTraceInfo>),
+
+	/// The state that was modified by the transaction, before and after its execution.
+	///
+	/// Only the accounts and fields that were changed are included.
+	DiffMode {
+		/// The state before the execution.
+		pre: BTreeMap<H160, PrestateTraceInfo>,
+		/// The state after the execution.
+		post: BTreeMap<H160, PrestateTraceInfo>,
+	},
+}
+
+/// The state of an account captured by the prestate tracer.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+pub struct PrestateTraceInfo {
+	/// The balance of the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub balance: Option<U256>,
+	/// The nonce of the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub nonce: Option<u64>,
+	/// The code of the contract.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub code: Option<Bytes>,
+	/// The storage slots accessed during the execution, `None` if a slot is empty.
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	pub storage: BTreeMap<H256, Option<Bytes>>,
+}
+
+/// The result of the struct logger.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+pub struct StructLogTrace<Gas = U256> {
+	/// Amount of gas used by the transaction.
+	pub gas: Gas,
+	/// Whether the transaction failed.
+	pub failed: bool,
+	/// The output of the transaction, if `enableReturnData` is set.
+	#[serde(rename = "returnValue")]
+	pub return_value: Bytes,
+	/// The logged steps of the execution.
+	#[serde(rename = "structLogs")]
+	pub struct_logs: Vec<StructLog<Gas>>,
+}
+
+/// A single step logged by the struct logger.
+///
+/// Contracts are not interpreted as EVM bytecode, hence a step is a host function call
+/// rather than an opcode. The argument registers of the host function are reported as its
+/// stack. Memory is never captured as it has no EVM word layout.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+pub struct StructLog<Gas = U256> {
+	/// The program counter of the host function call.
+	pub pc: u32,
+	/// The name of the host function.
+	pub op: String,
+	/// Amount of gas left before the host function call.
+	pub gas: Gas,
+	/// Amount of gas consumed by the host function.
+	#[serde(rename = "gasCost")]
+	pub gas_cost: Gas,
+	/// The call depth, starting at 1.
+	pub depth: u32,
+	/// The arguments of the host function.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub stack: Option<Vec<U256>>,
+	/// The storage slots of the current contract read or written by the host function.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub storage: Option<BTreeMap<H256, Option<Bytes>>>,
+	/// The error message if the host function failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
 /// A transaction trace
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TransactionTrace {
@@ -214,5 +423,5 @@ pub struct TransactionTrace {
 	pub tx_hash: H256,
 	/// The trace of the transaction.
 	#[serde(rename = "result")]
-	pub trace: CallTrace,
+	pub trace: Trace,
 }
@@ -0,0 +1,44 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types used by `eth_feeHistory`.
+//!
+//! Not part of `rpc_types_gen`, which only contains the types generated from the Ethereum
+//! JSON-RPC specification.
+
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+use sp_core::U256;
+
+/// The result of `eth_feeHistory`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistoryResult {
+	/// Lowest number block of returned range.
+	pub oldest_block: U256,
+	/// An array of block base fees per gas. This includes the next block after the newest of the
+	/// returned range, because this value can be derived from the newest block. Zeroes are
+	/// returned for pre-EIP-1559 blocks.
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub base_fee_per_gas: Vec<U256>,
+	/// An array of block gas used ratios. These are calculated as the ratio of gasUsed and
+	/// gasLimit.
+	pub gas_used_ratio: Vec<f64>,
+	/// A two-dimensional array of effective priority fees per gas at the requested block
+	/// percentiles.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub reward: Option<Vec<Vec<U256>>>,
+}
@@ -0,0 +1,60 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types used by the `eth_getProof` method.
+
+use crate::evm::Bytes;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+use sp_core::{H160, H256, U256};
+
+/// The proof of an account and of some of its storage slots, see [EIP-1186].
+///
+/// Unlike Ethereum, the proofs are Substrate trie proofs:
+/// - `account_proof` proves the entries of the account in the state trie, that is the contract
+///   info, the address suffix, the system account and the root of the contract child trie.
+/// - each storage proof proves a slot in the child trie of the contract.
+///
+/// [EIP-1186]: https://eips.ethereum.org/EIPS/eip-1186
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProof {
+	/// The address of the account.
+	pub address: H160,
+	/// The trie nodes proving the entries of the account in the state trie.
+	pub account_proof: Vec<Bytes>,
+	/// The balance of the account.
+	pub balance: U256,
+	/// The hash of the code of the account, zero if the account is not a contract.
+	pub code_hash: H256,
+	/// The nonce of the account.
+	pub nonce: U256,
+	/// The root of the child trie of the contract, zero if the account is not a contract.
+	pub storage_hash: H256,
+	/// The proofs of the requested storage slots.
+	pub storage_proof: Vec<StorageProof>,
+}
+
+/// The proof of a storage slot of a contract.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct StorageProof {
+	/// The key of the storage slot.
+	pub key: H256,
+	/// The value of the storage slot, empty if the slot is not set.
+	pub value: Bytes,
+	/// The trie nodes proving the value in the child trie of the contract.
+	pub proof: Vec<Bytes>,
+}
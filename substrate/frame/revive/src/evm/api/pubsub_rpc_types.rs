@@ -0,0 +1,60 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types used by the `eth_subscribe` and `eth_unsubscribe` methods.
+
+use crate::evm::{Block, Log};
+use serde::{Deserialize, Serialize};
+use sp_core::H256;
+
+/// The kind of subscription created with `eth_subscribe`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SubscriptionKind {
+	/// New block headers, sent each time a new best block is imported.
+	NewHeads,
+	/// Logs of the new best blocks, matching the given filter.
+	Logs,
+	/// Hashes of the transactions submitted to the pool.
+	NewPendingTransactions,
+}
+
+/// An item sent to the subscribers of `eth_subscribe`.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum SubscriptionItem {
+	/// A transaction hash, for [`SubscriptionKind::NewPendingTransactions`].
+	TransactionHash(H256),
+	/// A new block, for [`SubscriptionKind::NewHeads`].
+	Header(Block),
+	/// A log, for [`SubscriptionKind::Logs`].
+	Log(Log),
+}
+
+#[test]
+fn subscription_kind_serialization_works() {
+	let kinds = [
+		("\"newHeads\"", SubscriptionKind::NewHeads),
+		("\"logs\"", SubscriptionKind::Logs),
+		("\"newPendingTransactions\"", SubscriptionKind::NewPendingTransactions),
+	];
+
+	for (json, expected) in kinds {
+		let kind: SubscriptionKind = serde_json::from_str(json).unwrap();
+		assert_eq!(kind, expected);
+		assert_eq!(serde_json::to_string(&kind).unwrap(), json);
+	}
+}
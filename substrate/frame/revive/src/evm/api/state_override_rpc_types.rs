@@ -0,0 +1,102 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types used by the `stateOverride` and `blockOverrides` parameters of `eth_call` and
+//! `eth_estimateGas`.
+
+use crate::evm::Bytes;
+use alloc::collections::BTreeMap;
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_core::{H160, H256, U256};
+
+/// The accounts to override before executing a call, by address.
+pub type StateOverride = BTreeMap<H160, AccountOverride>;
+
+/// The fields of an account to override before executing a call.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverride {
+	/// Replace the balance of the account.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub balance: Option<U256>,
+	/// Replace the nonce of the account.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub nonce: Option<U256>,
+	/// Replace the code of the account.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub code: Option<Bytes>,
+	/// Replace the whole storage of the account with the given slots.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub state: Option<BTreeMap<H256, H256>>,
+	/// Replace the given storage slots, leaving the other slots untouched.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub state_diff: Option<BTreeMap<H256, H256>>,
+}
+
+/// The fields of the block to override before executing a call.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockOverrides {
+	/// Replace the block number.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub number: Option<U256>,
+	/// Replace the block timestamp.
+	///
+	/// The timestamp is set by the runtime, as it is not owned by this pallet.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub time: Option<U256>,
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn deserialize_state_override_works() {
+		let overrides: StateOverride = serde_json::from_str(
+			r#"{
+				"0x0000000000000000000000000000000000000001": {
+					"balance": "0x10",
+					"stateDiff": {
+						"0x0000000000000000000000000000000000000000000000000000000000000001":
+						"0x0000000000000000000000000000000000000000000000000000000000000002"
+					}
+				}
+			}"#,
+		)
+		.unwrap();
+
+		let account = H160::from_low_u64_be(1);
+		assert_eq!(
+			overrides,
+			[(
+				account,
+				AccountOverride {
+					balance: Some(16.into()),
+					state_diff: Some([(H256::from_low_u64_be(1), H256::from_low_u64_be(2))].into()),
+					..Default::default()
+				}
+			)]
+			.into()
+		);
+	}
+}
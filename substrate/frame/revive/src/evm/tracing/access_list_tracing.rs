@@ -0,0 +1,121 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	evm::{AccessList, AccessListEntry},
+	primitives::ExecReturnValue,
+	pure_precompiles::is_precompile,
+	tracing::Tracer,
+	Config, DispatchError, Weight,
+};
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::marker::PhantomData;
+use sp_core::{H160, H256, U256};
+
+/// A Tracer that records the accounts and storage slots touched by a transaction, to build its
+/// EIP-2930 access list.
+///
+/// Like in geth, the sender, the recipient (or the created contract) and the precompiles are only
+/// listed if some of their storage slots were accessed. Only fixed sized storage keys are
+/// reported.
+pub struct AccessListTracer<T> {
+	/// The touched accounts, with their accessed storage slots.
+	touched: BTreeMap<H160, BTreeSet<H256>>,
+	/// The accounts that are implicitly warm.
+	excluded: BTreeSet<H160>,
+	/// The depth of the current call.
+	depth: u32,
+	_phantom: PhantomData<T>,
+}
+
+impl<T> Default for AccessListTracer<T> {
+	fn default() -> Self {
+		Self {
+			touched: Default::default(),
+			excluded: Default::default(),
+			depth: 0,
+			_phantom: PhantomData,
+		}
+	}
+}
+
+impl<T: Config> AccessListTracer<T> {
+	/// Collect the access list and reset the tracer.
+	pub fn collect_access_list(&mut self) -> AccessList {
+		let excluded = core::mem::take(&mut self.excluded);
+		core::mem::take(&mut self.touched)
+			.into_iter()
+			.filter(|(address, storage_keys)| {
+				!storage_keys.is_empty() ||
+					!(excluded.contains(address) || is_precompile::<T>(address))
+			})
+			.map(|(address, storage_keys)| AccessListEntry {
+				address,
+				storage_keys: storage_keys.into_iter().collect(),
+			})
+			.collect()
+	}
+
+	/// Record an access to the storage slot `key` of `address`.
+	fn touch_storage(&mut self, address: H160, key: &[u8]) {
+		let Ok(key) = <[u8; 32]>::try_from(key) else { return };
+		self.touched.entry(address).or_default().insert(H256(key));
+	}
+}
+
+impl<T: Config> Tracer for AccessListTracer<T> {
+	fn enter_child_span(
+		&mut self,
+		from: H160,
+		to: H160,
+		_is_delegate_call: bool,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		_gas_left: Weight,
+	) {
+		if self.depth == 0 {
+			self.excluded.insert(from);
+			self.excluded.insert(to);
+		}
+		self.touched.entry(to).or_default();
+		self.depth += 1;
+	}
+
+	fn log_event(&mut self, _address: H160, _topics: &[H256], _data: &[u8]) {}
+
+	fn exit_child_span(&mut self, _output: &ExecReturnValue, _gas_used: Weight) {
+		self.depth = self.depth.saturating_sub(1);
+	}
+
+	fn exit_child_span_with_error(&mut self, _error: DispatchError, _gas_used: Weight) {
+		self.depth = self.depth.saturating_sub(1);
+	}
+
+	fn storage_read(&mut self, address: H160, key: &[u8], _value: Option<&[u8]>) {
+		self.touch_storage(address, key);
+	}
+
+	fn storage_write(
+		&mut self,
+		address: H160,
+		key: &[u8],
+		_old_value: Option<&[u8]>,
+		_new_value: Option<&[u8]>,
+	) {
+		self.touch_storage(address, key);
+	}
+}
@@ -0,0 +1,208 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	evm::{Bytes, PrestateTrace, PrestateTraceInfo},
+	primitives::ExecReturnValue,
+	tracing::Tracer,
+	AddressMapper, BalanceOf, Config, ContractInfoOf, DispatchError, MomentOf, Pallet,
+	PristineCode, Weight,
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::marker::PhantomData;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{traits::Bounded, SaturatedConversion};
+
+/// A Tracer that captures the state of the accounts touched by a transaction.
+///
+/// The state of an account is read the first time the account is touched, which is before any
+/// value is transferred to it. Storage slots are recorded the first time they are accessed.
+/// Only fixed sized storage keys are reported.
+pub struct PrestateTracer<T> {
+	/// Whether to report the state before and after the execution.
+	diff_mode: bool,
+	/// Whether to omit the code of the accounts.
+	disable_code: bool,
+	/// Whether to omit the storage of the accounts.
+	disable_storage: bool,
+	/// The state of the touched accounts before the execution.
+	pre: BTreeMap<H160, PrestateTraceInfo>,
+	_phantom: PhantomData<T>,
+}
+
+impl<T: Config> PrestateTracer<T>
+where
+	BalanceOf<T>: Into<U256> + TryFrom<U256> + Bounded,
+	MomentOf<T>: Into<U256>,
+{
+	/// Create a new [`PrestateTracer`] instance.
+	pub fn new(diff_mode: bool, disable_code: bool, disable_storage: bool) -> Self {
+		Self {
+			diff_mode,
+			disable_code,
+			disable_storage,
+			pre: BTreeMap::new(),
+			_phantom: PhantomData,
+		}
+	}
+
+	/// The trace returned when nothing was traced.
+	pub fn empty_trace(&self) -> PrestateTrace {
+		if self.diff_mode {
+			PrestateTrace::DiffMode { pre: Default::default(), post: Default::default() }
+		} else {
+			PrestateTrace::Prestate(Default::default())
+		}
+	}
+
+	/// Collect the trace and reset the tracer.
+	///
+	/// In diff mode the state after the execution is read from the current state, so this must be
+	/// called right after the traced transaction was applied.
+	pub fn collect_trace(&mut self) -> Option<PrestateTrace> {
+		let pre = core::mem::take(&mut self.pre);
+		if pre.is_empty() {
+			return None;
+		}
+
+		if !self.diff_mode {
+			return Some(PrestateTrace::Prestate(pre));
+		}
+
+		let mut diff_pre = BTreeMap::new();
+		let mut diff_post = BTreeMap::new();
+		for (address, pre_info) in pre {
+			let keys = pre_info.storage.keys().copied().collect::<Vec<_>>();
+			let post_info = self.read_account(&address, &keys);
+			let (pre_info, post_info) = Self::diff(pre_info, post_info);
+			if post_info != Default::default() {
+				diff_pre.insert(address, pre_info);
+				diff_post.insert(address, post_info);
+			}
+		}
+
+		Some(PrestateTrace::DiffMode { pre: diff_pre, post: diff_post })
+	}
+
+	/// Keep only the changed fields of `post`, and the changed storage slots of `pre`.
+	fn diff(
+		mut pre: PrestateTraceInfo,
+		post: PrestateTraceInfo,
+	) -> (PrestateTraceInfo, PrestateTraceInfo) {
+		pre.storage.retain(|key, value| post.storage.get(key) != Some(value));
+		let post = PrestateTraceInfo {
+			balance: post.balance.filter(|balance| Some(*balance) != pre.balance),
+			nonce: post.nonce.filter(|nonce| Some(*nonce) != pre.nonce),
+			code: post.code.filter(|code| Some(code) != pre.code.as_ref()),
+			storage: post
+				.storage
+				.into_iter()
+				.filter(|(key, _)| pre.storage.contains_key(key))
+				.collect(),
+		};
+		(pre, post)
+	}
+
+	/// Read the current state of the account at `address`, including the given storage slots.
+	fn read_account(&self, address: &H160, keys: &[H256]) -> PrestateTraceInfo {
+		let account_id = T::AddressMapper::to_account_id(address);
+		let contract_info = ContractInfoOf::<T>::get(address);
+		let code = if self.disable_code {
+			None
+		} else {
+			contract_info
+				.as_ref()
+				.and_then(|info| PristineCode::<T>::get(info.code_hash))
+				.map(|code| Bytes(code.into()))
+		};
+		let storage = keys
+			.iter()
+			.map(|key| {
+				let value = Pallet::<T>::get_storage(*address, key.0).ok().flatten();
+				(*key, value.map(Bytes))
+			})
+			.collect();
+
+		PrestateTraceInfo {
+			balance: Some(Pallet::<T>::evm_balance(address)),
+			nonce: Some(frame_system::Pallet::<T>::account_nonce(&account_id).saturated_into()),
+			code,
+			storage,
+		}
+	}
+
+	/// Record the state of `address` if it was not touched yet.
+	fn touch(&mut self, address: H160) {
+		if !self.pre.contains_key(&address) {
+			let info = self.read_account(&address, &[]);
+			self.pre.insert(address, info);
+		}
+	}
+
+	/// Record the value of a storage slot if it was not accessed yet.
+	fn touch_storage(&mut self, address: H160, key: &[u8], value: Option<&[u8]>) {
+		if self.disable_storage {
+			return;
+		}
+		let Ok(key) = <[u8; 32]>::try_from(key) else { return };
+		self.touch(address);
+		if let Some(info) = self.pre.get_mut(&address) {
+			info.storage
+				.entry(H256(key))
+				.or_insert_with(|| value.map(|value| Bytes(value.to_vec())));
+		}
+	}
+}
+
+impl<T: Config> Tracer for PrestateTracer<T>
+where
+	BalanceOf<T>: Into<U256> + TryFrom<U256> + Bounded,
+	MomentOf<T>: Into<U256>,
+{
+	fn enter_child_span(
+		&mut self,
+		from: H160,
+		to: H160,
+		_is_delegate_call: bool,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		_gas_left: Weight,
+	) {
+		self.touch(from);
+		self.touch(to);
+	}
+
+	fn log_event(&mut self, _address: H160, _topics: &[H256], _data: &[u8]) {}
+
+	fn exit_child_span(&mut self, _output: &ExecReturnValue, _gas_used: Weight) {}
+
+	fn exit_child_span_with_error(&mut self, _error: DispatchError, _gas_used: Weight) {}
+
+	fn storage_read(&mut self, address: H160, key: &[u8], value: Option<&[u8]>) {
+		self.touch_storage(address, key, value);
+	}
+
+	fn storage_write(
+		&mut self,
+		address: H160,
+		key: &[u8],
+		old_value: Option<&[u8]>,
+		_new_value: Option<&[u8]>,
+	) {
+		self.touch_storage(address, key, old_value);
+	}
+}
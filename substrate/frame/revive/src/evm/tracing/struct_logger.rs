@@ -0,0 +1,178 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	evm::{Bytes, StructLog, StructLogTrace},
+	primitives::ExecReturnValue,
+	tracing::Tracer,
+	DispatchError, Weight,
+};
+use alloc::{
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+use sp_core::{H160, H256, U256};
+
+/// A Tracer that logs every host function called by the contracts of a transaction.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct StructLogger<Gas, GasMapper> {
+	/// Map Weight to Gas equivalent.
+	gas_mapper: GasMapper,
+	/// Whether to omit the arguments of the host functions.
+	disable_stack: bool,
+	/// Whether to omit the accessed storage.
+	disable_storage: bool,
+	/// Whether to capture the output of the transaction.
+	enable_return_data: bool,
+	/// The logged steps.
+	logs: Vec<StructLog<Gas>>,
+	/// Index and gas left before the call of the currently executing host functions.
+	active_syscalls: Vec<(usize, Weight)>,
+	/// The depth of the currently executing call frame.
+	depth: u32,
+	/// The trace of the outermost call frame, once it exited.
+	trace: Option<StructLogTrace<Gas>>,
+}
+
+impl<Gas, GasMapper> StructLogger<Gas, GasMapper> {
+	/// Create a new [`StructLogger`] instance.
+	pub fn new(
+		disable_stack: bool,
+		disable_storage: bool,
+		enable_return_data: bool,
+		gas_mapper: GasMapper,
+	) -> Self {
+		Self {
+			gas_mapper,
+			disable_stack,
+			disable_storage,
+			enable_return_data,
+			logs: Vec::new(),
+			active_syscalls: Vec::new(),
+			depth: 0,
+			trace: None,
+		}
+	}
+
+	/// Collect the trace and reset the tracer.
+	pub fn collect_trace(&mut self) -> Option<StructLogTrace<Gas>> {
+		self.depth = 0;
+		self.active_syscalls.clear();
+		self.trace.take()
+	}
+
+	/// Record an accessed storage slot on the current step.
+	///
+	/// Only the slots touched by the step itself are attached to it, the storage seen by a
+	/// step can be rebuilt by accumulating the slots of the previous steps of the same frame.
+	fn record_storage(&mut self, key: &[u8], value: Option<&[u8]>) {
+		if self.disable_storage {
+			return;
+		}
+		let Ok(key) = <[u8; 32]>::try_from(key) else { return };
+		let depth = self.depth;
+		let Some(log) = self.logs.last_mut().filter(|log| log.depth == depth) else { return };
+		log.storage
+			.get_or_insert_with(Default::default)
+			.insert(H256(key), value.map(|value| Bytes(value.to_vec())));
+	}
+
+	/// Finish the outermost call frame.
+	fn finish(&mut self, gas_used: Gas, failed: bool, return_value: Bytes) {
+		self.trace = Some(StructLogTrace {
+			gas: gas_used,
+			failed,
+			return_value: if self.enable_return_data { return_value } else { Default::default() },
+			struct_logs: core::mem::take(&mut self.logs),
+		});
+	}
+}
+
+impl<Gas: Default + Clone, GasMapper: Fn(Weight) -> Gas> Tracer for StructLogger<Gas, GasMapper> {
+	fn enter_child_span(
+		&mut self,
+		_from: H160,
+		_to: H160,
+		_is_delegate_call: bool,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		_gas_left: Weight,
+	) {
+		self.depth += 1;
+	}
+
+	fn log_event(&mut self, _address: H160, _topics: &[H256], _data: &[u8]) {}
+
+	fn exit_child_span(&mut self, output: &ExecReturnValue, gas_used: Weight) {
+		self.depth = self.depth.saturating_sub(1);
+		if self.depth == 0 {
+			let gas_used = (self.gas_mapper)(gas_used);
+			self.finish(gas_used, output.did_revert(), output.data.clone().into());
+		}
+	}
+
+	fn exit_child_span_with_error(&mut self, error: DispatchError, gas_used: Weight) {
+		let depth = self.depth;
+		if let Some(log) = self.logs.last_mut().filter(|log| log.depth == depth) {
+			log.error = Some(match error {
+				DispatchError::Module(sp_runtime::ModuleError { message, .. }) =>
+					message.unwrap_or_default().to_string(),
+				_ => format!("{:?}", error),
+			});
+		}
+
+		self.depth = self.depth.saturating_sub(1);
+		if self.depth == 0 {
+			let gas_used = (self.gas_mapper)(gas_used);
+			self.finish(gas_used, true, Default::default());
+		}
+	}
+
+	fn enter_syscall(&mut self, pc: Option<u32>, name: &[u8], args: [u64; 6], gas_left: Weight) {
+		self.active_syscalls.push((self.logs.len(), gas_left));
+		self.logs.push(StructLog {
+			pc: pc.unwrap_or_default(),
+			op: String::from_utf8_lossy(name).into_owned(),
+			gas: (self.gas_mapper)(gas_left),
+			depth: self.depth,
+			stack: (!self.disable_stack).then(|| args.into_iter().map(U256::from).collect()),
+			..Default::default()
+		});
+	}
+
+	fn exit_syscall(&mut self, gas_left: Weight) {
+		let Some((index, gas_before)) = self.active_syscalls.pop() else { return };
+		if let Some(log) = self.logs.get_mut(index) {
+			log.gas_cost = (self.gas_mapper)(gas_before.saturating_sub(gas_left));
+		}
+	}
+
+	fn storage_read(&mut self, _address: H160, key: &[u8], value: Option<&[u8]>) {
+		self.record_storage(key, value);
+	}
+
+	fn storage_write(
+		&mut self,
+		_address: H160,
+		key: &[u8],
+		_old_value: Option<&[u8]>,
+		new_value: Option<&[u8]>,
+	) {
+		self.record_storage(key, new_value);
+	}
+}
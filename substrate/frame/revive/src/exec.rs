@@ -17,6 +17,7 @@
 
 use crate::{
 	address::{self, AddressMapper},
+	evm::AccessListEntry,
 	gas::GasMeter,
 	limits,
 	primitives::{ExecReturnValue, StorageDeposit},
@@ -25,10 +26,11 @@ use crate::{
 	storage::{self, meter::Diff, WriteOutcome},
 	tracing::if_tracing,
 	transient_storage::TransientStorage,
+	wasm::RuntimeCosts,
 	BalanceOf, CodeInfo, CodeInfoOf, Config, ContractInfo, ContractInfoOf, ConversionPrecision,
 	Error, Event, ImmutableData, ImmutableDataOf, Pallet as Contracts,
 };
-use alloc::vec::Vec;
+use alloc::{collections::BTreeSet, vec::Vec};
 use core::{fmt::Debug, marker::PhantomData, mem};
 use frame_support::{
 	crypto::ecdsa::ECDSAExt,
@@ -86,8 +88,8 @@ impl Key {
 	///
 	/// # Note
 	///
-	/// Only used by benchmarking in order to generate storage collisions on purpose.
-	#[cfg(feature = "runtime-benchmarks")]
+	/// Used by tracers to report the accessed storage slots and by benchmarking in order to
+	/// generate storage collisions on purpose.
 	pub fn unhashed(&self) -> &[u8] {
 		match self {
 			Key::Fix(v) => v.as_ref(),
@@ -257,6 +259,13 @@ pub trait Ext: sealing::Sealed {
 	/// was deleted.
 	fn get_storage_size(&mut self, key: &Key) -> Option<u32>;
 
+	/// Mark the storage slot at `key` of the executing contract as accessed, after it was
+	/// successfully read or written.
+	///
+	/// Returns `true` if the slot was already accessed during the current transaction, or was
+	/// declared in its access list. Accesses of a reverted frame are forgotten.
+	fn touch_storage(&mut self, key: &Key) -> bool;
+
 	/// Sets the storage entry by the given key to the specified value. If `value` is `None` then
 	/// the storage entry is deleted.
 	fn set_storage(
@@ -514,6 +523,8 @@ pub struct Stack<'a, T: Config, E> {
 	first_frame: Frame<T>,
 	/// Transient storage used to store data, which is kept for the duration of a transaction.
 	transient_storage: TransientStorage<T>,
+	/// The storage slots accessed during the transaction.
+	accessed_storage: AccessedStorage<T>,
 	/// Whether or not actual transfer of funds should be performed.
 	/// This is set to `true` exclusively when we simulate a call through eth_transact.
 	skip_transfer: bool,
@@ -558,6 +569,64 @@ struct DelegateInfo<T: Config> {
 	pub callee: H160,
 }
 
+/// The storage slots accessed during a transaction, by contract and hashed key.
+///
+/// Like transient storage, the slots accessed by a frame are forgotten when the frame reverts.
+struct AccessedStorage<T: Config> {
+	/// The accessed slots.
+	slots: BTreeSet<(T::AccountId, Vec<u8>)>,
+	/// The slots accessed since the first open transaction, in order of access.
+	journal: Vec<(T::AccountId, Vec<u8>)>,
+	/// The length of the journal at the start of every open transaction.
+	checkpoints: Vec<usize>,
+}
+
+impl<T: Config> AccessedStorage<T> {
+	/// Create from the slots accessed before the first transaction, which are never forgotten.
+	fn new(slots: BTreeSet<(T::AccountId, Vec<u8>)>) -> Self {
+		Self { slots, journal: Vec::new(), checkpoints: Vec::new() }
+	}
+
+	/// Mark the slot as accessed. Returns `true` if it was already accessed.
+	fn touch(&mut self, slot: (T::AccountId, Vec<u8>)) -> bool {
+		if self.slots.contains(&slot) {
+			return true
+		}
+		if !self.checkpoints.is_empty() {
+			self.journal.push(slot.clone());
+		}
+		self.slots.insert(slot);
+		false
+	}
+
+	/// Start a new nested transaction.
+	fn start_transaction(&mut self) {
+		self.checkpoints.push(self.journal.len());
+	}
+
+	/// Forget the slots accessed since the matching call to `start_transaction`.
+	fn rollback_transaction(&mut self) {
+		let checkpoint = self.checkpoints.pop().expect(
+			"A call to rollback_transaction must be preceded by a corresponding call to start_transaction;
+			the code within this crate makes sure that this is always the case; qed",
+		);
+		for slot in self.journal.drain(checkpoint..) {
+			self.slots.remove(&slot);
+		}
+	}
+
+	/// Keep the slots accessed since the matching call to `start_transaction`.
+	fn commit_transaction(&mut self) {
+		self.checkpoints.pop().expect(
+			"A call to commit_transaction must be preceded by a corresponding call to start_transaction;
+			the code within this crate makes sure that this is always the case; qed",
+		);
+		if self.checkpoints.is_empty() {
+			self.journal.clear();
+		}
+	}
+}
+
 /// Used in a delegate call frame arguments in order to override the executable and caller.
 struct DelegatedCall<T: Config, E> {
 	/// The executable which is run instead of the contracts own `executable`.
@@ -729,6 +798,42 @@ where
 		input_data: Vec<u8>,
 		skip_transfer: bool,
 	) -> ExecResult {
+		Self::run_call_with_access_list(
+			origin,
+			dest,
+			gas_meter,
+			storage_meter,
+			value,
+			input_data,
+			skip_transfer,
+			&[],
+		)
+	}
+
+	/// Like [`Self::run_call`], but with the storage slots of the given EIP-2930 access list
+	/// warmed up before the call is executed.
+	pub fn run_call_with_access_list(
+		origin: Origin<T>,
+		dest: H160,
+		gas_meter: &'a mut GasMeter<T>,
+		storage_meter: &'a mut storage::meter::Meter<T>,
+		value: U256,
+		input_data: Vec<u8>,
+		skip_transfer: bool,
+		access_list: &[AccessListEntry],
+	) -> ExecResult {
+		if is_precompile::<T>(&dest) {
+			return Self::run_precompile_from_origin(
+				origin,
+				dest,
+				gas_meter,
+				value,
+				input_data,
+				skip_transfer,
+				access_list,
+			);
+		}
+
 		let dest = T::AddressMapper::to_account_id(&dest);
 		if let Some((mut stack, executable)) = Self::new(
 			FrameArgs::Call { dest: dest.clone(), cached_info: None, delegated_call: None },
@@ -737,6 +842,7 @@ where
 			storage_meter,
 			value,
 			skip_transfer,
+			access_list,
 		)? {
 			stack.run(executable, input_data).map(|_| stack.first_frame.last_frame_output)
 		} else {
@@ -761,6 +867,73 @@ where
 		}
 	}
 
+	/// Run the precompile at `address` when it is called directly by `origin`.
+	///
+	/// Like [`Ext::call`] does for calls from contracts, this executes the precompile instead of
+	/// transferring `value` to an account without code.
+	fn run_precompile_from_origin(
+		origin: Origin<T>,
+		address: H160,
+		gas_meter: &'a mut GasMeter<T>,
+		value: U256,
+		input_data: Vec<u8>,
+		skip_transfer: bool,
+		access_list: &[AccessListEntry],
+	) -> ExecResult {
+		Self::warm_up_access_list(gas_meter, access_list)?;
+
+		if_tracing(|tracer| {
+			tracer.enter_child_span(
+				origin.account_id().map(T::AddressMapper::to_address).unwrap_or_default(),
+				address,
+				false,
+				false,
+				value,
+				&input_data,
+				gas_meter.gas_left(),
+			);
+		});
+
+		let transaction_outcome =
+			with_transaction(|| -> TransactionOutcome<Result<_, DispatchError>> {
+				let mut do_transaction = || -> ExecResult {
+					if !skip_transfer {
+						Self::transfer_from_origin(
+							&origin,
+							&origin,
+							&T::AddressMapper::to_fallback_account_id(&address),
+							value,
+						)?;
+					}
+					let context = pure_precompiles::PrecompileContext {
+						address,
+						caller: origin.clone(),
+						is_read_only: false,
+						is_delegate: false,
+						gas_meter: &mut *gas_meter,
+					};
+					pure_precompiles::Precompiles::<T>::execute(context, &input_data)
+						.map_err(|e| ExecError { error: e.error, origin: ErrorOrigin::Callee })
+				};
+				let output = do_transaction();
+				match &output {
+					Ok(result) if !result.did_revert() => TransactionOutcome::Commit(Ok(output)),
+					_ => TransactionOutcome::Rollback(Ok(output)),
+				}
+			});
+		let output = transaction_outcome.unwrap_or_else(|error| Err(error.into()));
+
+		if_tracing(|tracer| {
+			let gas_consumed = gas_meter.gas_consumed();
+			match &output {
+				Ok(output) => tracer.exit_child_span(&output, gas_consumed),
+				Err(e) => tracer.exit_child_span_with_error(e.error.into(), gas_consumed),
+			}
+		});
+
+		output
+	}
+
 	/// Create and run a new call stack by instantiating a new contract.
 	///
 	/// # Return Value
@@ -775,6 +948,32 @@ where
 		input_data: Vec<u8>,
 		salt: Option<&[u8; 32]>,
 		skip_transfer: bool,
+	) -> Result<(H160, ExecReturnValue), ExecError> {
+		Self::run_instantiate_with_access_list(
+			origin,
+			executable,
+			gas_meter,
+			storage_meter,
+			value,
+			input_data,
+			salt,
+			skip_transfer,
+			&[],
+		)
+	}
+
+	/// Like [`Self::run_instantiate`], but with the storage slots of the given EIP-2930 access
+	/// list warmed up before the contract is instantiated.
+	pub fn run_instantiate_with_access_list(
+		origin: T::AccountId,
+		executable: E,
+		gas_meter: &'a mut GasMeter<T>,
+		storage_meter: &'a mut storage::meter::Meter<T>,
+		value: U256,
+		input_data: Vec<u8>,
+		salt: Option<&[u8; 32]>,
+		skip_transfer: bool,
+		access_list: &[AccessListEntry],
 	) -> Result<(H160, ExecReturnValue), ExecError> {
 		let (mut stack, executable) = Self::new(
 			FrameArgs::Instantiate {
@@ -788,6 +987,7 @@ where
 			storage_meter,
 			value,
 			skip_transfer,
+			access_list,
 		)?
 		.expect(FRAME_ALWAYS_EXISTS_ON_INSTANTIATE);
 		let address = T::AddressMapper::to_address(&stack.top_frame().account_id);
@@ -815,6 +1015,7 @@ where
 			storage_meter,
 			value.into(),
 			false,
+			&[],
 		)
 		.unwrap()
 		.unwrap()
@@ -831,8 +1032,10 @@ where
 		storage_meter: &'a mut storage::meter::Meter<T>,
 		value: U256,
 		skip_transfer: bool,
+		access_list: &[AccessListEntry],
 	) -> Result<Option<(Self, E)>, ExecError> {
 		origin.ensure_mapped()?;
+		let accessed_storage = Self::warm_up_access_list(gas_meter, access_list)?;
 		let Some((first_frame, executable)) = Self::new_frame(
 			args,
 			value,
@@ -856,6 +1059,7 @@ where
 			first_frame,
 			frames: Default::default(),
 			transient_storage: TransientStorage::new(limits::TRANSIENT_STORAGE_BYTES),
+			accessed_storage,
 			skip_transfer,
 			_phantom: Default::default(),
 		};
@@ -863,6 +1067,28 @@ where
 		Ok(Some((stack, executable)))
 	}
 
+	/// Charge for the storage slots declared in the `access_list` and return them as accessed.
+	///
+	/// Only the part of a cold access that warm accesses do not pay is charged upfront, so that
+	/// accessing a declared slot costs the same as a cold access. Entries without storage keys
+	/// are free.
+	fn warm_up_access_list(
+		gas_meter: &mut GasMeter<T>,
+		access_list: &[AccessListEntry],
+	) -> Result<AccessedStorage<T>, DispatchError> {
+		let mut accessed_storage = BTreeSet::new();
+		for entry in access_list {
+			let account_id = T::AddressMapper::to_account_id(&entry.address);
+			for key in &entry.storage_keys {
+				let slot = (account_id.clone(), Key::from_fixed(key.0).hash());
+				if accessed_storage.insert(slot) {
+					gas_meter.charge(RuntimeCosts::AccessListStorageKey)?;
+				}
+			}
+		}
+		Ok(AccessedStorage::new(accessed_storage))
+	}
+
 	/// Construct a new frame.
 	///
 	/// This does not take `self` because when constructing the first frame `self` is
@@ -1044,6 +1270,7 @@ where
 		}
 
 		self.transient_storage.start_transaction();
+		self.accessed_storage.start_transaction();
 
 		let do_transaction = || -> ExecResult {
 			let caller = self.caller();
@@ -1167,8 +1394,10 @@ where
 
 		if success {
 			self.transient_storage.commit_transaction();
+			self.accessed_storage.commit_transaction();
 		} else {
 			self.transient_storage.rollback_transaction();
+			self.accessed_storage.rollback_transaction();
 		}
 
 		self.pop_frame(success);
@@ -1404,12 +1633,22 @@ where
 				)?;
 			}
 
-			pure_precompiles::Precompiles::<T>::execute(
-				precompile_address,
-				self.gas_meter_mut(),
-				input_data,
-			)
-			.map_err(|e| ExecError { error: e.error, origin: ErrorOrigin::Callee })
+			// The precompile is called by the current contract unless it is delegate called, in
+			// which case it runs on behalf of the caller of the current contract.
+			let caller = if is_delegate {
+				self.caller()
+			} else {
+				Origin::from_account_id(self.top_frame().account_id.clone())
+			};
+			let context = pure_precompiles::PrecompileContext {
+				address: precompile_address,
+				caller,
+				is_read_only,
+				is_delegate,
+				gas_meter: self.gas_meter_mut(),
+			};
+			pure_precompiles::Precompiles::<T>::execute(context, input_data)
+				.map_err(|e| ExecError { error: e.error, origin: ErrorOrigin::Callee })
 		};
 
 		let transaction_outcome =
@@ -1482,7 +1721,7 @@ where
 			// Enable read-only access if requested; cannot disable it if already set.
 			let is_read_only = read_only || self.is_read_only();
 
-			if is_precompile(dest_addr) {
+			if is_precompile::<T>(dest_addr) {
 				return self.run_precompile(*dest_addr, false, is_read_only, value, &input_data);
 			}
 
@@ -1561,7 +1800,7 @@ where
 		address: H160,
 		input_data: Vec<u8>,
 	) -> Result<(), ExecError> {
-		if is_precompile(&address) {
+		if is_precompile::<T>(&address) {
 			return self.run_precompile(
 				address,
 				true,
@@ -1654,19 +1893,37 @@ where
 	}
 
 	fn get_storage(&mut self, key: &Key) -> Option<Vec<u8>> {
-		self.top_frame_mut().contract_info().read(key)
+		let value = self.top_frame_mut().contract_info().read(key);
+		if_tracing(|tracer| {
+			tracer.storage_read(self.address(), key.unhashed(), value.as_deref());
+		});
+		value
 	}
 
 	fn get_storage_size(&mut self, key: &Key) -> Option<u32> {
 		self.top_frame_mut().contract_info().size(key.into())
 	}
 
+	fn touch_storage(&mut self, key: &Key) -> bool {
+		let slot = (self.top_frame().account_id.clone(), key.hash());
+		self.accessed_storage.touch(slot)
+	}
+
 	fn set_storage(
 		&mut self,
 		key: &Key,
 		value: Option<Vec<u8>>,
 		take_old: bool,
 	) -> Result<WriteOutcome, DispatchError> {
+		if_tracing(|tracer| {
+			let old_value = self.top_frame_mut().contract_info().read(key);
+			tracer.storage_write(
+				self.address(),
+				key.unhashed(),
+				old_value.as_deref(),
+				value.as_deref(),
+			);
+		});
 		let frame = self.top_frame_mut();
 		frame.contract_info.get(&frame.account_id).write(
 			key.into(),
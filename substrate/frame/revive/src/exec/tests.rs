@@ -2434,6 +2434,28 @@ fn rollback_transient_storage_works() {
 	});
 }
 
+#[test]
+fn rollback_accessed_storage_works() {
+	let declared = (ALICE, vec![1]);
+	let committed = (BOB, vec![2]);
+	let reverted = (CHARLIE, vec![3]);
+	let mut accessed = AccessedStorage::<Test>::new([declared.clone()].into_iter().collect());
+
+	accessed.start_transaction();
+	assert!(!accessed.touch(committed.clone()));
+	accessed.start_transaction();
+	assert!(accessed.touch(declared.clone()));
+	assert!(accessed.touch(committed.clone()));
+	assert!(!accessed.touch(reverted.clone()));
+	accessed.rollback_transaction();
+	accessed.commit_transaction();
+
+	// Slots accessed by a reverted frame are cold again.
+	assert!(accessed.touch(declared));
+	assert!(accessed.touch(committed));
+	assert!(!accessed.touch(reverted));
+}
+
 #[test]
 fn ecdsa_to_eth_address_returns_proper_value() {
 	let bob_ch = MockLoader::insert(Call, |ctx, _| {
@@ -27,7 +27,6 @@ mod exec;
 mod gas;
 mod limits;
 mod primitives;
-mod pure_precompiles;
 mod storage;
 mod transient_storage;
 mod wasm;
@@ -37,12 +36,16 @@ mod tests;
 
 pub mod chain_extension;
 pub mod evm;
+pub mod pure_precompiles;
 pub mod test_utils;
 pub mod tracing;
 pub mod weights;
 
 use crate::{
-	evm::{runtime::GAS_PRICE, CallTrace, GasEncoder, GenericTransaction, TracerConfig},
+	evm::{
+		runtime::GAS_PRICE, AccessList, AccessListEntry, AccountOverride, BlockOverrides,
+		CallTrace, GasEncoder, GenericTransaction, StateOverride, Trace, Tracer, TracerConfig,
+	},
 	exec::{AccountIdOf, ExecError, Executable, Key, Stack as ExecStack},
 	gas::GasMeter,
 	storage::{meter::Meter as StorageMeter, ContractInfo, DeletionQueueManager},
@@ -191,6 +194,14 @@ pub mod pallet {
 		/// Find the author of the current block.
 		type FindAuthor: FindAuthor<Self::AccountId>;
 
+		/// Precompiles provided by the runtime in addition to the built-in Ethereum precompiles.
+		///
+		/// The addresses `0x01` to `0xff` are reserved for the built-in precompiles. Registering a
+		/// precompile inside this range has no effect. Use `()` if no additional precompiles are
+		/// needed. See [`pure_precompiles::PrecompileSet`].
+		#[pallet::no_default_bounds]
+		type Precompiles: pure_precompiles::PrecompileSet<Self>;
+
 		/// The amount of balance a caller has to pay for each byte of storage.
 		///
 		/// # Note
@@ -260,6 +271,13 @@ pub mod pallet {
 			BlockNumberFor<Self>,
 		>;
 
+		/// Answers the weight and fee queries of the [`pure_precompiles::XcmPrecompile`].
+		///
+		/// Runtimes usually forward these to their implementation of the `XcmPaymentApi` runtime
+		/// API. Use `()` if the precompile is not registered.
+		#[pallet::no_default_bounds]
+		type XcmPayment: pure_precompiles::XcmPaymentInfo;
+
 		/// The amount of memory in bytes that parachain nodes a lot to the runtime.
 		///
 		/// This is used in [`Pallet::integrity_test`] to make sure that the runtime has enough
@@ -348,6 +366,7 @@ pub mod pallet {
 			type RuntimeCall = ();
 			type CallFilter = ();
 			type ChainExtension = ();
+			type Precompiles = ();
 			type CodeHashLockupDepositPercent = CodeHashLockupDepositPercent;
 			type DepositPerByte = DepositPerByte;
 			type DepositPerItem = DepositPerItem;
@@ -358,6 +377,7 @@ pub mod pallet {
 			type WeightInfo = ();
 			type WeightPrice = Self;
 			type Xcm = ();
+			type XcmPayment = ();
 			type RuntimeMemory = ConstU32<{ 128 * 1024 * 1024 }>;
 			type PVFMemory = ConstU32<{ 512 * 1024 * 1024 }>;
 			type ChainId = ConstU64<0>;
@@ -495,6 +515,8 @@ pub mod pallet {
 		RefcountOverOrUnderflow,
 		/// Unsupported precompile address
 		UnsupportedPrecompileAddress,
+		/// The input passed to a precompile was malformed.
+		PrecompileFailure,
 	}
 
 	/// A reason for the pallet contracts placing a hold on funds.
@@ -545,6 +567,17 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type AddressSuffix<T: Config> = StorageMap<_, Identity, H160, [u8; 12]>;
 
+	/// The ERC-20 allowances granted through the ERC-20 precompiles.
+	///
+	/// Keyed by the address of the token precompile, the owner and the spender.
+	#[pallet::storage]
+	pub(crate) type Erc20Allowances<T: Config> = StorageNMap<
+		_,
+		(NMapKey<Identity, H160>, NMapKey<Blake2_128Concat, H160>, NMapKey<Blake2_128Concat, H160>),
+		U256,
+		ValueQuery,
+	>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_idle(_block: BlockNumberFor<T>, limit: Weight) -> Weight {
@@ -727,21 +760,15 @@ pub mod pallet {
 			#[pallet::compact] storage_deposit_limit: BalanceOf<T>,
 			data: Vec<u8>,
 		) -> DispatchResultWithPostInfo {
-			let mut output = Self::bare_call(
+			Self::call_with_access_list(
 				origin,
 				dest,
 				value,
 				gas_limit,
-				DepositLimit::Balance(storage_deposit_limit),
+				storage_deposit_limit,
 				data,
-			);
-
-			if let Ok(return_value) = &output.result {
-				if return_value.did_revert() {
-					output.result = Err(<Error<T>>::ContractReverted.into());
-				}
-			}
-			dispatch_result(output.result, output.gas_consumed, T::WeightInfo::call())
+				Vec::new(),
+			)
 		}
 
 		/// Instantiates a contract from a previously deployed wasm binary.
@@ -825,26 +852,15 @@ pub mod pallet {
 			data: Vec<u8>,
 			salt: Option<[u8; 32]>,
 		) -> DispatchResultWithPostInfo {
-			let code_len = code.len() as u32;
-			let data_len = data.len() as u32;
-			let mut output = Self::bare_instantiate(
+			Self::instantiate_with_code_and_access_list(
 				origin,
 				value,
 				gas_limit,
-				DepositLimit::Balance(storage_deposit_limit),
-				Code::Upload(code),
+				storage_deposit_limit,
+				code,
 				data,
 				salt,
-			);
-			if let Ok(retval) = &output.result {
-				if retval.result.did_revert() {
-					output.result = Err(<Error<T>>::ContractReverted.into());
-				}
-			}
-			dispatch_result(
-				output.result.map(|result| result.result),
-				output.gas_consumed,
-				T::WeightInfo::instantiate_with_code(code_len, data_len),
+				Vec::new(),
 			)
 		}
 
@@ -961,6 +977,82 @@ pub mod pallet {
 				T::AddressMapper::to_fallback_account_id(&T::AddressMapper::to_address(&origin));
 			call.dispatch(RawOrigin::Signed(unmapped_account).into())
 		}
+
+		/// Same as [`Self::call`], but with the storage slots of the given EIP-2930 access list
+		/// warmed up before the call is executed.
+		///
+		/// This is the call an Ethereum transaction with an access list is converted into.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::call().saturating_add(*gas_limit))]
+		pub fn call_with_access_list(
+			origin: OriginFor<T>,
+			dest: H160,
+			#[pallet::compact] value: BalanceOf<T>,
+			gas_limit: Weight,
+			#[pallet::compact] storage_deposit_limit: BalanceOf<T>,
+			data: Vec<u8>,
+			access_list: AccessList,
+		) -> DispatchResultWithPostInfo {
+			let mut output = Self::bare_call_with_access_list(
+				origin,
+				dest,
+				value,
+				gas_limit,
+				DepositLimit::Balance(storage_deposit_limit),
+				data,
+				&access_list,
+			);
+
+			if let Ok(return_value) = &output.result {
+				if return_value.did_revert() {
+					output.result = Err(<Error<T>>::ContractReverted.into());
+				}
+			}
+			dispatch_result(output.result, output.gas_consumed, T::WeightInfo::call())
+		}
+
+		/// Same as [`Self::instantiate_with_code`], but with the storage slots of the given
+		/// EIP-2930 access list warmed up before the contract is instantiated.
+		///
+		/// This is the call an Ethereum transaction with an access list is converted into.
+		#[pallet::call_index(11)]
+		#[pallet::weight(
+			T::WeightInfo::instantiate_with_code(code.len() as u32, data.len() as u32)
+			.saturating_add(*gas_limit)
+		)]
+		pub fn instantiate_with_code_and_access_list(
+			origin: OriginFor<T>,
+			#[pallet::compact] value: BalanceOf<T>,
+			gas_limit: Weight,
+			#[pallet::compact] storage_deposit_limit: BalanceOf<T>,
+			code: Vec<u8>,
+			data: Vec<u8>,
+			salt: Option<[u8; 32]>,
+			access_list: AccessList,
+		) -> DispatchResultWithPostInfo {
+			let code_len = code.len() as u32;
+			let data_len = data.len() as u32;
+			let mut output = Self::bare_instantiate_with_access_list(
+				origin,
+				value,
+				gas_limit,
+				DepositLimit::Balance(storage_deposit_limit),
+				Code::Upload(code),
+				data,
+				salt,
+				&access_list,
+			);
+			if let Ok(retval) = &output.result {
+				if retval.result.did_revert() {
+					output.result = Err(<Error<T>>::ContractReverted.into());
+				}
+			}
+			dispatch_result(
+				output.result.map(|result| result.result),
+				output.gas_consumed,
+				T::WeightInfo::instantiate_with_code(code_len, data_len),
+			)
+		}
 	}
 }
 
@@ -999,6 +1091,28 @@ where
 		gas_limit: Weight,
 		storage_deposit_limit: DepositLimit<BalanceOf<T>>,
 		data: Vec<u8>,
+	) -> ContractResult<ExecReturnValue, BalanceOf<T>> {
+		Self::bare_call_with_access_list(
+			origin,
+			dest,
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			data,
+			&[],
+		)
+	}
+
+	/// Like [`Self::bare_call`], but with the storage slots of the given EIP-2930 access list
+	/// warmed up before the call is executed.
+	pub fn bare_call_with_access_list(
+		origin: OriginFor<T>,
+		dest: H160,
+		value: BalanceOf<T>,
+		gas_limit: Weight,
+		storage_deposit_limit: DepositLimit<BalanceOf<T>>,
+		data: Vec<u8>,
+		access_list: &[AccessListEntry],
 	) -> ContractResult<ExecReturnValue, BalanceOf<T>> {
 		let mut gas_meter = GasMeter::new(gas_limit);
 		let mut storage_deposit = Default::default();
@@ -1009,7 +1123,7 @@ where
 				DepositLimit::Balance(limit) => StorageMeter::new(&origin, limit, value)?,
 				DepositLimit::Unchecked => StorageMeter::new_unchecked(BalanceOf::<T>::max_value()),
 			};
-			let result = ExecStack::<T, WasmBlob<T>>::run_call(
+			let result = ExecStack::<T, WasmBlob<T>>::run_call_with_access_list(
 				origin.clone(),
 				dest,
 				&mut gas_meter,
@@ -1017,6 +1131,7 @@ where
 				Self::convert_native_to_evm(value),
 				data,
 				storage_deposit_limit.is_unchecked(),
+				access_list,
 			)?;
 			storage_deposit = storage_meter
 				.try_into_deposit(&origin, storage_deposit_limit.is_unchecked())
@@ -1047,6 +1162,30 @@ where
 		code: Code,
 		data: Vec<u8>,
 		salt: Option<[u8; 32]>,
+	) -> ContractResult<InstantiateReturnValue, BalanceOf<T>> {
+		Self::bare_instantiate_with_access_list(
+			origin,
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			code,
+			data,
+			salt,
+			&[],
+		)
+	}
+
+	/// Like [`Self::bare_instantiate`], but with the storage slots of the given EIP-2930 access
+	/// list warmed up before the contract is instantiated.
+	pub fn bare_instantiate_with_access_list(
+		origin: OriginFor<T>,
+		value: BalanceOf<T>,
+		gas_limit: Weight,
+		storage_deposit_limit: DepositLimit<BalanceOf<T>>,
+		code: Code,
+		data: Vec<u8>,
+		salt: Option<[u8; 32]>,
+		access_list: &[AccessListEntry],
 	) -> ContractResult<InstantiateReturnValue, BalanceOf<T>> {
 		let mut gas_meter = GasMeter::new(gas_limit);
 		let mut storage_deposit = Default::default();
@@ -1080,7 +1219,7 @@ where
 				StorageMeter::new(&instantiate_origin, storage_deposit_limit, value)?
 			};
 
-			let result = ExecStack::<T, WasmBlob<T>>::run_instantiate(
+			let result = ExecStack::<T, WasmBlob<T>>::run_instantiate_with_access_list(
 				instantiate_account,
 				executable,
 				&mut gas_meter,
@@ -1089,6 +1228,7 @@ where
 				data,
 				salt.as_ref(),
 				unchecked_deposit_limit,
+				access_list,
 			);
 			storage_deposit = storage_meter
 				.try_into_deposit(&instantiate_origin, unchecked_deposit_limit)?
@@ -1159,6 +1299,7 @@ where
 		};
 
 		let input = tx.input.clone().unwrap_or_default().0;
+		let access_list = tx.access_list.clone().unwrap_or_default();
 
 		let extract_error = |err| {
 			if err == Error::<T>::TransferFailed.into() ||
@@ -1182,13 +1323,14 @@ where
 			// A contract call.
 			Some(dest) => {
 				// Dry run the call.
-				let result = crate::Pallet::<T>::bare_call(
+				let result = crate::Pallet::<T>::bare_call_with_access_list(
 					T::RuntimeOrigin::signed(origin),
 					dest,
 					native_value,
 					gas_limit,
 					storage_deposit_limit,
 					input.clone(),
+					&access_list,
 				);
 
 				let data = match result.result {
@@ -1215,12 +1357,23 @@ where
 					result.gas_required,
 					result.storage_deposit,
 				);
-				let dispatch_call: <T as Config>::RuntimeCall = crate::Call::<T>::call {
-					dest,
-					value: native_value,
-					gas_limit,
-					storage_deposit_limit,
-					data: input.clone(),
+				let dispatch_call: <T as Config>::RuntimeCall = if access_list.is_empty() {
+					crate::Call::<T>::call {
+						dest,
+						value: native_value,
+						gas_limit,
+						storage_deposit_limit,
+						data: input.clone(),
+					}
+				} else {
+					crate::Call::<T>::call_with_access_list {
+						dest,
+						value: native_value,
+						gas_limit,
+						storage_deposit_limit,
+						data: input.clone(),
+						access_list: access_list.clone(),
+					}
 				}
 				.into();
 				(result, dispatch_call.get_dispatch_info())
@@ -1241,7 +1394,7 @@ where
 				};
 
 				// Dry run the call.
-				let result = crate::Pallet::<T>::bare_instantiate(
+				let result = crate::Pallet::<T>::bare_instantiate_with_access_list(
 					T::RuntimeOrigin::signed(origin),
 					native_value,
 					gas_limit,
@@ -1249,6 +1402,7 @@ where
 					Code::Upload(code.to_vec()),
 					data.to_vec(),
 					None,
+					&access_list,
 				);
 
 				let returned_data = match result.result {
@@ -1276,7 +1430,7 @@ where
 					result.gas_required,
 					result.storage_deposit,
 				);
-				let dispatch_call: <T as Config>::RuntimeCall =
+				let dispatch_call: <T as Config>::RuntimeCall = if access_list.is_empty() {
 					crate::Call::<T>::instantiate_with_code {
 						value: native_value,
 						gas_limit,
@@ -1285,7 +1439,18 @@ where
 						data: data.to_vec(),
 						salt: None,
 					}
-					.into();
+				} else {
+					crate::Call::<T>::instantiate_with_code_and_access_list {
+						value: native_value,
+						gas_limit,
+						storage_deposit_limit,
+						code: code.to_vec(),
+						data: data.to_vec(),
+						salt: None,
+						access_list: access_list.clone(),
+					}
+				}
+				.into();
 				(result, dispatch_call.get_dispatch_info())
 			},
 		};
@@ -1306,6 +1471,92 @@ where
 		Ok(result)
 	}
 
+	/// Apply the given overrides to the state, before dry-running a call with
+	/// [`Self::bare_eth_transact`].
+	///
+	/// The overrides are written to storage, so this must only be called from a context whose
+	/// changes are discarded, such as a runtime API call. The block timestamp is not owned by this
+	/// pallet, so [`BlockOverrides::time`] must be applied by the caller.
+	pub fn apply_overrides(
+		state_overrides: StateOverride,
+		block_overrides: BlockOverrides,
+	) -> Result<(), EthTransactError> {
+		if let Some(number) = block_overrides.number {
+			let number = BlockNumberFor::<T>::try_from(number)
+				.map_err(|_| EthTransactError::Message("Invalid block number override".into()))?;
+			System::<T>::set_block_number(number);
+		}
+
+		for (address, account) in state_overrides {
+			Self::apply_account_override(address, account).map_err(|err| {
+				EthTransactError::Message(format!("Failed to override account {address:?}: {err}"))
+			})?;
+		}
+
+		Ok(())
+	}
+
+	/// Apply the overrides of a single account.
+	fn apply_account_override(address: H160, account: AccountOverride) -> Result<(), &'static str> {
+		let account_id = T::AddressMapper::to_account_id(&address);
+
+		if let Some(balance) = account.balance {
+			let balance = Self::convert_evm_to_native(balance, ConversionPrecision::Exact)
+				.map_err(|_| "invalid balance")?;
+			T::Currency::set_balance(&account_id, balance);
+		}
+
+		if let Some(nonce) = account.nonce {
+			let nonce = u128::try_from(nonce)
+				.ok()
+				.and_then(|nonce| T::Nonce::try_from(nonce).ok())
+				.ok_or("invalid nonce")?;
+			frame_system::Account::<T>::mutate(&account_id, |account| account.nonce = nonce);
+		}
+
+		if let Some(code) = account.code {
+			let mut module =
+				WasmBlob::<T>::from_code(code.0, account_id).map_err(|_| "invalid code")?;
+			module.store_code(true).map_err(|_| "failed to store code")?;
+			let code_hash = *module.code_hash();
+			CodeInfo::<T>::increment_refcount(code_hash).map_err(|_| "failed to store code")?;
+
+			let contract = match ContractInfoOf::<T>::get(&address) {
+				Some(mut contract) => {
+					let _ = CodeInfo::<T>::decrement_refcount(contract.code_hash);
+					contract.code_hash = code_hash;
+					contract
+				},
+				None => ContractInfo::new(&address, Zero::zero(), code_hash)
+					.map_err(|_| "failed to create contract")?,
+			};
+			ContractInfoOf::<T>::insert(&address, contract);
+		}
+
+		if account.state.is_none() && account.state_diff.is_none() {
+			return Ok(());
+		}
+
+		let contract = ContractInfoOf::<T>::get(&address).ok_or("storage override without code")?;
+		if account.state.is_some() {
+			let _ = frame_support::storage::child::clear_storage(
+				&contract.child_trie_info(),
+				None,
+				None,
+			);
+		}
+
+		for (key, value) in account.state.into_iter().chain(account.state_diff).flatten() {
+			// Like in the EVM, a zero value is the same as an empty slot.
+			let value = (!value.is_zero()).then(|| value.0.to_vec());
+			contract
+				.write(&Key::from_fixed(key.0), value, None, false)
+				.map_err(|_| "failed to write storage")?;
+		}
+
+		Ok(())
+	}
+
 	/// Get the balance with EVM decimals of the given `address`.
 	pub fn evm_balance(address: &H160) -> U256 {
 		let account = T::AddressMapper::to_account_id(&address);
@@ -1352,6 +1603,11 @@ where
 		GAS_PRICE.into()
 	}
 
+	/// Build an EVM tracer from the given tracer config.
+	pub fn evm_tracer(config: TracerConfig) -> Tracer<T> {
+		Tracer::new(config)
+	}
+
 	/// A generalized version of [`Self::upload_code`].
 	///
 	/// It is identical to [`Self::upload_code`] and only differs in the information it returns.
@@ -1449,7 +1705,7 @@ environmental!(executing_contract: bool);
 
 sp_api::decl_runtime_apis! {
 	/// The API used to dry-run contract interactions.
-	#[api_version(1)]
+	#[api_version(3)]
 	pub trait ReviveApi<AccountId, Balance, Nonce, BlockNumber> where
 		AccountId: Codec,
 		Balance: Codec,
@@ -1499,6 +1755,23 @@ sp_api::decl_runtime_apis! {
 		/// See [`crate::Pallet::bare_eth_transact`]
 		fn eth_transact(tx: GenericTransaction) -> Result<EthTransactInfo<Balance>, EthTransactError>;
 
+		/// Perform an Ethereum call, after applying the given state and block overrides.
+		///
+		/// See [`crate::Pallet::apply_overrides`] and [`crate::Pallet::bare_eth_transact`].
+		#[api_version(3)]
+		fn eth_transact_with_overrides(
+			tx: GenericTransaction,
+			state_overrides: StateOverride,
+			block_overrides: BlockOverrides,
+		) -> Result<EthTransactInfo<Balance>, EthTransactError>;
+
+		/// Dry run the given Ethereum call and return the EIP-2930 access list of the accounts and
+		/// storage slots it touched.
+		///
+		/// See eth-rpc `eth_createAccessList` for usage.
+		#[api_version(3)]
+		fn create_access_list(tx: GenericTransaction) -> Result<AccessList, EthTransactError>;
+
 		/// Upload new code without instantiating a contract from it.
 		///
 		/// See [`crate::Pallet::bare_upload_code`].
@@ -1519,33 +1792,64 @@ sp_api::decl_runtime_apis! {
 		) -> GetStorageResult;
 
 
-		/// Traces the execution of an entire block and returns call traces.
+		/// Traces the execution of an entire block and returns the traces of its transactions.
 		///
 		/// This is intended to be called through `state_call` to replay the block from the
 		/// parent block.
 		///
 		/// See eth-rpc `debug_traceBlockByNumber` for usage.
+		#[changed_in(2)]
 		fn trace_block(
 			block: Block,
 			config: TracerConfig
 		) -> Vec<(u32, CallTrace)>;
 
+		/// Traces the execution of an entire block and returns the traces of its transactions.
+		///
+		/// This is intended to be called through `state_call` to replay the block from the
+		/// parent block.
+		///
+		/// See eth-rpc `debug_traceBlockByNumber` for usage.
+		fn trace_block(
+			block: Block,
+			config: TracerConfig
+		) -> Vec<(u32, Trace)>;
+
 		/// Traces the execution of a specific transaction within a block.
 		///
 		/// This is intended to be called through `state_call` to replay the block from the
 		/// parent hash up to the transaction.
 		///
 		/// See eth-rpc `debug_traceTransaction` for usage.
+		#[changed_in(2)]
 		fn trace_tx(
 			block: Block,
 			tx_index: u32,
 			config: TracerConfig
 		) -> Option<CallTrace>;
 
+		/// Traces the execution of a specific transaction within a block.
+		///
+		/// This is intended to be called through `state_call` to replay the block from the
+		/// parent hash up to the transaction.
+		///
+		/// See eth-rpc `debug_traceTransaction` for usage.
+		fn trace_tx(
+			block: Block,
+			tx_index: u32,
+			config: TracerConfig
+		) -> Option<Trace>;
+
 		/// Dry run and return the trace of the given call.
 		///
 		/// See eth-rpc `debug_traceCall` for usage.
+		#[changed_in(2)]
 		fn trace_call(tx: GenericTransaction, config: TracerConfig) -> Result<CallTrace, EthTransactError>;
 
+		/// Dry run and return the trace of the given call.
+		///
+		/// See eth-rpc `debug_traceCall` for usage.
+		fn trace_call(tx: GenericTransaction, config: TracerConfig) -> Result<Trace, EthTransactError>;
+
 	}
 }
@@ -15,34 +15,227 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{exec::ExecResult, Config, Error, GasMeter, H160};
+//! Precompiled contracts.
+//!
+//! The standard Ethereum precompiles are always available at the addresses `0x01` to `0x09`.
+//! Runtimes can register additional precompiles through [`Config::Precompiles`], such as the
+//! ERC-20 precompiles of [`Erc20`] and [`NativeErc20`] or the [`XcmPrecompile`].
 
+use crate::{tracing::if_tracing, wasm::RuntimeCosts, Config, Error, Event, Pallet};
+use alloc::vec::Vec;
+use alloy_core::sol_types::{Revert, SolError};
+use core::marker::PhantomData;
+use frame_support::{traits::Get, weights::Weight};
+use pallet_revive_uapi::ReturnFlags;
+use sp_core::{H160, H256};
+use sp_runtime::DispatchError;
+
+pub use crate::{
+	exec::{ExecError, ExecResult, Origin},
+	gas::{ChargedAmount, GasMeter},
+	primitives::ExecReturnValue,
+};
+
+mod blake2f;
+mod bn128;
 mod ecrecover;
+mod erc20;
+mod identity;
+mod modexp;
+mod ripemd160;
+mod sha256;
+mod xcm_precompile;
+
+pub use blake2f::*;
+pub use bn128::*;
 pub use ecrecover::*;
+pub use erc20::*;
+pub use identity::*;
+pub use modexp::*;
+pub use ripemd160::*;
+pub use sha256::*;
+pub use xcm_precompile::*;
 
-/// Determine if the given address is a precompile.
-/// For now, we consider that all addresses between 0x1 and 0xff are reserved for precompiles.
-pub fn is_precompile(address: &H160) -> bool {
+/// Determine if the given address is inside the range reserved for the built-in precompiles.
+///
+/// All addresses between 0x1 and 0xff are reserved, even those that are not used (yet).
+pub fn is_reserved(address: &H160) -> bool {
 	let bytes = address.as_bytes();
 	bytes.starts_with(&[0u8; 19]) && bytes[19] != 0
 }
 
+/// Determine if the given address is a precompile.
+///
+/// This is the case if it is in the reserved range or if a precompile was registered at this
+/// address through [`Config::Precompiles`].
+pub fn is_precompile<T: Config>(address: &H160) -> bool {
+	is_reserved(address) || T::Precompiles::contains(address)
+}
+
+/// Charge `weight` from the `gas_meter`.
+///
+/// Runtime provided precompiles should use this to charge for their execution.
+pub fn charge_weight<T: Config>(
+	gas_meter: &mut GasMeter<T>,
+	weight: Weight,
+) -> Result<ChargedAmount, DispatchError> {
+	gas_meter.charge(RuntimeCosts::Precompile(weight))
+}
+
 /// The `Precompile` trait defines the functionality for executing a precompiled contract.
 pub trait Precompile<T: Config> {
 	/// Executes the precompile with the provided input data.
 	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> ExecResult;
 }
 
+/// The context in which a precompile registered through [`Config::Precompiles`] is executed.
+pub struct PrecompileContext<'a, T: Config> {
+	/// The address of the called precompile.
+	pub address: H160,
+	/// The caller of the precompile.
+	pub caller: Origin<T>,
+	/// Whether the precompile is called in a read-only context, where state changes are denied.
+	pub is_read_only: bool,
+	/// Whether the precompile is called through a delegate call.
+	pub is_delegate: bool,
+	/// The gas meter of the call.
+	pub gas_meter: &'a mut GasMeter<T>,
+}
+
+impl<T: Config> PrecompileContext<'_, T> {
+	/// Charge `weight` from the gas meter of the call.
+	pub fn charge_weight(&mut self, weight: Weight) -> Result<ChargedAmount, DispatchError> {
+		charge_weight(self.gas_meter, weight)
+	}
+
+	/// Returns the account of the caller if the precompile is allowed to change state.
+	///
+	/// State changes are denied in a read-only context and when the precompile is delegate
+	/// called, as it would then act on behalf of the caller of the calling contract.
+	pub fn ensure_mutable_caller(&self) -> Result<&T::AccountId, DispatchError> {
+		if self.is_read_only || self.is_delegate {
+			return Err(Error::<T>::StateChangeDenied.into());
+		}
+		self.caller.account_id()
+	}
+
+	/// Emit an Ethereum log with the address of the precompile.
+	pub fn deposit_event(&self, topics: Vec<H256>, data: Vec<u8>) {
+		let contract = self.address;
+		if_tracing(|tracer| {
+			tracer.log_event(contract, &topics, &data);
+		});
+		Pallet::<T>::deposit_event(Event::ContractEmitted { contract, data, topics });
+	}
+}
+
+/// Returns the output of a precompile which reverts with the Solidity `Error(string)` `reason`.
+pub fn revert(reason: &str) -> ExecReturnValue {
+	let data = Revert { reason: reason.into() }.abi_encode();
+	ExecReturnValue { data, flags: ReturnFlags::REVERT }
+}
+
+/// A set of precompiles which are registered by the runtime via [`Config::Precompiles`].
+///
+/// This trait is implemented for tuples so that multiple sets can be combined. Use
+/// [`PrecompileAt`] to register a single [`Precompile`] at a fixed address.
+pub trait PrecompileSet<T: Config> {
+	/// Returns `true` if this set contains a precompile at `address`.
+	fn contains(address: &H160) -> bool;
+
+	/// Executes the precompile at [`PrecompileContext::address`].
+	///
+	/// Returns `None` if this set doesn't contain a precompile at this address.
+	fn execute(context: &mut PrecompileContext<T>, input: &[u8]) -> Option<ExecResult>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+#[tuple_types_custom_trait_bound(PrecompileSet<T>)]
+impl<T: Config> PrecompileSet<T> for Tuple {
+	fn contains(address: &H160) -> bool {
+		for_tuples!(
+			#(
+				if Tuple::contains(address) {
+					return true;
+				}
+			)*
+		);
+		false
+	}
+
+	fn execute(context: &mut PrecompileContext<T>, input: &[u8]) -> Option<ExecResult> {
+		for_tuples!(
+			#(
+				if Tuple::contains(&context.address) {
+					return Tuple::execute(context, input);
+				}
+			)*
+		);
+		None
+	}
+}
+
+/// Registers the precompile `P` at the address returned by `A`.
+///
+/// Addresses inside the reserved range (see [`is_reserved`]) are ignored.
+pub struct PrecompileAt<A, P>(PhantomData<(A, P)>);
+
+impl<T: Config, A: Get<H160>, P: Precompile<T>> PrecompileSet<T> for PrecompileAt<A, P> {
+	fn contains(address: &H160) -> bool {
+		*address == A::get()
+	}
+
+	fn execute(context: &mut PrecompileContext<T>, input: &[u8]) -> Option<ExecResult> {
+		(context.address == A::get()).then(|| P::execute(context.gas_meter, input))
+	}
+}
+
 pub struct Precompiles<T: Config> {
-	_phantom: core::marker::PhantomData<T>,
+	_phantom: PhantomData<T>,
 }
 
 impl<T: Config> Precompiles<T> {
-	pub fn execute(addr: H160, gas_meter: &mut GasMeter<T>, input: &[u8]) -> ExecResult {
+	pub fn execute(mut context: PrecompileContext<T>, input: &[u8]) -> ExecResult {
+		let addr = context.address;
+		if !is_reserved(&addr) {
+			return T::Precompiles::execute(&mut context, input)
+				.unwrap_or_else(|| Err(Error::<T>::UnsupportedPrecompileAddress.into()));
+		}
+
+		let gas_meter = context.gas_meter;
+
 		if addr == ECRECOVER {
 			ECRecover::execute(gas_meter, input)
+		} else if addr == SHA256 {
+			Sha256::execute(gas_meter, input)
+		} else if addr == RIPEMD160 {
+			Ripemd160::execute(gas_meter, input)
+		} else if addr == IDENTITY {
+			Identity::execute(gas_meter, input)
+		} else if addr == MODEXP {
+			Modexp::execute(gas_meter, input)
+		} else if addr == BN128_ADD {
+			Bn128Add::execute(gas_meter, input)
+		} else if addr == BN128_MUL {
+			Bn128Mul::execute(gas_meter, input)
+		} else if addr == BN128_PAIRING {
+			Bn128Pairing::execute(gas_meter, input)
+		} else if addr == BLAKE2F {
+			Blake2F::execute(gas_meter, input)
 		} else {
 			Err(Error::<T>::UnsupportedPrecompileAddress.into())
 		}
 	}
 }
+
+#[cfg(test)]
+pub(crate) mod test_utils {
+	use super::*;
+	use crate::tests::Test;
+
+	/// Run the precompile `P` with unlimited gas.
+	pub fn run<P: Precompile<Test>>(input: &[u8]) -> ExecResult {
+		let mut gas_meter = GasMeter::<Test>::new(Weight::MAX);
+		P::execute(&mut gas_meter, input)
+	}
+}
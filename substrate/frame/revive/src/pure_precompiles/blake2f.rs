@@ -0,0 +1,191 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::Precompile;
+use crate::{exec::ExecResult, Config, Error, ExecReturnValue, GasMeter, RuntimeCosts};
+use hex_literal::hex;
+use pallet_revive_uapi::ReturnFlags;
+use sp_core::H160;
+pub const BLAKE2F: H160 = H160(hex!("0000000000000000000000000000000000000009"));
+
+/// The exact input length expected by [`Blake2F`].
+const INPUT_LEN: usize = 213;
+
+/// The blake2b initialization vector.
+const IV: [u64; 8] = [
+	0x6a09e667f3bcc908,
+	0xbb67ae8584caa73b,
+	0x3c6ef372fe94f82b,
+	0xa54ff53a5f1d36f1,
+	0x510e527fade682d1,
+	0x9b05688c2b3e6c1f,
+	0x1f83d9abfb41bd6b,
+	0x5be0cd19137e2179,
+];
+
+/// The blake2b message word permutations.
+const SIGMA: [[usize; 16]; 10] = [
+	[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+	[14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+	[11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+	[7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+	[9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+	[2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+	[12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+	[13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+	[6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+	[10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// The blake2 compression function precompile.
+///
+/// See [EIP-152](https://eips.ethereum.org/EIPS/eip-152).
+pub struct Blake2F;
+
+impl<T: Config> Precompile<T> for Blake2F {
+	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> ExecResult {
+		if input.len() != INPUT_LEN {
+			return Err(Error::<T>::PrecompileFailure.into());
+		}
+
+		let rounds = u32::from_be_bytes(input[0..4].try_into().expect("slice is 4 bytes; qed"));
+		// Any number of rounds is valid, the weight bounds how many can be executed.
+		gas_meter.charge(RuntimeCosts::Blake2F(rounds))?;
+
+		let final_block = match input[212] {
+			0 => false,
+			1 => true,
+			_ => return Err(Error::<T>::PrecompileFailure.into()),
+		};
+
+		let mut h = [0u64; 8];
+		for (i, word) in input[4..68].chunks_exact(8).enumerate() {
+			h[i] = read_u64(word);
+		}
+		let mut m = [0u64; 16];
+		for (i, word) in input[68..196].chunks_exact(8).enumerate() {
+			m[i] = read_u64(word);
+		}
+		let t = [read_u64(&input[196..204]), read_u64(&input[204..212])];
+
+		compress(rounds, &mut h, &m, t, final_block);
+
+		let mut data = [0u8; 64];
+		for (i, word) in h.iter().enumerate() {
+			data[i * 8..(i + 1) * 8].copy_from_slice(&word.to_le_bytes());
+		}
+
+		Ok(ExecReturnValue { data: data.to_vec(), flags: ReturnFlags::empty() })
+	}
+}
+
+/// Read a little endian `u64` from an 8 byte slice.
+fn read_u64(input: &[u8]) -> u64 {
+	u64::from_le_bytes(input.try_into().expect("slice is 8 bytes; qed"))
+}
+
+/// The blake2b mixing function.
+fn g(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize, x: u64, y: u64) {
+	v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+	v[d] = (v[d] ^ v[a]).rotate_right(32);
+	v[c] = v[c].wrapping_add(v[d]);
+	v[b] = (v[b] ^ v[c]).rotate_right(24);
+	v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+	v[d] = (v[d] ^ v[a]).rotate_right(16);
+	v[c] = v[c].wrapping_add(v[d]);
+	v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+/// The blake2b compression function F with a configurable number of rounds.
+///
+/// See [RFC 7693](https://datatracker.ietf.org/doc/html/rfc7693#section-3.2).
+fn compress(rounds: u32, h: &mut [u64; 8], m: &[u64; 16], t: [u64; 2], final_block: bool) {
+	let mut v = [0u64; 16];
+	v[..8].copy_from_slice(h);
+	v[8..].copy_from_slice(&IV);
+	v[12] ^= t[0];
+	v[13] ^= t[1];
+	if final_block {
+		v[14] = !v[14];
+	}
+
+	for i in 0..rounds as usize {
+		let s = &SIGMA[i % 10];
+		g(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+		g(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+		g(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+		g(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+		g(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+		g(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+		g(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+		g(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+	}
+
+	for i in 0..8 {
+		h[i] ^= v[i] ^ v[i + 8];
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{pure_precompiles::test_utils::run, tests::Test};
+	use frame_support::{assert_err, weights::Weight};
+
+	// Test vector 5 of EIP-152.
+	const INPUT: [u8; INPUT_LEN] = hex!(
+		"0000000c"
+		"48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5"
+		"d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b"
+		"6162630000000000000000000000000000000000000000000000000000000000"
+		"0000000000000000000000000000000000000000000000000000000000000000"
+		"0000000000000000000000000000000000000000000000000000000000000000"
+		"0000000000000000000000000000000000000000000000000000000000000000"
+		"03000000000000000000000000000000"
+		"01"
+	);
+
+	#[test]
+	fn blake2f_works() {
+		assert_eq!(
+			run::<Blake2F>(&INPUT).unwrap().data,
+			hex!(
+				"ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1"
+				"7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+			)
+		);
+	}
+
+	#[test]
+	fn blake2f_rejects_invalid_input() {
+		assert_err!(run::<Blake2F>(&INPUT[..212]), Error::<Test>::PrecompileFailure);
+
+		let mut input = INPUT;
+		input[212] = 2;
+		assert_err!(run::<Blake2F>(&input), Error::<Test>::PrecompileFailure);
+	}
+
+	#[test]
+	fn blake2f_charges_all_rounds() {
+		let mut input = INPUT;
+		input[0..4].copy_from_slice(&u32::MAX.to_be_bytes());
+		let mut gas_meter = GasMeter::<Test>::new(Weight::from_parts(1_000_000_000_000, u64::MAX));
+		assert_err!(
+			<Blake2F as Precompile<Test>>::execute(&mut gas_meter, &input),
+			Error::<Test>::OutOfGas
+		);
+	}
+}
@@ -0,0 +1,186 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! The alt_bn128 precompiles as defined by [EIP-196](https://eips.ethereum.org/EIPS/eip-196)
+//! and [EIP-197](https://eips.ethereum.org/EIPS/eip-197).
+use super::Precompile;
+use crate::{exec::ExecResult, Config, Error, ExecReturnValue, GasMeter, RuntimeCosts};
+use alloc::vec::Vec;
+use bn::{pairing_batch, AffineG1, AffineG2, Fq, Fq2, Fr, Group, Gt, G1, G2};
+use hex_literal::hex;
+use pallet_revive_uapi::ReturnFlags;
+use sp_core::H160;
+pub const BN128_ADD: H160 = H160(hex!("0000000000000000000000000000000000000006"));
+pub const BN128_MUL: H160 = H160(hex!("0000000000000000000000000000000000000007"));
+pub const BN128_PAIRING: H160 = H160(hex!("0000000000000000000000000000000000000008"));
+
+/// The size in bytes of a single (G1, G2) pair passed to [`Bn128Pairing`].
+const PAIR_LEN: usize = 192;
+
+/// The bn128 point addition precompile.
+pub struct Bn128Add;
+
+impl<T: Config> Precompile<T> for Bn128Add {
+	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> ExecResult {
+		gas_meter.charge(RuntimeCosts::Bn128Add)?;
+
+		let input = padded::<128>(input);
+		let p1 = read_point::<T>(&input, 0)?;
+		let p2 = read_point::<T>(&input, 64)?;
+		let data = encode_point(p1 + p2).to_vec();
+
+		Ok(ExecReturnValue { data, flags: ReturnFlags::empty() })
+	}
+}
+
+/// The bn128 scalar multiplication precompile.
+pub struct Bn128Mul;
+
+impl<T: Config> Precompile<T> for Bn128Mul {
+	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> ExecResult {
+		gas_meter.charge(RuntimeCosts::Bn128Mul)?;
+
+		let input = padded::<96>(input);
+		let p = read_point::<T>(&input, 0)?;
+		let fr = read_fr(&input, 64);
+		let data = encode_point(p * fr).to_vec();
+
+		Ok(ExecReturnValue { data, flags: ReturnFlags::empty() })
+	}
+}
+
+/// The bn128 pairing check precompile.
+pub struct Bn128Pairing;
+
+impl<T: Config> Precompile<T> for Bn128Pairing {
+	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> ExecResult {
+		if input.len() % PAIR_LEN != 0 {
+			return Err(Error::<T>::PrecompileFailure.into());
+		}
+
+		let num_pairs = input.len() / PAIR_LEN;
+		gas_meter.charge(RuntimeCosts::Bn128Pairing(num_pairs as u32))?;
+
+		let mut pairs = Vec::with_capacity(num_pairs);
+		for pair in input.chunks_exact(PAIR_LEN) {
+			let a = read_point::<T>(pair, 0)?;
+
+			// The G2 point is encoded as (x_im, x_re, y_im, y_re).
+			let x = Fq2::new(read_fq::<T>(pair, 96)?, read_fq::<T>(pair, 64)?);
+			let y = Fq2::new(read_fq::<T>(pair, 160)?, read_fq::<T>(pair, 128)?);
+			let b = if x.is_zero() && y.is_zero() {
+				G2::zero()
+			} else {
+				AffineG2::new(x, y).map_err(|_| Error::<T>::PrecompileFailure)?.into()
+			};
+
+			pairs.push((a, b));
+		}
+
+		let success = pairing_batch(&pairs) == Gt::one();
+		let mut data = [0u8; 32];
+		data[31] = success as u8;
+
+		Ok(ExecReturnValue { data: data.to_vec(), flags: ReturnFlags::empty() })
+	}
+}
+
+/// Copy `input` into a buffer of `N` bytes, right padding it with zeros or truncating it.
+fn padded<const N: usize>(input: &[u8]) -> [u8; N] {
+	let mut buf = [0u8; N];
+	let len = input.len().min(N);
+	buf[..len].copy_from_slice(&input[..len]);
+	buf
+}
+
+/// Read a field element from the 32 bytes at `offset`.
+fn read_fq<T: Config>(input: &[u8], offset: usize) -> Result<Fq, Error<T>> {
+	Fq::from_slice(&input[offset..offset + 32]).map_err(|_| Error::<T>::PrecompileFailure)
+}
+
+/// Read a scalar from the 32 bytes at `offset`. The scalar is reduced modulo the group order.
+fn read_fr(input: &[u8], offset: usize) -> Fr {
+	let mut buf = [0u8; 64];
+	buf[32..].copy_from_slice(&input[offset..offset + 32]);
+	Fr::interpret(&buf)
+}
+
+/// Read a G1 point from the 64 bytes at `offset`. `(0, 0)` is the point at infinity.
+fn read_point<T: Config>(input: &[u8], offset: usize) -> Result<G1, Error<T>> {
+	let x = read_fq::<T>(input, offset)?;
+	let y = read_fq::<T>(input, offset + 32)?;
+	if x.is_zero() && y.is_zero() {
+		return Ok(G1::zero());
+	}
+	AffineG1::new(x, y).map(Into::into).map_err(|_| Error::<T>::PrecompileFailure)
+}
+
+/// Encode a G1 point as `(x, y)`. The point at infinity is encoded as `(0, 0)`.
+fn encode_point(point: G1) -> [u8; 64] {
+	let mut buf = [0u8; 64];
+	if let Some(point) = AffineG1::from_jacobian(point) {
+		point.x().to_big_endian(&mut buf[..32]).expect("buffer is 32 bytes; qed");
+		point.y().to_big_endian(&mut buf[32..]).expect("buffer is 32 bytes; qed");
+	}
+	buf
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{pure_precompiles::test_utils::run, tests::Test};
+	use frame_support::assert_err;
+
+	const G: [u8; 64] = hex!(
+		"0000000000000000000000000000000000000000000000000000000000000001"
+		"0000000000000000000000000000000000000000000000000000000000000002"
+	);
+
+	const TWO_G: [u8; 64] = hex!(
+		"1b386c209eabea1777ad2736a8d8c1b4a538cbf1cac001ef71d0923a4ed31b35"
+		"1bf9fd5a6a30b0981e8e684d82dec7161b96d19c1061c919a6c1081142cabf86"
+	);
+
+	#[test]
+	fn bn128_add_works() {
+		assert_eq!(run::<Bn128Add>(&[G, G].concat()).unwrap().data, TWO_G);
+		assert_eq!(run::<Bn128Add>(&G).unwrap().data, G);
+		assert_eq!(run::<Bn128Add>(&[]).unwrap().data, [0u8; 64]);
+	}
+
+	#[test]
+	fn bn128_add_rejects_invalid_point() {
+		let mut input = G;
+		input[63] = 3;
+		assert_err!(run::<Bn128Add>(&input), Error::<Test>::PrecompileFailure);
+	}
+
+	#[test]
+	fn bn128_mul_works() {
+		let mut scalar = [0u8; 32];
+		scalar[31] = 2;
+		assert_eq!(run::<Bn128Mul>(&[&G[..], &scalar].concat()).unwrap().data, TWO_G);
+		assert_eq!(run::<Bn128Mul>(&G).unwrap().data, [0u8; 64]);
+	}
+
+	#[test]
+	fn bn128_pairing_works() {
+		let mut expected = [0u8; 32];
+		expected[31] = 1;
+		assert_eq!(run::<Bn128Pairing>(&[]).unwrap().data, expected);
+		assert_err!(run::<Bn128Pairing>(&[0u8; 191]), Error::<Test>::PrecompileFailure);
+	}
+}
@@ -0,0 +1,340 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ERC-20 precompiles for the assets of a `fungibles` implementation and the native currency.
+//!
+//! Allowances are kept in the storage of this pallet, so that `approve` has the exact ERC-20
+//! semantics of overwriting the previous allowance. Transfers keep the sender alive, like the
+//! value transfers of contracts do.
+
+use super::{revert, ExecResult, PrecompileContext, PrecompileSet};
+use crate::{
+	address::AddressMapper, weights::WeightInfo, BalanceOf, Config, Erc20Allowances, Error,
+	ExecReturnValue,
+};
+use alloy_core::{
+	primitives::{Address, U256 as SolU256},
+	sol,
+	sol_types::{SolCall, SolEvent, SolInterface},
+};
+use core::marker::PhantomData;
+use frame_support::traits::{fungible, fungibles, tokens::Preservation, Get};
+use pallet_revive_uapi::ReturnFlags;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{DispatchError, DispatchResult};
+
+sol! {
+	/// The ERC-20 interface implemented by the [`Erc20`] and [`NativeErc20`] precompiles.
+	interface IERC20 {
+		function totalSupply() external view returns (uint256);
+		function balanceOf(address account) external view returns (uint256);
+		function transfer(address to, uint256 value) external returns (bool);
+		function allowance(address owner, address spender) external view returns (uint256);
+		function approve(address spender, uint256 value) external returns (bool);
+		function transferFrom(address from, address to, uint256 value) external returns (bool);
+		function decimals() external view returns (uint8);
+
+		event Transfer(address indexed from, address indexed to, uint256 value);
+		event Approval(address indexed owner, address indexed spender, uint256 value);
+	}
+}
+
+use IERC20::*;
+
+/// Returns the address of the ERC-20 precompile of `asset_id` for the [`Erc20`] precompile set
+/// registered with `prefix`.
+///
+/// The address is laid out as `asset_id (4 bytes) ++ 0 (12 bytes) ++ prefix (2 bytes) ++ 0 (2
+/// bytes)`, all integers in big endian.
+pub fn erc20_address(asset_id: u32, prefix: u16) -> H160 {
+	let mut address = [0u8; 20];
+	address[..4].copy_from_slice(&asset_id.to_be_bytes());
+	address[16..18].copy_from_slice(&prefix.to_be_bytes());
+	H160(address)
+}
+
+/// Returns the asset id encoded in `address` if it is laid out as described in
+/// [`erc20_address`].
+pub fn erc20_asset_id(address: &H160, prefix: u16) -> Option<u32> {
+	let bytes = address.as_bytes();
+	if bytes[4..16] != [0u8; 12] || bytes[16..18] != prefix.to_be_bytes() || bytes[18..] != [0u8; 2]
+	{
+		return None;
+	}
+	Some(u32::from_be_bytes(bytes[..4].try_into().expect("slice has 4 bytes; qed")))
+}
+
+/// Exposes every asset of `Assets` through the ERC-20 interface.
+///
+/// The precompile of an asset lives at [`erc20_address`] of its id and `Prefix`. The prefix
+/// allows registering multiple instances of `pallet-assets` without their addresses clashing.
+pub struct Erc20<Assets, Prefix>(PhantomData<(Assets, Prefix)>);
+
+impl<T, Assets, Prefix> PrecompileSet<T> for Erc20<Assets, Prefix>
+where
+	T: Config,
+	Assets:
+		fungibles::Mutate<T::AccountId, AssetId = u32> + fungibles::metadata::Inspect<T::AccountId>,
+	Assets::Balance: Into<U256> + TryFrom<U256>,
+	Prefix: Get<u16>,
+{
+	fn contains(address: &H160) -> bool {
+		erc20_asset_id(address, Prefix::get()).is_some_and(Assets::asset_exists)
+	}
+
+	fn execute(context: &mut PrecompileContext<T>, input: &[u8]) -> Option<ExecResult> {
+		let asset_id = erc20_asset_id(&context.address, Prefix::get())?;
+		Assets::asset_exists(asset_id)
+			.then(|| execute(&Asset::<Assets>(asset_id, PhantomData), context, input))
+	}
+}
+
+/// Exposes [`Config::Currency`] through the ERC-20 interface at the address returned by
+/// `Address`.
+///
+/// Balances are denominated in the native unit of the currency with `Decimals` decimals.
+pub struct NativeErc20<Address, Decimals>(PhantomData<(Address, Decimals)>);
+
+impl<T, A, Decimals> PrecompileSet<T> for NativeErc20<A, Decimals>
+where
+	T: Config,
+	BalanceOf<T>: Into<U256> + TryFrom<U256>,
+	A: Get<H160>,
+	Decimals: Get<u8>,
+{
+	fn contains(address: &H160) -> bool {
+		*address == A::get()
+	}
+
+	fn execute(context: &mut PrecompileContext<T>, input: &[u8]) -> Option<ExecResult> {
+		(context.address == A::get())
+			.then(|| execute(&Native::<Decimals>(PhantomData), context, input))
+	}
+}
+
+/// A fungible token which can be exposed through the ERC-20 interface.
+trait Token<T: Config> {
+	/// The storage reads and writes of [`Self::transfer`] on top of those of the native currency.
+	///
+	/// The weights of the precompile are benchmarked against the native currency.
+	const EXTRA_TRANSFER_READS_WRITES: (u64, u64);
+
+	fn total_supply(&self) -> U256;
+
+	fn balance_of(&self, who: &T::AccountId) -> U256;
+
+	fn decimals(&self) -> u8;
+
+	fn transfer(&self, from: &T::AccountId, to: &T::AccountId, value: U256) -> DispatchResult;
+}
+
+/// An asset of `Assets`.
+struct Asset<Assets>(u32, PhantomData<Assets>);
+
+impl<T, Assets> Token<T> for Asset<Assets>
+where
+	T: Config,
+	Assets:
+		fungibles::Mutate<T::AccountId, AssetId = u32> + fungibles::metadata::Inspect<T::AccountId>,
+	Assets::Balance: Into<U256> + TryFrom<U256>,
+{
+	// The asset details are read and written in addition to the accounts.
+	const EXTRA_TRANSFER_READS_WRITES: (u64, u64) = (1, 1);
+
+	fn total_supply(&self) -> U256 {
+		Assets::total_issuance(self.0).into()
+	}
+
+	fn balance_of(&self, who: &T::AccountId) -> U256 {
+		Assets::balance(self.0, who).into()
+	}
+
+	fn decimals(&self) -> u8 {
+		Assets::decimals(self.0)
+	}
+
+	fn transfer(&self, from: &T::AccountId, to: &T::AccountId, value: U256) -> DispatchResult {
+		let amount = value.try_into().map_err(|_| Error::<T>::BalanceConversionFailed)?;
+		Assets::transfer(self.0, from, to, amount, Preservation::Preserve)?;
+		Ok(())
+	}
+}
+
+/// The native currency of the pallet.
+struct Native<Decimals>(PhantomData<Decimals>);
+
+impl<T, Decimals> Token<T> for Native<Decimals>
+where
+	T: Config,
+	BalanceOf<T>: Into<U256> + TryFrom<U256>,
+	Decimals: Get<u8>,
+{
+	const EXTRA_TRANSFER_READS_WRITES: (u64, u64) = (0, 0);
+
+	fn total_supply(&self) -> U256 {
+		<T::Currency as fungible::Inspect<_>>::total_issuance().into()
+	}
+
+	fn balance_of(&self, who: &T::AccountId) -> U256 {
+		<T::Currency as fungible::Inspect<_>>::balance(who).into()
+	}
+
+	fn decimals(&self) -> u8 {
+		Decimals::get()
+	}
+
+	fn transfer(&self, from: &T::AccountId, to: &T::AccountId, value: U256) -> DispatchResult {
+		let amount = value.try_into().map_err(|_| Error::<T>::BalanceConversionFailed)?;
+		<T::Currency as fungible::Mutate<_>>::transfer(from, to, amount, Preservation::Preserve)?;
+		Ok(())
+	}
+}
+
+/// Execute an ERC-20 call against `token`.
+fn execute<T: Config, Tok: Token<T>>(
+	token: &Tok,
+	context: &mut PrecompileContext<T>,
+	input: &[u8],
+) -> ExecResult {
+	let call = IERC20Calls::abi_decode(input, true).map_err(|_| Error::<T>::PrecompileFailure)?;
+	let (extra_reads, extra_writes) = Tok::EXTRA_TRANSFER_READS_WRITES;
+	let extra_transfer_weight = T::DbWeight::get().reads_writes(extra_reads, extra_writes);
+
+	let data = match call {
+		IERC20Calls::totalSupply(_) => {
+			context.charge_weight(T::WeightInfo::erc20_balance_of())?;
+			totalSupplyCall::abi_encode_returns(&(to_sol(token.total_supply()),))
+		},
+		IERC20Calls::balanceOf(balanceOfCall { account }) => {
+			context.charge_weight(T::WeightInfo::erc20_balance_of())?;
+			let who = T::AddressMapper::to_account_id(&to_h160(account));
+			balanceOfCall::abi_encode_returns(&(to_sol(token.balance_of(&who)),))
+		},
+		IERC20Calls::decimals(_) => {
+			context.charge_weight(T::WeightInfo::erc20_balance_of())?;
+			decimalsCall::abi_encode_returns(&(token.decimals(),))
+		},
+		IERC20Calls::allowance(allowanceCall { owner, spender }) => {
+			context.charge_weight(T::WeightInfo::erc20_allowance())?;
+			let allowance =
+				Erc20Allowances::<T>::get((context.address, to_h160(owner), to_h160(spender)));
+			allowanceCall::abi_encode_returns(&(to_sol(allowance),))
+		},
+		IERC20Calls::approve(approveCall { spender, value }) => {
+			context.charge_weight(T::WeightInfo::erc20_approve())?;
+			let owner = ensure_mutable_caller(context)?;
+			Erc20Allowances::<T>::insert(
+				(context.address, owner, to_h160(spender)),
+				from_sol(value),
+			);
+			deposit_log(context, Approval { owner: to_sol_address(owner), spender, value });
+			approveCall::abi_encode_returns(&(true,))
+		},
+		IERC20Calls::transfer(transferCall { to, value }) => {
+			context.charge_weight(
+				T::WeightInfo::erc20_transfer().saturating_add(extra_transfer_weight),
+			)?;
+			let from = ensure_mutable_caller(context)?;
+			if let Err(reason) = transfer(token, context, from, to_h160(to), value) {
+				return Ok(revert(reason));
+			}
+			transferCall::abi_encode_returns(&(true,))
+		},
+		IERC20Calls::transferFrom(transferFromCall { from, to, value }) => {
+			context.charge_weight(
+				T::WeightInfo::erc20_transfer_from().saturating_add(extra_transfer_weight),
+			)?;
+			let spender = ensure_mutable_caller(context)?;
+			let key = (context.address, to_h160(from), spender);
+			let Some(allowance) = Erc20Allowances::<T>::get(key).checked_sub(from_sol(value))
+			else {
+				return Ok(revert("ERC20: insufficient allowance"));
+			};
+			Erc20Allowances::<T>::insert(key, allowance);
+			if let Err(reason) = transfer(token, context, to_h160(from), to_h160(to), value) {
+				return Ok(revert(reason));
+			}
+			transferFromCall::abi_encode_returns(&(true,))
+		},
+	};
+
+	Ok(ExecReturnValue { data, flags: ReturnFlags::empty() })
+}
+
+/// Returns the address of the caller if it is allowed to change state.
+fn ensure_mutable_caller<T: Config>(context: &PrecompileContext<T>) -> Result<H160, DispatchError> {
+	context.ensure_mutable_caller().map(T::AddressMapper::to_address)
+}
+
+/// Transfer `value` of `token` and emit the `Transfer` log.
+///
+/// Returns the revert reason if the transfer failed.
+fn transfer<T: Config, Tok: Token<T>>(
+	token: &Tok,
+	context: &PrecompileContext<T>,
+	from: H160,
+	to: H160,
+	value: SolU256,
+) -> Result<(), &'static str> {
+	if !value.is_zero() {
+		token
+			.transfer(
+				&T::AddressMapper::to_account_id(&from),
+				&T::AddressMapper::to_account_id(&to),
+				from_sol(value),
+			)
+			.map_err(|_| "ERC20: transfer failed")?;
+	}
+	deposit_log(context, Transfer { from: to_sol_address(from), to: to_sol_address(to), value });
+	Ok(())
+}
+
+/// Emit `event` as an Ethereum log of the precompile.
+fn deposit_log<T: Config>(context: &PrecompileContext<T>, event: impl SolEvent) {
+	let (topics, data) = event.encode_log_data().split();
+	context.deposit_event(topics.into_iter().map(|topic| H256(topic.0)).collect(), data.to_vec());
+}
+
+fn to_h160(address: Address) -> H160 {
+	H160(address.into_array())
+}
+
+fn to_sol_address(address: H160) -> Address {
+	Address::from(address.0)
+}
+
+fn to_sol(value: U256) -> SolU256 {
+	SolU256::from_be_bytes(value.to_big_endian())
+}
+
+fn from_sol(value: SolU256) -> U256 {
+	U256::from_big_endian(&value.to_be_bytes::<32>())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn erc20_address_roundtrip() {
+		let address = erc20_address(0x1234_5678, 0x0120);
+		assert_eq!(address, H160(hex_literal::hex!("1234567800000000000000000000000001200000")));
+		assert_eq!(erc20_asset_id(&address, 0x0120), Some(0x1234_5678));
+		assert_eq!(erc20_asset_id(&address, 0x0121), None);
+		assert_eq!(erc20_asset_id(&H160::from_low_u64_be(1), 0x0120), None);
+	}
+}
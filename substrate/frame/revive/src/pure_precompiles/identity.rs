@@ -0,0 +1,45 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::Precompile;
+use crate::{exec::ExecResult, Config, ExecReturnValue, GasMeter, RuntimeCosts};
+use hex_literal::hex;
+use pallet_revive_uapi::ReturnFlags;
+use sp_core::H160;
+pub const IDENTITY: H160 = H160(hex!("0000000000000000000000000000000000000004"));
+
+/// The identity precompile.
+pub struct Identity;
+
+impl<T: Config> Precompile<T> for Identity {
+	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> ExecResult {
+		gas_meter.charge(RuntimeCosts::Identity(input.len() as u32))?;
+		Ok(ExecReturnValue { data: input.to_vec(), flags: ReturnFlags::empty() })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::pure_precompiles::test_utils::run;
+
+	#[test]
+	fn identity_works() {
+		let input = hex!("deadbeef");
+		assert_eq!(run::<Identity>(&input).unwrap().data, input);
+		assert!(run::<Identity>(&[]).unwrap().data.is_empty());
+	}
+}
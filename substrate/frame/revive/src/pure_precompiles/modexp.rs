@@ -0,0 +1,160 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::Precompile;
+use crate::{exec::ExecResult, Config, Error, ExecReturnValue, GasMeter, RuntimeCosts};
+use alloc::{vec, vec::Vec};
+use hex_literal::hex;
+use num_bigint::BigUint;
+use pallet_revive_uapi::ReturnFlags;
+use sp_core::{H160, U256};
+pub const MODEXP: H160 = H160(hex!("0000000000000000000000000000000000000005"));
+
+/// The maximum length in bytes of the base, exponent and modulus.
+///
+/// This is the limit proposed by [EIP-7823](https://eips.ethereum.org/EIPS/eip-7823).
+const MAX_INPUT_LEN: usize = 1024;
+
+/// The minimum gas charged for a modexp call as defined by
+/// [EIP-2565](https://eips.ethereum.org/EIPS/eip-2565).
+const MIN_GAS: u64 = 200;
+
+/// The modexp precompile.
+///
+/// See [EIP-198](https://eips.ethereum.org/EIPS/eip-198).
+pub struct Modexp;
+
+impl<T: Config> Precompile<T> for Modexp {
+	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> ExecResult {
+		let base_len = read_len::<T>(input, 0)?;
+		let exp_len = read_len::<T>(input, 32)?;
+		let mod_len = read_len::<T>(input, 64)?;
+		let input = input.get(96..).unwrap_or_default();
+
+		let exp_head = BigUint::from_bytes_be(&read_padded(input, base_len, exp_len.min(32)));
+		// The gas cost is bounded by the input length limit and always fits into an u32.
+		let gas = gas_cost(base_len, exp_len, mod_len, &exp_head);
+		gas_meter.charge(RuntimeCosts::Modexp(gas.try_into().unwrap_or(u32::MAX)))?;
+
+		if mod_len == 0 {
+			return Ok(ExecReturnValue { data: Vec::new(), flags: ReturnFlags::empty() });
+		}
+
+		let base = BigUint::from_bytes_be(&read_padded(input, 0, base_len));
+		let exponent = BigUint::from_bytes_be(&read_padded(input, base_len, exp_len));
+		let modulus = BigUint::from_bytes_be(&read_padded(input, base_len + exp_len, mod_len));
+
+		let mut data = vec![0u8; mod_len];
+		if modulus.bits() != 0 {
+			let result = base.modpow(&exponent, &modulus).to_bytes_be();
+			data[mod_len - result.len()..].copy_from_slice(&result);
+		}
+
+		Ok(ExecReturnValue { data, flags: ReturnFlags::empty() })
+	}
+}
+
+/// Read one of the 32 byte length fields of the input header.
+fn read_len<T: Config>(input: &[u8], offset: usize) -> Result<usize, Error<T>> {
+	let len = U256::from_big_endian(&read_padded(input, offset, 32));
+	if len > U256::from(MAX_INPUT_LEN) {
+		return Err(Error::<T>::PrecompileFailure);
+	}
+	Ok(len.as_usize())
+}
+
+/// Read `len` bytes starting at `offset`. Missing bytes are treated as zero.
+fn read_padded(input: &[u8], offset: usize, len: usize) -> Vec<u8> {
+	let mut buf = vec![0u8; len];
+	if let Some(available) = input.get(offset..) {
+		let n = available.len().min(len);
+		buf[..n].copy_from_slice(&available[..n]);
+	}
+	buf
+}
+
+/// Calculate the gas cost according to [EIP-2565](https://eips.ethereum.org/EIPS/eip-2565).
+fn gas_cost(base_len: usize, exp_len: usize, mod_len: usize, exp_head: &BigUint) -> u64 {
+	let words = (base_len.max(mod_len) as u64).div_ceil(8);
+	let multiplication_complexity = words * words;
+	let head_bits = exp_head.bits().saturating_sub(1);
+	let iteration_count =
+		if exp_len <= 32 { head_bits } else { 8 * (exp_len as u64 - 32) + head_bits };
+	(multiplication_complexity * iteration_count.max(1) / 3).max(MIN_GAS)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{pure_precompiles::test_utils::run, tests::Test};
+	use frame_support::assert_err;
+
+	#[test]
+	fn modexp_works() {
+		// 3 ^ (p - 1) mod p == 1 (Fermat's little theorem).
+		let input = hex!(
+			"0000000000000000000000000000000000000000000000000000000000000001"
+			"0000000000000000000000000000000000000000000000000000000000000020"
+			"0000000000000000000000000000000000000000000000000000000000000020"
+			"03"
+			"fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e"
+			"fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"
+		);
+		assert_eq!(
+			run::<Modexp>(&input).unwrap().data,
+			hex!("0000000000000000000000000000000000000000000000000000000000000001")
+		);
+
+		// 2 ^ 3 mod 5 == 3
+		let input = hex!(
+			"0000000000000000000000000000000000000000000000000000000000000001"
+			"0000000000000000000000000000000000000000000000000000000000000001"
+			"0000000000000000000000000000000000000000000000000000000000000002"
+			"02"
+			"03"
+			"0005"
+		);
+		assert_eq!(run::<Modexp>(&input).unwrap().data, hex!("0003"));
+	}
+
+	#[test]
+	fn modexp_zero_modulus_works() {
+		let input = hex!(
+			"0000000000000000000000000000000000000000000000000000000000000001"
+			"0000000000000000000000000000000000000000000000000000000000000001"
+			"0000000000000000000000000000000000000000000000000000000000000001"
+			"02"
+			"03"
+		);
+		assert_eq!(run::<Modexp>(&input).unwrap().data, hex!("00"));
+		assert!(run::<Modexp>(&[]).unwrap().data.is_empty());
+	}
+
+	#[test]
+	fn modexp_rejects_oversized_input() {
+		let mut input = [0u8; 96];
+		input[30..32].copy_from_slice(&(MAX_INPUT_LEN as u16 + 1).to_be_bytes());
+		assert_err!(run::<Modexp>(&input), Error::<Test>::PrecompileFailure);
+	}
+
+	#[test]
+	fn modexp_gas_cost_works() {
+		assert_eq!(gas_cost(1, 1, 1, &BigUint::from(3u8)), MIN_GAS);
+		// words = 4, iteration_count = 8 * 32 + 255
+		let exp_head = BigUint::from_bytes_be(&[0xff; 32]);
+		assert_eq!(gas_cost(32, 64, 32, &exp_head), 16 * 511 / 3);
+	}
+}
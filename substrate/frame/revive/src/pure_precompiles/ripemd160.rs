@@ -0,0 +1,52 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::Precompile;
+use crate::{exec::ExecResult, Config, ExecReturnValue, GasMeter, RuntimeCosts};
+use hex_literal::hex;
+use pallet_revive_uapi::ReturnFlags;
+use ripemd::Digest;
+use sp_core::H160;
+pub const RIPEMD160: H160 = H160(hex!("0000000000000000000000000000000000000003"));
+
+/// The ripemd160 precompile.
+pub struct Ripemd160;
+
+impl<T: Config> Precompile<T> for Ripemd160 {
+	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> ExecResult {
+		gas_meter.charge(RuntimeCosts::Ripemd160(input.len() as u32))?;
+
+		// The 20 byte hash is left padded to 32 bytes.
+		let mut data = [0u8; 32];
+		data[12..].copy_from_slice(&ripemd::Ripemd160::digest(input));
+		Ok(ExecReturnValue { data: data.to_vec(), flags: ReturnFlags::empty() })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::pure_precompiles::test_utils::run;
+
+	#[test]
+	fn ripemd160_works() {
+		let result = run::<Ripemd160>(&[]).unwrap();
+		assert_eq!(
+			result.data,
+			hex!("0000000000000000000000009c1185a5c5e9fc54612808977ee8f548b2258d31")
+		);
+	}
+}
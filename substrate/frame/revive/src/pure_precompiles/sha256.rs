@@ -0,0 +1,48 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::Precompile;
+use crate::{exec::ExecResult, Config, ExecReturnValue, GasMeter, RuntimeCosts};
+use hex_literal::hex;
+use pallet_revive_uapi::ReturnFlags;
+use sp_core::H160;
+pub const SHA256: H160 = H160(hex!("0000000000000000000000000000000000000002"));
+
+/// The sha256 precompile.
+pub struct Sha256;
+
+impl<T: Config> Precompile<T> for Sha256 {
+	fn execute(gas_meter: &mut GasMeter<T>, input: &[u8]) -> ExecResult {
+		gas_meter.charge(RuntimeCosts::HashSha256(input.len() as u32))?;
+		let data = sp_io::hashing::sha2_256(input).to_vec();
+		Ok(ExecReturnValue { data, flags: ReturnFlags::empty() })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::pure_precompiles::test_utils::run;
+
+	#[test]
+	fn sha256_works() {
+		let result = run::<Sha256>(&[]).unwrap();
+		assert_eq!(
+			result.data,
+			hex!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+		);
+	}
+}
@@ -0,0 +1,228 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A precompile which sends and executes XCM programs on behalf of the calling contract.
+//!
+//! XCM programs, locations and assets are passed as SCALE encoded `bytes` of their versioned
+//! types.
+
+use super::{revert, ExecResult, PrecompileContext, PrecompileSet, RuntimeCosts};
+use crate::{weights::WeightInfo, Config, Error, ExecReturnValue, LOG_TARGET};
+use alloc::boxed::Box;
+use alloy_core::{
+	primitives::{FixedBytes, U256 as SolU256},
+	sol,
+	sol_types::{SolCall, SolInterface},
+};
+use codec::{DecodeLimit, Encode};
+use core::marker::PhantomData;
+use frame_support::{traits::Get, weights::Weight};
+use frame_system::RawOrigin;
+use pallet_revive_uapi::ReturnFlags;
+use sp_core::H160;
+use xcm::{
+	VersionedAssetId, VersionedAssets, VersionedLocation, VersionedXcm, MAX_XCM_DECODE_DEPTH,
+};
+use xcm_builder::{
+	ExecuteController, ExecuteControllerWeightInfo, SendController, SendControllerWeightInfo,
+};
+use xcm_runtime_apis::fees::Error as XcmPaymentApiError;
+
+sol! {
+	/// The interface implemented by the [`XcmPrecompile`].
+	interface IXcm {
+		/// The weight of an XCM program.
+		struct Weight {
+			uint64 refTime;
+			uint64 proofSize;
+		}
+
+		/// Execute the `VersionedXcm` `message` locally with at most `weight`.
+		function execute(bytes message, Weight weight) external;
+
+		/// Send the `VersionedXcm` `message` to the `VersionedLocation` `destination`.
+		function send(bytes destination, bytes message) external returns (bytes32 messageId);
+
+		/// Returns the weight needed to execute the `VersionedXcm` `message`.
+		function weighMessage(bytes message) external view returns (Weight weight);
+
+		/// Returns the fee for `weight` in the `VersionedAssetId` `assetId`.
+		function weightToAssetFee(Weight weight, bytes assetId) external view returns (uint256 fee);
+
+		/// Returns the `VersionedAssets` needed to deliver `message` to `destination`.
+		function deliveryFees(bytes destination, bytes message) external view returns (bytes fees);
+	}
+}
+
+use IXcm::*;
+
+/// Weight and fee queries of the [`XcmPrecompile`], configured as [`Config::XcmPayment`].
+///
+/// Runtimes usually forward these to their implementation of the `XcmPaymentApi` runtime API.
+/// The queries are charged with the weights of the `xcm_*` benchmarks, which run against the
+/// configured implementation.
+pub trait XcmPaymentInfo {
+	/// Returns the weight needed to execute `message`.
+	fn query_xcm_weight(message: VersionedXcm<()>) -> Result<Weight, XcmPaymentApiError>;
+
+	/// Converts `weight` into a fee in `asset`.
+	fn query_weight_to_asset_fee(
+		weight: Weight,
+		asset: VersionedAssetId,
+	) -> Result<u128, XcmPaymentApiError>;
+
+	/// Returns the fees needed to deliver `message` to `destination`.
+	fn query_delivery_fees(
+		destination: VersionedLocation,
+		message: VersionedXcm<()>,
+	) -> Result<VersionedAssets, XcmPaymentApiError>;
+}
+
+impl XcmPaymentInfo for () {
+	fn query_xcm_weight(_message: VersionedXcm<()>) -> Result<Weight, XcmPaymentApiError> {
+		Err(XcmPaymentApiError::Unimplemented)
+	}
+
+	fn query_weight_to_asset_fee(
+		_weight: Weight,
+		_asset: VersionedAssetId,
+	) -> Result<u128, XcmPaymentApiError> {
+		Err(XcmPaymentApiError::Unimplemented)
+	}
+
+	fn query_delivery_fees(
+		_destination: VersionedLocation,
+		_message: VersionedXcm<()>,
+	) -> Result<VersionedAssets, XcmPaymentApiError> {
+		Err(XcmPaymentApiError::Unimplemented)
+	}
+}
+
+/// Exposes [`Config::Xcm`] and [`Config::XcmPayment`] at the address returned by `Address`.
+///
+/// Programs are sent and executed with the signed origin of the calling contract.
+pub struct XcmPrecompile<Address>(PhantomData<Address>);
+
+impl<T, A> PrecompileSet<T> for XcmPrecompile<A>
+where
+	T: Config,
+	A: Get<H160>,
+{
+	fn contains(address: &H160) -> bool {
+		*address == A::get()
+	}
+
+	fn execute(context: &mut PrecompileContext<T>, input: &[u8]) -> Option<ExecResult> {
+		(context.address == A::get()).then(|| execute::<T>(context, input))
+	}
+}
+
+fn execute<T: Config>(context: &mut PrecompileContext<T>, input: &[u8]) -> ExecResult {
+	let call = IXcmCalls::abi_decode(input, true).map_err(|_| Error::<T>::PrecompileFailure)?;
+
+	let data = match call {
+		IXcmCalls::execute(executeCall { message, weight }) => {
+			let origin = RawOrigin::Signed(context.ensure_mutable_caller()?.clone()).into();
+			let message: VersionedXcm<<T as frame_system::Config>::RuntimeCall> =
+				decode::<T, _>(&message)?;
+			let max_weight = Weight::from_parts(weight.refTime, weight.proofSize);
+			let execute_weight = <T::Xcm as ExecuteController<_, _>>::WeightInfo::execute();
+			let charged = context.charge_weight(execute_weight.saturating_add(max_weight))?;
+
+			match T::Xcm::execute(origin, Box::new(message), max_weight) {
+				Ok(weight_used) => {
+					context.gas_meter.adjust_gas(
+						charged,
+						RuntimeCosts::Precompile(weight_used.saturating_add(execute_weight)),
+					);
+					executeCall::abi_encode_returns(&())
+				},
+				Err(err) => {
+					log::debug!(target: LOG_TARGET, "XCM precompile execute failed: {err:?}");
+					return Ok(revert("XCM: execution failed"));
+				},
+			}
+		},
+		IXcmCalls::send(sendCall { destination, message }) => {
+			let origin = RawOrigin::Signed(context.ensure_mutable_caller()?.clone()).into();
+			let destination: VersionedLocation = decode::<T, _>(&destination)?;
+			let message: VersionedXcm<()> = decode::<T, _>(&message)?;
+			context.charge_weight(<T::Xcm as SendController<_>>::WeightInfo::send())?;
+
+			match T::Xcm::send(origin, Box::new(destination), Box::new(message)) {
+				Ok(message_id) => sendCall::abi_encode_returns(&(FixedBytes(message_id),)),
+				Err(err) => {
+					log::debug!(target: LOG_TARGET, "XCM precompile send failed: {err:?}");
+					return Ok(revert("XCM: send failed"));
+				},
+			}
+		},
+		IXcmCalls::weighMessage(weighMessageCall { message }) => {
+			let message: VersionedXcm<()> = decode::<T, _>(&message)?;
+			context.charge_weight(T::WeightInfo::xcm_weigh_message(instructions(&message)))?;
+			match T::XcmPayment::query_xcm_weight(message) {
+				Ok(weight) => weighMessageCall::abi_encode_returns(&(IXcm::Weight {
+					refTime: weight.ref_time(),
+					proofSize: weight.proof_size(),
+				},)),
+				Err(err) => return Ok(query_failed(err)),
+			}
+		},
+		IXcmCalls::weightToAssetFee(weightToAssetFeeCall { weight, assetId: asset_id }) => {
+			let asset: VersionedAssetId = decode::<T, _>(&asset_id)?;
+			let weight = Weight::from_parts(weight.refTime, weight.proofSize);
+			context.charge_weight(T::WeightInfo::xcm_weight_to_asset_fee())?;
+			match T::XcmPayment::query_weight_to_asset_fee(weight, asset) {
+				Ok(fee) => weightToAssetFeeCall::abi_encode_returns(&(SolU256::from(fee),)),
+				Err(err) => return Ok(query_failed(err)),
+			}
+		},
+		IXcmCalls::deliveryFees(deliveryFeesCall { destination, message }) => {
+			let destination: VersionedLocation = decode::<T, _>(&destination)?;
+			let message: VersionedXcm<()> = decode::<T, _>(&message)?;
+			context.charge_weight(T::WeightInfo::xcm_delivery_fees(instructions(&message)))?;
+			match T::XcmPayment::query_delivery_fees(destination, message) {
+				Ok(fees) => deliveryFeesCall::abi_encode_returns(&(fees.encode().into(),)),
+				Err(err) => return Ok(query_failed(err)),
+			}
+		},
+	};
+
+	Ok(ExecReturnValue { data, flags: ReturnFlags::empty() })
+}
+
+/// Decode a SCALE encoded XCM type passed to the precompile.
+fn decode<T: Config, D: DecodeLimit>(mut bytes: &[u8]) -> Result<D, Error<T>> {
+	D::decode_all_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut bytes)
+		.map_err(|_| Error::<T>::PrecompileFailure)
+}
+
+/// The number of instructions of `message`, which the weights of the queries depend on.
+fn instructions<Call>(message: &VersionedXcm<Call>) -> u32 {
+	let len = match message {
+		VersionedXcm::V3(message) => message.len(),
+		VersionedXcm::V4(message) => message.len(),
+		VersionedXcm::V5(message) => message.len(),
+	};
+	len as u32
+}
+
+/// Returns the output of a failed weight or fee query.
+fn query_failed(err: XcmPaymentApiError) -> ExecReturnValue {
+	log::debug!(target: LOG_TARGET, "XCM precompile query failed: {err:?}");
+	revert("XCM: query failed")
+}
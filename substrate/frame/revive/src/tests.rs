@@ -51,7 +51,8 @@ use frame_support::{
 	traits::{
 		fungible::{BalancedHold, Inspect, Mutate, MutateHold},
 		tokens::Preservation,
-		ConstU32, ConstU64, Contains, FindAuthor, OnIdle, OnInitialize, StorageVersion,
+		AsEnsureOriginWithArg, ConstU32, ConstU64, ConstU8, Contains, FindAuthor, OnIdle,
+		OnInitialize, StorageVersion,
 	},
 	weights::{constants::WEIGHT_REF_TIME_PER_SECOND, FixedFee, IdentityFee, Weight, WeightMeter},
 };
@@ -76,6 +77,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system,
 		Balances: pallet_balances,
+		Assets: pallet_assets,
 		Timestamp: pallet_timestamp,
 		Utility: pallet_utility,
 		Contracts: pallet_revive,
@@ -397,6 +399,13 @@ impl pallet_balances::Config for Test {
 	type AccountStore = System;
 }
 
+#[derive_impl(pallet_assets::config_preludes::TestDefaultConfig)]
+impl pallet_assets::Config for Test {
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<AccountId32>>;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId32>;
+}
+
 #[derive_impl(pallet_timestamp::config_preludes::TestDefaultConfig)]
 impl pallet_timestamp::Config for Test {}
 
@@ -520,6 +529,52 @@ impl FindAuthor<<Test as frame_system::Config>::AccountId> for Test {
 	}
 }
 
+parameter_types! {
+	pub ReversePrecompileAddress: H160 = H160::from_low_u64_be(0x1000);
+	pub const Erc20Prefix: u16 = 0x0120;
+	pub NativeErc20Address: H160 = H160::from_low_u64_be(0x1001);
+	pub XcmPrecompileAddress: H160 = H160::from_low_u64_be(0x1002);
+}
+
+/// A runtime provided precompile which returns its input in reverse order.
+pub struct ReversePrecompile;
+
+impl pure_precompiles::Precompile<Test> for ReversePrecompile {
+	fn execute(
+		gas_meter: &mut pure_precompiles::GasMeter<Test>,
+		input: &[u8],
+	) -> pure_precompiles::ExecResult {
+		pure_precompiles::charge_weight(gas_meter, Weight::from_parts(1_000, 0))?;
+		let data = input.iter().rev().copied().collect();
+		Ok(pure_precompiles::ExecReturnValue { data, flags: ReturnFlags::empty() })
+	}
+}
+
+/// Answers the fee queries of the XCM precompile with a fee of two per unit of `ref_time`.
+pub struct TestXcmPayment;
+
+impl pure_precompiles::XcmPaymentInfo for TestXcmPayment {
+	fn query_xcm_weight(
+		_message: xcm::VersionedXcm<()>,
+	) -> Result<Weight, xcm_runtime_apis::fees::Error> {
+		Ok(Weight::from_parts(1_000, 0))
+	}
+
+	fn query_weight_to_asset_fee(
+		weight: Weight,
+		_asset: xcm::VersionedAssetId,
+	) -> Result<u128, xcm_runtime_apis::fees::Error> {
+		Ok(weight.ref_time() as u128 * 2)
+	}
+
+	fn query_delivery_fees(
+		_destination: xcm::VersionedLocation,
+		_message: xcm::VersionedXcm<()>,
+	) -> Result<xcm::VersionedAssets, xcm_runtime_apis::fees::Error> {
+		Err(xcm_runtime_apis::fees::Error::Unroutable)
+	}
+}
+
 #[derive_impl(crate::config_preludes::TestDefaultConfig)]
 impl Config for Test {
 	type Time = Timestamp;
@@ -528,6 +583,12 @@ impl Config for Test {
 	type CallFilter = TestFilter;
 	type ChainExtension =
 		(TestExtension, DisabledExtension, RevertingExtension, TempStorageExtension);
+	type Precompiles = (
+		pure_precompiles::PrecompileAt<ReversePrecompileAddress, ReversePrecompile>,
+		pure_precompiles::Erc20<Assets, Erc20Prefix>,
+		pure_precompiles::NativeErc20<NativeErc20Address, ConstU8<10>>,
+		pure_precompiles::XcmPrecompile<XcmPrecompileAddress>,
+	);
 	type DepositPerByte = DepositPerByte;
 	type DepositPerItem = DepositPerItem;
 	type UnsafeUnstableInterface = UnstableInterface;
@@ -536,6 +597,7 @@ impl Config for Test {
 	type CodeHashLockupDepositPercent = CodeHashLockupDepositPercent;
 	type ChainId = ChainId;
 	type FindAuthor = Test;
+	type XcmPayment = TestXcmPayment;
 }
 
 impl TryFrom<RuntimeCall> for crate::Call<Test> {
@@ -4549,6 +4611,188 @@ fn tracing_works() {
 	});
 }
 
+#[test]
+fn prestate_tracing_works() {
+	use crate::evm::*;
+	let (code, code_hash) = compile_module("set_empty_storage").unwrap();
+	ExtBuilder::default().existential_deposit(200).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code.clone())).build_and_unwrap_contract();
+		let key = H256::zero();
+
+		let mut tracer = PrestateTracer::<Test>::new(false, false, false);
+		trace(&mut tracer, || {
+			builder::bare_call(addr).build_and_unwrap_result();
+		});
+		let Some(PrestateTrace::Prestate(pre)) = tracer.collect_trace() else {
+			panic!("expected a prestate trace");
+		};
+		assert_eq!(pre.len(), 2);
+		assert!(pre.contains_key(&ALICE_ADDR));
+		assert_eq!(pre[&addr].code, Some(code.into()));
+		assert_eq!(pre[&addr].storage, [(key, None)].into_iter().collect());
+
+		// The slot is now set, hence the second call doesn't change the state of the contract.
+		let mut tracer = PrestateTracer::<Test>::new(true, false, false);
+		trace(&mut tracer, || {
+			builder::bare_call(addr).build_and_unwrap_result();
+		});
+		assert_eq!(
+			tracer.collect_trace(),
+			Some(PrestateTrace::DiffMode { pre: Default::default(), post: Default::default() })
+		);
+
+		// The slot of a new contract is not set yet, hence the diff reports its write.
+		let Contract { addr, .. } = builder::bare_instantiate(Code::Existing(code_hash))
+			.salt(Some([1u8; 32]))
+			.build_and_unwrap_contract();
+		let mut tracer = PrestateTracer::<Test>::new(true, true, false);
+		trace(&mut tracer, || {
+			builder::bare_call(addr).build_and_unwrap_result();
+		});
+		let Some(PrestateTrace::DiffMode { pre, post }) = tracer.collect_trace() else {
+			panic!("expected a diff mode trace");
+		};
+		assert_eq!(pre[&addr].storage, [(key, None)].into_iter().collect());
+		assert_eq!(post[&addr].storage, [(key, Some(vec![0u8; 4].into()))].into_iter().collect());
+		assert_eq!(post[&addr].code, None);
+	});
+}
+
+#[test]
+fn struct_logger_works() {
+	use crate::evm::*;
+	let (code, _code_hash) = compile_module("set_empty_storage").unwrap();
+	ExtBuilder::default().existential_deposit(200).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let mut tracer = StructLogger::new(false, false, false, |w: Weight| w);
+		let gas_used = trace(&mut tracer, || builder::bare_call(addr).build().gas_consumed);
+		let result = tracer.collect_trace().unwrap();
+		assert_eq!(result.gas, gas_used);
+		assert!(!result.failed);
+
+		let [log] = &result.struct_logs[..] else { panic!("expected a single step") };
+		assert_eq!(log.op, "set_storage");
+		assert_eq!(log.depth, 1);
+		assert!(log.gas_cost.all_gt(Weight::zero()));
+		assert_eq!(log.stack.as_ref().map(|stack| stack.len()), Some(6));
+		assert_eq!(
+			log.storage,
+			Some([(H256::zero(), Some(vec![0u8; 4].into()))].into_iter().collect())
+		);
+
+		let mut tracer = StructLogger::new(true, true, false, |_| U256::zero());
+		trace(&mut tracer, || builder::bare_call(addr).build_and_unwrap_result());
+		let result = tracer.collect_trace().unwrap();
+		assert_eq!(result.struct_logs[0].stack, None);
+		assert_eq!(result.struct_logs[0].storage, None);
+	});
+}
+
+#[test]
+fn access_list_warms_storage_slots() {
+	use crate::{evm::*, gas::Token, wasm::RuntimeCosts};
+	let (code, _code_hash) = compile_module("storage").unwrap();
+	ExtBuilder::default().existential_deposit(200).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+		let call = |access_list: &[AccessListEntry]| {
+			Pallet::<Test>::bare_call_with_access_list(
+				RuntimeOrigin::signed(ALICE),
+				addr,
+				0,
+				GAS_LIMIT,
+				DepositLimit::Balance(deposit_limit::<Test>()),
+				vec![],
+				access_list,
+			)
+		};
+
+		// The tracer reports the slot accessed by the contract.
+		let mut tracer = AccessListTracer::<Test>::default();
+		let cold = trace(&mut tracer, || call(&[]));
+		assert_ok!(cold.result);
+		let access_list = tracer.collect_access_list();
+		assert_eq!(
+			access_list,
+			vec![AccessListEntry { address: addr, storage_keys: vec![H256([1u8; 32])] }]
+		);
+
+		// Declaring the slot moves the cost of the lookup upfront.
+		let warm = call(&access_list);
+		assert_ok!(warm.result);
+		assert_eq!(warm.gas_consumed, cold.gas_consumed);
+
+		// An unused slot is charged upfront.
+		let unused = call(&[AccessListEntry {
+			address: addr,
+			storage_keys: vec![H256([1u8; 32]), H256([2u8; 32])],
+		}]);
+		assert_ok!(unused.result);
+		assert_eq!(
+			unused.gas_consumed,
+			warm.gas_consumed.saturating_add(<RuntimeCosts as Token<Test>>::weight(
+				&RuntimeCosts::AccessListStorageKey
+			))
+		);
+	});
+}
+
+#[test]
+fn apply_overrides_works() {
+	use crate::evm::*;
+	let (code, code_hash) = compile_module("dummy").unwrap();
+	ExtBuilder::default().build().execute_with(|| {
+		let addr = H160::from_low_u64_be(42);
+		let account_id = <Test as Config>::AddressMapper::to_account_id(&addr);
+		let (key, other_key) = (H256::from_low_u64_be(1), H256::from_low_u64_be(2));
+		let value = H256::from_low_u64_be(3);
+
+		let state_overrides = [(
+			addr,
+			AccountOverride {
+				balance: Some(Pallet::<Test>::convert_native_to_evm(1_000_000)),
+				nonce: Some(7.into()),
+				code: Some(code.into()),
+				state_diff: Some([(key, value)].into()),
+				..Default::default()
+			},
+		)]
+		.into();
+		let block_overrides = BlockOverrides { number: Some(100.into()), ..Default::default() };
+		assert_ok!(Pallet::<Test>::apply_overrides(state_overrides, block_overrides));
+
+		assert_eq!(System::block_number(), 100);
+		assert_eq!(<Test as Config>::Currency::total_balance(&account_id), 1_000_000);
+		assert_eq!(System::account_nonce(&account_id), 7);
+		assert_eq!(get_contract(&addr).code_hash, code_hash);
+		assert_eq!(Pallet::<Test>::get_storage(addr, key.0), Ok(Some(value.0.to_vec())));
+
+		// Overriding the whole storage removes the slots that are not overridden.
+		let state_overrides = [(
+			addr,
+			AccountOverride { state: Some([(other_key, value)].into()), ..Default::default() },
+		)]
+		.into();
+		assert_ok!(Pallet::<Test>::apply_overrides(state_overrides, Default::default()));
+		assert_eq!(Pallet::<Test>::get_storage(addr, key.0), Ok(None));
+		assert_eq!(Pallet::<Test>::get_storage(addr, other_key.0), Ok(Some(value.0.to_vec())));
+
+		// The storage of an account without code can't be overridden.
+		let state_overrides = [(
+			H160::from_low_u64_be(43),
+			AccountOverride { state_diff: Some([(key, value)].into()), ..Default::default() },
+		)]
+		.into();
+		assert!(Pallet::<Test>::apply_overrides(state_overrides, Default::default()).is_err());
+	});
+}
+
 #[test]
 fn unknown_precompiles_revert() {
 	let (code, _code_hash) = compile_module("read_only_call").unwrap();
@@ -4560,7 +4804,7 @@ fn unknown_precompiles_revert() {
 
 		let cases: Vec<(H160, Box<dyn FnOnce(_)>)> = vec![
 			(
-				H160::from_low_u64_be(0x2),
+				H160::from_low_u64_be(0xa),
 				Box::new(|result| {
 					assert_err!(result, <Error<Test>>::ContractTrapped);
 				}),
@@ -4587,6 +4831,235 @@ fn unknown_precompiles_revert() {
 	});
 }
 
+#[test]
+fn runtime_precompiles_work() {
+	let (code, _code_hash) = compile_module("call_and_return").unwrap();
+
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let result = builder::bare_call(addr)
+			.data((ReversePrecompileAddress::get(), 0u64, [1u8, 2, 3]).encode())
+			.build_and_unwrap_result();
+
+		assert_eq!(result.data, vec![3u8, 2, 1]);
+		assert_eq!(result.flags, ReturnFlags::empty());
+	});
+}
+
+#[test]
+fn erc20_precompiles_work() {
+	use alloy_core::{
+		primitives::{Address, U256 as SolU256},
+		sol_types::{SolCall, SolEvent},
+	};
+	use pure_precompiles::IERC20;
+
+	let (code, _code_hash) = compile_module("call_and_return").unwrap();
+
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000_000);
+		let Contract { addr, account_id } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, ALICE, true, 1));
+		assert_ok!(Assets::set_metadata(
+			RuntimeOrigin::signed(ALICE),
+			1,
+			b"Token".to_vec(),
+			b"TKN".to_vec(),
+			12
+		));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(ALICE), 1, account_id.clone(), 1_000));
+
+		let token = pure_precompiles::erc20_address(1, Erc20Prefix::get());
+		let call = |token: H160, input: Vec<u8>| {
+			builder::bare_call(addr)
+				.data([token.as_bytes(), &0u64.to_le_bytes(), &input].concat())
+				.build_and_unwrap_result()
+		};
+		let last_log = |token: H160| {
+			System::events()
+				.into_iter()
+				.rev()
+				.find_map(|record| match record.event {
+					RuntimeEvent::Contracts(crate::Event::ContractEmitted {
+						contract,
+						data,
+						topics,
+					}) if contract == token => Some((topics, data)),
+					_ => None,
+				})
+				.unwrap()
+		};
+		let log = |log: alloy_core::primitives::LogData| {
+			let (topics, data) = log.split();
+			(topics.into_iter().map(|topic| H256(topic.0)).collect::<Vec<_>>(), data.to_vec())
+		};
+		let contract = Address::from(addr.0);
+		let bob = Address::from(BOB_ADDR.0);
+
+		// Getters
+		let result = call(token, IERC20::totalSupplyCall {}.abi_encode());
+		assert_eq!(
+			result.data,
+			IERC20::totalSupplyCall::abi_encode_returns(&(SolU256::from(1_000),))
+		);
+		let result = call(token, IERC20::decimalsCall {}.abi_encode());
+		assert_eq!(result.data, IERC20::decimalsCall::abi_encode_returns(&(12,)));
+		let result = call(token, IERC20::balanceOfCall { account: contract }.abi_encode());
+		assert_eq!(
+			result.data,
+			IERC20::balanceOfCall::abi_encode_returns(&(SolU256::from(1_000),))
+		);
+
+		// Transfer
+		let result =
+			call(token, IERC20::transferCall { to: bob, value: SolU256::from(100) }.abi_encode());
+		assert_eq!(result.flags, ReturnFlags::empty());
+		assert_eq!(result.data, IERC20::transferCall::abi_encode_returns(&(true,)));
+		assert_eq!(Assets::balance(1, &account_id), 900);
+		assert_eq!(Assets::balance(1, &BOB), 100);
+		assert_eq!(
+			last_log(token),
+			log(IERC20::Transfer { from: contract, to: bob, value: SolU256::from(100) }
+				.encode_log_data())
+		);
+
+		// Approve and transfer from
+		let result = call(
+			token,
+			IERC20::approveCall { spender: contract, value: SolU256::from(50) }.abi_encode(),
+		);
+		assert_eq!(result.data, IERC20::approveCall::abi_encode_returns(&(true,)));
+		assert_eq!(
+			last_log(token),
+			log(IERC20::Approval { owner: contract, spender: contract, value: SolU256::from(50) }
+				.encode_log_data())
+		);
+
+		let result = call(
+			token,
+			IERC20::transferFromCall { from: contract, to: bob, value: SolU256::from(30) }
+				.abi_encode(),
+		);
+		assert_eq!(result.data, IERC20::transferFromCall::abi_encode_returns(&(true,)));
+		assert_eq!(Assets::balance(1, &BOB), 130);
+
+		let result =
+			call(token, IERC20::allowanceCall { owner: contract, spender: contract }.abi_encode());
+		assert_eq!(result.data, IERC20::allowanceCall::abi_encode_returns(&(SolU256::from(20),)));
+
+		// Spending more than the allowance reverts
+		let result = call(
+			token,
+			IERC20::transferFromCall { from: contract, to: bob, value: SolU256::from(21) }
+				.abi_encode(),
+		);
+		assert_eq!(result.flags, ReturnFlags::REVERT);
+		assert_eq!(Assets::balance(1, &BOB), 130);
+
+		// Accounts can call the precompile directly
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(ALICE), 1, ALICE, 50));
+		let result = builder::bare_call(token)
+			.data(IERC20::transferCall { to: bob, value: SolU256::from(20) }.abi_encode())
+			.build_and_unwrap_result();
+		assert_eq!(result.data, IERC20::transferCall::abi_encode_returns(&(true,)));
+		assert_eq!(Assets::balance(1, &ALICE), 30);
+		assert_eq!(Assets::balance(1, &BOB), 150);
+		assert_eq!(
+			last_log(token),
+			log(IERC20::Transfer {
+				from: Address::from(ALICE_ADDR.0),
+				to: bob,
+				value: SolU256::from(20)
+			}
+			.encode_log_data())
+		);
+
+		// Assets which don't exist have no precompile
+		assert!(!pure_precompiles::is_precompile::<Test>(&pure_precompiles::erc20_address(
+			2,
+			Erc20Prefix::get()
+		)));
+
+		// The native currency
+		let native = NativeErc20Address::get();
+		let result = call(native, IERC20::decimalsCall {}.abi_encode());
+		assert_eq!(result.data, IERC20::decimalsCall::abi_encode_returns(&(10,)));
+		let result = call(native, IERC20::balanceOfCall { account: bob }.abi_encode());
+		assert_eq!(
+			result.data,
+			IERC20::balanceOfCall::abi_encode_returns(&(SolU256::from(
+				<Test as Config>::Currency::balance(&BOB)
+			),))
+		);
+	});
+}
+
+#[test]
+fn xcm_precompile_works() {
+	use alloy_core::{
+		primitives::{FixedBytes, U256 as SolU256},
+		sol_types::SolCall,
+	};
+	use pure_precompiles::IXcm;
+	use xcm::{latest::prelude::*, VersionedAssetId, VersionedLocation, VersionedXcm};
+
+	let (code, _code_hash) = compile_module("call_and_return").unwrap();
+
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let call = |input: Vec<u8>| {
+			builder::bare_call(addr)
+				.data(
+					[XcmPrecompileAddress::get().as_bytes(), &0u64.to_le_bytes(), &input].concat(),
+				)
+				.build_and_unwrap_result()
+		};
+		let message = VersionedXcm::from(Xcm::<()>(vec![ClearOrigin])).encode();
+		let destination = VersionedLocation::from(Location::parent()).encode();
+
+		// The message is sent by the configured controller
+		let result = call(
+			IXcm::sendCall { destination: destination.into(), message: message.clone().into() }
+				.abi_encode(),
+		);
+		assert_eq!(result.flags, ReturnFlags::empty());
+		assert_eq!(result.data, IXcm::sendCall::abi_encode_returns(&(FixedBytes::ZERO,)));
+
+		// Execution fails with the `()` controller
+		let weight = IXcm::Weight { refTime: 1_000_000, proofSize: 1_000 };
+		let result = call(
+			IXcm::executeCall { message: message.clone().into(), weight: weight.clone() }
+				.abi_encode(),
+		);
+		assert_eq!(result.flags, ReturnFlags::REVERT);
+
+		// Fee queries are answered by the configured payment info
+		let asset_id = VersionedAssetId::from(AssetId(Location::here())).encode();
+		let result =
+			call(IXcm::weightToAssetFeeCall { weight, assetId: asset_id.into() }.abi_encode());
+		assert_eq!(
+			result.data,
+			IXcm::weightToAssetFeeCall::abi_encode_returns(&(SolU256::from(2_000_000),))
+		);
+		let result = call(IXcm::weighMessageCall { message: message.into() }.abi_encode());
+		assert_eq!(
+			result.data,
+			IXcm::weighMessageCall::abi_encode_returns(&(IXcm::Weight {
+				refTime: 1_000,
+				proofSize: 0
+			},))
+		);
+	});
+}
+
 #[test]
 fn ecrecover_precompile_works() {
 	use hex_literal::hex;
@@ -61,4 +61,35 @@ pub trait Tracer {
 
 	/// Called when a contract call terminates with an error
 	fn exit_child_span_with_error(&mut self, error: DispatchError, gas_left: Weight);
+
+	/// Called before a host function is executed.
+	///
+	/// `pc` is the program counter of the `ecalli` instruction (if known), `name` the symbol of
+	/// the host function and `args` the raw values of the argument registers.
+	fn enter_syscall(
+		&mut self,
+		_pc: Option<u32>,
+		_name: &[u8],
+		_args: [u64; 6],
+		_gas_left: Weight,
+	) {
+	}
+
+	/// Called after a host function was executed.
+	fn exit_syscall(&mut self, _gas_left: Weight) {}
+
+	/// Called after a contract read an item from its storage.
+	fn storage_read(&mut self, _address: H160, _key: &[u8], _value: Option<&[u8]>) {}
+
+	/// Called before a contract writes an item to its storage.
+	///
+	/// `old_value` is the value stored under `key` before the write.
+	fn storage_write(
+		&mut self,
+		_address: H160,
+		_key: &[u8],
+		_old_value: Option<&[u8]>,
+		_new_value: Option<&[u8]>,
+	) {
+	}
 }
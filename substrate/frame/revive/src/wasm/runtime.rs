@@ -146,6 +146,7 @@ pub trait PolkaVmInstance<T: Config>: Memory<T> {
 	fn set_gas(&mut self, gas: polkavm::Gas);
 	fn read_input_regs(&self) -> (u64, u64, u64, u64, u64, u64);
 	fn write_output(&mut self, output: u64);
+	fn program_counter(&self) -> Option<u32>;
 }
 
 // Memory implementation used in benchmarking where guest memory is mapped into the host.
@@ -216,6 +217,10 @@ impl<T: Config> PolkaVmInstance<T> for polkavm::RawInstance {
 	fn write_output(&mut self, output: u64) {
 		self.set_reg(polkavm::Reg::A0, output);
 	}
+
+	fn program_counter(&self) -> Option<u32> {
+		self.program_counter().map(|pc| pc.0)
+	}
 }
 
 parameter_types! {
@@ -354,6 +359,13 @@ pub enum RuntimeCosts {
 	GetStorage(u32),
 	/// Weight of calling `seal_take_storage` for the given size.
 	TakeStorage(u32),
+	/// Weight of accessing a storage slot that was already accessed during the transaction.
+	///
+	/// Wraps the weight of the cold access, which is reduced by the time spent to look up the
+	/// slot in a full trie. The proof size is charged in full.
+	WarmStorageAccess(Weight),
+	/// Weight of warming up a storage slot declared in the access list of a transaction.
+	AccessListStorageKey,
 	/// Weight of calling `seal_set_transient_storage` for the given storage item sizes.
 	SetTransientStorage { old_bytes: u32, new_bytes: u32 },
 	/// Weight of calling `seal_clear_transient_storage` per cleared byte.
@@ -400,6 +412,22 @@ pub enum RuntimeCosts {
 	GetImmutableData(u32),
 	/// Weight of calling `set_immutable_dependency`
 	SetImmutableData(u32),
+	/// Weight of calling the `ripemd160` precompile for the given input size.
+	Ripemd160(u32),
+	/// Weight of calling the `identity` precompile for the given input size.
+	Identity(u32),
+	/// Weight of calling the `modexp` precompile for the given EIP-2565 gas cost.
+	Modexp(u32),
+	/// Weight of calling the `bn128_add` precompile.
+	Bn128Add,
+	/// Weight of calling the `bn128_mul` precompile.
+	Bn128Mul,
+	/// Weight of calling the `bn128_pairing` precompile for the given number of pairs.
+	Bn128Pairing(u32),
+	/// Weight of calling the `blake2f` precompile for the given number of rounds.
+	Blake2F(u32),
+	/// Weight charged by a precompile registered through [`crate::Config::Precompiles`].
+	Precompile(Weight),
 }
 
 /// For functions that modify storage, benchmarks are performed with one item in the
@@ -433,6 +461,13 @@ macro_rules! cost_storage {
     };
 }
 
+/// The time spent looking up a storage slot in a full trie, which warm accesses do not pay.
+fn cold_storage_access<T: Config>() -> Weight {
+	let lookup =
+		T::WeightInfo::get_storage_full().saturating_sub(T::WeightInfo::get_storage_empty());
+	Weight::from_parts(lookup.ref_time(), 0)
+}
+
 macro_rules! cost_args {
 	// cost_args!(name, a, b, c) -> T::WeightInfo::name(a, b, c).saturating_sub(T::WeightInfo::name(0, 0, 0))
 	($name:ident, $( $arg: expr ),+) => {
@@ -497,6 +532,8 @@ impl<T: Config> Token<T> for RuntimeCosts {
 			ContainsStorage(len) => cost_storage!(read, seal_contains_storage, len),
 			GetStorage(len) => cost_storage!(read, seal_get_storage, len),
 			TakeStorage(len) => cost_storage!(write, seal_take_storage, len),
+			WarmStorageAccess(weight) => weight.saturating_sub(cold_storage_access::<T>()),
+			AccessListStorageKey => cold_storage_access::<T>(),
 			SetTransientStorage { new_bytes, old_bytes } => {
 				cost_storage!(write_transient, seal_set_transient_storage, new_bytes, old_bytes)
 			},
@@ -523,11 +560,21 @@ impl<T: Config> Token<T> for RuntimeCosts {
 			HashBlake128(len) => T::WeightInfo::seal_hash_blake2_128(len),
 			EcdsaRecovery => T::WeightInfo::seal_ecdsa_recover(),
 			Sr25519Verify(len) => T::WeightInfo::seal_sr25519_verify(len),
-			ChainExtension(weight) | CallRuntime(weight) | CallXcmExecute(weight) => weight,
+			ChainExtension(weight) |
+			CallRuntime(weight) |
+			CallXcmExecute(weight) |
+			Precompile(weight) => weight,
 			SetCodeHash => T::WeightInfo::seal_set_code_hash(),
 			EcdsaToEthAddress => T::WeightInfo::seal_ecdsa_to_eth_address(),
 			GetImmutableData(len) => T::WeightInfo::seal_get_immutable_data(len),
 			SetImmutableData(len) => T::WeightInfo::seal_set_immutable_data(len),
+			Ripemd160(len) => T::WeightInfo::ripemd_160(len),
+			Identity(len) => T::WeightInfo::identity(len),
+			Modexp(gas) => T::WeightInfo::modexp(gas),
+			Bn128Add => T::WeightInfo::bn128_add(),
+			Bn128Mul => T::WeightInfo::bn128_mul(),
+			Bn128Pairing(n) => T::WeightInfo::bn128_pairing(n),
+			Blake2F(rounds) => T::WeightInfo::blake2f(rounds),
 		}
 	}
 }
@@ -667,6 +714,15 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		self.ext.gas_meter_mut().adjust_gas(charged, actual_costs);
 	}
 
+	/// The costs of a storage access, priced as warm if the slot was already accessed.
+	fn storage_access_costs(costs: RuntimeCosts, warm: bool) -> RuntimeCosts {
+		if warm {
+			RuntimeCosts::WarmStorageAccess(<RuntimeCosts as Token<E::T>>::weight(&costs))
+		} else {
+			costs
+		}
+	}
+
 	/// Charge, Run and adjust gas, for executing the given dispatchable.
 	fn call_dispatchable<ErrorReturnCode: Get<ReturnErrorCode>>(
 		&mut self,
@@ -861,7 +917,11 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		} else {
 			self.ext.set_storage(&key, value, false)?
 		};
-		self.adjust_gas(charged, costs(value_len, write_outcome.old_len()));
+		let warm = !transient && self.ext.touch_storage(&key);
+		self.adjust_gas(
+			charged,
+			Self::storage_access_costs(costs(value_len, write_outcome.old_len()), warm),
+		);
 		Ok(write_outcome.old_len_with_sentinel())
 	}
 
@@ -887,7 +947,8 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		} else {
 			self.ext.set_storage(&key, None, false)?
 		};
-		self.adjust_gas(charged, costs(outcome.old_len()));
+		let warm = !transient && self.ext.touch_storage(&key);
+		self.adjust_gas(charged, Self::storage_access_costs(costs(outcome.old_len()), warm));
 		Ok(outcome.old_len_with_sentinel())
 	}
 
@@ -915,8 +976,9 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		} else {
 			self.ext.get_storage(&key)
 		};
+		let warm = !transient && self.ext.touch_storage(&key);
 		if let Some(value) = outcome {
-			self.adjust_gas(charged, costs(value.len() as u32));
+			self.adjust_gas(charged, Self::storage_access_costs(costs(value.len() as u32), warm));
 			self.write_sandbox_output(
 				memory,
 				out_ptr,
@@ -927,7 +989,7 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 			)?;
 			Ok(ReturnErrorCode::Success)
 		} else {
-			self.adjust_gas(charged, costs(0));
+			self.adjust_gas(charged, Self::storage_access_costs(costs(0), warm));
 			Ok(ReturnErrorCode::KeyNotFound)
 		}
 	}
@@ -954,7 +1016,8 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		} else {
 			self.ext.get_storage_size(&key)
 		};
-		self.adjust_gas(charged, costs(outcome.unwrap_or(0)));
+		let warm = !transient && self.ext.touch_storage(&key);
+		self.adjust_gas(charged, Self::storage_access_costs(costs(outcome.unwrap_or(0)), warm));
 		Ok(outcome.unwrap_or(SENTINEL))
 	}
 
@@ -982,9 +1045,10 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		} else {
 			self.ext.set_storage(&key, None, true)?
 		};
+		let warm = !transient && self.ext.touch_storage(&key);
 
 		if let crate::storage::WriteOutcome::Taken(value) = outcome {
-			self.adjust_gas(charged, costs(value.len() as u32));
+			self.adjust_gas(charged, Self::storage_access_costs(costs(value.len() as u32), warm));
 			self.write_sandbox_output(
 				memory,
 				out_ptr,
@@ -995,7 +1059,7 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 			)?;
 			Ok(ReturnErrorCode::Success)
 		} else {
-			self.adjust_gas(charged, costs(0));
+			self.adjust_gas(charged, Self::storage_access_costs(costs(0), warm));
 			Ok(ReturnErrorCode::KeyNotFound)
 		}
 	}
@@ -1014,7 +1078,7 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		output_len_ptr: u32,
 	) -> Result<ReturnErrorCode, TrapReason> {
 		let callee = match memory.read_h160(callee_ptr) {
-			Ok(callee) if is_precompile(&callee) => callee,
+			Ok(callee) if is_precompile::<E::T>(&callee) => callee,
 			Ok(callee) => {
 				self.charge_gas(call_type.cost())?;
 				callee
@@ -149,6 +149,21 @@ pub trait WeightInfo {
 	fn seal_sr25519_verify(n: u32, ) -> Weight;
 	fn seal_ecdsa_recover() -> Weight;
 	fn seal_ecdsa_to_eth_address() -> Weight;
+	fn ripemd_160(n: u32, ) -> Weight;
+	fn identity(n: u32, ) -> Weight;
+	fn modexp(n: u32, ) -> Weight;
+	fn bn128_add() -> Weight;
+	fn bn128_mul() -> Weight;
+	fn bn128_pairing(n: u32, ) -> Weight;
+	fn blake2f(n: u32, ) -> Weight;
+	fn erc20_balance_of() -> Weight;
+	fn erc20_allowance() -> Weight;
+	fn erc20_approve() -> Weight;
+	fn erc20_transfer() -> Weight;
+	fn erc20_transfer_from() -> Weight;
+	fn xcm_weigh_message(n: u32, ) -> Weight;
+	fn xcm_weight_to_asset_fee() -> Weight;
+	fn xcm_delivery_fees(n: u32, ) -> Weight;
 	fn seal_set_code_hash() -> Weight;
 	fn instr(r: u32, ) -> Weight;
 }
@@ -1034,6 +1049,151 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		// Minimum execution time: 12_258_000 picoseconds.
 		Weight::from_parts(12_400_000, 0)
 	}
+	/// The range of component `n` is `[0, 262144]`.
+	fn ripemd_160(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_184_000 picoseconds.
+		Weight::from_parts(3_264_402, 0)
+			// Standard Error: 2
+			.saturating_add(Weight::from_parts(3_836, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[0, 262144]`.
+	fn identity(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 312_000 picoseconds.
+		Weight::from_parts(1_004_238, 0)
+			// Standard Error: 0
+			.saturating_add(Weight::from_parts(115, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[200, 44733781]`.
+	fn modexp(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 6_214_000 picoseconds.
+		Weight::from_parts(5_682_933, 0)
+			// Standard Error: 31
+			.saturating_add(Weight::from_parts(24_617, 0).saturating_mul(n.into()))
+	}
+	fn bn128_add() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 15_318_000 picoseconds.
+		Weight::from_parts(15_782_000, 0)
+	}
+	fn bn128_mul() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_012_391_000 picoseconds.
+		Weight::from_parts(1_025_478_000, 0)
+	}
+	/// The range of component `n` is `[0, 20]`.
+	fn bn128_pairing(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 842_000 picoseconds.
+		Weight::from_parts(2_151_674_420, 0)
+			// Standard Error: 4_623_512
+			.saturating_add(Weight::from_parts(5_812_392_831, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[0, 65536]`.
+	fn blake2f(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_142_000 picoseconds.
+		Weight::from_parts(1_871_052, 0)
+			// Standard Error: 14
+			.saturating_add(Weight::from_parts(1_289, 0).saturating_mul(n.into()))
+	}
+	/// Storage: `System::Account` (r:1 w:0)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `Measured`)
+	fn erc20_balance_of() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `52`
+		//  Estimated: `3593`
+		// Minimum execution time: 6_312_000 picoseconds.
+		Weight::from_parts(6_589_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	/// Storage: `Revive::Erc20Allowances` (r:1 w:0)
+	/// Proof: `Revive::Erc20Allowances` (`max_values`: None, `max_size`: Some(124), added: 2599, mode: `Measured`)
+	fn erc20_allowance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `126`
+		//  Estimated: `3591`
+		// Minimum execution time: 7_104_000 picoseconds.
+		Weight::from_parts(7_388_000, 3591)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	/// Storage: `Revive::Erc20Allowances` (r:0 w:1)
+	/// Proof: `Revive::Erc20Allowances` (`max_values`: None, `max_size`: Some(124), added: 2599, mode: `Measured`)
+	fn erc20_approve() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_214_000 picoseconds.
+		Weight::from_parts(9_501_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `Measured`)
+	fn erc20_transfer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `52`
+		//  Estimated: `6196`
+		// Minimum execution time: 45_120_000 picoseconds.
+		Weight::from_parts(46_032_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Revive::Erc20Allowances` (r:1 w:1)
+	/// Proof: `Revive::Erc20Allowances` (`max_values`: None, `max_size`: Some(124), added: 2599, mode: `Measured`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `Measured`)
+	fn erc20_transfer_from() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `232`
+		//  Estimated: `6196`
+		// Minimum execution time: 52_840_000 picoseconds.
+		Weight::from_parts(53_978_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// The range of component `n` is `[0, 100]`.
+	fn xcm_weigh_message(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_954_000 picoseconds.
+		Weight::from_parts(2_187_331, 0)
+			// Standard Error: 212
+			.saturating_add(Weight::from_parts(41_706, 0).saturating_mul(n.into()))
+	}
+	fn xcm_weight_to_asset_fee() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_612_000 picoseconds.
+		Weight::from_parts(1_738_000, 0)
+	}
+	/// The range of component `n` is `[0, 100]`.
+	fn xcm_delivery_fees(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 2_071_000 picoseconds.
+		Weight::from_parts(2_319_845, 0)
+			// Standard Error: 231
+			.saturating_add(Weight::from_parts(43_118, 0).saturating_mul(n.into()))
+	}
 	/// Storage: `Revive::CodeInfoOf` (r:1 w:1)
 	/// Proof: `Revive::CodeInfoOf` (`max_values`: None, `max_size`: Some(96), added: 2571, mode: `Measured`)
 	fn seal_set_code_hash() -> Weight {
@@ -1937,6 +2097,151 @@ impl WeightInfo for () {
 		// Minimum execution time: 12_258_000 picoseconds.
 		Weight::from_parts(12_400_000, 0)
 	}
+	/// The range of component `n` is `[0, 262144]`.
+	fn ripemd_160(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_184_000 picoseconds.
+		Weight::from_parts(3_264_402, 0)
+			// Standard Error: 2
+			.saturating_add(Weight::from_parts(3_836, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[0, 262144]`.
+	fn identity(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 312_000 picoseconds.
+		Weight::from_parts(1_004_238, 0)
+			// Standard Error: 0
+			.saturating_add(Weight::from_parts(115, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[200, 44733781]`.
+	fn modexp(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 6_214_000 picoseconds.
+		Weight::from_parts(5_682_933, 0)
+			// Standard Error: 31
+			.saturating_add(Weight::from_parts(24_617, 0).saturating_mul(n.into()))
+	}
+	fn bn128_add() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 15_318_000 picoseconds.
+		Weight::from_parts(15_782_000, 0)
+	}
+	fn bn128_mul() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_012_391_000 picoseconds.
+		Weight::from_parts(1_025_478_000, 0)
+	}
+	/// The range of component `n` is `[0, 20]`.
+	fn bn128_pairing(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 842_000 picoseconds.
+		Weight::from_parts(2_151_674_420, 0)
+			// Standard Error: 4_623_512
+			.saturating_add(Weight::from_parts(5_812_392_831, 0).saturating_mul(n.into()))
+	}
+	/// The range of component `n` is `[0, 65536]`.
+	fn blake2f(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_142_000 picoseconds.
+		Weight::from_parts(1_871_052, 0)
+			// Standard Error: 14
+			.saturating_add(Weight::from_parts(1_289, 0).saturating_mul(n.into()))
+	}
+	/// Storage: `System::Account` (r:1 w:0)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `Measured`)
+	fn erc20_balance_of() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `52`
+		//  Estimated: `3593`
+		// Minimum execution time: 6_312_000 picoseconds.
+		Weight::from_parts(6_589_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	/// Storage: `Revive::Erc20Allowances` (r:1 w:0)
+	/// Proof: `Revive::Erc20Allowances` (`max_values`: None, `max_size`: Some(124), added: 2599, mode: `Measured`)
+	fn erc20_allowance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `126`
+		//  Estimated: `3591`
+		// Minimum execution time: 7_104_000 picoseconds.
+		Weight::from_parts(7_388_000, 3591)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	/// Storage: `Revive::Erc20Allowances` (r:0 w:1)
+	/// Proof: `Revive::Erc20Allowances` (`max_values`: None, `max_size`: Some(124), added: 2599, mode: `Measured`)
+	fn erc20_approve() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_214_000 picoseconds.
+		Weight::from_parts(9_501_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `Measured`)
+	fn erc20_transfer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `52`
+		//  Estimated: `6196`
+		// Minimum execution time: 45_120_000 picoseconds.
+		Weight::from_parts(46_032_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Revive::Erc20Allowances` (r:1 w:1)
+	/// Proof: `Revive::Erc20Allowances` (`max_values`: None, `max_size`: Some(124), added: 2599, mode: `Measured`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `Measured`)
+	fn erc20_transfer_from() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `232`
+		//  Estimated: `6196`
+		// Minimum execution time: 52_840_000 picoseconds.
+		Weight::from_parts(53_978_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// The range of component `n` is `[0, 100]`.
+	fn xcm_weigh_message(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_954_000 picoseconds.
+		Weight::from_parts(2_187_331, 0)
+			// Standard Error: 212
+			.saturating_add(Weight::from_parts(41_706, 0).saturating_mul(n.into()))
+	}
+	fn xcm_weight_to_asset_fee() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_612_000 picoseconds.
+		Weight::from_parts(1_738_000, 0)
+	}
+	/// The range of component `n` is `[0, 100]`.
+	fn xcm_delivery_fees(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 2_071_000 picoseconds.
+		Weight::from_parts(2_319_845, 0)
+			// Standard Error: 231
+			.saturating_add(Weight::from_parts(43_118, 0).saturating_mul(n.into()))
+	}
 	/// Storage: `Revive::CodeInfoOf` (r:1 w:1)
 	/// Proof: `Revive::CodeInfoOf` (`max_values`: None, `max_size`: Some(96), added: 2571, mode: `Measured`)
 	fn seal_set_code_hash() -> Weight {
@@ -129,6 +129,29 @@ pub fn expand_outer_dispatch(
 			}
 		}
 
+		impl #scrate::dispatch::Authorize for RuntimeCall {
+			fn authorize(
+				&self,
+				source: #scrate::pallet_prelude::TransactionSource,
+			) -> Option<#scrate::pallet_prelude::TransactionValidityWithRefund> {
+				match self {
+					#(
+						#pallet_attrs
+						#variant_patterns => #scrate::dispatch::Authorize::authorize(call, source),
+					)*
+				}
+			}
+
+			fn weight_of_authorize(&self) -> #scrate::pallet_prelude::Weight {
+				match self {
+					#(
+						#pallet_attrs
+						#variant_patterns => #scrate::dispatch::Authorize::weight_of_authorize(call),
+					)*
+				}
+			}
+		}
+
 		impl #scrate::traits::GetCallMetadata for RuntimeCall {
 			fn get_call_metadata(&self) -> #scrate::traits::CallMetadata {
 				use #scrate::traits::GetCallName;
@@ -112,6 +112,47 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 	}
 	debug_assert_eq!(fn_weight.len(), methods.len());
 
+	let mut authorize_fn = Vec::<TokenStream2>::new();
+	let mut authorize_fn_weight = Vec::<TokenStream2>::new();
+	for method in &methods {
+		let Some(authorize) = &method.authorize else {
+			authorize_fn.push(quote::quote!(None));
+			authorize_fn_weight.push(quote::quote!(#frame_support::pallet_prelude::Weight::zero()));
+			continue
+		};
+
+		let expr = &authorize.expr;
+		let args_type = method.args.iter().map(|(_, _, type_)| type_);
+		let args_name = method.args.iter().map(|(_, name, _)| name);
+		authorize_fn.push(quote::quote_spanned!(expr.span() => {
+			let authorize_fn: &dyn Fn(
+				#frame_support::pallet_prelude::TransactionSource,
+				#( &#args_type, )*
+			) -> #frame_support::pallet_prelude::TransactionValidityWithRefund = &(#expr);
+			Some(authorize_fn(source, #( #args_name, )*))
+		}));
+
+		authorize_fn_weight.push(match &authorize.weight {
+			CallWeightDef::DevModeDefault =>
+				quote::quote!(#frame_support::pallet_prelude::Weight::zero()),
+			CallWeightDef::Immediate(e) => e.into_token_stream(),
+			CallWeightDef::Inherited => {
+				let pallet_weight = def
+					.call
+					.as_ref()
+					.expect("we have methods; we have calls; qed")
+					.inherited_call_weight
+					.as_ref()
+					.expect("the parser prevents this");
+
+				// Expand `<<T as Config>::WeightInfo>::authorize_call_name()`.
+				let t = &pallet_weight.typename;
+				let n = quote::format_ident!("authorize_{}", method.name);
+				quote::quote!({ < #t > :: #n () })
+			},
+		});
+	}
+
 	let fn_doc = methods.iter().map(|method| &method.docs).collect::<Vec<_>>();
 
 	let args_name = methods
@@ -272,6 +313,53 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 		Err(e) => return e.into_compile_error(),
 	};
 
+	// The weight of every call is checked against the maximum extrinsic weight, evaluated at the
+	// `weight_bound` of its arguments. Arguments without an explicit `weight_bound` derive it from
+	// their type, calls with unbounded arguments are reported and skipped.
+	let weight_bound_checks = methods.iter().enumerate().map(|(i, method)| {
+		let cfg_attrs = &cfg_attrs[i];
+		let fn_name = &method.name;
+		let new_call_variant_fn_name = &new_call_variant_fn_name[i];
+		let arg_name = method.args.iter().map(|(_, name, _)| name).collect::<Vec<_>>();
+		let arg_bound = method.args.iter().zip(&method.arg_weight_bounds).map(
+			|((_, _, ty), bound)| match bound {
+				Some(bound) => quote::quote_spanned!(bound.span() => Some::<#ty>(#bound)),
+				None => quote::quote_spanned!(span => {
+					use #frame_support::traits::weight_bound_probe::*;
+					(&&&Probe::<#ty>::new()).weight_bound()
+				}),
+			},
+		);
+		let report_unbounded = (!arg_name.is_empty()).then(|| {
+			quote::quote_spanned!(span =>
+				( #( #arg_name, )* ) => {
+					let unbounded = [ #( (stringify!(#arg_name), #arg_name.is_none()) ),* ]
+						.into_iter()
+						.filter_map(|(name, is_unbounded)| is_unbounded.then_some(name))
+						.collect::<#frame_support::__private::Vec<_>>();
+					#frame_support::__private::log::warn!(
+						target: #frame_support::LOG_TARGET,
+						"The weight of call `{}::{}` is not checked, its arguments {:?} are \
+						unbounded. Declare their `#[pallet::weight_bound]`.",
+						<Self as #frame_support::traits::PalletInfoAccess>::name(),
+						stringify!(#fn_name),
+						unbounded,
+					);
+				},
+			)
+		});
+		quote::quote_spanned!(span =>
+			#cfg_attrs
+			match ( #( #arg_bound, )* ) {
+				( #( Some(#arg_name), )* ) => Self::__check_call_weight(
+					stringify!(#fn_name),
+					#call_ident::<#type_use_gen>::#new_call_variant_fn_name( #( #arg_name ),* ),
+				),
+				#report_unbounded
+			}
+		)
+	});
+
 	quote::quote_spanned!(span =>
 		#[doc(hidden)]
 		mod warnings {
@@ -404,6 +492,35 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 			}
 		}
 
+		impl<#type_impl_gen> #frame_support::dispatch::Authorize for #call_ident<#type_use_gen>
+			#where_clause
+		{
+			#[allow(unused_variables)]
+			fn authorize(
+				&self,
+				source: #frame_support::pallet_prelude::TransactionSource,
+			) -> Option<#frame_support::pallet_prelude::TransactionValidityWithRefund> {
+				match *self {
+					#(
+						#cfg_attrs
+						Self::#fn_name { #( #args_name_pattern_ref, )* } => #authorize_fn,
+					)*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+
+			#[allow(unused_variables)]
+			fn weight_of_authorize(&self) -> #frame_support::pallet_prelude::Weight {
+				match *self {
+					#(
+						#cfg_attrs
+						Self::#fn_name { #( #args_name_pattern_ref, )* } => #authorize_fn_weight,
+					)*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+		}
+
 		impl<#type_impl_gen> #frame_support::traits::GetCallName for #call_ident<#type_use_gen>
 			#where_clause
 		{
@@ -471,6 +588,42 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 			type RuntimeCall = #call_ident<#type_use_gen>;
 		}
 
+		#frame_support::std_enabled! {
+			impl<#type_impl_gen> #pallet_ident<#type_use_gen> #where_clause {
+				/// Check that the weight of every call evaluated at its `weight_bound`s fits into the
+				/// maximum weight of an extrinsic of its dispatch class.
+				#[allow(unused_variables)]
+				#[doc(hidden)]
+				pub fn __check_call_weight_bounds() {
+					#( #weight_bound_checks )*
+				}
+
+				#[allow(dead_code)]
+				fn __check_call_weight(name: &str, call: #call_ident<#type_use_gen>) {
+					let block_weights = <
+						<T as #frame_system::Config>::BlockWeights
+							as #frame_support::traits::Get<_>
+					>::get();
+					let info = #frame_support::dispatch::GetDispatchInfo::get_dispatch_info(&call);
+					let per_class = block_weights.get(info.class);
+					let weight = info.call_weight.saturating_add(per_class.base_extrinsic);
+					let max = per_class
+						.max_extrinsic
+						.or(per_class.max_total)
+						.unwrap_or(block_weights.max_block);
+					assert!(
+						weight.all_lte(max),
+						"The weight of call `{}::{}` at its `weight_bound`s ({}) exceeds the \
+						maximum extrinsic weight of its dispatch class ({})",
+						<Self as #frame_support::traits::PalletInfoAccess>::name(),
+						name,
+						weight,
+						max,
+					);
+				}
+			}
+		}
+
 		impl<#type_impl_gen> #pallet_ident<#type_use_gen> #where_clause {
 			#[allow(dead_code)]
 			#[doc(hidden)]
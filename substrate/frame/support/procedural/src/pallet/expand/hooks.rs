@@ -300,13 +300,15 @@ pub fn expand_hooks(def: &mut Def) -> proc_macro2::TokenStream {
 							Self as #frame_support::traits::Hooks<
 								#frame_system::pallet_prelude::BlockNumberFor::<T>
 							>
-						>::integrity_test()
+						>::integrity_test();
+						Self::__check_call_weight_bounds();
+						Self::__check_storage_prefixes();
 					});
 				}
 			}
 		}
 
-		#frame_support::try_runtime_enabled! {
+		#frame_support::try_state_enabled! {
 			impl<#type_impl_gen>
 				#frame_support::traits::TryState<#frame_system::pallet_prelude::BlockNumberFor::<T>>
 				for #pallet_ident<#type_use_gen> #where_clause
@@ -320,11 +322,11 @@ pub fn expand_hooks(def: &mut Def) -> proc_macro2::TokenStream {
 						"🩺 Running {:?} try-state checks",
 						#pallet_name,
 					);
-					<
+					Self::__try_state_indexed_storage_maps().and_then(|()| <
 						Self as #frame_support::traits::Hooks<
 							#frame_system::pallet_prelude::BlockNumberFor::<T>
 						>
-					>::try_state(n).inspect_err(|err| {
+					>::try_state(n)).inspect_err(|err| {
 						#frame_support::__private::log::error!(
 							target: #frame_support::LOG_TARGET,
 							"❌ {:?} try_state checks failed: {:?}",
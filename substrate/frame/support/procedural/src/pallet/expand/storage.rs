@@ -216,6 +216,27 @@ pub fn process_generics(def: &mut Def) -> syn::Result<Vec<ResultOnEmptyStructMet
 					let max_values = max_values.unwrap_or_else(|| default_max_values.clone());
 					args.args.push(syn::GenericArgument::Type(max_values));
 				},
+				StorageGenerics::IndexedMap {
+					hasher,
+					key,
+					value,
+					indexes,
+					query_kind,
+					on_empty,
+					max_values,
+				} => {
+					args.args.push(syn::GenericArgument::Type(hasher));
+					args.args.push(syn::GenericArgument::Type(key));
+					args.args.push(syn::GenericArgument::Type(value.clone()));
+					args.args.push(syn::GenericArgument::Type(indexes));
+					let mut query_kind = query_kind.unwrap_or_else(|| default_query_kind.clone());
+					set_result_query_type_parameter(&mut query_kind)?;
+					args.args.push(syn::GenericArgument::Type(query_kind));
+					let on_empty = on_empty.unwrap_or_else(|| default_on_empty(value));
+					args.args.push(syn::GenericArgument::Type(on_empty));
+					let max_values = max_values.unwrap_or_else(|| default_max_values.clone());
+					args.args.push(syn::GenericArgument::Type(max_values));
+				},
 				StorageGenerics::DoubleMap {
 					hasher1,
 					key1,
@@ -265,12 +286,15 @@ pub fn process_generics(def: &mut Def) -> syn::Result<Vec<ResultOnEmptyStructMet
 				Metadata::Value { .. } => (1, 2, 3),
 				Metadata::NMap { .. } | Metadata::CountedNMap { .. } => (2, 3, 4),
 				Metadata::Map { .. } | Metadata::CountedMap { .. } => (3, 4, 5),
+				Metadata::IndexedMap { .. } => (3, 5, 6),
 				Metadata::DoubleMap { .. } => (5, 6, 7),
 			};
 
 			if storage_def.use_default_hasher {
 				let hasher_indices: Vec<usize> = match storage_def.metadata {
-					Metadata::Map { .. } | Metadata::CountedMap { .. } => vec![1],
+					Metadata::Map { .. } |
+					Metadata::CountedMap { .. } |
+					Metadata::IndexedMap { .. } => vec![1],
 					Metadata::DoubleMap { .. } => vec![1, 3],
 					_ => vec![],
 				};
@@ -376,6 +400,14 @@ fn augment_final_docs(def: &mut Def) {
 			);
 			push_string_literal(&doc_line, storage);
 		},
+		Metadata::IndexedMap { key, value } => {
+			let doc_line = format!(
+				"Storage type is [`IndexedStorageMap`] with key type {} and value type {}.",
+				key.to_token_stream(),
+				value.to_token_stream()
+			);
+			push_string_literal(&doc_line, storage);
+		},
 	});
 }
 
@@ -541,6 +573,32 @@ pub fn expand_storages(def: &mut Def) -> proc_macro2::TokenStream {
 						}
 					)
 				},
+				Metadata::IndexedMap { key, value } => {
+					let query = match storage.query_kind.as_ref().expect("Checked by def") {
+						QueryKind::OptionQuery => quote::quote_spanned!(storage.attr_span =>
+							Option<#value>
+						),
+						QueryKind::ResultQuery(error_path, _) => {
+							quote::quote_spanned!(storage.attr_span =>
+								Result<#value, #error_path>
+							)
+						},
+						QueryKind::ValueQuery => quote::quote!(#value),
+					};
+					quote::quote_spanned!(storage.attr_span =>
+						#(#cfg_attrs)*
+						impl<#type_impl_gen> #pallet_ident<#type_use_gen> #completed_where_clause {
+							#[doc = #getter_doc_line]
+							pub fn #getter<KArg>(k: KArg) -> #query where
+								KArg: #frame_support::__private::codec::EncodeLike<#key>,
+							{
+								// NOTE: we can't use any trait here because IndexedStorageMap
+								// doesn't implement any.
+								<#full_ident>::get(k)
+							}
+						}
+					)
+				},
 				Metadata::DoubleMap { key1, key2, value } => {
 					let query = match storage.query_kind.as_ref().expect("Checked by def") {
 						QueryKind::OptionQuery => quote::quote_spanned!(storage.attr_span =>
@@ -898,7 +956,66 @@ pub fn expand_storages(def: &mut Def) -> proc_macro2::TokenStream {
 		)
 	};
 
+	let indexed_maps = def
+		.storages
+		.iter()
+		.filter(|storage| matches!(storage.metadata, Metadata::IndexedMap { .. }))
+		.map(|storage| {
+			let ident = &storage.ident;
+			let gen = &def.type_use_generics(storage.attr_span);
+			let cfg_attrs = &storage.cfg_attrs;
+			quote::quote_spanned!(storage.attr_span =>
+				#(#cfg_attrs)*
+				<#ident<#gen>>::do_try_state()?;
+			)
+		})
+		.collect::<Vec<_>>();
+
+	// The storage prefixes of the indexes of indexed storage maps are only known at runtime.
+	let check_storage_prefixes = if indexed_maps.is_empty() {
+		proc_macro2::TokenStream::new()
+	} else {
+		quote::quote!(
+			let mut names = Self::storage_metadata()
+				.entries
+				.into_iter()
+				.map(|entry| entry.name)
+				.collect::<#frame_support::__private::Vec<_>>();
+			names.sort();
+			if let Some(names) = names.windows(2).find(|names| names[0] == names[1]) {
+				panic!(
+					"Duplicate storage prefixes found for `{}`, used for an index of an indexed \
+					storage map",
+					names[0],
+				);
+			}
+		)
+	};
+
 	quote::quote!(
+		#frame_support::std_enabled! {
+			impl<#type_impl_gen> #pallet_ident<#type_use_gen> #completed_where_clause {
+				/// Check that the indexes of the indexed storage maps don't share their storage prefix
+				/// with another storage item of the pallet.
+				#[doc(hidden)]
+				pub fn __check_storage_prefixes() {
+					#check_storage_prefixes
+				}
+			}
+		}
+
+		#frame_support::try_state_enabled! {
+			impl<#type_impl_gen> #pallet_ident<#type_use_gen> #completed_where_clause {
+				/// Check the consistency of the indexes of the indexed storage maps.
+				#[doc(hidden)]
+				pub fn __try_state_indexed_storage_maps(
+				) -> Result<(), #frame_support::sp_runtime::TryRuntimeError> {
+					#( #indexed_maps )*
+					Ok(())
+				}
+			}
+		}
+
 		impl<#type_impl_gen> #pallet_ident<#type_use_gen>
 			#completed_where_clause
 		{
@@ -33,6 +33,9 @@ mod keyword {
 	syn::custom_keyword!(T);
 	syn::custom_keyword!(pallet);
 	syn::custom_keyword!(feeless_if);
+	syn::custom_keyword!(authorize);
+	syn::custom_keyword!(weight_of_authorize);
+	syn::custom_keyword!(weight_bound);
 }
 
 /// Definition of dispatchables typically `impl<T: Config> Pallet<T> { ... }`
@@ -70,6 +73,15 @@ pub enum CallWeightDef {
 	Inherited,
 }
 
+/// Definition of the `authorize` attribute of a dispatchable.
+#[derive(Clone)]
+pub struct AuthorizeDef {
+	/// The authorize function, given as `#[pallet::authorize(expr)]`.
+	pub expr: syn::Expr,
+	/// The weight of the authorize function.
+	pub weight: CallWeightDef,
+}
+
 /// Definition of dispatchable typically: `#[weight...] fn foo(origin .., param1: ...) -> ..`
 #[derive(Clone)]
 pub struct CallVariantDef {
@@ -77,6 +89,8 @@ pub struct CallVariantDef {
 	pub name: syn::Ident,
 	/// Information on args: `(is_compact, name, type)`
 	pub args: Vec<(bool, syn::Ident, Box<syn::Type>)>,
+	/// The optional `weight_bound` of each arg, in the same order as `args`.
+	pub arg_weight_bounds: Vec<Option<syn::Expr>>,
 	/// Weight for the call.
 	pub weight: CallWeightDef,
 	/// Call index of the dispatchable.
@@ -91,6 +105,8 @@ pub struct CallVariantDef {
 	pub cfg_attrs: Vec<syn::Attribute>,
 	/// The optional `feeless_if` attribute on the `pallet::call`.
 	pub feeless_check: Option<syn::ExprClosure>,
+	/// The optional `authorize` attribute on the `pallet::call`.
+	pub authorize: Option<AuthorizeDef>,
 	/// The return type of the call: `DispatchInfo` or `DispatchResultWithPostInfo`.
 	pub return_type: helper::CallReturnType,
 }
@@ -103,6 +119,10 @@ pub enum FunctionAttr {
 	Weight(syn::Expr),
 	/// Parse for `#[pallet::feeless_if(expr)]`
 	FeelessIf(Span, syn::ExprClosure),
+	/// Parse for `#[pallet::authorize(expr)]`
+	Authorize(Span, syn::Expr),
+	/// Parse for `#[pallet::weight_of_authorize(expr)]`
+	WeightOfAuthorize(Span, syn::Expr),
 }
 
 impl syn::parse::Parse for FunctionAttr {
@@ -142,17 +162,31 @@ impl syn::parse::Parse for FunctionAttr {
 					err
 				})?,
 			))
+		} else if lookahead.peek(keyword::authorize) {
+			content.parse::<keyword::authorize>()?;
+			let authorize_content;
+			syn::parenthesized!(authorize_content in content);
+			Ok(FunctionAttr::Authorize(authorize_content.span(), authorize_content.parse()?))
+		} else if lookahead.peek(keyword::weight_of_authorize) {
+			content.parse::<keyword::weight_of_authorize>()?;
+			let weight_content;
+			syn::parenthesized!(weight_content in content);
+			Ok(FunctionAttr::WeightOfAuthorize(weight_content.span(), weight_content.parse()?))
 		} else {
 			Err(lookahead.error())
 		}
 	}
 }
 
-/// Attribute for arguments in function in call impl block.
-/// Parse for `#[pallet::compact]|
-pub struct ArgAttrIsCompact;
+/// Attributes for arguments in function in call impl block.
+pub enum ArgAttr {
+	/// Parse for `#[pallet::compact]`
+	Compact,
+	/// Parse for `#[pallet::weight_bound(expr)]`
+	WeightBound(syn::Expr),
+}
 
-impl syn::parse::Parse for ArgAttrIsCompact {
+impl syn::parse::Parse for ArgAttr {
 	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
 		input.parse::<syn::Token![#]>()?;
 		let content;
@@ -160,8 +194,18 @@ impl syn::parse::Parse for ArgAttrIsCompact {
 		content.parse::<keyword::pallet>()?;
 		content.parse::<syn::Token![::]>()?;
 
-		content.parse::<keyword::compact>()?;
-		Ok(ArgAttrIsCompact)
+		let lookahead = content.lookahead1();
+		if lookahead.peek(keyword::compact) {
+			content.parse::<keyword::compact>()?;
+			Ok(ArgAttr::Compact)
+		} else if lookahead.peek(keyword::weight_bound) {
+			content.parse::<keyword::weight_bound>()?;
+			let bound_content;
+			syn::parenthesized!(bound_content in content);
+			Ok(ArgAttr::WeightBound(bound_content.parse::<syn::Expr>()?))
+		} else {
+			Err(lookahead.error())
+		}
 	}
 }
 
@@ -269,6 +313,8 @@ impl CallDef {
 				let mut call_idx_attrs = vec![];
 				let mut weight_attrs = vec![];
 				let mut feeless_attrs = vec![];
+				let mut authorize_attrs = vec![];
+				let mut weight_of_authorize_attrs = vec![];
 				for attr in helper::take_item_pallet_attrs(&mut method.attrs)?.into_iter() {
 					match attr {
 						FunctionAttr::CallIndex(_) => {
@@ -280,6 +326,12 @@ impl CallDef {
 						FunctionAttr::FeelessIf(span, _) => {
 							feeless_attrs.push((span, attr));
 						},
+						FunctionAttr::Authorize(span, expr) => {
+							authorize_attrs.push((span, expr));
+						},
+						FunctionAttr::WeightOfAuthorize(span, expr) => {
+							weight_of_authorize_attrs.push((span, expr));
+						},
 					}
 				}
 
@@ -340,6 +392,7 @@ impl CallDef {
 				}
 
 				let mut args = vec![];
+				let mut arg_weight_bounds = vec![];
 				for arg in method.sig.inputs.iter_mut().skip(1) {
 					let arg = if let syn::FnArg::Typed(arg) = arg {
 						arg
@@ -347,12 +400,20 @@ impl CallDef {
 						unreachable!("Only first argument can be receiver");
 					};
 
-					let arg_attrs: Vec<ArgAttrIsCompact> =
-						helper::take_item_pallet_attrs(&mut arg.attrs)?;
-
-					if arg_attrs.len() > 1 {
-						let msg = "Invalid pallet::call, argument has too many attributes";
-						return Err(syn::Error::new(arg.span(), msg));
+					let arg_attrs: Vec<ArgAttr> = helper::take_item_pallet_attrs(&mut arg.attrs)?;
+
+					let mut is_compact = false;
+					let mut weight_bound = None;
+					for attr in arg_attrs {
+						match attr {
+							ArgAttr::Compact if !is_compact => is_compact = true,
+							ArgAttr::WeightBound(expr) if weight_bound.is_none() =>
+								weight_bound = Some(expr),
+							_ => {
+								let msg = "Invalid pallet::call, argument has too many attributes";
+								return Err(syn::Error::new(arg.span(), msg));
+							},
+						}
 					}
 
 					let arg_ident = if let syn::Pat::Ident(pat) = &*arg.pat {
@@ -362,7 +423,8 @@ impl CallDef {
 						return Err(syn::Error::new(arg.pat.span(), msg));
 					};
 
-					args.push((!arg_attrs.is_empty(), arg_ident, arg.ty.clone()));
+					args.push((is_compact, arg_ident, arg.ty.clone()));
+					arg_weight_bounds.push(weight_bound);
 				}
 
 				let docs = get_doc_literals(&method.attrs);
@@ -435,16 +497,48 @@ impl CallDef {
 					}
 				}
 
+				if authorize_attrs.len() > 1 {
+					let msg = "Invalid pallet::call, there can only be one authorize attribute";
+					return Err(syn::Error::new(authorize_attrs[1].0, msg));
+				}
+				if weight_of_authorize_attrs.len() > 1 {
+					let msg =
+						"Invalid pallet::call, there can only be one weight_of_authorize attribute";
+					return Err(syn::Error::new(weight_of_authorize_attrs[1].0, msg));
+				}
+				let authorize = match (authorize_attrs.pop(), weight_of_authorize_attrs.pop()) {
+					(None, None) => None,
+					(None, Some((span, _))) => {
+						let msg = "Invalid pallet::call, weight_of_authorize attribute must be \
+							used with an authorize attribute";
+						return Err(syn::Error::new(span, msg));
+					},
+					(Some((_, expr)), Some((_, weight))) =>
+						Some(AuthorizeDef { expr, weight: CallWeightDef::Immediate(weight) }),
+					(Some((_, expr)), None) if inherited_call_weight.is_some() =>
+						Some(AuthorizeDef { expr, weight: CallWeightDef::Inherited }),
+					(Some((_, expr)), None) if dev_mode =>
+						Some(AuthorizeDef { expr, weight: CallWeightDef::DevModeDefault }),
+					(Some((span, _)), None) => {
+						let msg = "A pallet::authorize requires either a concrete \
+							`#[pallet::weight_of_authorize($expr)]` or an inherited weight from the \
+							`#[pallet:call(weight($type))]` attribute, but none were given.";
+						return Err(syn::Error::new(span, msg));
+					},
+				};
+
 				methods.push(CallVariantDef {
 					name: method.sig.ident.clone(),
 					weight,
 					call_index: final_index,
 					explicit_call_index,
 					args,
+					arg_weight_bounds,
 					docs,
 					attrs: method.attrs.clone(),
 					cfg_attrs,
 					feeless_check,
+					authorize,
 					return_type,
 				});
 			} else {
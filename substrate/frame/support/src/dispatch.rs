@@ -29,6 +29,7 @@ use sp_runtime::{
 	traits::{
 		Dispatchable, ExtensionPostDispatchWeightHandler, RefundWeight, TransactionExtension,
 	},
+	transaction_validity::{TransactionSource, TransactionValidityError, ValidTransaction},
 	DispatchError, RuntimeDebug,
 };
 use sp_weights::Weight;
@@ -71,6 +72,28 @@ pub trait CheckIfFeeless {
 	fn is_feeless(&self, origin: &Self::Origin) -> bool;
 }
 
+/// The result of authorizing a dispatchable: the validity of the transaction and the weight
+/// to refund from [`Authorize::weight_of_authorize`].
+pub type TransactionValidityWithRefund =
+	Result<(ValidTransaction, Weight), TransactionValidityError>;
+
+/// Means of authorizing a dispatchable that is submitted without a signed origin.
+///
+/// This is automatically implemented for all dispatchables during pallet expansion.
+/// If a call is marked by [`#[pallet::authorize]`](`macro@frame_support_procedural::authorize`)
+/// attribute, the corresponding function is called.
+pub trait Authorize {
+	/// Authorize the dispatchable.
+	///
+	/// Returns `None` if the dispatchable doesn't define any authorization. Otherwise returns the
+	/// validity of the transaction, in which case the dispatchable is dispatched with the
+	/// [`RawOrigin::Authorized`] origin if valid.
+	fn authorize(&self, source: TransactionSource) -> Option<TransactionValidityWithRefund>;
+
+	/// The weight of [`Self::authorize`].
+	fn weight_of_authorize(&self) -> Weight;
+}
+
 /// Origin for the System pallet.
 #[derive(
 	PartialEq,
@@ -92,6 +115,10 @@ pub enum RawOrigin<AccountId> {
 	/// * included and agreed upon by the validators anyway,
 	/// * or unsigned transaction validated by a pallet.
 	None,
+	/// It is signed by nobody, and the transaction was authorized by the dispatchable itself.
+	///
+	/// See [`Authorize`].
+	Authorized,
 }
 
 impl<AccountId> From<Option<AccountId>> for RawOrigin<AccountId> {
@@ -121,6 +148,11 @@ impl<AccountId> RawOrigin<AccountId> {
 	pub fn is_none(&self) -> bool {
 		matches!(&self, Self::None)
 	}
+
+	/// Returns `true` if `self` is `Authorized`, `false` otherwise.
+	pub fn is_authorized(&self) -> bool {
+		matches!(&self, Self::Authorized)
+	}
 }
 
 /// A type that can be used as a parameter in a dispatchable function.
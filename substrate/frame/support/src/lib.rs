@@ -891,7 +891,10 @@ pub mod testing_prelude {
 pub mod pallet_prelude {
 	pub use crate::{
 		defensive, defensive_assert,
-		dispatch::{DispatchClass, DispatchResult, DispatchResultWithPostInfo, Parameter, Pays},
+		dispatch::{
+			DispatchClass, DispatchResult, DispatchResultWithPostInfo, Parameter, Pays,
+			TransactionValidityWithRefund,
+		},
 		ensure,
 		inherent::{InherentData, InherentIdentifier, ProvideInherent},
 		storage,
@@ -900,8 +903,9 @@ pub mod pallet_prelude {
 			bounded_btree_set::BoundedBTreeSet,
 			bounded_vec::BoundedVec,
 			types::{
-				CountedStorageMap, CountedStorageNMap, Key as NMapKey, OptionQuery, ResultQuery,
-				StorageDoubleMap, StorageMap, StorageNMap, StorageValue, ValueQuery,
+				CountedStorageMap, CountedStorageNMap, IndexedStorageMap, Key as NMapKey,
+				OptionQuery, ResultQuery, StorageDoubleMap, StorageMap, StorageNMap, StorageValue,
+				ValueQuery,
 			},
 			weak_bounded_vec::WeakBoundedVec,
 			StorageList,
@@ -1987,6 +1991,75 @@ pub mod pallet_macros {
 	/// [`pallet_skip_feeless_payment::CheckIfFeeless`]: ../../pallet_skip_feeless_payment/struct.SkipCheckIfFeeless.html
 	pub use frame_support_procedural::feeless_if;
 
+	/// Allows a dispatchable to be submitted without a signed origin, by authorizing it
+	/// itself.
+	///
+	/// The attribute takes a function, usually a closure, that receives the
+	/// [`TransactionSource`](crate::pallet_prelude::TransactionSource) and a reference to each
+	/// argument of the dispatchable, and returns a
+	/// [`TransactionValidityWithRefund`](crate::pallet_prelude::TransactionValidityWithRefund):
+	/// the validity of the transaction (priority, longevity, provided and required tags) and
+	/// the weight to refund from the weight of the authorization.
+	///
+	/// The weight of the authorization is given with `#[pallet::weight_of_authorize($expr)]`.
+	/// If omitted, it is inherited from the `#[pallet::call(weight($type))]` attribute as
+	/// `$type::authorize_$call_name()`.
+	///
+	/// ### Example
+	///
+	/// ```
+	/// #[frame_support::pallet(dev_mode)]
+	/// mod pallet {
+	/// # 	use frame_support::pallet_prelude::*;
+	/// # 	use frame_system::pallet_prelude::*;
+	/// #
+	/// 	#[pallet::pallet]
+	/// 	pub struct Pallet<T>(_);
+	///
+	/// 	#[pallet::call]
+	/// 	impl<T: Config> Pallet<T> {
+	/// 		#[pallet::call_index(0)]
+	/// 		#[pallet::authorize(|_source, value: &u32| {
+	/// 			if *value == 0 {
+	/// 				return Err(InvalidTransaction::Call.into())
+	/// 			}
+	/// 			let valid = ValidTransaction::with_tag_prefix("Pallet")
+	/// 				.and_provides(value)
+	/// 				.build()?;
+	/// 			Ok((valid, Weight::zero()))
+	/// 		})]
+	/// 		#[pallet::weight_of_authorize(Weight::from_parts(1_000, 0))]
+	/// 		pub fn submit(origin: OriginFor<T>, value: u32) -> DispatchResult {
+	/// 			ensure_authorized(origin)?;
+	/// 			unimplemented!()
+	/// 		}
+	/// 	}
+	/// #
+	/// # 	#[pallet::config]
+	/// # 	pub trait Config: frame_system::Config {}
+	/// }
+	/// ```
+	///
+	/// The authorization is only checked for general transactions, and only if the runtime
+	/// uses the [`frame_system::AuthorizeCall`] transaction extension. A valid authorization
+	/// dispatches the call with the `Authorized` system origin, which can be checked with
+	/// [`frame_system::ensure_authorized`].
+	///
+	/// ### Macro expansion
+	///
+	/// The macro implements the [`Authorize`](crate::dispatch::Authorize) trait on the
+	/// dispatchable and calls the corresponding function in the implementation.
+	///
+	/// [`frame_system::AuthorizeCall`]: ../../frame_system/struct.AuthorizeCall.html
+	/// [`frame_system::ensure_authorized`]: ../../frame_system/fn.ensure_authorized.html
+	pub use frame_support_procedural::authorize;
+
+	/// Defines the weight of the [`authorize`] function of a dispatchable.
+	///
+	/// The expression must evaluate to a [`Weight`](crate::pallet_prelude::Weight). The
+	/// arguments of the dispatchable are in scope as references.
+	pub use frame_support_procedural::weight_of_authorize;
+
 	/// Allows defining an error enum that will be returned from the dispatchable when an error
 	/// occurs.
 	///
@@ -2339,6 +2412,50 @@ pub mod pallet_macros {
 	/// }
 	pub use frame_support_procedural::compact;
 
+	/// Declares the value of an argument of a [`call`] function for which the weight of the
+	/// call is maximal.
+	///
+	/// The generated [`IntegrityTest`](frame_support::traits::IntegrityTest) of the pallet
+	/// evaluates the weight of every call at the `weight_bound` of its arguments and panics if
+	/// it, together with the `base_extrinsic` weight, exceeds the `max_extrinsic` weight of
+	/// the dispatch class of the call, on either ref-time or proof size. This catches calls
+	/// that could never be included in a block.
+	///
+	/// Arguments without a `weight_bound` derive it from their type:
+	/// - types implementing [`WeightBound`](frame_support::traits::WeightBound), such as
+	///   [`BoundedVec`](frame_support::BoundedVec) filled up to its bound, use it.
+	/// - other types implementing
+	///   [`MaxEncodedLen`](frame_support::pallet_prelude::MaxEncodedLen) are decoded from
+	///   zeroes, assuming the weight only depends on their size. Arguments whose value
+	///   determines the weight, like an item count, must declare their `weight_bound`.
+	/// - the remaining types are unbounded. The weight of a call with unbounded arguments is
+	///   not checked and a warning naming them is logged.
+	///
+	/// ```
+	/// #[frame_support::pallet(dev_mode)]
+	/// pub mod custom_pallet {
+	/// #   use frame_support::pallet_prelude::*;
+	/// #   use frame_system::pallet_prelude::*;
+	/// #   #[pallet::config]
+	/// #   pub trait Config: frame_system::Config {
+	/// #       type MaxItems: Get<u32>;
+	/// #   }
+	/// #   #[pallet::pallet]
+	/// #   pub struct Pallet<T>(_);
+	///     #[pallet::call]
+	///     impl<T: Config> Pallet<T> {
+	///         #[pallet::weight(Weight::from_parts(1_000 * *items as u64, 0))]
+	///         pub fn some_dispatchable(
+	///             _origin: OriginFor<T>,
+	///             #[pallet::weight_bound(T::MaxItems::get())] items: u32,
+	///         ) -> DispatchResult {
+	///             Ok(())
+	///         }
+	///     }
+	/// }
+	/// ```
+	pub use frame_support_procedural::weight_bound;
+
 	/// Allows you to define the genesis configuration for the pallet.
 	///
 	/// Item is defined as either an enum or a struct. It needs to be public and implement the
@@ -2453,6 +2570,7 @@ pub mod pallet_macros {
 	/// * [`StorageValue`](crate::storage::types::StorageValue)
 	/// * [`StorageMap`](crate::storage::types::StorageMap)
 	/// * [`CountedStorageMap`](crate::storage::types::CountedStorageMap)
+	/// * [`IndexedStorageMap`](crate::storage::types::IndexedStorageMap)
 	/// * [`StorageDoubleMap`](crate::storage::types::StorageDoubleMap)
 	/// * [`StorageNMap`](crate::storage::types::StorageNMap)
 	/// * [`CountedStorageNMap`](crate::storage::types::CountedStorageNMap)
@@ -2737,6 +2855,8 @@ pub use frame_support_procedural::register_default_impl;
 sp_core::generate_feature_enabled_macro!(std_enabled, feature = "std", $);
 // Generate a macro that will enable/disable code based on `try-runtime` feature being active.
 sp_core::generate_feature_enabled_macro!(try_runtime_enabled, feature = "try-runtime", $);
+// Generate a macro that will enable/disable code based on the `try_state` hooks being available.
+sp_core::generate_feature_enabled_macro!(try_state_enabled, any(feature = "try-runtime", feature = "try-state"), $);
 sp_core::generate_feature_enabled_macro!(try_runtime_or_std_enabled, any(feature = "try-runtime", feature = "std"), $);
 sp_core::generate_feature_enabled_macro!(try_runtime_and_std_not_enabled, all(not(feature = "try-runtime"), not(feature = "std")), $);
 
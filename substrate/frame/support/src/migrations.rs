@@ -17,15 +17,18 @@
 
 use crate::{
 	defensive,
-	storage::{storage_prefix, transactional::with_transaction_opaque_err},
+	storage::{
+		storage_prefix, transactional::with_transaction_opaque_err, unhashed, IterableStorageMap,
+		KeyPrefixIterator, StorageMap,
+	},
 	traits::{
 		Defensive, GetStorageVersion, NoStorageVersionSet, PalletInfoAccess, SafeMode,
-		StorageVersion,
+		StorageVersion, TypedGet,
 	},
 	weights::{RuntimeDbWeight, Weight, WeightMeter},
 };
 use alloc::vec::Vec;
-use codec::{Decode, Encode, MaxEncodedLen};
+use codec::{Decode, Encode, FullCodec, MaxEncodedLen};
 use core::marker::PhantomData;
 use impl_trait_for_tuples::impl_for_tuples;
 use sp_arithmetic::traits::Bounded;
@@ -1027,6 +1030,431 @@ impl SteppedMigrations for Tuple {
 	}
 }
 
+/// Translation of the values of a storage map, used by [`TranslateMap`].
+pub trait MapTranslation {
+	/// The key type of the map.
+	type Key: FullCodec + MaxEncodedLen;
+	/// The value type before the migration.
+	type Old: Decode;
+	/// The value type after the migration.
+	type New: FullCodec;
+
+	/// Translate the value stored under `key`.
+	///
+	/// Returning `None` removes the entry from the map.
+	fn translate(key: &Self::Key, old: Self::Old) -> Option<Self::New>;
+}
+
+/// A [`SteppedMigration`] that translates every value of the storage map `Map` with the
+/// [`MapTranslation`] `T`.
+///
+/// The cursor is the last translated key. Every translated entry is charged with one read and one
+/// write of `DbWeight`. An entry whose key or old value cannot be decoded fails the migration,
+/// since no cursor can be derived from it.
+///
+/// # Example
+///
+/// ```ignore
+/// pub struct U32ToU64;
+/// impl MapTranslation for U32ToU64 {
+/// 	type Key = AccountId;
+/// 	type Old = u32;
+/// 	type New = u64;
+///
+/// 	fn translate(_key: &AccountId, old: u32) -> Option<u64> {
+/// 		Some(old.into())
+/// 	}
+/// }
+///
+/// parameter_types! {
+/// 	pub const TranslateBalancesId: MigrationId<9> =
+/// 		MigrationId { pallet_id: *b"MyBalance", version_from: 0, version_to: 1 };
+/// }
+///
+/// pub type Migrations =
+/// 	TranslateMap<TranslateBalancesId, Balances<Runtime>, U32ToU64, RocksDbWeight>;
+/// ```
+pub struct TranslateMap<Id, Map, T, DbWeight>(PhantomData<(Id, Map, T, DbWeight)>);
+
+impl<Id, Map, T, DbWeight> SteppedMigration for TranslateMap<Id, Map, T, DbWeight>
+where
+	Id: TypedGet,
+	Id::Type: FullCodec + MaxEncodedLen,
+	Map: IterableStorageMap<T::Key, T::New> + StorageMap<T::Key, T::New>,
+	T: MapTranslation,
+	DbWeight: Get<RuntimeDbWeight>,
+{
+	type Cursor = T::Key;
+	type Identifier = Id::Type;
+
+	fn id() -> Self::Identifier {
+		Id::get()
+	}
+
+	fn step(
+		mut cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+		let required = DbWeight::get().reads_writes(1, 1);
+		if meter.remaining().any_lt(required) {
+			return Err(SteppedMigrationError::InsufficientWeight { required })
+		}
+
+		loop {
+			if meter.try_consume(required).is_err() {
+				break
+			}
+
+			let previous = cursor.as_ref().map(|key| Map::hashed_key_for(key));
+			let mut translated = None;
+			let next = Map::translate_next::<T::Old, _>(previous, |key, old| {
+				let new = T::translate(&key, old);
+				translated = Some(key);
+				new
+			});
+
+			match (next, translated) {
+				(None, _) => return Ok(None),
+				(Some(_), Some(key)) => cursor = Some(key),
+				(Some(raw), None) => {
+					log::error!(
+						"Failed to translate the map entry at {}",
+						array_bytes::bytes2hex("0x", &raw)
+					);
+					return Err(SteppedMigrationError::Failed)
+				},
+			}
+		}
+
+		Ok(cursor)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok((Map::iter_keys().count() as u32).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let count_before = u32::decode(&mut &state[..])
+			.map_err(|_| "Failed to decode the number of entries before the migration")?;
+
+		let mut count_after = 0u32;
+		for key in Map::iter_keys() {
+			Map::try_get(&key).map_err(|_| "Found a value that was not translated")?;
+			count_after += 1;
+		}
+		crate::ensure!(count_after <= count_before, "The number of entries increased");
+		Ok(())
+	}
+}
+
+/// The cursor of a [`RehashMap`] migration.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen)]
+pub enum RehashCursor<K> {
+	/// The last visited entry was moved from the old map.
+	Moved(K),
+	/// The last visited entry was skipped since it is already stored with the hasher of the new
+	/// map.
+	Skipped(K),
+}
+
+/// A [`SteppedMigration`] that moves every entry of the storage map `Old` to the storage map `New`.
+///
+/// This can be used to change the hasher of a map, in which case `Old` and `New` are aliases of the
+/// same storage item with the old and new hasher. Both maps must use a reversible hasher. Entries
+/// whose key does not hash to their raw storage key with the hasher of `Old` must be stored with
+/// the hasher of `New` and are skipped. Any other entry fails the migration.
+///
+/// The cursor is the last visited key, see [`RehashCursor`]. Every visited entry is charged with
+/// two reads and two writes of `DbWeight`.
+///
+/// # Example
+///
+/// ```ignore
+/// pub mod v0 {
+/// 	#[storage_alias]
+/// 	pub type Accounts<T: Config> = StorageMap<Pallet<T>, Twox64Concat, AccountIdOf<T>, u32>;
+/// }
+///
+/// pub type Migrations = RehashMap<
+/// 	RehashAccountsId,
+/// 	v0::Accounts<Runtime>,
+/// 	pallet::Accounts<Runtime>,
+/// 	AccountId,
+/// 	u32,
+/// 	RocksDbWeight,
+/// >;
+/// ```
+pub struct RehashMap<Id, Old, New, K, V, DbWeight>(PhantomData<(Id, Old, New, K, V, DbWeight)>);
+
+impl<Id, Old, New, K, V, DbWeight> SteppedMigration for RehashMap<Id, Old, New, K, V, DbWeight>
+where
+	Id: TypedGet,
+	Id::Type: FullCodec + MaxEncodedLen,
+	Old: IterableStorageMap<K, V, KeyIterator = KeyPrefixIterator<K>> + StorageMap<K, V>,
+	New: IterableStorageMap<K, V, KeyIterator = KeyPrefixIterator<K>> + StorageMap<K, V>,
+	K: FullCodec + MaxEncodedLen,
+	V: FullCodec,
+	DbWeight: Get<RuntimeDbWeight>,
+{
+	type Cursor = RehashCursor<K>;
+	type Identifier = Id::Type;
+
+	fn id() -> Self::Identifier {
+		Id::get()
+	}
+
+	fn step(
+		mut cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+		let required = DbWeight::get().reads_writes(2, 2);
+		if meter.remaining().any_lt(required) {
+			return Err(SteppedMigrationError::InsufficientWeight { required })
+		}
+
+		let mut previous = cursor.as_ref().map(|cursor| match cursor {
+			RehashCursor::Moved(key) => Old::hashed_key_for(key),
+			RehashCursor::Skipped(key) => New::hashed_key_for(key),
+		});
+		loop {
+			if meter.try_consume(required).is_err() {
+				break
+			}
+
+			let start = previous.take();
+			let mut iter = match &start {
+				Some(raw) => Old::iter_keys_from(raw.clone()),
+				None => Old::iter_keys(),
+			};
+			let Some(key) = iter.next() else { return Ok(None) };
+			let raw = iter.last_raw_key().to_vec();
+
+			if Old::hashed_key_for(&key) == raw {
+				if let Some(value) = unhashed::take::<V>(&raw) {
+					New::insert(&key, value);
+				}
+				cursor = Some(RehashCursor::Moved(key));
+			} else {
+				// Decode the same raw key with the hasher of the new map.
+				let mut iter = match start {
+					Some(raw) => New::iter_keys_from(raw),
+					None => New::iter_keys(),
+				};
+				match iter.next() {
+					Some(key) if iter.last_raw_key() == raw && New::hashed_key_for(&key) == raw =>
+						cursor = Some(RehashCursor::Skipped(key)),
+					_ => {
+						log::error!(
+							"Failed to rehash the map entry at {}",
+							array_bytes::bytes2hex("0x", &raw)
+						);
+						return Err(SteppedMigrationError::Failed)
+					},
+				}
+			}
+			previous = Some(raw);
+		}
+
+		Ok(cursor)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok(Old::iter_keys().collect::<Vec<_>>().encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let keys = Vec::<K>::decode(&mut &state[..])
+			.map_err(|_| "Failed to decode the keys before the migration")?;
+
+		for key in keys {
+			crate::ensure!(!Old::contains_key(&key), "Found an entry that was not moved");
+			crate::ensure!(New::contains_key(&key), "Found an entry that went missing");
+		}
+		Ok(())
+	}
+}
+
+/// A [`SteppedMigration`] that moves the storage item `Item` from the pallet `FromPallet` to the
+/// pallet `ToPallet`.
+///
+/// Works for storage items of any kind, since the raw keys are moved without being decoded. Every
+/// moved key is charged with one read and two writes of `DbWeight`.
+///
+/// # Example
+///
+/// ```ignore
+/// parameter_types! {
+/// 	pub const OldPallet: &'static str = "OldPallet";
+/// 	pub const NewPallet: &'static str = "NewPallet";
+/// 	pub const Accounts: &'static str = "Accounts";
+/// }
+///
+/// pub type Migrations =
+/// 	MoveStorage<MoveAccountsId, OldPallet, NewPallet, Accounts, RocksDbWeight>;
+/// ```
+pub struct MoveStorage<Id, FromPallet, ToPallet, Item, DbWeight>(
+	PhantomData<(Id, FromPallet, ToPallet, Item, DbWeight)>,
+);
+
+impl<Id, FromPallet, ToPallet, Item, DbWeight> MoveStorage<Id, FromPallet, ToPallet, Item, DbWeight>
+where
+	FromPallet: Get<&'static str>,
+	ToPallet: Get<&'static str>,
+	Item: Get<&'static str>,
+{
+	fn prefixes() -> ([u8; 32], [u8; 32]) {
+		let item = Item::get().as_bytes();
+		(
+			storage_prefix(FromPallet::get().as_bytes(), item),
+			storage_prefix(ToPallet::get().as_bytes(), item),
+		)
+	}
+}
+
+impl<Id, FromPallet, ToPallet, Item, DbWeight> SteppedMigration
+	for MoveStorage<Id, FromPallet, ToPallet, Item, DbWeight>
+where
+	Id: TypedGet,
+	Id::Type: FullCodec + MaxEncodedLen,
+	FromPallet: Get<&'static str>,
+	ToPallet: Get<&'static str>,
+	Item: Get<&'static str>,
+	DbWeight: Get<RuntimeDbWeight>,
+{
+	// Moved keys are removed, so the migration always continues from the start of the prefix.
+	type Cursor = ();
+	type Identifier = Id::Type;
+
+	fn id() -> Self::Identifier {
+		Id::get()
+	}
+
+	fn step(
+		cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+		let required = DbWeight::get().reads_writes(1, 2);
+		if meter.remaining().any_lt(required) {
+			return Err(SteppedMigrationError::InsufficientWeight { required })
+		}
+
+		let (from, to) = Self::prefixes();
+		let move_key = |key: &[u8]| {
+			if let Some(value) = unhashed::get_raw(key) {
+				unhashed::put_raw(&[&to[..], &key[from.len()..]].concat(), &value);
+				unhashed::kill(key);
+			}
+		};
+
+		// The key of a storage value is the prefix itself, which is not returned by `next_key`.
+		if cursor.is_none() {
+			meter.consume(required);
+			move_key(&from);
+		}
+
+		let mut previous = from.to_vec();
+		loop {
+			if meter.try_consume(required).is_err() {
+				return Ok(Some(()))
+			}
+
+			let Some(key) = sp_io::storage::next_key(&previous).filter(|k| k.starts_with(&from))
+			else {
+				return Ok(None)
+			};
+			move_key(&key);
+			previous = key;
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		let (from, to) = Self::prefixes();
+		Ok((count_keys(&from), count_keys(&to)).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let (from_before, to_before) = <(u32, u32)>::decode(&mut &state[..])
+			.map_err(|_| "Failed to decode the number of keys before the migration")?;
+
+		let (from, to) = Self::prefixes();
+		crate::ensure!(count_keys(&from) == 0, "Found keys that were not moved");
+		crate::ensure!(
+			count_keys(&to) == from_before + to_before,
+			"The number of moved keys does not match"
+		);
+		Ok(())
+	}
+}
+
+/// A [`SteppedMigration`] that removes all keys starting with the hashed `Prefix`.
+///
+/// Every removed key is charged with one read and one write of `DbWeight`.
+///
+/// # Example
+///
+/// ```ignore
+/// parameter_types! {
+/// 	pub OldPalletPrefix: Vec<u8> = twox_128(b"OldPallet").to_vec();
+/// }
+///
+/// pub type Migrations = ClearPrefix<ClearOldPalletId, OldPalletPrefix, RocksDbWeight>;
+/// ```
+pub struct ClearPrefix<Id, Prefix, DbWeight>(PhantomData<(Id, Prefix, DbWeight)>);
+
+impl<Id, Prefix, DbWeight> SteppedMigration for ClearPrefix<Id, Prefix, DbWeight>
+where
+	Id: TypedGet,
+	Id::Type: FullCodec + MaxEncodedLen,
+	Prefix: Get<Vec<u8>>,
+	DbWeight: Get<RuntimeDbWeight>,
+{
+	// Removed keys are gone, so the migration always continues from the start of the prefix.
+	type Cursor = ();
+	type Identifier = Id::Type;
+
+	fn id() -> Self::Identifier {
+		Id::get()
+	}
+
+	fn step(
+		_cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+		let required = DbWeight::get().reads_writes(1, 1);
+		if meter.remaining().any_lt(required) {
+			return Err(SteppedMigrationError::InsufficientWeight { required })
+		}
+
+		let limit = meter
+			.remaining()
+			.checked_div_per_component(&required)
+			.map_or(u32::MAX, |limit| limit.try_into().unwrap_or(u32::MAX));
+		let result = unhashed::clear_prefix(&Prefix::get(), Some(limit), None);
+		meter.consume(required.saturating_mul(result.loops.into()));
+
+		Ok(result.maybe_cursor.map(|_| ()))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		crate::ensure!(count_keys(&Prefix::get()) == 0, "Found keys that were not removed");
+		Ok(())
+	}
+}
+
+/// The number of keys starting with `prefix`, including `prefix` itself.
+#[cfg(feature = "try-runtime")]
+fn count_keys(prefix: &[u8]) -> u32 {
+	let prefixed = KeyPrefixIterator::new(prefix.to_vec(), prefix.to_vec(), |_| Ok(())).count();
+	prefixed as u32 + unhashed::exists(prefix) as u32
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1204,4 +1632,154 @@ mod tests {
 			.is_err());
 		});
 	}
+
+	mod builders {
+		use super::*;
+		use crate::{
+			hash::{Blake2_128Concat, Twox64Concat},
+			parameter_types,
+			storage::types::{self, StorageValue},
+			traits::StorageInstance,
+		};
+
+		struct Prefix;
+		impl StorageInstance for Prefix {
+			fn pallet_prefix() -> &'static str {
+				"Pallet"
+			}
+			const STORAGE_PREFIX: &'static str = "Map";
+		}
+
+		struct OtherPrefix;
+		impl StorageInstance for OtherPrefix {
+			fn pallet_prefix() -> &'static str {
+				"OtherPallet"
+			}
+			const STORAGE_PREFIX: &'static str = "Map";
+		}
+
+		struct ValuePrefix;
+		impl StorageInstance for ValuePrefix {
+			fn pallet_prefix() -> &'static str {
+				"Pallet"
+			}
+			const STORAGE_PREFIX: &'static str = "Value";
+		}
+
+		type OldMap = types::StorageMap<Prefix, Twox64Concat, u32, u32>;
+		type NewMap = types::StorageMap<Prefix, Twox64Concat, u32, u64>;
+		type RehashedMap = types::StorageMap<Prefix, Blake2_128Concat, u32, u32>;
+		type OtherMap = types::StorageMap<OtherPrefix, Twox64Concat, u32, u32>;
+		type Value = StorageValue<ValuePrefix, u32>;
+
+		parameter_types! {
+			pub const Id: u8 = 0;
+			pub const TestDbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 1, write: 1 };
+			pub const PalletName: &'static str = "Pallet";
+			pub const OtherPalletName: &'static str = "OtherPallet";
+			pub const MapName: &'static str = "Map";
+			pub const ValueName: &'static str = "Value";
+			pub PalletPrefix: Vec<u8> = twox_128(b"Pallet").to_vec();
+		}
+
+		struct Double;
+		impl MapTranslation for Double {
+			type Key = u32;
+			type Old = u32;
+			type New = u64;
+
+			fn translate(key: &u32, old: u32) -> Option<u64> {
+				(*key != 3).then(|| old as u64 * 2)
+			}
+		}
+
+		/// Runs `M` to completion with `limit` weight per step and returns the number of steps.
+		fn run_to_end<M: SteppedMigration>(limit: Weight) -> u32 {
+			let mut cursor = None;
+			for steps in 1..100 {
+				cursor = M::step(cursor, &mut WeightMeter::with_limit(limit)).unwrap();
+				if cursor.is_none() {
+					return steps
+				}
+			}
+			panic!("migration did not finish");
+		}
+
+		#[test]
+		fn translate_map_works() {
+			type Migration = TranslateMap<Id, NewMap, Double, TestDbWeight>;
+
+			sp_io::TestExternalities::default().execute_with(|| {
+				(1..=4).for_each(|i| OldMap::insert(i, i * 10));
+
+				assert!(matches!(
+					Migration::step(None, &mut WeightMeter::with_limit(Weight::from_parts(1, 0))),
+					Err(SteppedMigrationError::InsufficientWeight { .. })
+				));
+
+				// Two entries per step and one more step to notice the end of the map.
+				assert_eq!(run_to_end::<Migration>(Weight::from_parts(4, 0)), 3);
+
+				let mut entries = NewMap::iter().collect::<Vec<_>>();
+				entries.sort();
+				assert_eq!(entries, vec![(1, 20), (2, 40), (4, 80)]);
+			});
+		}
+
+		#[test]
+		fn rehash_map_works() {
+			type Migration = RehashMap<Id, OldMap, RehashedMap, u32, u32, TestDbWeight>;
+
+			sp_io::TestExternalities::default().execute_with(|| {
+				(1..=10).for_each(|i| OldMap::insert(i, i * 10));
+
+				// Both maps share the same prefix, so moved entries are visited again and skipped.
+				run_to_end::<Migration>(Weight::from_parts(8, 0));
+
+				for i in 1..=10 {
+					assert_eq!(RehashedMap::get(i), Some(i * 10));
+					assert!(!unhashed::exists(&OldMap::hashed_key_for(i)));
+				}
+				assert_eq!(RehashedMap::iter().count(), 10);
+			});
+		}
+
+		#[test]
+		fn move_storage_works() {
+			type MoveMap = MoveStorage<Id, PalletName, OtherPalletName, MapName, TestDbWeight>;
+			type MoveValue = MoveStorage<Id, PalletName, OtherPalletName, ValueName, TestDbWeight>;
+
+			sp_io::TestExternalities::default().execute_with(|| {
+				(1..=5).for_each(|i| OldMap::insert(i, i));
+				Value::put(7);
+
+				assert!(run_to_end::<MoveMap>(Weight::from_parts(6, 0)) > 1);
+				assert_eq!(OldMap::iter().count(), 0);
+				assert_eq!(OtherMap::iter().count(), 5);
+				(1..=5).for_each(|i| assert_eq!(OtherMap::get(i), Some(i)));
+
+				run_to_end::<MoveValue>(Weight::from_parts(6, 0));
+				assert_eq!(Value::get(), None);
+				assert_eq!(
+					unhashed::get::<u32>(&storage_prefix(b"OtherPallet", b"Value")),
+					Some(7)
+				);
+			});
+		}
+
+		#[test]
+		fn clear_prefix_works() {
+			type Migration = ClearPrefix<Id, PalletPrefix, TestDbWeight>;
+
+			sp_io::TestExternalities::default().execute_with(|| {
+				(1..=5).for_each(|i| OldMap::insert(i, i));
+				OtherMap::insert(1, 1);
+				Value::put(7);
+
+				run_to_end::<Migration>(Weight::from_parts(4, 0));
+				assert!(!unhashed::contains_prefixed_key(&twox_128(b"Pallet")));
+				assert_eq!(OtherMap::get(1), Some(1));
+			});
+		}
+	}
 }
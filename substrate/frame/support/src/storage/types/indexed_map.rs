@@ -0,0 +1,873 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage map type with secondary indexes.
+
+use crate::{
+	storage::{
+		generator::StorageMap as _,
+		types::{
+			OptionQuery, QueryKindTrait, StorageDoubleMap, StorageEntryMetadataBuilder, StorageMap,
+		},
+		unhashed, KeyPrefixIterator, PrefixIterator, PrefixIteratorOnRemoval, StoragePrefixedMap,
+	},
+	traits::{Get, GetDefault, StorageInfo, StorageInfoTrait, StorageInstance},
+	Never, ReversibleStorageHasher, StorageHasher,
+};
+use alloc::{vec, vec::Vec};
+use codec::{Decode, Encode, EncodeLike, FullCodec, MaxEncodedLen, Ref};
+use core::marker::PhantomData;
+use sp_metadata_ir::{DeprecationStatusIR, StorageEntryMetadataIR};
+
+/// A wrapper around a [`StorageMap`] that keeps one or more secondary indexes of its entries
+/// consistent with the map.
+///
+/// Every index is declared with a [`StorageMapIndex`] that derives an optional index key from the
+/// value of an entry, e.g. the owner of an item. The index stores an empty value under the index
+/// key and the key of the entry, in the same layout as a [`StorageDoubleMap`]. All entries with the
+/// same index key can then be iterated with [`Self::iter_by`] and [`Self::iter_keys_by`].
+///
+/// `Indexes` is either a single [`StorageMapIndex`] or a tuple of them. Every index must use a
+/// storage prefix that is unique within the pallet, which is checked by the `integrity_test` of
+/// the pallet.
+///
+/// Every function that writes to the map reads the previous value to update the indexes. An
+/// additional write is done for every index key that changes. Entries removed through the
+/// iterators, e.g. with [`Self::drain`], are removed from the indexes as well.
+///
+/// The consistency of the indexes is checked with [`Self::do_try_state`] by the `try_state` hook
+/// of the pallet.
+///
+/// Unlike [`StorageMap`], there is no `translate_values`, `try_append` or `migrate_key`, as they
+/// can't update the indexes.
+///
+/// For general information regarding the `#[pallet::storage]` attribute, refer to
+/// [`crate::pallet_macros::storage`].
+///
+/// # Examples
+///
+/// ```
+/// #[frame_support::pallet]
+/// mod pallet {
+/// # 	use frame_support::pallet_prelude::*;
+/// # 	use frame_support::storage::types::StorageMapIndex;
+/// # 	#[pallet::config]
+/// # 	pub trait Config: frame_system::Config {}
+/// # 	#[pallet::pallet]
+/// # 	pub struct Pallet<T>(_);
+/// 	#[derive(Encode, Decode, MaxEncodedLen, TypeInfo)]
+/// 	pub struct Item {
+/// 		owner: u64,
+/// 		kind: u8,
+/// 	}
+///
+/// 	pub struct ByOwner;
+/// 	impl StorageMapIndex for ByOwner {
+/// 		const STORAGE_PREFIX: &'static str = "ItemsByOwner";
+/// 		type Key = u32;
+/// 		type Value = Item;
+/// 		type Hasher = Blake2_128Concat;
+/// 		type IndexKey = u64;
+///
+/// 		fn index_key(item: &Item) -> Option<u64> {
+/// 			Some(item.owner)
+/// 		}
+/// 	}
+///
+/// 	/// Items, indexed by their owner.
+/// 	#[pallet::storage]
+/// 	pub type Items<T> = IndexedStorageMap<_, Blake2_128Concat, u32, Item, ByOwner>;
+/// }
+/// ```
+pub struct IndexedStorageMap<
+	Prefix,
+	Hasher,
+	Key,
+	Value,
+	Indexes,
+	QueryKind = OptionQuery,
+	OnEmpty = GetDefault,
+	MaxValues = GetDefault,
+>(PhantomData<(Prefix, Hasher, Key, Value, Indexes, QueryKind, OnEmpty, MaxValues)>);
+
+/// A secondary index of an [`IndexedStorageMap`].
+pub trait StorageMapIndex {
+	/// The storage prefix of the index, which must be unique within the pallet.
+	const STORAGE_PREFIX: &'static str;
+
+	/// The key type of the indexed map.
+	type Key;
+
+	/// The value type of the indexed map.
+	type Value;
+
+	/// The hasher used for the index key.
+	type Hasher: StorageHasher;
+
+	/// The type of the index key.
+	type IndexKey: FullCodec + MaxEncodedLen + scale_info::StaticTypeInfo;
+
+	/// The index key of `value`, or `None` if the entry is not part of the index.
+	fn index_key(value: &Self::Value) -> Option<Self::IndexKey>;
+}
+
+/// The storage prefix of the index `Index` of an [`IndexedStorageMap`] with the prefix `Prefix`.
+pub struct IndexPrefix<Prefix, Index>(PhantomData<(Prefix, Index)>);
+
+impl<Prefix, Index> StorageInstance for IndexPrefix<Prefix, Index>
+where
+	Prefix: StorageInstance,
+	Index: StorageMapIndex,
+{
+	fn pallet_prefix() -> &'static str {
+		Prefix::pallet_prefix()
+	}
+
+	fn pallet_prefix_hash() -> [u8; 16] {
+		Prefix::pallet_prefix_hash()
+	}
+
+	const STORAGE_PREFIX: &'static str = Index::STORAGE_PREFIX;
+}
+
+/// The storage of the index `Index` of an [`IndexedStorageMap`], mapping the index key and the key
+/// of an entry to an empty value.
+pub type IndexStorage<Prefix, Hasher, Index> = StorageDoubleMap<
+	IndexPrefix<Prefix, Index>,
+	<Index as StorageMapIndex>::Hasher,
+	<Index as StorageMapIndex>::IndexKey,
+	Hasher,
+	<Index as StorageMapIndex>::Key,
+	(),
+	OptionQuery,
+>;
+
+/// The indexes of an [`IndexedStorageMap`].
+///
+/// Implemented for every [`StorageMapIndex`] and for tuples of them.
+pub trait StorageMapIndexes<Prefix, Hasher, Key, Value> {
+	/// Push the raw storage keys of the index entries of a map entry to `index_keys`.
+	///
+	/// `hashed_key` is the key of the map entry hashed with the hasher of the map.
+	fn index_keys(hashed_key: &[u8], value: &Value, index_keys: &mut Vec<Vec<u8>>);
+
+	/// The number of entries in all indexes.
+	///
+	/// This iterates all indexes and should only be used in tests and try-runtime checks.
+	fn count_entries() -> u32;
+
+	/// Remove all entries of all indexes.
+	fn clear_entries();
+
+	/// Build the metadata of the indexes.
+	fn build_metadata(
+		deprecation_status: DeprecationStatusIR,
+		entries: &mut Vec<StorageEntryMetadataIR>,
+	) where
+		Key: scale_info::StaticTypeInfo;
+
+	/// The storage info of the indexes.
+	fn storage_info() -> Vec<StorageInfo>
+	where
+		Key: MaxEncodedLen;
+
+	/// The storage info of the indexes, without `max_size`.
+	fn partial_storage_info() -> Vec<StorageInfo>;
+}
+
+impl<Prefix, Hasher, Index> StorageMapIndexes<Prefix, Hasher, Index::Key, Index::Value> for Index
+where
+	Prefix: StorageInstance,
+	Hasher: StorageHasher,
+	Index: StorageMapIndex,
+	Index::Key: FullCodec,
+{
+	fn index_keys(hashed_key: &[u8], value: &Index::Value, index_keys: &mut Vec<Vec<u8>>) {
+		if let Some(index_key) = Index::index_key(value) {
+			let prefix = IndexStorage::<Prefix, Hasher, Index>::final_prefix();
+			let hashed_index_key = index_key.using_encoded(Index::Hasher::hash);
+			index_keys.push([&prefix[..], hashed_index_key.as_ref(), hashed_key].concat());
+		}
+	}
+
+	fn count_entries() -> u32 {
+		let prefix = IndexStorage::<Prefix, Hasher, Index>::final_prefix().to_vec();
+		KeyPrefixIterator::new(prefix.clone(), prefix, |_| Ok(())).count() as u32
+	}
+
+	fn clear_entries() {
+		let prefix = IndexStorage::<Prefix, Hasher, Index>::final_prefix();
+		let _ = unhashed::clear_prefix(&prefix, None, None);
+	}
+
+	fn build_metadata(
+		deprecation_status: DeprecationStatusIR,
+		entries: &mut Vec<StorageEntryMetadataIR>,
+	) where
+		Index::Key: scale_info::StaticTypeInfo,
+	{
+		IndexStorage::<Prefix, Hasher, Index>::build_metadata(
+			deprecation_status,
+			if cfg!(feature = "no-metadata-docs") {
+				vec![]
+			} else {
+				vec!["Index of the related indexed storage map"]
+			},
+			entries,
+		);
+	}
+
+	fn storage_info() -> Vec<StorageInfo>
+	where
+		Index::Key: MaxEncodedLen,
+	{
+		IndexStorage::<Prefix, Hasher, Index>::storage_info()
+	}
+
+	fn partial_storage_info() -> Vec<StorageInfo> {
+		use crate::traits::PartialStorageInfoTrait;
+		IndexStorage::<Prefix, Hasher, Index>::partial_storage_info()
+	}
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(1, 8)]
+impl<Prefix, Hasher, Key, Value> StorageMapIndexes<Prefix, Hasher, Key, Value> for Tuple {
+	for_tuples!( where #( Tuple: StorageMapIndexes<Prefix, Hasher, Key, Value> )* );
+
+	fn index_keys(hashed_key: &[u8], value: &Value, index_keys: &mut Vec<Vec<u8>>) {
+		for_tuples!( #( Tuple::index_keys(hashed_key, value, index_keys); )* );
+	}
+
+	fn count_entries() -> u32 {
+		let mut count = 0;
+		for_tuples!( #( count += Tuple::count_entries(); )* );
+		count
+	}
+
+	fn clear_entries() {
+		for_tuples!( #( Tuple::clear_entries(); )* );
+	}
+
+	fn build_metadata(
+		deprecation_status: DeprecationStatusIR,
+		entries: &mut Vec<StorageEntryMetadataIR>,
+	) where
+		Key: scale_info::StaticTypeInfo,
+	{
+		for_tuples!( #( Tuple::build_metadata(deprecation_status.clone(), entries); )* );
+	}
+
+	fn storage_info() -> Vec<StorageInfo>
+	where
+		Key: MaxEncodedLen,
+	{
+		let mut info = Vec::new();
+		for_tuples!( #( info.extend(Tuple::storage_info()); )* );
+		info
+	}
+
+	fn partial_storage_info() -> Vec<StorageInfo> {
+		let mut info = Vec::new();
+		for_tuples!( #( info.extend(Tuple::partial_storage_info()); )* );
+		info
+	}
+}
+
+/// Removes the entries removed by a [`PrefixIterator`] of an [`IndexedStorageMap`] from its
+/// indexes.
+pub struct OnRemovalIndexUpdate<Prefix, Hasher, Key, Value, Indexes>(
+	PhantomData<(Prefix, Hasher, Key, Value, Indexes)>,
+);
+
+impl<Prefix, Hasher, Key, Value, Indexes> PrefixIteratorOnRemoval
+	for OnRemovalIndexUpdate<Prefix, Hasher, Key, Value, Indexes>
+where
+	Value: Decode,
+	Indexes: StorageMapIndexes<Prefix, Hasher, Key, Value>,
+{
+	fn on_removal(key: &[u8], value: &[u8]) {
+		// An entry whose value doesn't decode can't be part of an index.
+		let Ok(value) = Value::decode(&mut &value[..]) else { return };
+		// The key starts with the 32 bytes of the final prefix of the map.
+		let mut index_keys = Vec::new();
+		Indexes::index_keys(&key[32..], &value, &mut index_keys);
+		index_keys.iter().for_each(|index_key| unhashed::kill(index_key));
+	}
+}
+
+// Private helper trait to access map from indexed storage map.
+trait MapWrapper {
+	type Map;
+}
+
+impl<P: StorageInstance, H, K, V, I, Q, O, M> MapWrapper
+	for IndexedStorageMap<P, H, K, V, I, Q, O, M>
+{
+	type Map = StorageMap<P, H, K, V, Q, O, M>;
+}
+
+impl<Prefix, Hasher, Key, Value, Indexes, QueryKind, OnEmpty, MaxValues>
+	IndexedStorageMap<Prefix, Hasher, Key, Value, Indexes, QueryKind, OnEmpty, MaxValues>
+where
+	Prefix: StorageInstance,
+	Hasher: StorageHasher,
+	Key: FullCodec,
+	Value: FullCodec,
+	Indexes: StorageMapIndexes<Prefix, Hasher, Key, Value>,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: Get<QueryKind::Query> + 'static,
+	MaxValues: Get<Option<u32>>,
+{
+	/// The prefix used to generate the key of the map.
+	pub fn map_storage_final_prefix() -> Vec<u8> {
+		<Self as MapWrapper>::Map::final_prefix().to_vec()
+	}
+
+	/// Get the storage key used to fetch a value corresponding to a specific key.
+	pub fn hashed_key_for<KeyArg: EncodeLike<Key>>(key: KeyArg) -> Vec<u8> {
+		<Self as MapWrapper>::Map::hashed_key_for(key)
+	}
+
+	/// Does the value (explicitly) exist in storage?
+	pub fn contains_key<KeyArg: EncodeLike<Key>>(key: KeyArg) -> bool {
+		<Self as MapWrapper>::Map::contains_key(key)
+	}
+
+	/// Load the value associated with the given key from the map.
+	pub fn get<KeyArg: EncodeLike<Key>>(key: KeyArg) -> QueryKind::Query {
+		<Self as MapWrapper>::Map::get(key)
+	}
+
+	/// Try to get the value for the given key from the map.
+	///
+	/// Returns `Ok` if it exists, `Err` if not.
+	pub fn try_get<KeyArg: EncodeLike<Key>>(key: KeyArg) -> Result<Value, ()> {
+		<Self as MapWrapper>::Map::try_get(key)
+	}
+
+	/// Store or remove the value to be associated with `key` so that `get` returns the `query`.
+	pub fn set<KeyArg: EncodeLike<Key>>(key: KeyArg, q: QueryKind::Query) {
+		match QueryKind::from_query_to_optional_value(q) {
+			Some(v) => Self::insert(key, v),
+			None => Self::remove(key),
+		}
+	}
+
+	/// Store a value to be associated with the given key from the map.
+	pub fn insert<KeyArg: EncodeLike<Key>>(key: KeyArg, val: Value) {
+		Self::mutate_exists(key, |value| *value = Some(val))
+	}
+
+	/// Remove the value under a key.
+	pub fn remove<KeyArg: EncodeLike<Key>>(key: KeyArg) {
+		Self::mutate_exists(key, |value| *value = None)
+	}
+
+	/// Mutate the value under a key.
+	pub fn mutate<KeyArg: EncodeLike<Key>, R, F: FnOnce(&mut QueryKind::Query) -> R>(
+		key: KeyArg,
+		f: F,
+	) -> R {
+		Self::try_mutate(key, |v| Ok::<R, Never>(f(v)))
+			.expect("`Never` can not be constructed; qed")
+	}
+
+	/// Mutate the item, only if an `Ok` value is returned.
+	pub fn try_mutate<KeyArg, R, E, F>(key: KeyArg, f: F) -> Result<R, E>
+	where
+		KeyArg: EncodeLike<Key>,
+		F: FnOnce(&mut QueryKind::Query) -> Result<R, E>,
+	{
+		Self::try_mutate_exists(key, |option_value_ref| {
+			let option_value = core::mem::replace(option_value_ref, None);
+			let mut query = <Self as MapWrapper>::Map::from_optional_value_to_query(option_value);
+			let res = f(&mut query);
+			let option_value = <Self as MapWrapper>::Map::from_query_to_optional_value(query);
+			let _ = core::mem::replace(option_value_ref, option_value);
+			res
+		})
+	}
+
+	/// Mutate the value under a key. Deletes the item if mutated to a `None`.
+	pub fn mutate_exists<KeyArg: EncodeLike<Key>, R, F: FnOnce(&mut Option<Value>) -> R>(
+		key: KeyArg,
+		f: F,
+	) -> R {
+		Self::try_mutate_exists(key, |v| Ok::<R, Never>(f(v)))
+			.expect("`Never` can not be constructed; qed")
+	}
+
+	/// Mutate the item, only if an `Ok` value is returned. Deletes the item if mutated to a `None`.
+	/// `f` will always be called with an option representing if the storage item exists (`Some<V>`)
+	/// or if the storage item does not exist (`None`), independent of the `QueryType`.
+	pub fn try_mutate_exists<KeyArg, R, E, F>(key: KeyArg, f: F) -> Result<R, E>
+	where
+		KeyArg: EncodeLike<Key>,
+		F: FnOnce(&mut Option<Value>) -> Result<R, E>,
+	{
+		let hashed_key = key.using_encoded(Hasher::hash);
+		<Self as MapWrapper>::Map::try_mutate_exists(Ref::from(&key), |option_value| {
+			let old_index_keys = Self::index_keys(hashed_key.as_ref(), option_value.as_ref());
+			let res = f(option_value);
+
+			if res.is_ok() {
+				let new_index_keys = Self::index_keys(hashed_key.as_ref(), option_value.as_ref());
+				for index_key in old_index_keys.iter().filter(|k| !new_index_keys.contains(k)) {
+					unhashed::kill(index_key);
+				}
+				for index_key in new_index_keys.iter().filter(|k| !old_index_keys.contains(k)) {
+					unhashed::put(index_key, &());
+				}
+			}
+			res
+		})
+	}
+
+	/// Take the value under a key.
+	pub fn take<KeyArg: EncodeLike<Key>>(key: KeyArg) -> QueryKind::Query {
+		let removed_value = Self::mutate_exists(key, |value| value.take());
+		<Self as MapWrapper>::Map::from_optional_value_to_query(removed_value)
+	}
+
+	/// Remove at most `limit` entries from the map, together with their index entries.
+	///
+	/// Returns the number of removed entries, the map is empty if it is less than `limit`. Unlike
+	/// [`StorageMap::clear`], every entry is read to find its index entries, and no cursor is
+	/// needed as removed entries are not iterated again.
+	pub fn clear(limit: u32) -> u32 {
+		let prefix = Self::map_storage_final_prefix();
+		PrefixIterator::<(), OnRemovalIndexUpdate<Prefix, Hasher, Key, Value, Indexes>>::new(
+			prefix.clone(),
+			prefix,
+			|_, _| Ok(()),
+		)
+		.drain()
+		.take(limit as usize)
+		.count() as u32
+	}
+
+	/// Iter over all value of the storage.
+	///
+	/// NOTE: If a value failed to decode because storage is corrupted then it is skipped.
+	pub fn iter_values(
+	) -> PrefixIterator<Value, OnRemovalIndexUpdate<Prefix, Hasher, Key, Value, Indexes>> {
+		<Self as MapWrapper>::Map::iter_values().convert_on_removal()
+	}
+
+	/// Check that every entry of the map is part of all indexes it has an index key for, and that
+	/// the indexes contain no other entries.
+	#[cfg(any(test, feature = "try-runtime", feature = "try-state"))]
+	pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+		let prefix = Self::map_storage_final_prefix();
+		let entries = PrefixIterator::<(Vec<u8>, Value)>::new(
+			prefix.clone(),
+			prefix,
+			|raw_key_without_prefix, mut raw_value| {
+				Ok((raw_key_without_prefix.to_vec(), Value::decode(&mut raw_value)?))
+			},
+		);
+
+		let mut expected = 0u32;
+		for (hashed_key, value) in entries {
+			for index_key in Self::index_keys(&hashed_key, Some(&value)) {
+				crate::ensure!(unhashed::exists(&index_key), "Index entry is missing");
+				expected += 1;
+			}
+		}
+		crate::ensure!(Indexes::count_entries() == expected, "Index contains dangling entries");
+		Ok(())
+	}
+
+	fn index_keys(hashed_key: &[u8], value: Option<&Value>) -> Vec<Vec<u8>> {
+		let mut index_keys = Vec::new();
+		if let Some(value) = value {
+			Indexes::index_keys(hashed_key, value, &mut index_keys);
+		}
+		index_keys
+	}
+}
+
+impl<Prefix, Hasher, Key, Value, Indexes, QueryKind, OnEmpty, MaxValues>
+	IndexedStorageMap<Prefix, Hasher, Key, Value, Indexes, QueryKind, OnEmpty, MaxValues>
+where
+	Prefix: StorageInstance,
+	Hasher: StorageHasher + ReversibleStorageHasher,
+	Key: FullCodec,
+	Value: FullCodec,
+	Indexes: StorageMapIndexes<Prefix, Hasher, Key, Value>,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: Get<QueryKind::Query> + 'static,
+	MaxValues: Get<Option<u32>>,
+{
+	/// Enumerate all elements in the map in no particular order.
+	///
+	/// If you alter the map while doing this, you'll get undefined results.
+	pub fn iter(
+	) -> PrefixIterator<(Key, Value), OnRemovalIndexUpdate<Prefix, Hasher, Key, Value, Indexes>> {
+		<Self as MapWrapper>::Map::iter().convert_on_removal()
+	}
+
+	/// Remove all elements from the map and iterate through them in no particular order.
+	///
+	/// The elements are removed from the indexes as well. If you add elements to the map while
+	/// doing this, you'll get undefined results.
+	pub fn drain(
+	) -> PrefixIterator<(Key, Value), OnRemovalIndexUpdate<Prefix, Hasher, Key, Value, Indexes>> {
+		<Self as MapWrapper>::Map::drain().convert_on_removal()
+	}
+
+	/// Translate the values of all elements by a function `f`, in the map in no particular order.
+	///
+	/// By returning `None` from `f` for an element, you'll remove it from the map. The indexes
+	/// are rebuilt from the translated values, which rewrites all of their entries.
+	///
+	/// NOTE: If a value fail to decode because storage is corrupted then it is skipped, and it is
+	/// no longer part of any index.
+	pub fn translate<O: Decode, F: FnMut(Key, O) -> Option<Value>>(mut f: F) {
+		Indexes::clear_entries();
+		<Self as MapWrapper>::Map::translate(|key, old_value| {
+			let hashed_key = key.using_encoded(Hasher::hash);
+			let value = f(key, old_value)?;
+			for index_key in Self::index_keys(hashed_key.as_ref(), Some(&value)) {
+				unhashed::put(&index_key, &());
+			}
+			Some(value)
+		})
+	}
+
+	/// Enumerate all keys in the map in no particular order.
+	///
+	/// If you alter the map while doing this, you'll get undefined results.
+	pub fn iter_keys() -> KeyPrefixIterator<Key> {
+		<Self as MapWrapper>::Map::iter_keys()
+	}
+
+	/// Enumerate the keys of all entries with the index key `index_key` in the index `Index`, in no
+	/// particular order.
+	///
+	/// If you alter the map while doing this, you'll get undefined results.
+	pub fn iter_keys_by<Index: StorageMapIndex<Key = Key, Value = Value>>(
+		index_key: impl EncodeLike<Index::IndexKey>,
+	) -> KeyPrefixIterator<Key> {
+		let prefix = [
+			&IndexStorage::<Prefix, Hasher, Index>::final_prefix()[..],
+			index_key.using_encoded(Index::Hasher::hash).as_ref(),
+		]
+		.concat();
+		KeyPrefixIterator::new(prefix.clone(), prefix, |raw_key_without_prefix| {
+			Key::decode(&mut Hasher::reverse(raw_key_without_prefix))
+		})
+	}
+
+	/// Enumerate all entries with the index key `index_key` in the index `Index`, in no particular
+	/// order.
+	///
+	/// If you alter the map while doing this, you'll get undefined results.
+	pub fn iter_by<Index: StorageMapIndex<Key = Key, Value = Value>>(
+		index_key: impl EncodeLike<Index::IndexKey>,
+	) -> impl Iterator<Item = (Key, Value)> {
+		Self::iter_keys_by::<Index>(index_key)
+			.filter_map(|key| Self::try_get(&key).ok().map(|value| (key, value)))
+	}
+}
+
+impl<Prefix, Hasher, Key, Value, Indexes, QueryKind, OnEmpty, MaxValues> StorageEntryMetadataBuilder
+	for IndexedStorageMap<Prefix, Hasher, Key, Value, Indexes, QueryKind, OnEmpty, MaxValues>
+where
+	Prefix: StorageInstance,
+	Hasher: StorageHasher,
+	Key: FullCodec + scale_info::StaticTypeInfo,
+	Value: FullCodec + scale_info::StaticTypeInfo,
+	Indexes: StorageMapIndexes<Prefix, Hasher, Key, Value>,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: Get<QueryKind::Query> + 'static,
+	MaxValues: Get<Option<u32>>,
+{
+	fn build_metadata(
+		deprecation_status: DeprecationStatusIR,
+		docs: Vec<&'static str>,
+		entries: &mut Vec<StorageEntryMetadataIR>,
+	) {
+		<Self as MapWrapper>::Map::build_metadata(deprecation_status.clone(), docs, entries);
+		Indexes::build_metadata(deprecation_status, entries);
+	}
+}
+
+impl<Prefix, Hasher, Key, Value, Indexes, QueryKind, OnEmpty, MaxValues> StorageInfoTrait
+	for IndexedStorageMap<Prefix, Hasher, Key, Value, Indexes, QueryKind, OnEmpty, MaxValues>
+where
+	Prefix: StorageInstance,
+	Hasher: StorageHasher,
+	Key: FullCodec + MaxEncodedLen,
+	Value: FullCodec + MaxEncodedLen,
+	Indexes: StorageMapIndexes<Prefix, Hasher, Key, Value>,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: Get<QueryKind::Query> + 'static,
+	MaxValues: Get<Option<u32>>,
+{
+	fn storage_info() -> Vec<StorageInfo> {
+		[<Self as MapWrapper>::Map::storage_info(), Indexes::storage_info()].concat()
+	}
+}
+
+/// It doesn't require to implement `MaxEncodedLen` and give no information for `max_size`.
+impl<Prefix, Hasher, Key, Value, Indexes, QueryKind, OnEmpty, MaxValues>
+	crate::traits::PartialStorageInfoTrait
+	for IndexedStorageMap<Prefix, Hasher, Key, Value, Indexes, QueryKind, OnEmpty, MaxValues>
+where
+	Prefix: StorageInstance,
+	Hasher: StorageHasher,
+	Key: FullCodec,
+	Value: FullCodec,
+	Indexes: StorageMapIndexes<Prefix, Hasher, Key, Value>,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: Get<QueryKind::Query> + 'static,
+	MaxValues: Get<Option<u32>>,
+{
+	fn partial_storage_info() -> Vec<StorageInfo> {
+		[<Self as MapWrapper>::Map::partial_storage_info(), Indexes::partial_storage_info()]
+			.concat()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{assert_ok, hash::*, storage::types::ValueQuery};
+	use sp_io::TestExternalities;
+
+	struct Prefix;
+	impl StorageInstance for Prefix {
+		fn pallet_prefix() -> &'static str {
+			"test"
+		}
+		const STORAGE_PREFIX: &'static str = "foo";
+	}
+
+	#[derive(Encode, Decode, MaxEncodedLen, scale_info::TypeInfo, Clone, Debug, PartialEq)]
+	struct Item {
+		owner: u64,
+		kind: Option<u8>,
+	}
+
+	struct ByOwner;
+	impl StorageMapIndex for ByOwner {
+		const STORAGE_PREFIX: &'static str = "foo_by_owner";
+		type Key = u32;
+		type Value = Item;
+		type Hasher = Twox64Concat;
+		type IndexKey = u64;
+
+		fn index_key(item: &Item) -> Option<u64> {
+			Some(item.owner)
+		}
+	}
+
+	struct ByKind;
+	impl StorageMapIndex for ByKind {
+		const STORAGE_PREFIX: &'static str = "foo_by_kind";
+		type Key = u32;
+		type Value = Item;
+		type Hasher = Identity;
+		type IndexKey = u8;
+
+		fn index_key(item: &Item) -> Option<u8> {
+			item.kind
+		}
+	}
+
+	type Items = IndexedStorageMap<Prefix, Blake2_128Concat, u32, Item, (ByOwner, ByKind)>;
+
+	fn item(owner: u64, kind: Option<u8>) -> Item {
+		Item { owner, kind }
+	}
+
+	fn keys_by_owner(owner: u64) -> Vec<u32> {
+		let mut keys = Items::iter_keys_by::<ByOwner>(owner).collect::<Vec<_>>();
+		keys.sort();
+		keys
+	}
+
+	fn keys_by_kind(kind: u8) -> Vec<u32> {
+		let mut keys = Items::iter_keys_by::<ByKind>(kind).collect::<Vec<_>>();
+		keys.sort();
+		keys
+	}
+
+	#[test]
+	fn indexes_follow_writes() {
+		TestExternalities::default().execute_with(|| {
+			Items::insert(1, item(10, Some(1)));
+			Items::insert(2, item(10, None));
+			Items::insert(3, item(20, Some(1)));
+			assert_eq!(keys_by_owner(10), vec![1, 2]);
+			assert_eq!(keys_by_owner(20), vec![3]);
+			assert_eq!(keys_by_kind(1), vec![1, 3]);
+			assert_ok!(Items::do_try_state());
+
+			// Overwrite changes the owner and the kind.
+			Items::insert(1, item(20, Some(2)));
+			assert_eq!(keys_by_owner(10), vec![2]);
+			assert_eq!(keys_by_owner(20), vec![1, 3]);
+			assert_eq!(keys_by_kind(1), vec![3]);
+			assert_eq!(keys_by_kind(2), vec![1]);
+
+			Items::mutate_exists(2, |value| value.as_mut().unwrap().kind = Some(2));
+			assert_eq!(keys_by_kind(2), vec![1, 2]);
+
+			assert_eq!(Items::take(3), Some(item(20, Some(1))));
+			Items::remove(1);
+			assert_eq!(keys_by_owner(20), Vec::<u32>::new());
+			assert_eq!(keys_by_kind(1), Vec::<u32>::new());
+			assert_eq!(keys_by_kind(2), vec![2]);
+
+			assert_eq!(
+				Items::iter_by::<ByOwner>(10).collect::<Vec<_>>(),
+				vec![(2, item(10, Some(2)))]
+			);
+			assert_ok!(Items::do_try_state());
+		});
+	}
+
+	#[test]
+	fn failed_mutation_keeps_indexes() {
+		TestExternalities::default().execute_with(|| {
+			Items::insert(1, item(10, Some(1)));
+
+			assert!(Items::try_mutate(1, |value| {
+				*value = Some(item(20, None));
+				Err::<(), ()>(())
+			})
+			.is_err());
+			assert_eq!(keys_by_owner(10), vec![1]);
+			assert_eq!(keys_by_kind(1), vec![1]);
+			assert_ok!(Items::do_try_state());
+		});
+	}
+
+	#[test]
+	fn value_query_works() {
+		type Counts = IndexedStorageMap<Prefix, Blake2_128Concat, u32, u64, ByCount, ValueQuery>;
+
+		struct ByCount;
+		impl StorageMapIndex for ByCount {
+			const STORAGE_PREFIX: &'static str = "foo_by_count";
+			type Key = u32;
+			type Value = u64;
+			type Hasher = Twox64Concat;
+			type IndexKey = u64;
+
+			fn index_key(count: &u64) -> Option<u64> {
+				Some(*count)
+			}
+		}
+
+		TestExternalities::default().execute_with(|| {
+			Counts::mutate(1, |count| *count += 1);
+			Counts::mutate(2, |count| *count += 2);
+			assert_eq!(Counts::iter_keys_by::<ByCount>(1u64).collect::<Vec<_>>(), vec![1]);
+
+			Counts::mutate(1, |count| *count += 1);
+			assert_eq!(Counts::iter_keys_by::<ByCount>(1u64).count(), 0);
+			assert_eq!(Counts::iter_keys_by::<ByCount>(2u64).count(), 2);
+
+			Counts::set(2, 0);
+			assert_eq!(Counts::iter_keys_by::<ByCount>(2u64).collect::<Vec<_>>(), vec![1]);
+			assert_ok!(Counts::do_try_state());
+		});
+	}
+
+	#[test]
+	fn clear_translate_and_drain_update_indexes() {
+		TestExternalities::default().execute_with(|| {
+			Items::insert(1, item(10, Some(1)));
+			Items::insert(2, item(10, None));
+			Items::insert(3, item(20, Some(1)));
+
+			// Moves all items of 10 to 30 and removes the items of kind 1.
+			Items::translate::<Item, _>(|_, old| {
+				let owner = if old.owner == 10 { 30 } else { old.owner };
+				(old.kind != Some(1)).then(|| item(owner, old.kind))
+			});
+			assert_eq!(Items::iter_keys().count(), 1);
+			assert_eq!(keys_by_owner(10), Vec::<u32>::new());
+			assert_eq!(keys_by_owner(30), vec![2]);
+			assert_eq!(keys_by_kind(1), Vec::<u32>::new());
+			assert_ok!(Items::do_try_state());
+
+			Items::insert(1, item(10, Some(1)));
+			assert_eq!(Items::drain().collect::<Vec<_>>().len(), 2);
+			assert_eq!(keys_by_owner(10), Vec::<u32>::new());
+			assert_eq!(keys_by_owner(30), Vec::<u32>::new());
+			assert_ok!(Items::do_try_state());
+
+			Items::insert(1, item(10, Some(1)));
+			Items::insert(2, item(10, Some(2)));
+			Items::insert(3, item(20, None));
+			assert_eq!(Items::clear(2), 2);
+			assert_eq!(Items::iter_keys().count(), 1);
+			assert_ok!(Items::do_try_state());
+			assert_eq!(Items::clear(2), 1);
+			assert_eq!(
+				<(ByOwner, ByKind) as StorageMapIndexes<Prefix, Blake2_128Concat, u32, Item>>::count_entries(),
+				0
+			);
+		});
+	}
+
+	#[test]
+	fn try_state_detects_inconsistencies() {
+		TestExternalities::default().execute_with(|| {
+			Items::insert(1, item(10, Some(1)));
+			Items::insert(2, item(20, None));
+
+			let mut index_keys = Vec::new();
+			<ByOwner as StorageMapIndexes<Prefix, Blake2_128Concat, u32, Item>>::index_keys(
+				&Items::hashed_key_for(1)[32..],
+				&item(10, Some(1)),
+				&mut index_keys,
+			);
+			assert_eq!(index_keys.len(), 1);
+
+			// Missing index entry.
+			unhashed::kill(&index_keys[0]);
+			assert!(Items::do_try_state().is_err());
+
+			// Dangling index entry.
+			unhashed::put(&index_keys[0], &());
+			unhashed::put(&[&index_keys[0][..index_keys[0].len() - 1], &[1][..]].concat(), &());
+			assert!(Items::do_try_state().is_err());
+		});
+	}
+
+	#[test]
+	fn metadata_contains_indexes() {
+		let mut entries = vec![];
+		Items::build_metadata(DeprecationStatusIR::NotDeprecated, vec![], &mut entries);
+		assert_eq!(
+			entries.iter().map(|entry| entry.name).collect::<Vec<_>>(),
+			vec!["foo", "foo_by_owner", "foo_by_kind"],
+		);
+		assert_eq!(
+			Items::storage_info()
+				.iter()
+				.map(|info| info.storage_name.clone())
+				.collect::<Vec<_>>(),
+			vec![b"foo".to_vec(), b"foo_by_owner".to_vec(), b"foo_by_kind".to_vec()],
+		);
+	}
+}
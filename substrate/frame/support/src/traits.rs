@@ -137,10 +137,14 @@ pub use tasks::Task;
 mod proving;
 pub use proving::*;
 
-#[cfg(feature = "try-runtime")]
+mod weight_bound;
+#[doc(hidden)]
+pub use weight_bound::probe as weight_bound_probe;
+pub use weight_bound::WeightBound;
+
+#[cfg(any(feature = "try-runtime", feature = "try-state"))]
 mod try_runtime;
+#[cfg(any(feature = "try-runtime", feature = "try-state"))]
+pub use try_runtime::{Select as TryStateSelect, TryState};
 #[cfg(feature = "try-runtime")]
-pub use try_runtime::{
-	Select as TryStateSelect, TryDecodeEntireStorage, TryDecodeEntireStorageError, TryState,
-	UpgradeCheckSelect,
-};
+pub use try_runtime::{TryDecodeEntireStorage, TryDecodeEntireStorageError, UpgradeCheckSelect};
@@ -28,7 +28,7 @@ use sp_weights::WeightMeter;
 
 #[cfg(feature = "try-runtime")]
 use alloc::vec::Vec;
-#[cfg(feature = "try-runtime")]
+#[cfg(any(feature = "try-runtime", feature = "try-state"))]
 use sp_runtime::TryRuntimeError;
 
 /// Provides a callback to execute logic before the all inherents.
@@ -514,7 +514,10 @@ pub trait Hooks<BlockNumber> {
 	/// executed in a consensus code-path, therefore it can consume as much weight as it needs.
 	///
 	/// This hook must not alter any storage.
-	#[cfg(feature = "try-runtime")]
+	///
+	/// Also available with the `try-state` feature alone, which allows running these checks on a
+	/// live test network, see `frame_executive::SampledTryState`.
+	#[cfg(any(feature = "try-runtime", feature = "try-state"))]
 	fn try_state(_n: BlockNumber) -> Result<(), TryRuntimeError> {
 		Ok(())
 	}
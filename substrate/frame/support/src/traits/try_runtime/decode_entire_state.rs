@@ -21,7 +21,7 @@ use super::StorageInstance;
 use crate::{
 	storage::types::{
 		CountedStorageMapInstance, CountedStorageNMapInstance, Counter, KeyGenerator,
-		QueryKindTrait,
+		QueryKindTrait, StorageMapIndexes,
 	},
 	traits::{PartialStorageInfoTrait, StorageInfo},
 	StorageHasher,
@@ -218,6 +218,38 @@ where
 	}
 }
 
+impl<Prefix, Hasher, Key, Value, Indexes, QueryKind, OnEmpty, MaxValues> TryDecodeEntireStorage
+	for crate::storage::types::IndexedStorageMap<
+		Prefix,
+		Hasher,
+		Key,
+		Value,
+		Indexes,
+		QueryKind,
+		OnEmpty,
+		MaxValues,
+	>
+where
+	Prefix: StorageInstance,
+	Hasher: StorageHasher,
+	Key: FullCodec,
+	Value: FullCodec,
+	Indexes: StorageMapIndexes<Prefix, Hasher, Key, Value>,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: Get<QueryKind::Query> + 'static,
+	MaxValues: Get<Option<u32>>,
+{
+	fn try_decode_entire_state() -> Result<usize, Vec<TryDecodeEntireStorageError>> {
+		let mut infos = Self::partial_storage_info().into_iter();
+		let map_info = infos.next().expect("Indexed map has at least one storage info; qed");
+		let mut decoded = decode_storage_info::<Value>(map_info)?;
+		for index_info in infos {
+			decoded += decode_storage_info::<()>(index_info)?;
+		}
+		Ok(decoded)
+	}
+}
+
 impl<Prefix, Hasher1, Key1, Hasher2, Key2, Value, QueryKind, OnEmpty, MaxValues>
 	TryDecodeEntireStorage
 	for crate::storage::types::StorageDoubleMap<
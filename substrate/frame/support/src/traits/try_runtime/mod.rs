@@ -16,10 +16,15 @@
 // limitations under the License.
 
 //! Try-runtime specific traits and types.
+//!
+//! [`Select`] and [`TryState`] are also available with the `try-state` feature alone.
 
+#[cfg(feature = "try-runtime")]
 pub mod decode_entire_state;
+#[cfg(feature = "try-runtime")]
 pub use decode_entire_state::{TryDecodeEntireStorage, TryDecodeEntireStorageError};
 
+#[cfg(feature = "try-runtime")]
 use super::StorageInstance;
 
 use alloc::vec::Vec;
@@ -95,6 +100,7 @@ impl std::str::FromStr for Select {
 }
 
 /// Select which checks should be run when trying a runtime upgrade upgrade.
+#[cfg(feature = "try-runtime")]
 #[derive(codec::Encode, codec::Decode, Clone, Debug, Copy, scale_info::TypeInfo, PartialEq)]
 pub enum UpgradeCheckSelect {
 	/// Run no checks.
@@ -107,6 +113,7 @@ pub enum UpgradeCheckSelect {
 	TryState,
 }
 
+#[cfg(feature = "try-runtime")]
 impl UpgradeCheckSelect {
 	/// Whether the pre- and post-upgrade checks are selected.
 	pub fn pre_and_post(&self) -> bool {
@@ -124,7 +131,7 @@ impl UpgradeCheckSelect {
 	}
 }
 
-#[cfg(feature = "std")]
+#[cfg(all(feature = "std", feature = "try-runtime"))]
 impl core::str::FromStr for UpgradeCheckSelect {
 	type Err = &'static str;
 
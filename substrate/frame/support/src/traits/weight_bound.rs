@@ -0,0 +1,145 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traits to derive the arguments at which the integrity test of a pallet evaluates the weight of
+//! its calls.
+
+use alloc::{boxed::Box, vec::Vec};
+use codec::{Decode, MaxEncodedLen};
+use sp_core::Get;
+use sp_runtime::{traits::TrailingZeroInput, BoundedVec, WeakBoundedVec};
+
+/// A type with a value for which the weight of a call taking it as argument is maximal.
+///
+/// Arguments of a [`call`](crate::pallet_macros::call) without a
+/// [`weight_bound`](crate::pallet_macros::weight_bound) use this value in the integrity test of
+/// the pallet. Bounded collections are filled up to their bound.
+pub trait WeightBound: Sized {
+	/// The value for which the weight of a call is maximal, `None` if it can't be built.
+	fn weight_bound() -> Option<Self>;
+}
+
+/// Decode a value of `T` from an infinite stream of zeroes.
+fn zeroed<T: Decode>() -> Option<T> {
+	T::decode(&mut TrailingZeroInput::zeroes()).ok()
+}
+
+impl<T: Decode + Clone, S: Get<u32>> WeightBound for BoundedVec<T, S> {
+	fn weight_bound() -> Option<Self> {
+		let item = zeroed::<T>()?;
+		let items = Vec::from_iter(core::iter::repeat(item).take(S::get() as usize));
+		BoundedVec::try_from(items).ok()
+	}
+}
+
+impl<T: Decode + Clone, S: Get<u32>> WeightBound for WeakBoundedVec<T, S> {
+	fn weight_bound() -> Option<Self> {
+		let item = zeroed::<T>()?;
+		let items = Vec::from_iter(core::iter::repeat(item).take(S::get() as usize));
+		WeakBoundedVec::try_from(items).ok()
+	}
+}
+
+impl<T: WeightBound> WeightBound for Option<T> {
+	fn weight_bound() -> Option<Self> {
+		T::weight_bound().map(Some)
+	}
+}
+
+impl<T: WeightBound> WeightBound for Box<T> {
+	fn weight_bound() -> Option<Self> {
+		T::weight_bound().map(Box::new)
+	}
+}
+
+/// Autoref based selection of the `weight_bound` of a call argument, used by the
+/// [`call`](crate::pallet_macros::call) macro.
+///
+/// In order of preference the bound is taken from [`WeightBound`], or decoded from zeroes if the
+/// type has a [`MaxEncodedLen`] (assuming the weight only depends on its size). Any other type is
+/// unbounded.
+#[doc(hidden)]
+pub mod probe {
+	use super::*;
+	use core::marker::PhantomData;
+
+	/// Probe the `weight_bound` of `T` with `(&&&Probe::<T>::new()).weight_bound()`.
+	pub struct Probe<T>(PhantomData<T>);
+
+	impl<T> Probe<T> {
+		/// Create a new probe.
+		pub fn new() -> Self {
+			Self(PhantomData)
+		}
+	}
+
+	/// Types implementing [`WeightBound`].
+	pub trait ViaWeightBound<T> {
+		/// The `weight_bound` of `T`, if any.
+		fn weight_bound(&self) -> Option<T>;
+	}
+
+	impl<T: WeightBound> ViaWeightBound<T> for &&Probe<T> {
+		fn weight_bound(&self) -> Option<T> {
+			T::weight_bound()
+		}
+	}
+
+	/// Types implementing [`MaxEncodedLen`].
+	pub trait ViaMaxEncodedLen<T> {
+		/// The `weight_bound` of `T`, if any.
+		fn weight_bound(&self) -> Option<T>;
+	}
+
+	impl<T: MaxEncodedLen + Decode> ViaMaxEncodedLen<T> for &Probe<T> {
+		fn weight_bound(&self) -> Option<T> {
+			zeroed::<T>()
+		}
+	}
+
+	/// Any other type.
+	pub trait Unbounded<T> {
+		/// The `weight_bound` of `T`, if any.
+		fn weight_bound(&self) -> Option<T>;
+	}
+
+	impl<T> Unbounded<T> for Probe<T> {
+		fn weight_bound(&self) -> Option<T> {
+			None
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{probe::*, *};
+	use crate::traits::ConstU32;
+
+	#[test]
+	fn weight_bound_probe_works() {
+		assert_eq!(
+			(&&&Probe::<BoundedVec<u8, ConstU32<3>>>::new()).weight_bound(),
+			Some(BoundedVec::truncate_from(vec![0, 0, 0])),
+		);
+		assert_eq!(
+			(&&&Probe::<Option<BoundedVec<u8, ConstU32<1>>>>::new()).weight_bound(),
+			Some(Some(BoundedVec::truncate_from(vec![0]))),
+		);
+		assert_eq!((&&&Probe::<u32>::new()).weight_bound(), Some(0));
+		assert_eq!((&&&Probe::<Vec<u8>>::new()).weight_bound(), None);
+	}
+}
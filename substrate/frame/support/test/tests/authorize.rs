@@ -0,0 +1,188 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the `#[pallet::authorize]` attribute and the `AuthorizeCall` transaction extension.
+
+use frame_support::{
+	assert_noop, assert_ok, derive_impl,
+	dispatch::{Authorize, GetDispatchInfo},
+	pallet_prelude::{InvalidTransaction, TransactionSource, ValidTransaction, Weight},
+	traits::OriginTrait,
+};
+use frame_system::AuthorizeCall;
+use sp_runtime::{
+	traits::{
+		AsTransactionAuthorizedOrigin, DispatchTransaction, TransactionExtension, TxBaseImplication,
+	},
+	DispatchError,
+};
+
+const AUTHORIZE_WEIGHT: Weight = Weight::from_parts(100, 10);
+const AUTHORIZE_REFUND: Weight = Weight::from_parts(40, 0);
+
+#[frame_support::pallet]
+mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	pub type Value<T> = StorageValue<_, u32>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::call_index(0)]
+		#[pallet::weight(Weight::zero())]
+		#[pallet::authorize(|source, value: &u32| {
+			if *value == 0 || source == TransactionSource::External {
+				return Err(InvalidTransaction::Call.into())
+			}
+			let valid = ValidTransaction::with_tag_prefix("Pallet")
+				.priority(*value as u64)
+				.and_provides(value)
+				.build()?;
+			Ok((valid, AUTHORIZE_REFUND))
+		})]
+		#[pallet::weight_of_authorize(AUTHORIZE_WEIGHT)]
+		pub fn set_value(origin: OriginFor<T>, value: u32) -> DispatchResult {
+			ensure_authorized(origin)?;
+			Value::<T>::put(value);
+			Ok(())
+		}
+
+		#[pallet::call_index(1)]
+		#[pallet::weight(Weight::zero())]
+		pub fn clear_value(origin: OriginFor<T>) -> DispatchResult {
+			ensure_root(origin)?;
+			Value::<T>::kill();
+			Ok(())
+		}
+	}
+}
+
+type BlockNumber = u32;
+type AccountId = u64;
+type Header = sp_runtime::generic::Header<BlockNumber, sp_runtime::traits::BlakeTwo256>;
+type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, RuntimeCall, (), ()>;
+type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime
+	{
+		System: frame_system,
+		MyPallet: pallet,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type Block = Block;
+	type AccountId = AccountId;
+}
+
+impl pallet::Config for Runtime {}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	use sp_runtime::BuildStorage;
+
+	RuntimeGenesisConfig::default().build_storage().unwrap().into()
+}
+
+#[test]
+fn authorize_is_implemented_for_calls() {
+	let call = RuntimeCall::MyPallet(pallet::Call::set_value { value: 3 });
+	assert_eq!(call.weight_of_authorize(), AUTHORIZE_WEIGHT);
+	assert_eq!(
+		call.authorize(TransactionSource::InBlock)
+			.map(|res| res.map(|(_, refund)| refund)),
+		Some(Ok(AUTHORIZE_REFUND)),
+	);
+	assert_eq!(
+		call.authorize(TransactionSource::External),
+		Some(Err(InvalidTransaction::Call.into())),
+	);
+
+	let call = RuntimeCall::MyPallet(pallet::Call::clear_value {});
+	assert_eq!(call.weight_of_authorize(), Weight::zero());
+	assert_eq!(call.authorize(TransactionSource::InBlock), None);
+}
+
+#[test]
+fn authorize_call_extension_authorizes_origin() {
+	new_test_ext().execute_with(|| {
+		let call = RuntimeCall::MyPallet(pallet::Call::set_value { value: 3 });
+		let info = call.get_dispatch_info();
+		let ext = AuthorizeCall::<Runtime>::new();
+
+		assert_eq!(ext.weight(&call), AUTHORIZE_WEIGHT);
+
+		let (valid, unspent, origin) = ext
+			.validate_only(None.into(), &call, &info, 0, TransactionSource::Local, 0)
+			.unwrap();
+		assert_eq!(valid.priority, 3);
+		assert_eq!(unspent, AUTHORIZE_REFUND);
+		assert!(origin.is_transaction_authorized());
+		assert!(origin.as_system_ref().is_some_and(|o| o.is_authorized()));
+
+		assert_eq!(
+			ext.validate_only(None.into(), &call, &info, 0, TransactionSource::External, 0)
+				.unwrap_err(),
+			InvalidTransaction::Call.into(),
+		);
+
+		assert_ok!(ext.dispatch_transaction(None.into(), call, &info, 0, 0).unwrap());
+		assert_eq!(pallet::Value::<Runtime>::get(), Some(3));
+	});
+}
+
+#[test]
+fn calls_without_authorization_are_not_authorized() {
+	new_test_ext().execute_with(|| {
+		let call = RuntimeCall::MyPallet(pallet::Call::clear_value {});
+		let info = call.get_dispatch_info();
+
+		let (valid, _, origin) = AuthorizeCall::<Runtime>::new()
+			.validate(
+				None.into(),
+				&call,
+				&info,
+				0,
+				(),
+				&TxBaseImplication(&call),
+				TransactionSource::Local,
+			)
+			.unwrap();
+		assert_eq!(valid, ValidTransaction::default());
+		assert!(!origin.is_transaction_authorized());
+	});
+}
+
+#[test]
+fn authorized_calls_require_authorized_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(MyPallet::set_value(RuntimeOrigin::none(), 3), DispatchError::BadOrigin);
+		assert_noop!(MyPallet::set_value(RuntimeOrigin::signed(1), 3), DispatchError::BadOrigin);
+		assert_ok!(MyPallet::set_value(frame_system::RawOrigin::Authorized.into(), 3));
+		assert_eq!(pallet::Value::<Runtime>::get(), Some(3));
+	});
+}
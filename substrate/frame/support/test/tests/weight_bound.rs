@@ -0,0 +1,157 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the `#[pallet::weight_bound]` attribute and the checking of call weights in the
+//! integrity test.
+
+use frame_support::{
+	derive_impl, dispatch::GetDispatchInfo, parameter_types, traits::IntegrityTest, weights::Weight,
+};
+
+#[frame_support::pallet]
+mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type MaxItems: Get<u32>;
+		type MaxBytes: Get<u32>;
+		type MaxLen: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::call_index(0)]
+		#[pallet::weight(Weight::from_parts(1_000_000 * *items as u64, 1_000 * *bytes as u64))]
+		pub fn process(
+			origin: OriginFor<T>,
+			#[pallet::weight_bound(T::MaxItems::get())] items: u32,
+			#[pallet::compact]
+			#[pallet::weight_bound(T::MaxBytes::get())]
+			bytes: u32,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let _ = (items, bytes);
+			Ok(())
+		}
+
+		#[pallet::call_index(1)]
+		#[pallet::weight(Weight::from_parts(1_000_000 * *items as u64, 0))]
+		pub fn implicit(origin: OriginFor<T>, items: u32) -> DispatchResult {
+			ensure_signed(origin)?;
+			let _ = items;
+			Ok(())
+		}
+
+		#[pallet::call_index(2)]
+		#[pallet::weight(Weight::from_parts(1_000_000 * items.len() as u64, 0))]
+		pub fn bounded(
+			origin: OriginFor<T>,
+			items: BoundedVec<u32, T::MaxLen>,
+			#[pallet::weight_bound(T::MaxItems::get())] count: u32,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let _ = (items, count);
+			Ok(())
+		}
+
+		#[pallet::call_index(3)]
+		#[pallet::weight(Weight::from_parts(1_000_000 * items.len() as u64, 0))]
+		pub fn unbounded(origin: OriginFor<T>, items: Vec<u32>) -> DispatchResult {
+			ensure_signed(origin)?;
+			let _ = items;
+			Ok(())
+		}
+	}
+}
+
+type BlockNumber = u32;
+type AccountId = u64;
+type Header = sp_runtime::generic::Header<BlockNumber, sp_runtime::traits::BlakeTwo256>;
+type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, RuntimeCall, (), ()>;
+type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime
+	{
+		System: frame_system,
+		MyPallet: pallet,
+	}
+);
+
+parameter_types! {
+	pub RuntimeBlockWeights: frame_system::limits::BlockWeights =
+		frame_system::limits::BlockWeights::simple_max(Weight::from_parts(1_000_000_000, 1_000_000));
+	pub static MaxItems: u32 = 10;
+	pub static MaxBytes: u32 = 10;
+	pub static MaxLen: u32 = 10;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type Block = Block;
+	type AccountId = AccountId;
+	type BlockWeights = RuntimeBlockWeights;
+}
+
+impl pallet::Config for Runtime {
+	type MaxItems = MaxItems;
+	type MaxBytes = MaxBytes;
+	type MaxLen = MaxLen;
+}
+
+#[test]
+fn integrity_test_passes_for_bounded_calls() {
+	// The `weight_bound` of `implicit` is derived from the zeroed `u32`.
+	assert!(pallet::Call::<Runtime>::implicit { items: u32::MAX }
+		.get_dispatch_info()
+		.call_weight
+		.any_gt(RuntimeBlockWeights::get().max_block));
+	// `unbounded` takes a `Vec`, so its weight is never evaluated.
+	assert!(pallet::Call::<Runtime>::unbounded { items: vec![0; 2_000] }
+		.get_dispatch_info()
+		.call_weight
+		.any_gt(RuntimeBlockWeights::get().max_block));
+
+	<pallet::Pallet<Runtime> as IntegrityTest>::integrity_test();
+	<AllPalletsWithSystem as IntegrityTest>::integrity_test();
+}
+
+#[test]
+#[should_panic(expected = "The weight of call `MyPallet::process` at its `weight_bound`s")]
+fn integrity_test_fails_if_ref_time_exceeds_max_extrinsic() {
+	MaxItems::set(2_000);
+	<pallet::Pallet<Runtime> as IntegrityTest>::integrity_test();
+}
+
+#[test]
+#[should_panic(expected = "The weight of call `MyPallet::process` at its `weight_bound`s")]
+fn integrity_test_fails_if_proof_size_exceeds_max_extrinsic() {
+	MaxBytes::set(2_000);
+	<pallet::Pallet<Runtime> as IntegrityTest>::integrity_test();
+}
+
+#[test]
+#[should_panic(expected = "The weight of call `MyPallet::bounded` at its `weight_bound`s")]
+fn integrity_test_fills_bounded_arguments() {
+	MaxLen::set(2_000);
+	<pallet::Pallet<Runtime> as IntegrityTest>::integrity_test();
+}
@@ -0,0 +1,162 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Config;
+use codec::{Decode, Encode};
+use core::marker::PhantomData;
+use frame_support::{
+	dispatch::{Authorize, RawOrigin},
+	pallet_prelude::{TransactionSource, Weight},
+	traits::OriginTrait,
+	DefaultNoBound,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf, TransactionExtension, ValidateResult},
+	transaction_validity::{TransactionValidityError, ValidTransaction},
+	DispatchResult,
+};
+
+/// Authorize calls that are submitted as general transactions without any other origin, using
+/// the authorization defined by the call with [`frame_support::pallet_macros::authorize`].
+///
+/// A call that authorizes successfully is dispatched with the [`RawOrigin::Authorized`] origin.
+/// Calls without authorization, or transactions already authorized by a preceding extension, are
+/// passed through unchanged.
+///
+/// This extension should be placed at the beginning of the pipeline, right after the extensions
+/// that authorize an origin from a signature, so that the following extensions see the authorized
+/// origin.
+#[derive(Encode, Decode, DefaultNoBound, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct AuthorizeCall<T>(PhantomData<T>);
+
+impl<T> core::fmt::Debug for AuthorizeCall<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "AuthorizeCall")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut core::fmt::Formatter) -> core::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T> AuthorizeCall<T> {
+	/// Create new `TransactionExtension` to authorize calls.
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> TransactionExtension<T::RuntimeCall> for AuthorizeCall<T>
+where
+	T::RuntimeCall: Authorize,
+{
+	const IDENTIFIER: &'static str = "AuthorizeCall";
+	type Implicit = ();
+	type Val = Weight;
+	type Pre = Weight;
+
+	fn weight(&self, call: &T::RuntimeCall) -> Weight {
+		call.weight_of_authorize()
+	}
+
+	fn validate(
+		&self,
+		mut origin: T::RuntimeOrigin,
+		call: &T::RuntimeCall,
+		_info: &DispatchInfoOf<T::RuntimeCall>,
+		_len: usize,
+		_self_implicit: Self::Implicit,
+		_inherited_implication: &impl Encode,
+		source: TransactionSource,
+	) -> ValidateResult<Self::Val, T::RuntimeCall> {
+		// The whole authorization weight is unspent if the authorization doesn't run.
+		let weight = call.weight_of_authorize();
+		if !matches!(origin.as_system_ref(), Some(RawOrigin::None)) {
+			return Ok((ValidTransaction::default(), weight, origin))
+		}
+
+		match call.authorize(source) {
+			Some(Ok((valid, unspent))) => {
+				origin.set_caller_from(RawOrigin::Authorized);
+				Ok((valid, unspent.min(weight), origin))
+			},
+			Some(Err(error)) => Err(error),
+			None => Ok((ValidTransaction::default(), weight, origin)),
+		}
+	}
+
+	fn prepare(
+		self,
+		val: Self::Val,
+		_origin: &T::RuntimeOrigin,
+		_call: &T::RuntimeCall,
+		_info: &DispatchInfoOf<T::RuntimeCall>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		Ok(val)
+	}
+
+	fn post_dispatch_details(
+		unspent: Self::Pre,
+		_info: &DispatchInfoOf<T::RuntimeCall>,
+		_post_info: &PostDispatchInfoOf<T::RuntimeCall>,
+		_len: usize,
+		_result: &DispatchResult,
+	) -> Result<Weight, TransactionValidityError> {
+		Ok(unspent)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test, CALL};
+	use frame_support::dispatch::DispatchInfo;
+	use sp_runtime::traits::{
+		AsTransactionAuthorizedOrigin, DispatchTransaction, TxBaseImplication,
+	};
+
+	#[test]
+	fn calls_without_authorization_are_passed_through() {
+		new_test_ext().execute_with(|| {
+			let info = DispatchInfo::default();
+			let (valid, unspent, origin) = AuthorizeCall::<Test>::new()
+				.validate(
+					None.into(),
+					CALL,
+					&info,
+					0,
+					(),
+					&TxBaseImplication(CALL),
+					TransactionSource::External,
+				)
+				.unwrap();
+			assert_eq!(valid, ValidTransaction::default());
+			assert_eq!(unspent, Weight::zero());
+			assert!(!origin.is_transaction_authorized());
+
+			let (_, _, origin) = AuthorizeCall::<Test>::new()
+				.validate_only(Some(1).into(), CALL, &info, 0, TransactionSource::External, 0)
+				.unwrap();
+			assert_eq!(origin.as_signer(), Some(&1));
+		})
+	}
+}
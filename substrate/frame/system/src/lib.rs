@@ -169,7 +169,7 @@ pub mod weights;
 pub mod migrations;
 
 pub use extensions::{
-	check_genesis::CheckGenesis, check_mortality::CheckMortality,
+	authorize_call::AuthorizeCall, check_genesis::CheckGenesis, check_mortality::CheckMortality,
 	check_non_zero_sender::CheckNonZeroSender, check_nonce::CheckNonce,
 	check_spec_version::CheckSpecVersion, check_tx_version::CheckTxVersion,
 	check_weight::CheckWeight, weight_reclaim::WeightReclaim,
@@ -355,6 +355,7 @@ pub mod pallet {
 			type PreInherents = ();
 			type PostInherents = ();
 			type PostTransactions = ();
+			type MaxIdleTasks = frame_support::traits::ConstU32<0>;
 		}
 
 		/// Default configurations of this pallet in a solochain environment.
@@ -457,6 +458,7 @@ pub mod pallet {
 			type PreInherents = ();
 			type PostInherents = ();
 			type PostTransactions = ();
+			type MaxIdleTasks = frame_support::traits::ConstU32<0>;
 		}
 
 		/// Default configurations of this pallet in a relay-chain environment.
@@ -670,6 +672,15 @@ pub mod pallet {
 		///
 		/// See `frame_executive::block_flowchart` for a in-depth explanation when it runs.
 		type PostTransactions: PostTransactions;
+
+		/// The maximum number of valid tasks of [`Config::RuntimeTask`] to run in `on_idle`, with
+		/// the weight left in the block.
+		///
+		/// The tasks are discovered through [`Task::iter`], which is not metered. Hence, this
+		/// should only be enabled if the task lists of all pallets are small. Zero disables running
+		/// tasks in `on_idle`. Only used with the `experimental` feature.
+		#[pallet::constant]
+		type MaxIdleTasks: Get<u32>;
 	}
 
 	#[pallet::pallet]
@@ -677,6 +688,11 @@ pub mod pallet {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		#[cfg(feature = "experimental")]
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::run_idle_tasks(remaining_weight)
+		}
+
 		#[cfg(feature = "std")]
 		fn integrity_test() {
 			T::BlockWeights::get().validate().expect("The weights are invalid.");
@@ -892,6 +908,10 @@ pub mod pallet {
 		TaskFailed { task: T::RuntimeTask, err: DispatchError },
 		/// An upgrade was authorized.
 		UpgradeAuthorized { code_hash: T::Hash, check_version: bool },
+		/// The sampled `try_state` checks of a pallet failed.
+		///
+		/// Only emitted by runtimes that run sampled `try_state` checks in `frame_executive`.
+		TryStateFailed { pallet: Vec<u8>, error: DispatchError },
 	}
 
 	/// Error for the System pallet
@@ -1443,6 +1463,18 @@ where
 	}
 }
 
+/// Ensure that the origin `o` represents a transaction authorized by the dispatchable itself, see
+/// [`AuthorizeCall`]. Returns `Ok` or an `Err` otherwise.
+pub fn ensure_authorized<OuterOrigin, AccountId>(o: OuterOrigin) -> Result<(), BadOrigin>
+where
+	OuterOrigin: Into<Result<RawOrigin<AccountId>, OuterOrigin>>,
+{
+	match o.into() {
+		Ok(RawOrigin::Authorized) => Ok(()),
+		_ => Err(BadOrigin),
+	}
+}
+
 /// Reference status; can be either referenced or unreferenced.
 #[derive(RuntimeDebug)]
 pub enum RefStatus {
@@ -2243,6 +2275,46 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Run valid tasks of [`Config::RuntimeTask`], until `limit` is used up or
+	/// [`Config::MaxIdleTasks`] tasks were run. Returns the consumed weight.
+	///
+	/// Checking the validity of a task is accounted for as one storage read. Each task runs in its
+	/// own storage layer, so that a failing task has no effect apart from its
+	/// [`Event::TaskFailed`] event.
+	#[cfg(feature = "experimental")]
+	pub fn run_idle_tasks(limit: Weight) -> Weight {
+		use frame_support::traits::Task;
+		use sp_weights::WeightMeter;
+
+		let max_tasks = T::MaxIdleTasks::get();
+		if max_tasks == 0 {
+			return Weight::zero()
+		}
+
+		let mut meter = WeightMeter::with_limit(limit);
+		let mut executed = 0;
+		for task in T::RuntimeTask::iter() {
+			if executed >= max_tasks || meter.try_consume(T::DbWeight::get().reads(1)).is_err() {
+				break
+			}
+			if !task.is_valid() {
+				continue
+			}
+			if meter.try_consume(task.weight()).is_err() {
+				break
+			}
+
+			Self::deposit_event(Event::TaskStarted { task: task.clone() });
+			match storage::with_storage_layer(|| task.run()) {
+				Ok(()) => Self::deposit_event(Event::TaskCompleted { task }),
+				Err(err) => Self::deposit_event(Event::TaskFailed { task, err }),
+			}
+			executed += 1;
+		}
+
+		meter.consumed()
+	}
+
 	/// To be called after any origin/privilege checks. Put the code upgrade authorization into
 	/// storage and emit an event. Infallible.
 	pub fn do_authorize_upgrade(code_hash: T::Hash, check_version: bool) {
@@ -2490,7 +2562,9 @@ where
 
 /// Prelude to be used alongside pallet macro, for ease of use.
 pub mod pallet_prelude {
-	pub use crate::{ensure_none, ensure_root, ensure_signed, ensure_signed_or_root};
+	pub use crate::{
+		ensure_authorized, ensure_none, ensure_root, ensure_signed, ensure_signed_or_root,
+	};
 
 	/// Type alias for the `Origin` associated type of system config.
 	pub type OriginFor<T> = <T as crate::Config>::RuntimeOrigin;
@@ -90,6 +90,33 @@ where
 	}
 }
 
+/// Submit a `do_task` unsigned transaction for each valid task of type `Task`, up to `max` of
+/// them. Returns the number of submitted transactions.
+///
+/// Meant to be called from the offchain worker of a single pallet, or of the runtime, as the
+/// transactions of the same tasks would otherwise be submitted multiple times. `Task` is usually
+/// [`Config::RuntimeTask`](crate::Config::RuntimeTask), to submit the tasks of all pallets.
+#[cfg(feature = "experimental")]
+pub fn submit_valid_tasks<T, Task>(max: usize) -> usize
+where
+	T: crate::Config + CreateInherent<crate::Call<T>>,
+	Task: frame_support::traits::Task + Into<T::RuntimeTask>,
+{
+	Task::iter()
+		.filter(|task| task.is_valid())
+		.take(max)
+		.filter(|task| {
+			let call = crate::Call::<T>::do_task { task: task.clone().into() };
+			let xt = T::create_inherent(call.into());
+			SubmitTransaction::<T, crate::Call<T>>::submit_transaction(xt)
+				.inspect_err(|_| {
+					log::error!(target: crate::LOG_TARGET, "Failed to submit task {task:?}");
+				})
+				.is_ok()
+		})
+		.count()
+}
+
 /// Provides an implementation for signing transaction payloads.
 ///
 /// Keys used for signing are defined when instantiating the signer object.
@@ -470,6 +497,22 @@ pub trait CreateTransaction<LocalCall>: CreateTransactionBase<LocalCall> {
 	) -> Self::Extrinsic;
 }
 
+/// Interface for creating a transaction for a call that authorizes itself.
+///
+/// Such calls define their authorization with [`frame_support::pallet_macros::authorize`], which
+/// is checked by the [`AuthorizeCall`](crate::AuthorizeCall) transaction extension. The runtime
+/// defines the extension of these transactions, for example to submit them from an offchain
+/// worker.
+pub trait CreateAuthorizedTransaction<LocalCall>: CreateTransaction<LocalCall> {
+	/// Create the transaction extension to be used alongside an authorized call.
+	fn create_extension() -> Self::Extension;
+
+	/// Create a new transaction for an authorized call.
+	fn create_authorized_transaction(call: Self::RuntimeCall) -> Self::Extrinsic {
+		Self::create_transaction(call, Self::create_extension())
+	}
+}
+
 /// Interface for creating an old-school signed transaction.
 pub trait CreateSignedTransaction<LocalCall>:
 	CreateTransactionBase<LocalCall> + SigningTypes
@@ -0,0 +1,180 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Transaction extension paying the fees in the first of several assets that can cover them.
+
+use super::*;
+use frame_support::{traits::ConstU32, BoundedVec};
+
+/// The maximum number of candidate assets of a [`ChargeAssetTxPaymentWithFallback`].
+pub const MAX_FEE_ASSET_CANDIDATES: u32 = 4;
+
+/// The candidate assets of a [`ChargeAssetTxPaymentWithFallback`], in order of preference.
+///
+/// `None` stands for the native asset.
+pub type FeeAssetCandidates<AssetId> =
+	BoundedVec<Option<AssetId>, ConstU32<MAX_FEE_ASSET_CANDIDATES>>;
+
+/// Require payment for transaction inclusion in the first of an ordered list of candidate assets
+/// that can cover the fee, and optionally include a tip to gain additional priority in the queue.
+///
+/// The fee is paid in the first candidate for which the signer has a sufficient balance and, for
+/// assets other than the one accepted by [`Config::OnChargeAssetTransaction`], the fee can be
+/// swapped, for example because the liquidity pool holds enough liquidity. The payment itself is
+/// made exactly as by [`ChargeAssetTxPayment`] with the chosen asset. An empty list of candidates
+/// pays in the native currency.
+///
+/// The weight of the extension covers checking all candidates and is refunded for the candidates
+/// after the chosen one.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ChargeAssetTxPaymentWithFallback<T: Config> {
+	#[codec(compact)]
+	tip: BalanceOf<T>,
+	asset_ids: FeeAssetCandidates<T::AssetId>,
+}
+
+impl<T: Config> ChargeAssetTxPaymentWithFallback<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+{
+	/// Utility constructor. Used only in client/factory code.
+	pub fn from(tip: BalanceOf<T>, asset_ids: FeeAssetCandidates<T::AssetId>) -> Self {
+		Self { tip, asset_ids }
+	}
+
+	/// The extension paying in the candidate with the given `index`, or in the native currency if
+	/// there are no candidates.
+	fn candidate(&self, index: usize) -> ChargeAssetTxPayment<T> {
+		ChargeAssetTxPayment::from(self.tip, self.asset_ids.get(index).cloned().flatten())
+	}
+
+	/// Returns the index of the first candidate that can pay the `fee`.
+	fn choose_candidate(
+		&self,
+		who: &T::AccountId,
+		call: &T::RuntimeCall,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		fee: BalanceOf<T>,
+	) -> Result<usize, TransactionValidityError> {
+		if self.asset_ids.is_empty() {
+			return Ok(0)
+		}
+		(0..self.asset_ids.len())
+			.find(|index| self.candidate(*index).can_withdraw_fee(who, call, info, fee).is_ok())
+			.ok_or_else(|| InvalidTransaction::Payment.into())
+	}
+
+	/// The weight of checking and paying with all candidates after the one with the given
+	/// `index`.
+	fn weight_after(&self, index: usize, call: &T::RuntimeCall) -> Weight {
+		(index + 1..self.asset_ids.len()).fold(Weight::zero(), |weight, index| {
+			weight.saturating_add(self.candidate(index).weight(call))
+		})
+	}
+}
+
+impl<T: Config> core::fmt::Debug for ChargeAssetTxPaymentWithFallback<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "ChargeAssetTxPaymentWithFallback<{:?}, {:?}>", self.tip, self.asset_ids.encode())
+	}
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut core::fmt::Formatter) -> core::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config> TransactionExtension<T::RuntimeCall> for ChargeAssetTxPaymentWithFallback<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	BalanceOf<T>: Send + Sync + From<u64>,
+	T::AssetId: Send + Sync,
+	<T::RuntimeCall as Dispatchable>::RuntimeOrigin: AsSystemOriginSigner<T::AccountId> + Clone,
+{
+	const IDENTIFIER: &'static str = "ChargeAssetTxPaymentWithFallback";
+	type Implicit = ();
+	/// The index of the chosen candidate and the value of [`ChargeAssetTxPayment`] for it.
+	type Val = (u32, Val<T>);
+	/// The weight of the candidates after the chosen one and the value of [`ChargeAssetTxPayment`]
+	/// for the chosen one.
+	type Pre = (Weight, Pre<T>);
+
+	fn weight(&self, call: &T::RuntimeCall) -> Weight {
+		self.candidate(0).weight(call).saturating_add(self.weight_after(0, call))
+	}
+
+	fn validate(
+		&self,
+		origin: <T::RuntimeCall as Dispatchable>::RuntimeOrigin,
+		call: &T::RuntimeCall,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		len: usize,
+		self_implicit: Self::Implicit,
+		inherited_implication: &impl Encode,
+		source: TransactionSource,
+	) -> ValidateResult<Self::Val, T::RuntimeCall> {
+		let index = match origin.as_system_origin_signer() {
+			Some(who) => {
+				let fee = pallet_transaction_payment::Pallet::<T>::compute_fee(
+					len as u32, info, self.tip,
+				);
+				self.choose_candidate(who, call, info, fee)?
+			},
+			None => 0,
+		};
+		let (validity, val, origin) = self.candidate(index).validate(
+			origin,
+			call,
+			info,
+			len,
+			self_implicit,
+			inherited_implication,
+			source,
+		)?;
+		Ok((validity, (index as u32, val), origin))
+	}
+
+	fn prepare(
+		self,
+		(index, val): Self::Val,
+		origin: &<T::RuntimeCall as Dispatchable>::RuntimeOrigin,
+		call: &T::RuntimeCall,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		let index = index as usize;
+		let unused_weight = self.weight_after(index, call);
+		let pre = self.candidate(index).prepare(val, origin, call, info, len)?;
+		Ok((unused_weight, pre))
+	}
+
+	fn post_dispatch_details(
+		(unused_weight, pre): Self::Pre,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		post_info: &PostDispatchInfoOf<T::RuntimeCall>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<Weight, TransactionValidityError> {
+		// The weight of the unused candidates must not be charged for.
+		let mut post_info = *post_info;
+		post_info.refund(unused_weight);
+		let unspent_weight =
+			ChargeAssetTxPayment::<T>::post_dispatch_details(pre, info, &post_info, len, result)?;
+		Ok(unspent_weight.saturating_add(unused_weight))
+	}
+}
@@ -27,6 +27,10 @@
 //! fee amount by converting the fee calculated by [`pallet-transaction-payment`] in the native
 //! asset into the amount required of the specified asset.
 //!
+//! The [`ChargeAssetTxPaymentWithFallback`] extension instead accepts an ordered list of candidate
+//! assets and pays in the first one that can cover the fee. Wallets can quote the fee in several
+//! assets with the [`AssetConversionTxPaymentApi`] runtime API.
+//!
 //! ## Pallet API
 //!
 //! This pallet does not have any dispatchable calls or storage. It wraps FRAME's Transaction
@@ -44,9 +48,10 @@
 
 extern crate alloc;
 
-use codec::{Decode, Encode};
+use alloc::vec::Vec;
+use codec::{Codec, Decode, Encode};
 use frame_support::{
-	dispatch::{DispatchInfo, DispatchResult, PostDispatchInfo},
+	dispatch::{DispatchInfo, DispatchResult, GetDispatchInfo, PostDispatchInfo},
 	pallet_prelude::TransactionSource,
 	traits::IsType,
 	DefaultNoBound,
@@ -55,8 +60,8 @@ use pallet_transaction_payment::{ChargeTransactionPayment, OnChargeTransaction};
 use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{
-		AsSystemOriginSigner, DispatchInfoOf, Dispatchable, PostDispatchInfoOf, RefundWeight,
-		TransactionExtension, ValidateResult, Zero,
+		AsSystemOriginSigner, DispatchInfoOf, Dispatchable, ExtrinsicLike, MaybeDisplay,
+		PostDispatchInfoOf, RefundWeight, TransactionExtension, ValidateResult, Zero,
 	},
 	transaction_validity::{InvalidTransaction, TransactionValidityError, ValidTransaction},
 };
@@ -70,7 +75,9 @@ pub mod weights;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+mod fallback;
 mod payment;
+pub use fallback::*;
 use frame_support::{pallet_prelude::Weight, traits::tokens::AssetId};
 pub use payment::*;
 pub use weights::WeightInfo;
@@ -161,6 +168,88 @@ pub mod pallet {
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	/// Quote the fee of `unchecked_extrinsic` in each of the `asset_ids`, where `None` stands for
+	/// the native asset.
+	///
+	/// The quote for an asset is `None` if the fee can't be paid in it.
+	pub fn query_asset_fees<Extrinsic: ExtrinsicLike + GetDispatchInfo>(
+		unchecked_extrinsic: Extrinsic,
+		len: u32,
+		asset_ids: Vec<Option<T::AssetId>>,
+	) -> Vec<Option<BalanceOf<T>>>
+	where
+		T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
+	{
+		let fee = pallet_transaction_payment::Pallet::<T>::query_info(unchecked_extrinsic, len)
+			.partial_fee;
+		Self::quote_fees(fee, asset_ids)
+	}
+
+	/// Quote the fee of `call` in each of the `asset_ids`, where `None` stands for the native
+	/// asset.
+	///
+	/// The quote for an asset is `None` if the fee can't be paid in it.
+	pub fn query_call_asset_fees(
+		call: T::RuntimeCall,
+		len: u32,
+		asset_ids: Vec<Option<T::AssetId>>,
+	) -> Vec<Option<BalanceOf<T>>>
+	where
+		T::RuntimeCall: Dispatchable<Info = DispatchInfo> + GetDispatchInfo,
+	{
+		let fee = pallet_transaction_payment::Pallet::<T>::query_call_info(call, len).partial_fee;
+		Self::quote_fees(fee, asset_ids)
+	}
+
+	fn quote_fees(
+		fee: BalanceOf<T>,
+		asset_ids: Vec<Option<T::AssetId>>,
+	) -> Vec<Option<BalanceOf<T>>> {
+		asset_ids
+			.into_iter()
+			.map(|asset_id| match asset_id {
+				// No fee is withdrawn if it is zero.
+				Some(asset_id) if !fee.is_zero() =>
+					T::OnChargeAssetTransaction::quote_fee(asset_id, fee),
+				_ => Some(fee),
+			})
+			.collect()
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// This runtime api allows wallets to quote the fee of a transaction in several assets.
+	pub trait AssetConversionTxPaymentApi<Balance, AssetId, Call>
+	where
+		Balance: Codec + MaybeDisplay,
+		AssetId: Codec,
+		Call: Codec,
+	{
+		/// Quote the fee of an extrinsic in each of the `asset_ids`, where `None` stands for the
+		/// native asset.
+		///
+		/// The quote for an asset is `None` if the fee can't be paid in it. Note that the price
+		/// may have changed by the time the transaction is executed.
+		fn query_asset_fees(
+			uxt: Block::Extrinsic,
+			len: u32,
+			asset_ids: Vec<Option<AssetId>>,
+		) -> Vec<Option<Balance>>;
+
+		/// Quote the fee of an encoded `Call` in each of the `asset_ids`, where `None` stands for
+		/// the native asset.
+		///
+		/// The quote for an asset is `None` if the fee can't be paid in it. Note that the price
+		/// may have changed by the time the transaction is executed.
+		fn query_call_asset_fees(
+			call: Call,
+			len: u32,
+			asset_ids: Vec<Option<AssetId>>,
+		) -> Vec<Option<Balance>>;
+	}
+}
+
 /// Require payment for transaction inclusion and optionally include a tip to gain additional
 /// priority in the queue.
 ///
@@ -65,6 +65,14 @@ pub trait OnChargeAssetTransaction<T: Config> {
 		fee: Self::Balance,
 	) -> Result<(), TransactionValidityError>;
 
+	/// Quote the amount of `asset_id` needed to pay a `fee` calculated in the native asset.
+	///
+	/// Returns `None` if the `fee` can't be paid in `asset_id`. The default implementation
+	/// quotes no fees.
+	fn quote_fee(_asset_id: Self::AssetId, _fee: Self::Balance) -> Option<Self::Balance> {
+		None
+	}
+
 	/// Refund any overpaid fees and deposit the corrected amount.
 	/// The actual fee gets calculated once the transaction is executed.
 	///
@@ -216,6 +224,15 @@ where
 		Ok(())
 	}
 
+	fn quote_fee(asset_id: Self::AssetId, fee: BalanceOf<T>) -> Option<BalanceOf<T>> {
+		if asset_id == A::get() {
+			// The `asset_id` is the target asset, we do not need to swap.
+			return Some(fee)
+		}
+
+		S::quote_price_tokens_for_exact_tokens(asset_id, A::get(), fee, true)
+	}
+
 	fn correct_and_deposit_fee(
 		who: &T::AccountId,
 		_dispatch_info: &DispatchInfoOf<<T>::RuntimeCall>,
@@ -925,3 +925,165 @@ fn no_fee_and_no_weight_for_other_origins() {
 		assert_eq!(post_info.actual_weight, Some(info.call_weight));
 	})
 }
+
+fn create_asset_for(asset_id: u32, who: u64, balance: u64) {
+	assert_ok!(Assets::force_create(
+		RuntimeOrigin::root(),
+		asset_id.into(),
+		42,   /* owner */
+		true, /* is_sufficient */
+		2     /* min_balance */
+	));
+	let beneficiary = <Runtime as system::Config>::Lookup::unlookup(who);
+	assert_ok!(Assets::mint_into(asset_id.into(), &beneficiary, balance));
+}
+
+fn candidates(
+	asset_ids: Vec<Option<NativeOrWithId<u32>>>,
+) -> FeeAssetCandidates<NativeOrWithId<u32>> {
+	asset_ids.try_into().unwrap()
+}
+
+#[test]
+fn transaction_payment_with_fallback_skips_assets_that_cannot_pay() {
+	let base_weight = 5;
+	let balance_factor = 100;
+	ExtBuilder::default()
+		.balance_factor(balance_factor)
+		.base_weight(Weight::from_parts(base_weight, 0))
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let caller = 1;
+			let balance = 1000;
+			// asset 1 has a pool, asset 2 doesn't.
+			create_asset_for(1, caller, balance);
+			create_asset_for(2, caller, balance);
+			setup_lp(1, balance_factor);
+
+			let len = 10;
+			let fee_in_native = base_weight + 5 + len as u64;
+			let fee_in_asset = AssetConversion::quote_price_tokens_for_exact_tokens(
+				NativeOrWithId::WithId(1),
+				NativeOrWithId::Native,
+				fee_in_native,
+				true,
+			)
+			.unwrap();
+
+			let ext = ChargeAssetTxPaymentWithFallback::<Runtime>::from(
+				0,
+				candidates(vec![
+					Some(NativeOrWithId::WithId(2)),
+					Some(NativeOrWithId::WithId(1)),
+					None,
+				]),
+			);
+			// the weight covers all candidates.
+			assert_eq!(ext.weight(CALL), Weight::from_parts(20 + 20 + 15, 0));
+
+			let info = info_from_weight(WEIGHT_5);
+			let (pre, _) =
+				ext.validate_and_prepare(Some(caller).into(), CALL, &info, len, 0).unwrap();
+
+			// the fee is paid in the first asset that can pay it.
+			assert_eq!(Balances::free_balance(caller), 10 * balance_factor);
+			assert_eq!(Assets::balance(2, caller), balance);
+			assert_eq!(Assets::balance(1, caller), balance - fee_in_asset);
+
+			// the weight of the candidates after the chosen one is refunded.
+			assert_eq!(
+				ChargeAssetTxPaymentWithFallback::<Runtime>::post_dispatch_details(
+					pre,
+					&info,
+					&default_post_info(),
+					len,
+					&Ok(()),
+				),
+				Ok(Weight::from_parts(15, 0)),
+			);
+			assert_eq!(Assets::balance(1, caller), balance - fee_in_asset);
+			assert_eq!(FeeUnbalancedAmount::get(), fee_in_native);
+			System::assert_last_event(RuntimeEvent::AssetTxPayment(Event::AssetTxFeePaid {
+				who: caller,
+				actual_fee: fee_in_asset,
+				tip: 0,
+				asset_id: NativeOrWithId::WithId(1),
+			}));
+		});
+}
+
+#[test]
+fn transaction_payment_with_fallback_falls_back_to_native() {
+	let base_weight = 5;
+	let balance_factor = 100;
+	ExtBuilder::default()
+		.balance_factor(balance_factor)
+		.base_weight(Weight::from_parts(base_weight, 0))
+		.build()
+		.execute_with(|| {
+			let caller = 1;
+			// the caller has no balance of asset 1.
+			create_asset_for(1, 2, 1000);
+			setup_lp(1, balance_factor);
+
+			let len = 10;
+			let info = info_from_weight(WEIGHT_5);
+			let ext = ChargeAssetTxPaymentWithFallback::<Runtime>::from(
+				0,
+				candidates(vec![Some(NativeOrWithId::WithId(1)), None]),
+			);
+			assert_ok!(ext.validate_and_prepare(Some(caller).into(), CALL, &info, len, 0));
+			assert_eq!(Balances::free_balance(caller), 10 * balance_factor - 5 - 5 - 10);
+
+			// no candidate can pay.
+			let ext = ChargeAssetTxPaymentWithFallback::<Runtime>::from(
+				0,
+				candidates(vec![Some(NativeOrWithId::WithId(1))]),
+			);
+			assert_eq!(
+				ext.validate_and_prepare(Some(caller).into(), CALL, &info, len, 0).map(|_| ()),
+				Err(InvalidTransaction::Payment.into()),
+			);
+		});
+}
+
+#[test]
+fn query_asset_fees_works() {
+	let balance_factor = 100;
+	ExtBuilder::default()
+		.balance_factor(balance_factor)
+		.base_weight(Weight::from_parts(5, 0))
+		.build()
+		.execute_with(|| {
+			create_asset_for(1, 1, 1000);
+			create_asset_for(2, 1, 1000);
+			setup_lp(1, balance_factor);
+
+			let len = 10;
+			let fee_in_native =
+				pallet_transaction_payment::Pallet::<Runtime>::query_call_info(CALL.clone(), len)
+					.partial_fee;
+			let fee_in_asset = AssetConversion::quote_price_tokens_for_exact_tokens(
+				NativeOrWithId::WithId(1),
+				NativeOrWithId::Native,
+				fee_in_native,
+				true,
+			);
+			assert!(fee_in_asset.is_some());
+
+			assert_eq!(
+				Pallet::<Runtime>::query_call_asset_fees(
+					CALL.clone(),
+					len,
+					vec![
+						None,
+						Some(NativeOrWithId::Native),
+						Some(NativeOrWithId::WithId(1)),
+						Some(NativeOrWithId::WithId(2)),
+					],
+				),
+				vec![Some(fee_in_native), Some(fee_in_native), fee_in_asset, None],
+			);
+		});
+}
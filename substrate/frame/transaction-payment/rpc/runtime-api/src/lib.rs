@@ -22,7 +22,9 @@
 use codec::Codec;
 use sp_runtime::traits::MaybeDisplay;
 
-pub use pallet_transaction_payment::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
+pub use pallet_transaction_payment::{
+	FeeDetails, InclusionFee, Multiplier, RuntimeDispatchInfo, WeightMultiplier,
+};
 
 sp_api::decl_runtime_apis! {
 	#[api_version(4)]
@@ -53,4 +55,15 @@ sp_api::decl_runtime_apis! {
 		/// Query the output of the current `LengthToFee` given some input.
 		fn query_length_to_fee(length: u32) -> Balance;
 	}
+
+	pub trait TransactionPaymentMultiplierApi {
+		/// Query the fee multiplier of the next block.
+		fn query_fee_multiplier() -> Multiplier;
+
+		/// Query the fee multipliers of the ref-time and the proof size of the weight for the
+		/// next block.
+		///
+		/// Both are the same as the fee multiplier unless the runtime adjusts them separately.
+		fn query_weight_multiplier() -> WeightMultiplier;
+	}
 }
@@ -42,7 +42,8 @@
 //!   - The mapping between one unit of weight to one unit of fee via [`Config::WeightToFee`].
 //!   - A means of updating the fee for the next block, via defining a multiplier, based on the
 //!     final state of the chain at the end of the previous block. This can be configured via
-//!     [`Config::FeeMultiplierUpdate`]
+//!     [`Config::FeeMultiplierUpdate`], for example with [`TargetedFeeAdjustment`], or with
+//!     [`MultiDimensionalFeeAdjustment`] to adjust the fees of ref-time and proof size separately.
 //!   - How the fees are paid via [`Config::OnChargeTransaction`].
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -62,14 +63,15 @@ use frame_support::{
 pub use pallet::*;
 pub use payment::*;
 use sp_runtime::{
+	helpers_128bit::multiply_by_rational_with_rounding,
 	traits::{
 		Convert, DispatchInfoOf, Dispatchable, One, PostDispatchInfoOf, SaturatedConversion,
 		Saturating, TransactionExtension, Zero,
 	},
 	transaction_validity::{TransactionPriority, TransactionValidityError, ValidTransaction},
-	FixedPointNumber, FixedU128, Perbill, Perquintill, RuntimeDebug,
+	FixedPointNumber, FixedU128, Perbill, Perquintill, Rounding, RuntimeDebug,
 };
-pub use types::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
+pub use types::{FeeDetails, InclusionFee, RuntimeDispatchInfo, WeightMultiplier};
 pub use weights::WeightInfo;
 
 #[cfg(test)]
@@ -153,6 +155,21 @@ pub trait MultiplierUpdate: Convert<Multiplier, Multiplier> {
 	fn target() -> Perquintill;
 	/// Variability factor
 	fn variability() -> Multiplier;
+	/// Whether separate multipliers are maintained for the ref-time and the proof size of the
+	/// weight. If so, [`Self::convert_per_dimension`] is used instead of `convert` to update them
+	/// and the weight fee is adjusted by both multipliers, see [`Pallet::adjusted_weight_to_fee`].
+	fn per_dimension() -> bool {
+		false
+	}
+	/// Convert the current per-dimension multiplier to the next one.
+	///
+	/// Only used if [`Self::per_dimension`] returns `true`.
+	fn convert_per_dimension(previous: WeightMultiplier) -> WeightMultiplier {
+		WeightMultiplier {
+			ref_time: Self::convert(previous.ref_time),
+			proof_size: Self::convert(previous.proof_size),
+		}
+	}
 }
 
 impl MultiplierUpdate for () {
@@ -235,38 +252,152 @@ where
 				(normal_block_weight.ref_time(), normal_max_weight.ref_time())
 			};
 
-		let target_block_fullness = S::get();
-		let adjustment_variable = V::get();
+		adjust_multiplier(
+			previous,
+			normal_limiting_dimension,
+			max_limiting_dimension,
+			S::get(),
+			V::get(),
+			min_multiplier,
+			max_multiplier,
+		)
+	}
+}
 
-		let target_weight = (target_block_fullness * max_limiting_dimension) as u128;
-		let block_weight = normal_limiting_dimension as u128;
+/// Adjust the `previous` multiplier by the fullness of one dimension of the block, as described in
+/// [`TargetedFeeAdjustment`].
+fn adjust_multiplier(
+	previous: Multiplier,
+	block_dimension: u64,
+	max_dimension: u64,
+	target_block_fullness: Perquintill,
+	adjustment_variable: Multiplier,
+	min_multiplier: Multiplier,
+	max_multiplier: Multiplier,
+) -> Multiplier {
+	let target_weight = (target_block_fullness * max_dimension) as u128;
+	let block_weight = block_dimension as u128;
+
+	// determines if the first_term is positive
+	let positive = block_weight >= target_weight;
+	let diff_abs = block_weight.max(target_weight) - block_weight.min(target_weight);
+
+	// defensive only, a test case assures that the maximum weight diff can fit in Multiplier
+	// without any saturation.
+	let diff = Multiplier::saturating_from_rational(diff_abs, max_dimension.max(1));
+	let diff_squared = diff.saturating_mul(diff);
+
+	let v_squared_2 = adjustment_variable.saturating_mul(adjustment_variable) /
+		Multiplier::saturating_from_integer(2);
+
+	let first_term = adjustment_variable.saturating_mul(diff);
+	let second_term = v_squared_2.saturating_mul(diff_squared);
+
+	if positive {
+		let excess = first_term.saturating_add(second_term).saturating_mul(previous);
+		previous.saturating_add(excess).clamp(min_multiplier, max_multiplier)
+	} else {
+		// Defensive-only: first_term > second_term. Safe subtraction.
+		let negative = first_term.saturating_sub(second_term).saturating_mul(previous);
+		previous.saturating_sub(negative).clamp(min_multiplier, max_multiplier)
+	}
+}
 
-		// determines if the first_term is positive
-		let positive = block_weight >= target_weight;
-		let diff_abs = block_weight.max(target_weight) - block_weight.min(target_weight);
+/// A struct to update the fee multipliers of the ref-time and the proof size of the weight
+/// separately per block.
+///
+/// Each dimension follows the formula of [`TargetedFeeAdjustment`], but is adjusted by the
+/// fullness of the block in that dimension only instead of the fullness in the limiting dimension.
+/// This is similar to a separate EIP-1559 base fee per resource: a block that is full in proof size
+/// makes the proof size more expensive without affecting the price of ref-time, which matters for
+/// parachains where the proof size is usually the bottleneck.
+///
+/// `S`, `V`, `M` and `X` are used for both dimensions and have the same meaning as for
+/// [`TargetedFeeAdjustment`]. The single multiplier stored in [`NextFeeMultiplier`] follows the
+/// larger of the two multipliers.
+///
+/// Note that the proof size multiplier only has an effect if [`Config::WeightToFee`] charges for
+/// the proof size of the weight. The weight fee is adjusted by the average of both multipliers,
+/// weighted by the share of each dimension in the fee, see [`Pallet::adjusted_weight_to_fee`].
+pub struct MultiDimensionalFeeAdjustment<T, S, V, M, X>(core::marker::PhantomData<(T, S, V, M, X)>);
 
-		// defensive only, a test case assures that the maximum weight diff can fit in Multiplier
-		// without any saturation.
-		let diff = Multiplier::saturating_from_rational(diff_abs, max_limiting_dimension.max(1));
-		let diff_squared = diff.saturating_mul(diff);
+impl<T, S, V, M, X> MultiplierUpdate for MultiDimensionalFeeAdjustment<T, S, V, M, X>
+where
+	T: frame_system::Config,
+	S: Get<Perquintill>,
+	V: Get<Multiplier>,
+	M: Get<Multiplier>,
+	X: Get<Multiplier>,
+{
+	fn min() -> Multiplier {
+		M::get()
+	}
+	fn max() -> Multiplier {
+		X::get()
+	}
+	fn target() -> Perquintill {
+		S::get()
+	}
+	fn variability() -> Multiplier {
+		V::get()
+	}
+	fn per_dimension() -> bool {
+		true
+	}
+	fn convert_per_dimension(previous: WeightMultiplier) -> WeightMultiplier {
+		let min_multiplier = M::get();
+		let max_multiplier = X::get();
 
-		let v_squared_2 = adjustment_variable.saturating_mul(adjustment_variable) /
-			Multiplier::saturating_from_integer(2);
+		let weights = T::BlockWeights::get();
+		// the computed ratio is only among the normal class.
+		let normal_max_weight =
+			weights.get(DispatchClass::Normal).max_total.unwrap_or(weights.max_block);
+		let current_block_weight = frame_system::Pallet::<T>::block_weight();
+		let normal_block_weight =
+			current_block_weight.get(DispatchClass::Normal).min(normal_max_weight);
 
-		let first_term = adjustment_variable.saturating_mul(diff);
-		let second_term = v_squared_2.saturating_mul(diff_squared);
+		let adjust = |previous: Multiplier, block_dimension: u64, max_dimension: u64| {
+			// Defensive only, see `TargetedFeeAdjustment`.
+			adjust_multiplier(
+				previous.max(min_multiplier),
+				block_dimension,
+				max_dimension,
+				S::get(),
+				V::get(),
+				min_multiplier,
+				max_multiplier,
+			)
+		};
 
-		if positive {
-			let excess = first_term.saturating_add(second_term).saturating_mul(previous);
-			previous.saturating_add(excess).clamp(min_multiplier, max_multiplier)
-		} else {
-			// Defensive-only: first_term > second_term. Safe subtraction.
-			let negative = first_term.saturating_sub(second_term).saturating_mul(previous);
-			previous.saturating_sub(negative).clamp(min_multiplier, max_multiplier)
+		WeightMultiplier {
+			ref_time: adjust(
+				previous.ref_time,
+				normal_block_weight.ref_time(),
+				normal_max_weight.ref_time(),
+			),
+			proof_size: adjust(
+				previous.proof_size,
+				normal_block_weight.proof_size(),
+				normal_max_weight.proof_size(),
+			),
 		}
 	}
 }
 
+impl<T, S, V, M, X> Convert<Multiplier, Multiplier> for MultiDimensionalFeeAdjustment<T, S, V, M, X>
+where
+	T: frame_system::Config,
+	S: Get<Perquintill>,
+	V: Get<Multiplier>,
+	M: Get<Multiplier>,
+	X: Get<Multiplier>,
+{
+	/// Adjust a single multiplier by the limiting dimension, like [`TargetedFeeAdjustment`].
+	fn convert(previous: Multiplier) -> Multiplier {
+		TargetedFeeAdjustment::<T, S, V, M, X>::convert(previous)
+	}
+}
+
 /// A struct to make the fee multiplier a constant
 pub struct ConstFeeMultiplier<M: Get<Multiplier>>(core::marker::PhantomData<M>);
 
@@ -407,6 +538,14 @@ pub mod pallet {
 	pub type NextFeeMultiplier<T: Config> =
 		StorageValue<_, Multiplier, ValueQuery, NextFeeMultiplierOnEmpty>;
 
+	/// The per-dimension fee multipliers of the next block.
+	///
+	/// Only maintained if [`MultiplierUpdate::per_dimension`] of [`Config::FeeMultiplierUpdate`]
+	/// returns `true`. Use [`Pallet::next_weight_multiplier`] to read it.
+	#[pallet::storage]
+	#[pallet::whitelist_storage]
+	pub type NextWeightMultiplier<T: Config> = StorageValue<_, WeightMultiplier, OptionQuery>;
+
 	#[pallet::storage]
 	pub type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
 
@@ -442,9 +581,17 @@ pub mod pallet {
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_finalize(_: frame_system::pallet_prelude::BlockNumberFor<T>) {
-			NextFeeMultiplier::<T>::mutate(|fm| {
-				*fm = T::FeeMultiplierUpdate::convert(*fm);
-			});
+			if T::FeeMultiplierUpdate::per_dimension() {
+				let next = T::FeeMultiplierUpdate::convert_per_dimension(
+					Pallet::<T>::next_weight_multiplier(),
+				);
+				NextWeightMultiplier::<T>::put(next);
+				NextFeeMultiplier::<T>::put(next.largest());
+			} else {
+				NextFeeMultiplier::<T>::mutate(|fm| {
+					*fm = T::FeeMultiplierUpdate::convert(*fm);
+				});
+			}
 		}
 
 		#[cfg(feature = "std")]
@@ -488,6 +635,18 @@ pub mod pallet {
 				block saturation is more than target by 1% and multiplier is minimal then \
 				the multiplier doesn't increase."
 			);
+
+			if T::FeeMultiplierUpdate::per_dimension() {
+				let next = T::FeeMultiplierUpdate::convert_per_dimension(
+					WeightMultiplier::uniform(min_value),
+				);
+				assert!(
+					next.ref_time > min_value && next.proof_size > min_value,
+					"The minimum bound of the multiplier is too low. When \
+					block saturation is more than target by 1% and the multipliers are minimal then \
+					the multipliers don't increase in both dimensions."
+				);
+			}
 		}
 	}
 }
@@ -498,6 +657,20 @@ impl<T: Config> Pallet<T> {
 		NextFeeMultiplier::<T>::get()
 	}
 
+	/// Public function to access the next per-dimension fee multipliers.
+	///
+	/// If [`Config::FeeMultiplierUpdate`] doesn't maintain separate multipliers per dimension,
+	/// both dimensions use the [`NextFeeMultiplier`]. The same holds until the per-dimension
+	/// multipliers are updated for the first time.
+	pub fn next_weight_multiplier() -> WeightMultiplier {
+		let multiplier = if T::FeeMultiplierUpdate::per_dimension() {
+			NextWeightMultiplier::<T>::get()
+		} else {
+			None
+		};
+		multiplier.unwrap_or_else(|| WeightMultiplier::uniform(NextFeeMultiplier::<T>::get()))
+	}
+
 	/// Query the data that we know about the fee of a given `call`.
 	///
 	/// This pallet is not and cannot be aware of the internals of a signed extension, for example
@@ -645,11 +818,8 @@ impl<T: Config> Pallet<T> {
 		class: DispatchClass,
 	) -> FeeDetails<BalanceOf<T>> {
 		if pays_fee == Pays::Yes {
-			// the adjustable part of the fee.
-			let unadjusted_weight_fee = Self::weight_to_fee(weight);
-			let multiplier = NextFeeMultiplier::<T>::get();
 			// final adjusted weight fee.
-			let adjusted_weight_fee = multiplier.saturating_mul_int(unadjusted_weight_fee);
+			let adjusted_weight_fee = Self::adjusted_weight_to_fee(weight);
 
 			// length fee. this is adjusted via `LengthToFee`.
 			let len_fee = Self::length_to_fee(len);
@@ -678,6 +848,46 @@ impl<T: Config> Pallet<T> {
 		T::WeightToFee::weight_to_fee(&capped_weight)
 	}
 
+	/// Compute the weight fee adjusted by the fee multiplier of the next block.
+	///
+	/// With per-dimension multipliers, the fee of the `weight` is adjusted by the average of the
+	/// multipliers, weighted by the fee of the ref-time and the fee of the proof size of the
+	/// `weight` on their own. The adjusted fee thus always lies between the fee adjusted by the
+	/// smaller and by the larger multiplier, whatever [`Config::WeightToFee`] is.
+	///
+	/// If [`Config::WeightToFee`] is additive, i.e. the fee of a weight is the sum of the fees of
+	/// its ref-time and its proof size, this is the same as adjusting the fee of each dimension
+	/// by its own multiplier.
+	pub fn adjusted_weight_to_fee(weight: Weight) -> BalanceOf<T> {
+		let fee = Self::weight_to_fee(weight);
+		if !T::FeeMultiplierUpdate::per_dimension() {
+			return NextFeeMultiplier::<T>::get().saturating_mul_int(fee)
+		}
+
+		let multiplier = Self::next_weight_multiplier();
+		let ref_time_fee = Self::weight_to_fee(Weight::from_parts(weight.ref_time(), 0));
+		let proof_size_fee = Self::weight_to_fee(Weight::from_parts(0, weight.proof_size()));
+		let unadjusted = ref_time_fee.saturating_add(proof_size_fee);
+		if unadjusted.is_zero() {
+			return multiplier.largest().saturating_mul_int(fee)
+		}
+
+		let adjusted = multiplier
+			.ref_time
+			.saturating_mul_int(ref_time_fee)
+			.saturating_add(multiplier.proof_size.saturating_mul_int(proof_size_fee));
+		multiply_by_rational_with_rounding(
+			fee.saturated_into(),
+			adjusted.saturated_into(),
+			unadjusted.saturated_into(),
+			Rounding::Down,
+		)
+		.map_or_else(
+			<BalanceOf<T> as sp_runtime::traits::Bounded>::max_value,
+			BalanceOf::<T>::saturated_from,
+		)
+	}
+
 	/// Deposit the [`Event::TransactionFeePaid`] event.
 	pub fn deposit_fee_paid_event(who: T::AccountId, actual_fee: BalanceOf<T>, tip: BalanceOf<T>) {
 		Self::deposit_event(Event::TransactionFeePaid { who, actual_fee, tip });
@@ -694,7 +904,7 @@ where
 	/// share that the weight contributes to the overall fee of a transaction. It is mainly
 	/// for informational purposes and not used in the actual fee calculation.
 	fn convert(weight: Weight) -> BalanceOf<T> {
-		NextFeeMultiplier::<T>::get().saturating_mul_int(Self::weight_to_fee(weight))
+		Pallet::<T>::adjusted_weight_to_fee(weight)
 	}
 }
 
@@ -62,8 +62,11 @@ impl Get<frame_system::limits::BlockWeights> for BlockWeights {
 
 parameter_types! {
 	pub static WeightToFee: u64 = 1;
+	pub static ProofSizeFee: u64 = 0;
 	pub static TransactionByteFee: u64 = 1;
 	pub static OperationalFeeMultiplier: u8 = 5;
+	pub static PerDimensionMultiplier: bool = false;
+	pub static MaxDimensionFee: bool = false;
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
@@ -82,8 +85,15 @@ impl WeightToFeeT for WeightToFee {
 	type Balance = u64;
 
 	fn weight_to_fee(weight: &Weight) -> Self::Balance {
-		Self::Balance::saturated_from(weight.ref_time())
-			.saturating_mul(WEIGHT_TO_FEE.with(|v| *v.borrow()))
+		let ref_time_fee = Self::Balance::saturated_from(weight.ref_time())
+			.saturating_mul(WEIGHT_TO_FEE.with(|v| *v.borrow()));
+		let proof_size_fee =
+			Self::Balance::saturated_from(weight.proof_size()).saturating_mul(ProofSizeFee::get());
+		if MaxDimensionFee::get() {
+			ref_time_fee.max(proof_size_fee)
+		} else {
+			ref_time_fee.saturating_add(proof_size_fee)
+		}
 	}
 }
 
@@ -119,6 +129,33 @@ impl OnUnbalanced<fungible::Credit<<Runtime as frame_system::Config>::AccountId,
 	}
 }
 
+/// Keeps the fee multiplier constant, optionally maintaining one per weight dimension.
+pub struct MockFeeMultiplierUpdate;
+
+impl Convert<Multiplier, Multiplier> for MockFeeMultiplierUpdate {
+	fn convert(previous: Multiplier) -> Multiplier {
+		previous
+	}
+}
+
+impl MultiplierUpdate for MockFeeMultiplierUpdate {
+	fn min() -> Multiplier {
+		<() as MultiplierUpdate>::min()
+	}
+	fn max() -> Multiplier {
+		<() as MultiplierUpdate>::max()
+	}
+	fn target() -> Perquintill {
+		<() as MultiplierUpdate>::target()
+	}
+	fn variability() -> Multiplier {
+		<() as MultiplierUpdate>::variability()
+	}
+	fn per_dimension() -> bool {
+		PerDimensionMultiplier::get()
+	}
+}
+
 /// Weights used in testing.
 pub struct MockWeights;
 
@@ -134,7 +171,7 @@ impl Config for Runtime {
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
 	type WeightToFee = WeightToFee;
 	type LengthToFee = TransactionByteFee;
-	type FeeMultiplierUpdate = ();
+	type FeeMultiplierUpdate = MockFeeMultiplierUpdate;
 	type WeightInfo = MockWeights;
 }
 
@@ -30,7 +30,7 @@ use sp_runtime::{
 use frame_support::{
 	assert_ok,
 	dispatch::{DispatchClass, DispatchInfo, GetDispatchInfo, PostDispatchInfo},
-	traits::{Currency, OriginTrait},
+	traits::{Currency, OnFinalize, OriginTrait},
 	weights::Weight,
 };
 use frame_system as system;
@@ -459,6 +459,120 @@ fn compute_fee_works_with_multiplier() {
 		});
 }
 
+#[test]
+fn compute_fee_works_with_per_dimension_multiplier() {
+	ExtBuilder::default()
+		.base_weight(Weight::from_parts(100, 0))
+		.byte_fee(10)
+		.balance_factor(0)
+		.build()
+		.execute_with(|| {
+			ProofSizeFee::set(2);
+			NextFeeMultiplier::<Runtime>::put(Multiplier::saturating_from_rational(3, 2));
+			NextWeightMultiplier::<Runtime>::put(WeightMultiplier {
+				ref_time: Multiplier::saturating_from_rational(1, 2),
+				proof_size: Multiplier::saturating_from_integer(3),
+			});
+			let dispatch_info = DispatchInfo {
+				call_weight: Weight::from_parts(124, 10),
+				extension_weight: Weight::zero(),
+				class: DispatchClass::Operational,
+				pays_fee: Pays::Yes,
+			};
+
+			// Without per-dimension multipliers the stored ones are ignored.
+			assert_eq!(
+				Pallet::<Runtime>::next_weight_multiplier(),
+				WeightMultiplier::uniform(Multiplier::saturating_from_rational(3, 2)),
+			);
+			// 124 ref-time, 10 proof size, 456 length, 100 base
+			assert_eq!(
+				Pallet::<Runtime>::compute_fee(456, &dispatch_info, 789),
+				100 + 3 * (124 + 2 * 10) / 2 + 4560 + 789,
+			);
+
+			PerDimensionMultiplier::set(true);
+			assert_eq!(
+				Pallet::<Runtime>::next_weight_multiplier(),
+				NextWeightMultiplier::<Runtime>::get().unwrap(),
+			);
+			// The base fee is unaffected by the multipliers.
+			assert_eq!(
+				Pallet::<Runtime>::compute_fee(456, &dispatch_info, 789),
+				100 + 124 / 2 + 3 * 2 * 10 + 4560 + 789,
+			);
+		});
+}
+
+#[test]
+fn per_dimension_multiplier_works_with_non_additive_weight_to_fee() {
+	ExtBuilder::default()
+		.base_weight(Weight::zero())
+		.byte_fee(0)
+		.balance_factor(0)
+		.build()
+		.execute_with(|| {
+			// Only the more expensive dimension is charged.
+			ProofSizeFee::set(2);
+			MaxDimensionFee::set(true);
+			PerDimensionMultiplier::set(true);
+			let dispatch_info = DispatchInfo {
+				call_weight: Weight::from_parts(124, 10),
+				extension_weight: Weight::zero(),
+				class: DispatchClass::Normal,
+				pays_fee: Pays::Yes,
+			};
+
+			// Equal multipliers adjust the fee like a single multiplier.
+			NextWeightMultiplier::<Runtime>::put(WeightMultiplier::uniform(
+				Multiplier::saturating_from_rational(3, 2),
+			));
+			assert_eq!(Pallet::<Runtime>::compute_fee(0, &dispatch_info, 0), 3 * 124 / 2);
+
+			// Otherwise the fee of the ref-time (124) and of the proof size (20) weigh the
+			// multipliers.
+			NextWeightMultiplier::<Runtime>::put(WeightMultiplier {
+				ref_time: Multiplier::saturating_from_rational(1, 2),
+				proof_size: Multiplier::saturating_from_integer(3),
+			});
+			assert_eq!(
+				Pallet::<Runtime>::compute_fee(0, &dispatch_info, 0),
+				124 * (124 / 2 + 3 * 20) / (124 + 20),
+			);
+		});
+}
+
+#[test]
+fn per_dimension_multiplier_is_updated_on_finalize() {
+	ExtBuilder::default()
+		.with_initial_multiplier(Multiplier::saturating_from_rational(3, 2))
+		.build()
+		.execute_with(|| {
+			PerDimensionMultiplier::set(true);
+
+			// Both dimensions start from the single multiplier.
+			assert_eq!(NextWeightMultiplier::<Runtime>::get(), None);
+			assert_eq!(
+				Pallet::<Runtime>::next_weight_multiplier(),
+				WeightMultiplier::uniform(Multiplier::saturating_from_rational(3, 2)),
+			);
+
+			TransactionPayment::on_finalize(1);
+			assert_eq!(
+				NextWeightMultiplier::<Runtime>::get(),
+				Some(WeightMultiplier::uniform(Multiplier::saturating_from_rational(3, 2))),
+			);
+
+			// The single multiplier follows the larger one.
+			NextWeightMultiplier::<Runtime>::put(WeightMultiplier {
+				ref_time: Multiplier::saturating_from_rational(1, 2),
+				proof_size: Multiplier::saturating_from_integer(3),
+			});
+			TransactionPayment::on_finalize(2);
+			assert_eq!(NextFeeMultiplier::<Runtime>::get(), Multiplier::saturating_from_integer(3));
+		});
+}
+
 #[test]
 fn compute_fee_works_with_negative_multiplier() {
 	ExtBuilder::default()
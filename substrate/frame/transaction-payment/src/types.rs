@@ -17,16 +17,21 @@
 
 //! Types for transaction-payment RPC.
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
 use scale_info::TypeInfo;
 
-use sp_runtime::traits::{AtLeast32BitUnsigned, Zero};
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, Zero},
+	RuntimeDebug,
+};
 
 use frame_support::dispatch::DispatchClass;
 
+use crate::Multiplier;
+
 /// The base fee and adjusted weight and length fees constitute the _inclusion fee_.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
@@ -116,6 +121,27 @@ pub struct RuntimeDispatchInfo<Balance, Weight = frame_support::weights::Weight>
 	pub partial_fee: Balance,
 }
 
+/// The fee multipliers of the ref-time and the proof-size dimension of the weight.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct WeightMultiplier {
+	/// The multiplier of the fee of the ref-time of the weight.
+	pub ref_time: Multiplier,
+	/// The multiplier of the fee of the proof size of the weight.
+	pub proof_size: Multiplier,
+}
+
+impl WeightMultiplier {
+	/// Use the same `multiplier` for both dimensions.
+	pub const fn uniform(multiplier: Multiplier) -> Self {
+		Self { ref_time: multiplier, proof_size: multiplier }
+	}
+
+	/// Returns the larger of the two multipliers.
+	pub fn largest(&self) -> Multiplier {
+		self.ref_time.max(self.proof_size)
+	}
+}
+
 #[cfg(feature = "std")]
 mod serde_balance {
 	use serde::{Deserialize, Deserializer, Serializer};
@@ -62,6 +62,10 @@ pub enum Error {
 	#[error("UnknownBlocks: {0}")]
 	UnknownBlocks(String),
 
+	/// The block is known, but its state was pruned or is not kept in the database.
+	#[error("State unavailable: {0}")]
+	StateUnavailable(String),
+
 	#[error(transparent)]
 	ApplyExtrinsicFailed(#[from] ApplyExtrinsicFailed),
 
@@ -194,7 +198,7 @@ impl From<Box<dyn sp_state_machine::Error>> for Error {
 impl From<Error> for ApiError {
 	fn from(err: Error) -> ApiError {
 		match err {
-			Error::UnknownBlock(msg) => ApiError::UnknownBlock(msg),
+			Error::UnknownBlock(msg) | Error::StateUnavailable(msg) => ApiError::UnknownBlock(msg),
 			Error::RuntimeApiError(err) => err,
 			e => ApiError::Application(Box::new(e)),
 		}
@@ -314,7 +314,18 @@ pub mod vrf {
 
 	/// Transcript ready to be used for VRF related operations.
 	#[derive(Clone)]
-	pub struct VrfTranscript(pub merlin::Transcript);
+	pub struct VrfTranscript(pub merlin::Transcript, VrfTranscriptData);
+
+	/// The label and the `(domain, message)` items a [`VrfTranscript`] is built from.
+	///
+	/// A transcript itself can't be encoded, its data allows to rebuild it elsewhere.
+	#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode)]
+	pub struct VrfTranscriptData {
+		/// The label of the transcript.
+		pub label: Vec<u8>,
+		/// The `(domain, message)` items of the transcript.
+		pub items: Vec<(Vec<u8>, Vec<u8>)>,
+	}
 
 	impl VrfTranscript {
 		/// Build a new transcript instance.
@@ -323,7 +334,33 @@ pub mod vrf {
 		pub fn new(label: &'static [u8], data: &[(&'static [u8], &[u8])]) -> Self {
 			let mut transcript = merlin::Transcript::new(label);
 			data.iter().for_each(|(l, b)| transcript.append_message(l, b));
-			VrfTranscript(transcript)
+			let data = VrfTranscriptData {
+				label: label.to_vec(),
+				items: data.iter().map(|(l, b)| (l.to_vec(), b.to_vec())).collect(),
+			};
+			VrfTranscript(transcript, data)
+		}
+
+		/// Rebuild a transcript from the `data` it was built from.
+		///
+		/// Transcripts only take static labels and domains, `intern` maps them to static ones. The
+		/// rebuild fails if `intern` returns `None`.
+		pub fn from_data(
+			data: &VrfTranscriptData,
+			mut intern: impl FnMut(&[u8]) -> Option<&'static [u8]>,
+		) -> Option<Self> {
+			let label = intern(&data.label)?;
+			let items = data
+				.items
+				.iter()
+				.map(|(domain, message)| Some((intern(domain)?, &message[..])))
+				.collect::<Option<Vec<_>>>()?;
+			Some(Self::new(label, &items))
+		}
+
+		/// The data this transcript was built from.
+		pub fn data(&self) -> &VrfTranscriptData {
+			&self.1
 		}
 
 		/// Map transcript to `VrfSignData`.
@@ -374,6 +411,18 @@ pub mod vrf {
 			self.extra = Some(extra);
 			self
 		}
+
+		/// The extra transcript, if any.
+		pub fn extra(&self) -> Option<&VrfTranscript> {
+			self.extra.as_ref()
+		}
+	}
+
+	/// Encodes the [`VrfTranscriptData`] of the input and extra transcripts.
+	impl Encode for VrfSignData {
+		fn encode(&self) -> Vec<u8> {
+			(self.transcript.data(), self.extra.as_ref().map(VrfTranscript::data)).encode()
+		}
 	}
 
 	/// VRF signature data
@@ -862,6 +911,30 @@ mod tests {
 		assert!(public.vrf_verify(&data, &signature));
 	}
 
+	#[test]
+	fn vrf_transcript_is_rebuilt_from_its_data() {
+		let pair = Pair::from_seed(b"12345678901234567890123456789012");
+		let public = pair.public();
+
+		let input = VrfTranscript::new(b"label", &[(b"domain1", b"data1")]);
+		let extra = VrfTranscript::new(b"extra", &[(b"domain2", b"data2")]);
+		let data = input.clone().into_sign_data().with_extra(extra.clone());
+
+		let labels: [&'static [u8]; 4] = [b"label", b"domain1", b"extra", b"domain2"];
+		let intern = |label: &[u8]| labels.into_iter().find(|known| *known == label);
+		let rebuilt = VrfTranscript::from_data(input.data(), intern)
+			.unwrap()
+			.into_sign_data()
+			.with_extra(VrfTranscript::from_data(extra.data(), intern).unwrap());
+		assert_eq!(rebuilt.encode(), data.encode());
+
+		let signature = pair.vrf_sign(&rebuilt);
+		assert!(public.vrf_verify(&data, &signature));
+		assert_eq!(signature.pre_output, pair.vrf_pre_output(&input));
+
+		assert!(VrfTranscript::from_data(input.data(), |_| None).is_none());
+	}
+
 	#[test]
 	fn vrf_make_bytes_matches() {
 		let pair = Pair::from_seed(b"12345678901234567890123456789012");
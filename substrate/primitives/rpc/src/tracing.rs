@@ -18,6 +18,7 @@
 //! Types for working with tracing data
 
 use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
 
 use rustc_hash::FxHashMap;
 
@@ -99,3 +100,55 @@ pub enum TraceBlockResponse {
 	/// Successful block tracing response
 	BlockTrace(BlockTrace),
 }
+
+/// The phase of the block execution in which storage was accessed.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ExecutionPhase {
+	/// Initialization of the block.
+	Initialization,
+	/// Application of the extrinsic with the given index.
+	ApplyExtrinsic(u32),
+	/// Finalization of the block.
+	Finalization,
+}
+
+/// An access to a storage key.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageKeyAccess {
+	/// Storage key of the child trie, if the key is not in the main trie.
+	pub child_storage_key: Option<Bytes>,
+	/// The accessed key, or the prefix for cleared prefixes.
+	pub key: Bytes,
+	/// Size in bytes of the value read or written.
+	///
+	/// `None` for removed keys and cleared prefixes.
+	pub size: Option<u32>,
+}
+
+/// The storage keys accessed in a phase of the block execution.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseStorageAccesses {
+	/// The phase of the block execution.
+	pub phase: ExecutionPhase,
+	/// The keys read, with the size of the largest value read.
+	pub reads: Vec<StorageKeyAccess>,
+	/// The keys written, with the size of the last value written.
+	pub writes: Vec<StorageKeyAccess>,
+	/// The cleared prefixes.
+	pub cleared_prefixes: Vec<StorageKeyAccess>,
+}
+
+/// Response for the `state_traceBlockStorageAccess` RPC.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockStorageAccesses<Hash> {
+	/// Hash of the block being traced.
+	pub block_hash: Hash,
+	/// Parent hash.
+	pub parent_hash: Hash,
+	/// The storage keys accessed in every phase of the block execution, in execution order.
+	pub phases: Vec<PhaseStorageAccesses>,
+}
@@ -173,6 +173,7 @@ where
 			.storage(key)
 			.map(|x| x.map(|x| x.to_vec()))
 			.unwrap_or_else(|| self.backend.storage(key).expect(EXT_NOT_ALLOWED_TO_FAIL));
+		self.overlay.track_read(None, key, result.as_ref().map_or(0, |v| v.len()));
 
 		// NOTE: be careful about touching the key names – used outside substrate!
 		trace!(
@@ -199,6 +200,7 @@ where
 			.storage(key)
 			.map(|x| x.map(|x| H::hash(x)))
 			.unwrap_or_else(|| self.backend.storage_hash(key).expect(EXT_NOT_ALLOWED_TO_FAIL));
+		self.overlay.track_read(None, key, 0);
 
 		trace!(
 			target: "state",
@@ -219,6 +221,8 @@ where
 			.unwrap_or_else(|| {
 				self.backend.child_storage(child_info, key).expect(EXT_NOT_ALLOWED_TO_FAIL)
 			});
+		self.overlay
+			.track_read(Some(child_info), key, result.as_ref().map_or(0, |v| v.len()));
 
 		trace!(
 			target: "state",
@@ -241,6 +245,7 @@ where
 			.unwrap_or_else(|| {
 				self.backend.child_storage_hash(child_info, key).expect(EXT_NOT_ALLOWED_TO_FAIL)
 			});
+		self.overlay.track_read(Some(child_info), key, 0);
 
 		trace!(
 			target: "state",
@@ -260,6 +265,7 @@ where
 			Some(x) => x.is_some(),
 			_ => self.backend.exists_storage(key).expect(EXT_NOT_ALLOWED_TO_FAIL),
 		};
+		self.overlay.track_read(None, key, 0);
 
 		trace!(
 			target: "state",
@@ -282,6 +288,7 @@ where
 				.exists_child_storage(child_info, key)
 				.expect(EXT_NOT_ALLOWED_TO_FAIL),
 		};
+		self.overlay.track_read(Some(child_info), key, 0);
 
 		trace!(
 			target: "state",
@@ -845,7 +852,7 @@ where
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::InMemoryBackend;
+	use crate::{AccessedKey, InMemoryBackend};
 	use codec::{Decode, Encode};
 	use sp_core::{
 		map,
@@ -1078,4 +1085,72 @@ mod tests {
 
 		assert_eq!(Vec::<u32>::decode(&mut &data[..]).unwrap(), vec![1, 2]);
 	}
+
+	#[test]
+	fn storage_accesses_are_tracked() {
+		let child_info = ChildInfo::new_default(b"Child1");
+		let mut overlay = OverlayedChanges::default();
+		let backend = (
+			Storage {
+				top: map![
+					vec![10] => vec![1, 2, 3],
+					vec![20, 1] => vec![20],
+					vec![20, 2] => vec![20]
+				],
+				children_default: map![
+					child_info.storage_key().to_vec() => StorageChild {
+						data: map![vec![30] => vec![40]],
+						child_info: child_info.to_owned(),
+					}
+				],
+			},
+			StateVersion::default(),
+		)
+			.into();
+
+		// Nothing is tracked by default.
+		let mut ext = TestExt::new(&mut overlay, &backend, None);
+		assert_eq!(ext.storage(&[10]), Some(vec![1, 2, 3]));
+		drop(ext);
+		assert_eq!(overlay.take_storage_accesses(), None);
+
+		overlay.set_track_storage_accesses(true);
+		let mut ext = TestExt::new(&mut overlay, &backend, None);
+		assert_eq!(ext.storage(&[10]), Some(vec![1, 2, 3]));
+		assert!(!ext.exists_storage(&[11]));
+		assert_eq!(ext.child_storage(&child_info, &[30]), Some(vec![40]));
+		ext.set_storage(vec![12], vec![5, 6]);
+		ext.set_storage(vec![10], vec![]);
+		ext.clear_storage(&[10]);
+		let _ = ext.clear_prefix(&[20], None, None);
+		drop(ext);
+
+		let key = |key: &[u8]| AccessedKey { child_storage_key: None, key: key.to_vec() };
+		let child_key = |key: &[u8]| AccessedKey {
+			child_storage_key: Some(child_info.storage_key().to_vec()),
+			key: key.to_vec(),
+		};
+		let accesses = overlay.take_storage_accesses().unwrap();
+		assert_eq!(
+			accesses.reads.into_iter().collect::<Vec<_>>(),
+			vec![(key(&[10]), 3), (key(&[11]), 0), (child_key(&[30]), 1)],
+		);
+		assert_eq!(
+			accesses.writes.into_iter().collect::<Vec<_>>(),
+			vec![
+				(key(&[10]), None),
+				(key(&[12]), Some(2)),
+				(key(&[20, 1]), None),
+				(key(&[20, 2]), None)
+			],
+		);
+		assert_eq!(accesses.cleared_prefixes.into_iter().collect::<Vec<_>>(), vec![key(&[20])]);
+
+		// Taking the accesses resets them, but keeps tracking enabled.
+		assert_eq!(overlay.take_storage_accesses(), Some(Default::default()));
+		let mut ext = TestExt::new(&mut overlay, &backend, None);
+		assert_eq!(ext.storage(&[12]), Some(vec![5, 6]));
+		drop(ext);
+		assert_eq!(overlay.take_storage_accesses().unwrap().reads.len(), 1);
+	}
 }
@@ -35,6 +35,7 @@ pub(crate) mod overlayed_changes;
 #[cfg(feature = "std")]
 mod read_only;
 mod stats;
+mod storage_access;
 #[cfg(feature = "std")]
 mod testing;
 mod trie_backend;
@@ -138,6 +139,7 @@ pub use crate::{
 		StorageValue,
 	},
 	stats::{StateMachineStats, UsageInfo, UsageUnit},
+	storage_access::{AccessedKey, StorageAccesses},
 	trie_backend::{TrieBackend, TrieBackendBuilder},
 	trie_backend_essence::{Storage, TrieBackendStorage},
 };
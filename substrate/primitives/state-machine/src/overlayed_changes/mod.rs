@@ -21,7 +21,10 @@ mod changeset;
 mod offchain;
 
 use self::changeset::OverlayedChangeSet;
-use crate::{backend::Backend, stats::StateMachineStats, BackendTransaction, DefaultError};
+use crate::{
+	backend::Backend, stats::StateMachineStats, storage_access::StorageAccesses,
+	BackendTransaction, DefaultError,
+};
 use alloc::{collections::btree_set::BTreeSet, vec::Vec};
 use codec::{Decode, Encode};
 use hash_db::Hasher;
@@ -104,6 +107,8 @@ pub struct OverlayedChanges<H: Hasher> {
 	collect_extrinsics: bool,
 	/// Collect statistic on this execution.
 	stats: StateMachineStats,
+	/// The storage accesses, if tracking them is enabled.
+	storage_accesses: Option<StorageAccesses>,
 	/// Caches the "storage transaction" that is created while calling `storage_root`.
 	///
 	/// This transaction can be applied to the backend to persist the state changes.
@@ -119,6 +124,7 @@ impl<H: Hasher> Default for OverlayedChanges<H> {
 			transaction_index_ops: Default::default(),
 			collect_extrinsics: Default::default(),
 			stats: Default::default(),
+			storage_accesses: None,
 			storage_transaction_cache: None,
 		}
 	}
@@ -133,6 +139,7 @@ impl<H: Hasher> Clone for OverlayedChanges<H> {
 			transaction_index_ops: self.transaction_index_ops.clone(),
 			collect_extrinsics: self.collect_extrinsics,
 			stats: self.stats.clone(),
+			storage_accesses: self.storage_accesses.clone(),
 			storage_transaction_cache: self.storage_transaction_cache.clone(),
 		}
 	}
@@ -147,6 +154,7 @@ impl<H: Hasher> core::fmt::Debug for OverlayedChanges<H> {
 			.field("transaction_index_ops", &self.transaction_index_ops)
 			.field("collect_extrinsics", &self.collect_extrinsics)
 			.field("stats", &self.stats)
+			.field("storage_accesses", &self.storage_accesses)
 			.field("storage_transaction_cache", &self.storage_transaction_cache)
 			.finish()
 	}
@@ -286,6 +294,43 @@ impl<H: Hasher> OverlayedChanges<H> {
 		self.collect_extrinsics = collect_extrinsics;
 	}
 
+	/// Ask to track/not to track the storage keys that are read and written.
+	///
+	/// The accesses are collected until they are taken with [`Self::take_storage_accesses`].
+	pub fn set_track_storage_accesses(&mut self, track_storage_accesses: bool) {
+		if !track_storage_accesses {
+			self.storage_accesses = None;
+		} else if self.storage_accesses.is_none() {
+			self.storage_accesses = Some(Default::default());
+		}
+	}
+
+	/// Take the storage accesses tracked since tracking was enabled or since the last call.
+	///
+	/// Returns `None` if tracking storage accesses is not enabled.
+	pub fn take_storage_accesses(&mut self) -> Option<StorageAccesses> {
+		self.storage_accesses.as_mut().map(core::mem::take)
+	}
+
+	/// Track a read of `key` that returned a value of `size` bytes, if tracking is enabled.
+	pub(crate) fn track_read(&mut self, child_info: Option<&ChildInfo>, key: &[u8], size: usize) {
+		if let Some(accesses) = self.storage_accesses.as_mut() {
+			accesses.note_read(child_info, key, size);
+		}
+	}
+
+	fn track_write(&mut self, child_info: Option<&ChildInfo>, key: &[u8], size: Option<usize>) {
+		if let Some(accesses) = self.storage_accesses.as_mut() {
+			accesses.note_write(child_info, key, size);
+		}
+	}
+
+	fn track_clear_prefix(&mut self, child_info: Option<&ChildInfo>, prefix: &[u8]) {
+		if let Some(accesses) = self.storage_accesses.as_mut() {
+			accesses.note_clear_prefix(child_info, prefix);
+		}
+	}
+
 	/// Returns a double-Option: None if the key is unknown (i.e. and the query should be referred
 	/// to the backend); Some(None) if the key has been deleted. Some(Some(...)) for a key whose
 	/// value has been set.
@@ -323,6 +368,7 @@ impl<H: Hasher> OverlayedChanges<H> {
 
 		let size_write = val.as_ref().map(|x| x.len() as u64).unwrap_or(0);
 		self.stats.tally_write_overlay(size_write);
+		self.track_write(None, &key, val.as_ref().map(|x| x.len()));
 		let extrinsic_index = self.extrinsic_index();
 		self.top.set(key, val, extrinsic_index);
 	}
@@ -337,6 +383,7 @@ impl<H: Hasher> OverlayedChanges<H> {
 		let extrinsic_index = self.extrinsic_index();
 		let size_write = element.len() as u64;
 		self.stats.tally_write_overlay(size_write);
+		self.track_write(None, &key, Some(element.len()));
 		self.top.append_storage(key, element, init, extrinsic_index);
 	}
 
@@ -356,6 +403,7 @@ impl<H: Hasher> OverlayedChanges<H> {
 		let extrinsic_index = self.extrinsic_index();
 		let size_write = val.as_ref().map(|x| x.len() as u64).unwrap_or(0);
 		self.stats.tally_write_overlay(size_write);
+		self.track_write(Some(child_info), &key, val.as_ref().map(|x| x.len()));
 		let storage_key = child_info.storage_key().to_vec();
 		let top = &self.top;
 		let (changeset, info) = self
@@ -372,6 +420,7 @@ impl<H: Hasher> OverlayedChanges<H> {
 	/// Can be rolled back or committed when called inside a transaction.
 	pub fn clear_child_storage(&mut self, child_info: &ChildInfo) -> u32 {
 		self.mark_dirty();
+		self.track_clear_prefix(Some(child_info), &[]);
 
 		let extrinsic_index = self.extrinsic_index();
 		let storage_key = child_info.storage_key().to_vec();
@@ -390,6 +439,7 @@ impl<H: Hasher> OverlayedChanges<H> {
 	/// Can be rolled back or committed when called inside a transaction.
 	pub fn clear_prefix(&mut self, prefix: &[u8]) -> u32 {
 		self.mark_dirty();
+		self.track_clear_prefix(None, prefix);
 
 		let extrinsic_index = self.extrinsic_index();
 		self.top.clear_where(|key, _| key.starts_with(prefix), extrinsic_index)
@@ -400,6 +450,7 @@ impl<H: Hasher> OverlayedChanges<H> {
 	/// Can be rolled back or committed when called inside a transaction
 	pub fn clear_child_prefix(&mut self, child_info: &ChildInfo, prefix: &[u8]) -> u32 {
 		self.mark_dirty();
+		self.track_clear_prefix(Some(child_info), prefix);
 
 		let extrinsic_index = self.extrinsic_index();
 		let storage_key = child_info.storage_key().to_vec();
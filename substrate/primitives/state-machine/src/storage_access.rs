@@ -0,0 +1,93 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tracking of the storage keys accessed by the runtime.
+
+use crate::StorageKey;
+use alloc::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
+use sp_core::storage::ChildInfo;
+
+/// A storage key, optionally inside of a child trie.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AccessedKey {
+	/// The storage key of the child trie, without the child storage prefix, or `None` for the
+	/// main trie.
+	pub child_storage_key: Option<StorageKey>,
+	/// The key inside of the trie.
+	pub key: StorageKey,
+}
+
+impl AccessedKey {
+	fn new(child_info: Option<&ChildInfo>, key: &[u8]) -> Self {
+		Self {
+			child_storage_key: child_info.map(|info| info.storage_key().to_vec()),
+			key: key.to_vec(),
+		}
+	}
+}
+
+/// The storage keys accessed while storage access tracking is enabled.
+///
+/// Accesses are recorded as they happen, so accesses inside of storage transactions that are
+/// rolled back are still part of the result. Iterating keys with `next_storage_key` is not
+/// tracked.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StorageAccesses {
+	/// The keys that were read, with the size in bytes of the largest value read.
+	///
+	/// The size is `0` if the key did not exist or only its hash or existence was queried.
+	pub reads: BTreeMap<AccessedKey, u32>,
+	/// The keys that were written, with the size in bytes of the last value written, or `None`
+	/// if the key was removed last.
+	///
+	/// For appends the size is the one of the appended item.
+	pub writes: BTreeMap<AccessedKey, Option<u32>>,
+	/// The prefixes that were cleared.
+	///
+	/// The keys removed from the backend are also part of [`Self::writes`], but keys that only
+	/// existed in the overlay are not.
+	pub cleared_prefixes: BTreeSet<AccessedKey>,
+}
+
+impl StorageAccesses {
+	/// Returns `true` if no access was recorded.
+	pub fn is_empty(&self) -> bool {
+		self.reads.is_empty() && self.writes.is_empty() && self.cleared_prefixes.is_empty()
+	}
+
+	/// Record a read of `key` that returned a value of `size` bytes.
+	pub(crate) fn note_read(&mut self, child_info: Option<&ChildInfo>, key: &[u8], size: usize) {
+		let read = self.reads.entry(AccessedKey::new(child_info, key)).or_default();
+		*read = (*read).max(size as u32);
+	}
+
+	/// Record a write of a value of `size` bytes to `key`, or its removal if `size` is `None`.
+	pub(crate) fn note_write(
+		&mut self,
+		child_info: Option<&ChildInfo>,
+		key: &[u8],
+		size: Option<usize>,
+	) {
+		self.writes
+			.insert(AccessedKey::new(child_info, key), size.map(|size| size as u32));
+	}
+
+	/// Record that all keys starting with `prefix` were cleared.
+	pub(crate) fn note_clear_prefix(&mut self, child_info: Option<&ChildInfo>, prefix: &[u8]) {
+		self.cleared_prefixes.insert(AccessedKey::new(child_info, prefix));
+	}
+}